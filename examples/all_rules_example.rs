@@ -1,5 +1,8 @@
 use raff_core::error::Result;
-use raff_core::{AllArgs, AllOutputFormat, CouplingGranularity, all_rules};
+use raff_core::{
+    AllArgs, AllOutputFormat, ComponentMode, CouplingGranularity, RustCodeAnalysisBackend,
+    all_rules,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
@@ -9,9 +12,21 @@ fn main() -> Result<()> {
         fast: false,
         quiet: false,
         fail_on_warnings: false,
+        components: ComponentMode::Directory,
         sc_threshold: 10,
         vol_alpha: 0.01,
         vol_since: None,
+        vol_until: None,
+        vol_from: None,
+        vol_to: None,
+        vol_range: None,
+        vol_branch: None,
+        vol_include_paths: vec![],
+        vol_exclude_paths: vec![],
+        vol_exclude_authors: vec![],
+        vol_exclude_messages: vec![],
+        vol_ignore_revs_file: None,
+        vol_jobs: None,
         vol_normalize: false,
         vol_skip_merges: false,
         coup_granularity: CouplingGranularity::Module,
@@ -19,9 +34,19 @@ fn main() -> Result<()> {
         rca_jobs: num_cpus::get(),
         rca_metrics: true,
         rca_language: "rust".to_string(),
+        rca_backend: RustCodeAnalysisBackend::Builtin,
+        rca_max_file_cognitive: None,
+        rca_min_file_mi: None,
+        rca_max_file_abc: None,
+        rca_max_function_cyclomatic: None,
+        rca_max_function_cognitive: None,
+        rca_max_function_args: None,
+        rca_max_function_length: None,
         ci_output: None,
         output_file: None,
         staged: false,
+        no_cache: false,
+        component_definitions: Default::default(),
     };
 
     all_rules::run_all(&args)
@@ -1,12 +1,17 @@
 use raff_core::error::Result;
-use raff_core::{ContributorReportArgs, ContributorReportOutputFormat, ContributorReportRule};
+use raff_core::{
+    ContributorReportArgs, ContributorReportOutputFormat, ContributorReportPeriod,
+    ContributorReportRule, HistoryArgs,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
     let args = ContributorReportArgs {
         path: PathBuf::from("."),
-        since: None,
+        history: HistoryArgs::default(),
         decay: 0.01,
+        period: ContributorReportPeriod::Month,
+        aliases: Default::default(),
         output: ContributorReportOutputFormat::Table,
         ci_output: None,
         output_file: None,
@@ -10,6 +10,7 @@ fn main() -> Result<()> {
         ci_output: None,
         output_file: None,
         staged: false,
+        component_definitions: Default::default(),
     };
 
     let rule = CouplingRule::new();
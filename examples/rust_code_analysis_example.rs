@@ -1,5 +1,8 @@
 use raff_core::error::Result;
-use raff_core::{RustCodeAnalysisArgs, RustCodeAnalysisOutputFormat, RustCodeAnalysisRule};
+use raff_core::{
+    ComponentMode, RustCodeAnalysisArgs, RustCodeAnalysisBackend, RustCodeAnalysisOutputFormat,
+    RustCodeAnalysisRule,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
@@ -10,8 +13,19 @@ fn main() -> Result<()> {
         output: RustCodeAnalysisOutputFormat::Table,
         metrics: true,
         language: "rust".to_string(),
+        backend: RustCodeAnalysisBackend::Builtin,
+        components: ComponentMode::Directory,
+        component_definitions: Default::default(),
+        max_file_cognitive: None,
+        min_file_mi: None,
+        max_file_abc: None,
+        max_function_cyclomatic: None,
+        max_function_cognitive: None,
+        max_function_args: None,
+        max_function_length: None,
         ci_output: None,
         output_file: None,
+        no_cache: false,
     };
 
     let rule = RustCodeAnalysisRule::new();
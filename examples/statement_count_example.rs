@@ -1,15 +1,30 @@
 use raff_core::error::Result;
-use raff_core::{StatementCountArgs, StatementCountOutputFormat, StatementCountRule};
+use raff_core::{
+    ComponentMode, StatementCountArgs, StatementCountGranularity, StatementCountOutputFormat,
+    StatementCountRule,
+};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
     let args = StatementCountArgs {
         path: PathBuf::from("."),
         threshold: 10,
+        components: ComponentMode::Directory,
+        granularity: StatementCountGranularity::Component,
+        top: 20,
+        max_function_statements: None,
+        include_tests: false,
+        parse_macros: false,
+        max_component_statements: None,
+        min_component_statements: None,
+        max_file_statements: None,
+        max_module_statements: None,
+        max_gini: None,
         output: StatementCountOutputFormat::Table,
         ci_output: None,
         output_file: None,
         staged: false,
+        component_definitions: Default::default(),
     };
 
     let rule = StatementCountRule::new();
@@ -1,17 +1,18 @@
 use raff_core::error::Result;
-use raff_core::{VolatilityArgs, VolatilityOutputFormat, VolatilityRule};
+use raff_core::{HistoryArgs, VolatilityArgs, VolatilityOutputFormat, VolatilityRule};
 use std::path::PathBuf;
 
 fn main() -> Result<()> {
     let args = VolatilityArgs {
         path: PathBuf::from("."),
         alpha: 0.01,
-        since: None,
+        history: HistoryArgs::default(),
         normalize: false,
         skip_merges: false,
         output: VolatilityOutputFormat::Table,
         ci_output: None,
         output_file: None,
+        component_definitions: Default::default(),
     };
 
     let rule = VolatilityRule::new();
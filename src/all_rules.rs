@@ -9,12 +9,13 @@
 //! The consolidated report supports two output formats:
 //!
 //! - **JSON**: Combines results from all rules into a single JSON document
-//! - **HTML**: Generates an HTML report with all analysis results combined
+//! - **HTML**: Generates an HTML report with all analysis results combined, led by a
+//!   component dashboard that joins size, coupling, volatility and complexity per component
 //!
 //! # Example
 //!
 //! ```rust,no_run
-//! use raff_core::{run_all, AllArgs, AllOutputFormat};
+//! use raff_core::{run_all, AllArgs, AllOutputFormat, ComponentMode, RustCodeAnalysisBackend};
 //! use std::path::PathBuf;
 //!
 //! # fn main() -> raff_core::error::Result<()> {
@@ -25,10 +26,24 @@
 //!     quiet: false,
 //!     fail_on_warnings: false,
 //!     staged: false,
+//!     no_cache: false,
+//!     components: ComponentMode::Directory,
+//!     component_definitions: Default::default(),
 //!     // .. other fields
 //!     sc_threshold: 10,
 //!     vol_alpha: 0.01,
 //!     vol_since: None,
+//!     vol_until: None,
+//!     vol_from: None,
+//!     vol_to: None,
+//!     vol_range: None,
+//!     vol_branch: None,
+//!     vol_include_paths: vec![],
+//!     vol_exclude_paths: vec![],
+//!     vol_exclude_authors: vec![],
+//!     vol_exclude_messages: vec![],
+//!     vol_ignore_revs_file: None,
+//!     vol_jobs: None,
 //!     vol_normalize: false,
 //!     vol_skip_merges: false,
 //!     coup_granularity: raff_core::CouplingGranularity::Both,
@@ -36,6 +51,14 @@
 //!     rca_jobs: 4,
 //!     rca_metrics: true,
 //!     rca_language: "rust".to_string(),
+//!     rca_backend: RustCodeAnalysisBackend::Builtin,
+//!     rca_max_file_cognitive: None,
+//!     rca_min_file_mi: None,
+//!     rca_max_file_abc: None,
+//!     rca_max_function_cyclomatic: None,
+//!     rca_max_function_cognitive: None,
+//!     rca_max_function_args: None,
+//!     rca_max_function_length: None,
 //!     ci_output: None,
 //!     output_file: None,
 //! };
@@ -49,14 +72,16 @@ use crate::ci_report::{Severity, ToFindings};
 use crate::error::Result;
 use crate::{
     cli::{AllArgs, AllOutputFormat, CiOutputFormat},
-    coupling_rule::{CouplingData, CouplingRule},
+    components::{ComponentResolver, discover_crates},
+    coupling_rule::{CouplingData, CouplingRule, aggregate_component_coupling},
     html_utils,
-    rust_code_analysis_rule::{RustCodeAnalysisData, RustCodeAnalysisRule},
+    rust_code_analysis_rule::{ComplexityRollup, RustCodeAnalysisData, RustCodeAnalysisRule},
     statement_count_rule::{StatementCountData, StatementCountRule},
     volatility_rule::{VolatilityData, VolatilityRule},
 };
 use maud::Markup;
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::Write;
 
@@ -119,43 +144,10 @@ pub fn run_all(args: &AllArgs) -> Result<()> {
     let coup_rule = CouplingRule::new();
     let rca_rule = RustCodeAnalysisRule::new();
 
-    // We need to construct the specific args for each rule from the AllArgs
-    let sc_args = crate::cli::StatementCountArgs {
-        path: args.path.clone(),
-        threshold: args.sc_threshold,
-        output: crate::cli::StatementCountOutputFormat::Table, // format is irrelevant for analyze
-        ci_output: None,
-        output_file: args.output_file.clone(),
-        staged: args.staged,
-    };
-    let vol_args = crate::cli::VolatilityArgs {
-        path: args.path.clone(),
-        alpha: args.vol_alpha,
-        since: args.vol_since.clone(),
-        normalize: args.vol_normalize,
-        skip_merges: args.vol_skip_merges,
-        output: crate::cli::VolatilityOutputFormat::Table, // format is irrelevant for analyze
-        ci_output: None,
-        output_file: args.output_file.clone(),
-    };
-    let coup_args = crate::cli::CouplingArgs {
-        path: args.path.clone(),
-        granularity: args.coup_granularity.clone(),
-        output: crate::cli::CouplingOutputFormat::Table, // format is irrelevant for analyze
-        ci_output: None,
-        output_file: args.output_file.clone(),
-        staged: args.staged,
-    };
-    let rca_args = crate::cli::RustCodeAnalysisArgs {
-        path: args.path.clone(),
-        extra_flags: args.rca_extra_flags.clone(),
-        jobs: args.rca_jobs,
-        metrics: args.rca_metrics,
-        language: args.rca_language.clone(),
-        output: crate::cli::RustCodeAnalysisOutputFormat::Table, // format is irrelevant for analyze
-        ci_output: None,
-        output_file: args.output_file.clone(),
-    };
+    let sc_args = statement_count_args(args);
+    let vol_args = volatility_args(args);
+    let coup_args = coupling_args(args);
+    let rca_args = rust_code_analysis_args(args);
 
     let all_data = if args.fast {
         // In staged fast mode, skip statement-count because component-percentage
@@ -340,6 +332,10 @@ pub fn run_all(args: &AllArgs) -> Result<()> {
         AllOutputFormat::Html => {
             let mut html_body_parts: Vec<Markup> = vec![];
 
+            if let Some(dashboard) = render_component_dashboard(&all_data, &crate_components(args)?)
+            {
+                html_body_parts.push(dashboard);
+            }
             if let Some(Ok(data)) = &all_data.statement_count {
                 html_body_parts.push(sc_rule.render_statement_count_html_body(data)?);
             }
@@ -360,10 +356,7 @@ pub fn run_all(args: &AllArgs) -> Result<()> {
                 html_body_parts.push(coup_rule.render_coupling_html_body(data)?);
             }
             if let Some(Ok(data)) = &all_data.rust_code_analysis {
-                html_body_parts.push(rca_rule.render_rust_code_analysis_html_body(
-                    &data.analysis_results,
-                    &data.analysis_path,
-                )?);
+                html_body_parts.push(rca_rule.render_rust_code_analysis_html_body(data)?);
             }
 
             let full_html = html_utils::render_html_doc(
@@ -377,6 +370,221 @@ pub fn run_all(args: &AllArgs) -> Result<()> {
     Ok(())
 }
 
+/// Builds the statement-count arguments for `raff all` from `args`.
+fn statement_count_args(args: &AllArgs) -> crate::cli::StatementCountArgs {
+    crate::cli::StatementCountArgs {
+        path: args.path.clone(),
+        threshold: args.sc_threshold,
+        components: args.components,
+        granularity: crate::cli::StatementCountGranularity::Component,
+        top: 20,
+        max_function_statements: None,
+        include_tests: false,
+        parse_macros: false,
+        max_component_statements: None,
+        min_component_statements: None,
+        max_file_statements: None,
+        max_module_statements: None,
+        max_gini: None,
+        output: crate::cli::StatementCountOutputFormat::Table, // format is irrelevant for analyze
+        ci_output: None,
+        output_file: args.output_file.clone(),
+        staged: args.staged,
+        component_definitions: args.component_definitions.clone(),
+    }
+}
+
+/// Builds the volatility arguments for `raff all` from `args`.
+fn volatility_args(args: &AllArgs) -> crate::cli::VolatilityArgs {
+    crate::cli::VolatilityArgs {
+        path: args.path.clone(),
+        alpha: args.vol_alpha,
+        history: crate::cli::HistoryArgs {
+            since: args.vol_since.clone(),
+            until: args.vol_until.clone(),
+            from: args.vol_from.clone(),
+            to: args.vol_to.clone(),
+            range: args.vol_range.clone(),
+            branch: args.vol_branch.clone(),
+            include_paths: args.vol_include_paths.clone(),
+            exclude_paths: args.vol_exclude_paths.clone(),
+            exclude_authors: args.vol_exclude_authors.clone(),
+            exclude_messages: args.vol_exclude_messages.clone(),
+            ignore_revs_file: args.vol_ignore_revs_file.clone(),
+            jobs: args.vol_jobs,
+        },
+        normalize: args.vol_normalize,
+        skip_merges: args.vol_skip_merges,
+        output: crate::cli::VolatilityOutputFormat::Table, // format is irrelevant for analyze
+        ci_output: None,
+        output_file: args.output_file.clone(),
+        component_definitions: args.component_definitions.clone(),
+    }
+}
+
+/// Builds the coupling arguments for `raff all` from `args`.
+fn coupling_args(args: &AllArgs) -> crate::cli::CouplingArgs {
+    crate::cli::CouplingArgs {
+        path: args.path.clone(),
+        granularity: args.coup_granularity.clone(),
+        output: crate::cli::CouplingOutputFormat::Table, // format is irrelevant for analyze
+        ci_output: None,
+        output_file: args.output_file.clone(),
+        staged: args.staged,
+        component_definitions: args.component_definitions.clone(),
+    }
+}
+
+/// Builds the rust-code-analysis arguments for `raff all` from `args`.
+fn rust_code_analysis_args(args: &AllArgs) -> crate::cli::RustCodeAnalysisArgs {
+    crate::cli::RustCodeAnalysisArgs {
+        path: args.path.clone(),
+        extra_flags: args.rca_extra_flags.clone(),
+        jobs: args.rca_jobs,
+        metrics: args.rca_metrics,
+        language: args.rca_language.clone(),
+        backend: args.rca_backend,
+        components: args.components,
+        component_definitions: args.component_definitions.clone(),
+        max_file_cognitive: args.rca_max_file_cognitive,
+        min_file_mi: args.rca_min_file_mi,
+        max_file_abc: args.rca_max_file_abc,
+        max_function_cyclomatic: args.rca_max_function_cyclomatic,
+        max_function_cognitive: args.rca_max_function_cognitive,
+        max_function_args: args.rca_max_function_args,
+        max_function_length: args.rca_max_function_length,
+        output: crate::cli::RustCodeAnalysisOutputFormat::Table, // format is irrelevant for analyze
+        ci_output: None,
+        output_file: args.output_file.clone(),
+        no_cache: args.no_cache,
+    }
+}
+
+/// One component's row in the component dashboard; `None` when the rule that
+/// provides a column did not run or did not see the component.
+#[derive(Debug, Default)]
+struct DashboardRow<'a> {
+    statements: Option<usize>,
+    complexity: Option<&'a ComplexityRollup>,
+    coupling: Option<(usize, usize)>,
+    volatility: Option<f64>,
+}
+
+/// Maps every workspace crate to the component `args` assigns its sources
+/// to, so crate-level coupling and volatility line up with the per-file
+/// components of the other rules.
+fn crate_components(args: &AllArgs) -> Result<HashMap<String, String>> {
+    let analysis_path = args.path.canonicalize()?;
+    let components =
+        ComponentResolver::new(&analysis_path, args.components, &args.component_definitions)?;
+    let crates = if components.crates().is_empty() {
+        discover_crates(&analysis_path)?
+    } else {
+        components.crates().to_vec()
+    };
+    Ok(crates
+        .into_iter()
+        .map(|krate| {
+            let component = components.component_of(&krate.root.join("src"));
+            (krate.name, component)
+        })
+        .collect())
+}
+
+/// Collects the per-component results of every rule that succeeded, keyed
+/// by component name. `crate_components` maps crate-level results onto
+/// components.
+fn dashboard_rows<'a>(
+    all_data: &'a AllReportData,
+    crate_components: &HashMap<String, String>,
+) -> BTreeMap<String, DashboardRow<'a>> {
+    let mut rows: BTreeMap<String, DashboardRow<'a>> = BTreeMap::new();
+    if let Some(Ok(data)) = &all_data.statement_count {
+        for (name, &(_files, statements)) in &data.component_stats {
+            rows.entry(name.clone()).or_default().statements = Some(statements);
+        }
+    }
+    if let Some(Ok(data)) = &all_data.rust_code_analysis {
+        for rollup in &data.rollups.components {
+            rows.entry(rollup.name.clone()).or_default().complexity = Some(rollup);
+        }
+    }
+    if let Some(Ok(data)) = &all_data.coupling {
+        for component in aggregate_component_coupling(&data.crates, crate_components) {
+            rows.entry(component.name).or_default().coupling = Some((component.ce, component.ca));
+        }
+    }
+    if let Some(Ok(data)) = &all_data.volatility {
+        // Defined components are already keyed by name; crates are summed
+        // into their component before normalizing
+        let mut churn: BTreeMap<&str, (f64, usize)> = BTreeMap::new();
+        for (name, stats) in &data.crate_stats_map {
+            let component = crate_components.get(name).unwrap_or(name);
+            let entry = churn.entry(component).or_default();
+            entry.0 += stats.raw_score;
+            entry.1 += stats.total_loc.unwrap_or(0);
+        }
+        for (name, (raw_score, loc)) in churn {
+            rows.entry(name.to_string()).or_default().volatility =
+                Some(if data.normalize && loc > 0 {
+                    raw_score / loc as f64
+                } else {
+                    raw_score
+                });
+        }
+    }
+    rows
+}
+
+/// Renders one table combining size, coupling, volatility and complexity per
+/// component, or `None` if no rule produced per-component results.
+fn render_component_dashboard(
+    all_data: &AllReportData,
+    crate_components: &HashMap<String, String>,
+) -> Option<Markup> {
+    let rows = dashboard_rows(all_data, crate_components);
+    if rows.is_empty() {
+        return None;
+    }
+    let dash = || "-".to_string();
+    Some(maud::html! {
+        h2 { "Component Dashboard" }
+        table class="sortable-table" {
+            caption { "Size, coupling, volatility and complexity per component" }
+            thead {
+                tr {
+                    @for (index, header) in ["Component", "Stmts", "SLOC", "Fns", "Ce", "Ca", "I", "Volatility", "Cyc Mean", "Cyc P90", "Cyc Max", "Cog Mean", "Cog P90", "H Effort", "MI Mean"].iter().enumerate() {
+                        th class="sortable-header" data-column-index=(index) data-sort-type=(if index == 0 { "string" } else { "number" }) { (header) }
+                    }
+                }
+            }
+            tbody {
+                @for (name, row) in &rows {
+                    @let complexity = row.complexity;
+                    @let instability = row.coupling.map(|(ce, ca)| if ce + ca > 0 { ce as f64 / (ce + ca) as f64 } else { 0.0 });
+                    tr {
+                        td { (name) }
+                        td { (row.statements.map_or_else(dash, |s| s.to_string())) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.0}", c.sloc))) }
+                        td { (complexity.map_or_else(dash, |c| c.functions.to_string())) }
+                        td { (row.coupling.map_or_else(dash, |(ce, _)| ce.to_string())) }
+                        td { (row.coupling.map_or_else(dash, |(_, ca)| ca.to_string())) }
+                        td style=(instability.map_or_else(String::new, |i| html_utils::get_cell_style(i, 0.5, 0.8, false))) { (instability.map_or_else(dash, |i| format!("{i:.2}"))) }
+                        td { (row.volatility.map_or_else(dash, |v| format!("{v:.2}"))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.1}", c.cyclomatic.mean))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.0}", c.cyclomatic.p90))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.0}", c.cyclomatic.max))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.1}", c.cognitive.mean))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.0}", c.cognitive.p90))) }
+                        td { (complexity.map_or_else(dash, |c| format!("{:.0}", c.halstead_effort))) }
+                        td { (complexity.and_then(|c| c.mi_mean).map_or_else(dash, |mi| format!("{mi:.1}"))) }
+                    }
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,9 +600,21 @@ mod tests {
             fast: false,
             quiet: false,
             fail_on_warnings: false,
+            components: crate::cli::ComponentMode::Directory,
             sc_threshold: 10,
             vol_alpha: 0.01,
             vol_since: None,
+            vol_until: None,
+            vol_from: None,
+            vol_to: None,
+            vol_range: None,
+            vol_branch: None,
+            vol_include_paths: vec![],
+            vol_exclude_paths: vec![],
+            vol_exclude_authors: vec![],
+            vol_exclude_messages: vec![],
+            vol_ignore_revs_file: None,
+            vol_jobs: None,
             vol_normalize: false,
             vol_skip_merges: false,
             coup_granularity: CouplingGranularity::Both,
@@ -402,9 +622,19 @@ mod tests {
             rca_jobs: 1,
             rca_metrics: true,
             rca_language: "rust".to_string(),
+            rca_backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            rca_max_file_cognitive: None,
+            rca_min_file_mi: None,
+            rca_max_file_abc: None,
+            rca_max_function_cyclomatic: None,
+            rca_max_function_cognitive: None,
+            rca_max_function_args: None,
+            rca_max_function_length: None,
             ci_output: None,
             output_file: None,
             staged: false,
+            no_cache: false,
+            component_definitions: BTreeMap::new(),
         }
     }
 
@@ -417,10 +647,22 @@ mod tests {
         crate::cli::StatementCountArgs {
             path: PathBuf::from(path),
             threshold,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: crate::cli::StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         }
     }
 
@@ -430,12 +672,13 @@ mod tests {
         crate::cli::VolatilityArgs {
             path: PathBuf::from(path),
             alpha,
-            since: None,
+            history: crate::cli::HistoryArgs::default(),
             normalize: false,
             skip_merges: false,
             output: crate::cli::VolatilityOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         }
     }
 
@@ -450,6 +693,7 @@ mod tests {
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         }
     }
 
@@ -462,9 +706,20 @@ mod tests {
             jobs: 1,
             metrics: true,
             language: "rust".to_string(),
+            backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             output: crate::cli::RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: false,
         }
     }
 
@@ -649,10 +904,22 @@ mod tests {
         let sc_args = crate::cli::StatementCountArgs {
             path: all_args.path.clone(),
             threshold: all_args.sc_threshold,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: crate::cli::StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: all_args.staged,
+            component_definitions: Default::default(),
         };
 
         assert_eq!(
@@ -680,12 +947,16 @@ mod tests {
         let vol_args = crate::cli::VolatilityArgs {
             path: all_args.path.clone(),
             alpha: all_args.vol_alpha,
-            since: all_args.vol_since.clone(),
+            history: crate::cli::HistoryArgs {
+                since: all_args.vol_since.clone(),
+                ..crate::cli::HistoryArgs::default()
+            },
             normalize: all_args.vol_normalize,
             skip_merges: all_args.vol_skip_merges,
             output: crate::cli::VolatilityOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         assert_eq!(
@@ -698,7 +969,7 @@ mod tests {
             "VolatilityArgs alpha should match AllArgs vol_alpha"
         );
         assert_eq!(
-            vol_args.since, None,
+            vol_args.history.since, None,
             "VolatilityArgs since should match AllArgs vol_since"
         );
         assert!(
@@ -723,6 +994,7 @@ mod tests {
             ci_output: None,
             output_file: None,
             staged: all_args.staged,
+            component_definitions: Default::default(),
         };
 
         assert_eq!(
@@ -740,6 +1012,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_component_settings_reach_every_rule() {
+        use crate::cli::ComponentMode;
+        let mut all_args = create_test_args("/test/path");
+        all_args.components = ComponentMode::Crate;
+        all_args.component_definitions =
+            BTreeMap::from([("billing".to_string(), vec!["src/payments/".to_string()])]);
+
+        let sc_args = statement_count_args(&all_args);
+        assert_eq!(sc_args.components, ComponentMode::Crate);
+        assert_eq!(
+            sc_args.component_definitions,
+            all_args.component_definitions
+        );
+        assert_eq!(
+            volatility_args(&all_args).component_definitions,
+            all_args.component_definitions
+        );
+        assert_eq!(
+            coupling_args(&all_args).component_definitions,
+            all_args.component_definitions
+        );
+        let rca_args = rust_code_analysis_args(&all_args);
+        assert_eq!(rca_args.components, ComponentMode::Crate);
+        assert_eq!(
+            rca_args.component_definitions,
+            all_args.component_definitions
+        );
+    }
+
+    #[test]
+    fn test_rca_thresholds_reach_rust_code_analysis_args() {
+        let mut all_args = create_test_args("/test/path");
+        all_args.rca_max_file_cognitive = Some(50.0);
+        all_args.rca_min_file_mi = Some(20.0);
+        all_args.rca_max_file_abc = Some(80.0);
+        all_args.rca_max_function_cyclomatic = Some(10.0);
+        all_args.rca_max_function_cognitive = Some(15.0);
+        all_args.rca_max_function_args = Some(5);
+        all_args.rca_max_function_length = Some(60);
+
+        let rca_args = rust_code_analysis_args(&all_args);
+        assert_eq!(rca_args.max_file_cognitive, Some(50.0));
+        assert_eq!(rca_args.min_file_mi, Some(20.0));
+        assert_eq!(rca_args.max_file_abc, Some(80.0));
+        assert_eq!(rca_args.max_function_cyclomatic, Some(10.0));
+        assert_eq!(rca_args.max_function_cognitive, Some(15.0));
+        assert_eq!(rca_args.max_function_args, Some(5));
+        assert_eq!(rca_args.max_function_length, Some(60));
+    }
+
     #[test]
     fn test_all_args_creates_valid_rca_args() {
         let all_args = create_test_args("/test/path");
@@ -750,9 +1073,20 @@ mod tests {
             jobs: all_args.rca_jobs,
             metrics: all_args.rca_metrics,
             language: all_args.rca_language.clone(),
+            backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             output: crate::cli::RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: false,
         };
 
         assert_eq!(
@@ -788,16 +1122,20 @@ mod tests {
         let vol_args = crate::cli::VolatilityArgs {
             path: all_args.path.clone(),
             alpha: all_args.vol_alpha,
-            since: all_args.vol_since.clone(),
+            history: crate::cli::HistoryArgs {
+                since: all_args.vol_since.clone(),
+                ..crate::cli::HistoryArgs::default()
+            },
             normalize: all_args.vol_normalize,
             skip_merges: all_args.vol_skip_merges,
             output: VolatilityOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         assert_eq!(
-            vol_args.since,
+            vol_args.history.since,
             Some("2023-01-01".to_string()),
             "VolatilityArgs since should match AllArgs vol_since when set"
         );
@@ -811,12 +1149,16 @@ mod tests {
         let vol_args = crate::cli::VolatilityArgs {
             path: all_args.path.clone(),
             alpha: all_args.vol_alpha,
-            since: all_args.vol_since.clone(),
+            history: crate::cli::HistoryArgs {
+                since: all_args.vol_since.clone(),
+                ..crate::cli::HistoryArgs::default()
+            },
             normalize: all_args.vol_normalize,
             skip_merges: all_args.vol_skip_merges,
             output: crate::cli::VolatilityOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         assert!(
@@ -836,9 +1178,20 @@ mod tests {
             jobs: all_args.rca_jobs,
             metrics: all_args.rca_metrics,
             language: all_args.rca_language.clone(),
+            backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             output: crate::cli::RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: false,
         };
 
         assert_eq!(
@@ -1385,4 +1738,161 @@ mod tests {
             "run_all with quiet=true and Html output should not panic"
         );
     }
+
+    #[test]
+    fn test_component_dashboard_joins_rules_by_component() {
+        use crate::rust_code_analysis_rule::{
+            ComplexityRollups, ComplexityThresholds, Distribution,
+        };
+
+        assert!(render_component_dashboard(&AllReportData::new(), &HashMap::new()).is_none());
+
+        // core depends on util and is depended on by three apps
+        let krate = |name: &str, dependencies: &[&str]| crate::coupling_rule::CrateCoupling {
+            name: name.to_string(),
+            ce: dependencies.len(),
+            ca: 0,
+            modules: vec![],
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        };
+        let coupling = CouplingData {
+            crates: vec![
+                krate("core", &["util"]),
+                krate("util", &[]),
+                krate("app_a", &["core"]),
+                krate("app_b", &["core"]),
+                krate("app_c", &["core"]),
+            ],
+            components: vec![],
+            granularity: crate::cli::CouplingGranularity::Crate,
+            analysis_path: PathBuf::from("."),
+        };
+        let rca = RustCodeAnalysisData {
+            analysis_results: vec![],
+            analysis_path: PathBuf::from("."),
+            thresholds: ComplexityThresholds::default(),
+            rollups: ComplexityRollups {
+                components: vec![ComplexityRollup {
+                    name: "cli".to_string(),
+                    files: 1,
+                    functions: 2,
+                    sloc: 40.0,
+                    lloc: 12.0,
+                    cyclomatic: Distribution::from_values(&[1.0, 5.0]),
+                    cognitive: Distribution::default(),
+                    halstead_effort: 100.0,
+                    mi_mean: Some(72.5),
+                }],
+                ..Default::default()
+            },
+        };
+        let data = AllReportData::with_results(None, None, Some(Ok(coupling)), Some(Ok(rca)));
+
+        let rows = dashboard_rows(&data, &HashMap::new());
+        assert_eq!(
+            rows.keys().collect::<Vec<_>>(),
+            ["app_a", "app_b", "app_c", "cli", "core", "util"]
+        );
+        assert!(rows["cli"].complexity.is_some() && rows["cli"].coupling.is_none());
+        assert_eq!(rows["core"].coupling, Some((1, 3)));
+
+        let html = render_component_dashboard(&data, &HashMap::new())
+            .unwrap()
+            .into_string();
+        assert!(html.contains("Component Dashboard"));
+        assert!(html.contains("72.5"));
+        assert!(html.contains("0.25"), "instability of core is 1 / (1 + 3)");
+    }
+
+    #[test]
+    fn test_component_dashboard_keys_every_rule_by_defined_component() {
+        use crate::cli::ComponentMode;
+        use crate::volatility_rule::CrateStats;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for (name, file, source) in [
+            (
+                "core",
+                "lib.rs",
+                "pub fn add(a: i32, b: i32) -> i32 {\n    let sum = a + b;\n    sum\n}\n",
+            ),
+            ("cli", "main.rs", "fn main() {\n    println!(\"hi\");\n}\n"),
+        ] {
+            let crate_dir = temp_dir.path().join("crates").join(name);
+            std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+            std::fs::write(
+                crate_dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+            )
+            .unwrap();
+            std::fs::write(crate_dir.join("src").join(file), source).unwrap();
+        }
+
+        let mut args = create_test_args(temp_dir.path().to_str().unwrap());
+        args.components = ComponentMode::Crate;
+        args.component_definitions =
+            BTreeMap::from([("platform".to_string(), vec!["crates/core/".to_string()])]);
+        let crate_components = crate_components(&args).unwrap();
+        assert_eq!(crate_components["core"], "platform");
+        assert_eq!(crate_components["cli"], "cli");
+
+        let statement_count = StatementCountRule::new().analyze(&statement_count_args(&args));
+        let coupling = CouplingData {
+            crates: vec![
+                crate::coupling_rule::CrateCoupling {
+                    name: "cli".to_string(),
+                    ce: 1,
+                    ca: 0,
+                    modules: vec![],
+                    dependencies: ["core".to_string()].into(),
+                },
+                crate::coupling_rule::CrateCoupling {
+                    name: "core".to_string(),
+                    ce: 0,
+                    ca: 1,
+                    modules: vec![],
+                    dependencies: Default::default(),
+                },
+            ],
+            components: vec![],
+            granularity: crate::cli::CouplingGranularity::Crate,
+            analysis_path: temp_dir.path().to_path_buf(),
+        };
+        let stats = |raw_score| CrateStats {
+            raw_score,
+            ..Default::default()
+        };
+        let volatility = VolatilityData {
+            crate_stats_map: [
+                ("core".to_string(), stats(3.0)),
+                ("platform".to_string(), stats(2.0)),
+                ("cli".to_string(), stats(1.0)),
+            ]
+            .into(),
+            normalize: false,
+            alpha: 0.01,
+            analysis_path: temp_dir.path().to_path_buf(),
+        };
+        let data = AllReportData::with_results(
+            Some(statement_count),
+            Some(Ok(volatility)),
+            Some(Ok(coupling)),
+            None,
+        );
+
+        let rows = dashboard_rows(&data, &crate_components);
+        assert_eq!(rows.keys().collect::<Vec<_>>(), ["cli", "platform"]);
+        assert_eq!(rows["platform"].statements, Some(2));
+        assert_eq!(rows["cli"].statements, Some(1));
+        assert_eq!(rows["platform"].coupling, Some((0, 1)));
+        assert_eq!(rows["cli"].coupling, Some((1, 0)));
+        assert_eq!(rows["platform"].volatility, Some(5.0));
+        assert_eq!(rows["cli"].volatility, Some(1.0));
+
+        let html = render_component_dashboard(&data, &crate_components)
+            .unwrap()
+            .into_string();
+        assert!(html.contains("Stmts"));
+        assert!(html.contains("platform"));
+    }
 }
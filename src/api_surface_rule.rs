@@ -0,0 +1,1484 @@
+//! API Surface Rule
+//!
+//! This module provides the API surface rule, which measures how much of each
+//! library crate is exposed to its dependents.
+//!
+//! # Overview
+//!
+//! Starting from `src/lib.rs`, the rule follows every `mod` declaration to
+//! build the crate's module tree, then works out which items are reachable
+//! from the crate root: `pub` items in `pub` modules, and anything exposed
+//! through a `pub use` re-export, including glob re-exports. For each crate it
+//! reports:
+//!
+//! - **Public / Private Items**: Reachable `pub` items against every other item
+//! - **Pub Fields**: `pub` fields on reachable structs and unions
+//! - **Public Statements**: The share of the crate's statements that sit in
+//!   reachable functions and methods
+//! - **Leaks**: Reachable items whose signature names a crate type that is
+//!   not itself reachable, so dependents can see it but not name it
+//!
+//! Only plain `pub` counts as public; `pub(crate)` and `pub(super)` items are
+//! private. Test code is ignored. Together with the coupling instability
+//! numbers, a growing surface or a high share of public statements points at
+//! crates whose encapsulation is eroding.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::api_surface_rule::ApiSurfaceRule;
+//! use raff_core::{ApiSurfaceArgs, ApiSurfaceOutputFormat};
+//! use std::path::PathBuf;
+//!
+//! let rule = ApiSurfaceRule::new();
+//! let args = ApiSurfaceArgs {
+//!     path: PathBuf::from("."),
+//!     max_public_items: Some(200),
+//!     max_public_statement_share: Some(50.0),
+//!     output: ApiSurfaceOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path does not exist or is not a directory
+//! - No library crates are found
+//! - A module file cannot be read or is not valid Rust
+//! - A crate breaches `--max-public-items` or `--max-public-statement-share`
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Attribute, Expr, Fields, GenericParam, ImplItem, Item, Lit, Meta, UseTree, Visibility};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{ApiSurfaceArgs, ApiSurfaceOutputFormat, CiOutputFormat};
+use crate::components::discover_crates;
+use crate::counter::{ItemKind, StmtCounter, is_test_attr, item_attrs, type_name};
+use crate::error::{RaffError, Result};
+use crate::html_utils::{self, MetricRanges};
+use crate::output_utils::{path_uri, percentage};
+use crate::rule::Rule;
+use crate::statement_count_rule::child_module_dir;
+
+/// How many `use` declarations may be followed when resolving one path.
+const MAX_RESOLVE_DEPTH: usize = 16;
+
+/// Kind of an item counted towards a crate's surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiItemKind {
+    Function,
+    /// A function in an inherent `impl` block.
+    Method,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TypeAlias,
+    Const,
+    Static,
+    /// A `macro_rules!` macro; public when marked `#[macro_export]`.
+    Macro,
+}
+
+impl ApiItemKind {
+    /// Returns `true` for kinds that name a type or trait.
+    fn is_type(self) -> bool {
+        matches!(
+            self,
+            Self::Struct | Self::Enum | Self::Union | Self::Trait | Self::TypeAlias
+        )
+    }
+}
+
+impl std::fmt::Display for ApiItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Function => "fn",
+            Self::Method => "method",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Union => "union",
+            Self::Trait => "trait",
+            Self::TypeAlias => "type",
+            Self::Const => "const",
+            Self::Static => "static",
+            Self::Macro => "macro",
+        };
+        f.write_str(s)
+    }
+}
+
+/// An item reachable from the crate root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicItem {
+    /// Path of the item where it is defined, e.g. `my_crate::config::Config`.
+    pub path: String,
+    pub kind: ApiItemKind,
+    /// File defining the item, relative to the analysis path.
+    pub file: PathBuf,
+    /// Line of the item (1-indexed).
+    pub line: usize,
+    /// Number of `pub` fields, for structs and unions.
+    pub pub_fields: usize,
+}
+
+/// A reachable item whose signature names a type dependents cannot reach.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeakedType {
+    /// Path of the reachable item.
+    pub item: String,
+    /// Path of the internal type it names.
+    pub internal_type: String,
+    /// File defining the reachable item, relative to the analysis path.
+    pub file: PathBuf,
+    /// Line of the reachable item (1-indexed).
+    pub line: usize,
+}
+
+/// The public surface of one library crate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrateApiSurface {
+    /// Package name of the crate.
+    pub name: String,
+    /// The crate root, relative to the analysis path.
+    pub root_file: PathBuf,
+    /// Number of items reachable from the crate root.
+    pub public_items: usize,
+    /// Number of items that are not reachable from the crate root.
+    pub private_items: usize,
+    /// Public items per private item; the public count when nothing is private.
+    pub pub_to_private_ratio: f64,
+    /// Public items per kind.
+    pub public_by_kind: BTreeMap<ApiItemKind, usize>,
+    /// Number of `pub` fields on reachable structs and unions.
+    pub pub_fields: usize,
+    /// Number of non-test statements in the crate.
+    pub statements: usize,
+    /// Number of statements in reachable functions and methods.
+    pub public_statements: usize,
+    /// Percentage of the crate's statements in reachable functions and methods.
+    pub public_statement_share: f64,
+    /// Internal types named by reachable items.
+    pub leaks: Vec<LeakedType>,
+    /// The reachable items, sorted by path.
+    pub items: Vec<PublicItem>,
+}
+
+/// Data type for API surface analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSurfaceData {
+    /// Library crates, with the largest surface first.
+    pub crates: Vec<CrateApiSurface>,
+    /// Maximum number of public items per crate, if set.
+    pub max_public_items: Option<usize>,
+    /// Maximum percentage of statements in public functions, if set.
+    pub max_public_statement_share: Option<f64>,
+    /// The analyzed directory.
+    pub analysis_path: PathBuf,
+}
+
+/// A crate exceeding one of the configured limits.
+struct ThresholdBreach<'a> {
+    krate: &'a CrateApiSurface,
+    /// Short name of the limit, used in fingerprints.
+    limit: &'static str,
+    message: String,
+}
+
+impl ApiSurfaceData {
+    /// Returns every crate that exceeds a configured limit.
+    fn threshold_breaches(&self) -> Vec<ThresholdBreach<'_>> {
+        let mut breaches = Vec::new();
+        for krate in &self.crates {
+            if let Some(max) = self.max_public_items
+                && krate.public_items > max
+            {
+                breaches.push(ThresholdBreach {
+                    krate,
+                    limit: "public-items",
+                    message: format!(
+                        "Crate '{}' exposes {} public items, exceeding the limit of {max}",
+                        krate.name, krate.public_items
+                    ),
+                });
+            }
+            if let Some(max) = self.max_public_statement_share
+                && krate.public_statement_share > max
+            {
+                breaches.push(ThresholdBreach {
+                    krate,
+                    limit: "public-statement-share",
+                    message: format!(
+                        "Crate '{}' has {:.1}% of its statements in public functions, exceeding the limit of {max}%",
+                        krate.name, krate.public_statement_share
+                    ),
+                });
+            }
+        }
+        breaches
+    }
+
+    /// Returns the error to exit with when an API surface limit is breached.
+    fn thresholds_error(&self) -> Option<RaffError> {
+        let breaches = self.threshold_breaches().len();
+        (breaches > 0).then(|| {
+            RaffError::analysis_error(
+                "api_surface",
+                format!("{breaches} API surface threshold(s) breached."),
+            )
+        })
+    }
+}
+
+impl ToFindings for ApiSurfaceData {
+    fn to_findings(&self) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = self
+            .threshold_breaches()
+            .into_iter()
+            .map(|breach| Finding {
+                rule_id: "api-surface".to_string(),
+                rule_name: "API Surface Rule".to_string(),
+                severity: Severity::Error,
+                message: breach.message,
+                location: Some(Location::new(path_uri(&breach.krate.root_file))),
+                related_locations: Vec::new(),
+                help_uri: Some("https://github.com/liamwh/raff/docs/api-surface".to_string()),
+                fingerprint: Some(format!(
+                    "api-surface:{}:{}",
+                    breach.limit, breach.krate.name
+                )),
+            })
+            .collect();
+        for krate in &self.crates {
+            findings.extend(krate.leaks.iter().map(|leak| Finding {
+                rule_id: "api-surface".to_string(),
+                rule_name: "API Surface Rule".to_string(),
+                severity: Severity::Warning,
+                message: format!(
+                    "Public item '{}' exposes internal type '{}', which dependents cannot name",
+                    leak.item, leak.internal_type
+                ),
+                location: Some(Location::with_lines(
+                    path_uri(&leak.file),
+                    leak.line,
+                    leak.line,
+                )),
+                related_locations: Vec::new(),
+                help_uri: Some("https://github.com/liamwh/raff/docs/api-surface".to_string()),
+                fingerprint: Some(format!(
+                    "api-surface:leak:{}:{}",
+                    leak.item, leak.internal_type
+                )),
+            }));
+        }
+        findings
+    }
+}
+
+/// Rule to measure the public API surface of each library crate.
+#[derive(Debug, Default)]
+pub struct ApiSurfaceRule;
+
+impl Rule for ApiSurfaceRule {
+    type Config = ApiSurfaceArgs;
+    type Data = ApiSurfaceData;
+
+    fn name() -> &'static str {
+        "api_surface"
+    }
+
+    fn description() -> &'static str {
+        "Measures the public items, pub fields and leaked internal types of each library crate"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+impl ApiSurfaceRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &ApiSurfaceArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &ApiSurfaceArgs) -> Result<ApiSurfaceData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &ApiSurfaceArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "api-surface")?,
+            };
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+            // Leaks are warnings that don't fail CI; breached thresholds do
+            return match data.thresholds_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            };
+        }
+
+        let output = match args.output {
+            ApiSurfaceOutputFormat::Table => {
+                self.print_table(&data);
+                None
+            }
+            ApiSurfaceOutputFormat::Json => Some(serde_json::to_string_pretty(&data)?),
+            ApiSurfaceOutputFormat::Yaml => Some(serde_yaml::to_string(&data)?),
+            ApiSurfaceOutputFormat::Html => Some(html_utils::render_html_doc(
+                &format!("API Surface Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            )),
+        };
+        if let Some(output) = output {
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+        }
+        match data.thresholds_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &ApiSurfaceArgs) -> Result<ApiSurfaceData> {
+        let analysis_path = &args.path;
+        if !analysis_path.exists() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Path not found",
+                analysis_path.display().to_string(),
+            ));
+        }
+        if !analysis_path.is_dir() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Provided path is not a directory",
+                analysis_path.display().to_string(),
+            ));
+        }
+
+        let canonical_path = analysis_path.canonicalize()?;
+        let mut crates = Vec::new();
+        for krate in discover_crates(analysis_path)? {
+            let root_file = krate.directory(&canonical_path).join("src").join("lib.rs");
+            if !root_file.is_file() {
+                tracing::debug!(krate = %krate.name, "Skipping crate without a library target");
+                continue;
+            }
+            let tree = CrateTree::load(&krate.name.replace('-', "_"), &root_file)?;
+            crates.push(tree.surface(&krate.name, &canonical_path));
+        }
+        if crates.is_empty() {
+            return Err(RaffError::analysis_error(
+                "api_surface",
+                format!("No library crates found under {}", analysis_path.display()),
+            ));
+        }
+        crates.sort_by(|a, b| {
+            b.public_items
+                .cmp(&a.public_items)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(ApiSurfaceData {
+            crates,
+            max_public_items: args.max_public_items,
+            max_public_statement_share: args.max_public_statement_share,
+            analysis_path: analysis_path.clone(),
+        })
+    }
+
+    fn print_table(&self, data: &ApiSurfaceData) {
+        println!("\nAPI Surface Report Interpretation:");
+        println!("-----------------------------------");
+        println!(
+            "- Public Items: Items reachable from the crate root, directly or through `pub use` re-exports."
+        );
+        println!("- Pub:Private: Public items per private item; lower means better encapsulation.");
+        println!("- Pub Fields: `pub` fields on reachable structs and unions.");
+        println!(
+            "- Public Stmt %: Share of the crate's statements inside reachable functions and methods."
+        );
+        println!(
+            "- Leaks: Reachable items naming a crate type that dependents cannot reach themselves."
+        );
+        println!("-----------------------------------");
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Crate",
+            "Public Items",
+            "Private Items",
+            "Pub:Private",
+            "Pub Fields",
+            "Statements",
+            "Public Stmts",
+            "Public Stmt %",
+            "Leaks",
+            "Public Kinds"
+        ]);
+        for krate in &data.crates {
+            table.add_row(row![
+                krate.name,
+                r->krate.public_items,
+                r->krate.private_items,
+                r->format!("{:.2}", krate.pub_to_private_ratio),
+                r->krate.pub_fields,
+                r->krate.statements,
+                r->krate.public_statements,
+                r->format!("{:.1}", krate.public_statement_share),
+                r->krate.leaks.len(),
+                kind_summary(krate)
+            ]);
+        }
+        println!("\nPublic Surface by Crate:");
+        table.printstd();
+
+        if data.crates.iter().any(|krate| !krate.leaks.is_empty()) {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Crate", "Public Item", "Internal Type", "Location"]);
+            for krate in &data.crates {
+                for leak in &krate.leaks {
+                    table.add_row(row![
+                        krate.name,
+                        leak.item,
+                        leak.internal_type,
+                        format!("{}:{}", path_uri(&leak.file), leak.line)
+                    ]);
+                }
+            }
+            println!("\nLeaked Internal Types:");
+            table.printstd();
+        }
+
+        let breaches = data.threshold_breaches();
+        if !breaches.is_empty() {
+            println!("\nThreshold Breaches:");
+            for breach in breaches {
+                println!("- {}", breach.message);
+            }
+        }
+    }
+
+    fn render_html_body(&self, data: &ApiSurfaceData) -> Markup {
+        let explanations = [
+            (
+                "Public Items",
+                "Items reachable from the crate root, directly or through `pub use` re-exports. Only plain `pub` counts.",
+            ),
+            (
+                "Pub:Private",
+                "Public items per private item; lower means better encapsulation.",
+            ),
+            (
+                "Pub Fields",
+                "`pub` fields on reachable structs and unions, which freeze the type's representation.",
+            ),
+            (
+                "Public Stmt %",
+                "Share of the crate's statements inside reachable functions and methods.",
+            ),
+            (
+                "Leaks",
+                "Reachable items naming a crate type that dependents cannot reach themselves.",
+            ),
+        ];
+        let shares: Vec<f64> = data
+            .crates
+            .iter()
+            .map(|krate| krate.public_statement_share)
+            .collect();
+        let ranges = MetricRanges::from_values(&shares, false);
+        let breaches = data.threshold_breaches();
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { "Public Surface by Crate" }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Crate" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="number" { "Public Items" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Private Items" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Pub:Private" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Pub Fields" }
+                        th class="sortable-header" data-column-index="5" data-sort-type="number" { "Statements" }
+                        th class="sortable-header" data-column-index="6" data-sort-type="number" { "Public Stmts" }
+                        th class="sortable-header" data-column-index="7" data-sort-type="number" { "Public Stmt %" }
+                        th class="sortable-header" data-column-index="8" data-sort-type="number" { "Leaks" }
+                        th { "Public Kinds" }
+                    }
+                }
+                tbody {
+                    @for krate in &data.crates {
+                        tr {
+                            td { (krate.name) }
+                            td { (krate.public_items) }
+                            td { (krate.private_items) }
+                            td { (format!("{:.2}", krate.pub_to_private_ratio)) }
+                            td { (krate.pub_fields) }
+                            td { (krate.statements) }
+                            td { (krate.public_statements) }
+                            td style=({ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(krate.public_statement_share, r))}) { (format!("{:.1}", krate.public_statement_share)) }
+                            td { (krate.leaks.len()) }
+                            td { (kind_summary(krate)) }
+                        }
+                    }
+                }
+            }
+            @if data.crates.iter().any(|krate| !krate.leaks.is_empty()) {
+                h2 { "Leaked Internal Types" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Crate" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Public Item" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Internal Type" }
+                            th { "Location" }
+                        }
+                    }
+                    tbody {
+                        @for krate in &data.crates {
+                            @for leak in &krate.leaks {
+                                tr {
+                                    td { (krate.name) }
+                                    td { (leak.item) }
+                                    td { (leak.internal_type) }
+                                    td { (format!("{}:{}", path_uri(&leak.file), leak.line)) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !breaches.is_empty() {
+                h2 { "Threshold Breaches" }
+                ul {
+                    @for breach in &breaches {
+                        li { (breach.message) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A module of the crate being analyzed.
+#[derive(Debug)]
+struct Module {
+    /// Path of the module, starting with the crate name.
+    path: String,
+    /// Whether the module is declared plain `pub`.
+    public: bool,
+    parent: Option<usize>,
+    children: BTreeMap<String, usize>,
+    /// Indices of the items defined directly in the module.
+    definitions: Vec<usize>,
+    uses: Vec<UseDecl>,
+}
+
+/// One name imported by a `use` declaration.
+#[derive(Debug)]
+struct UseDecl {
+    /// The imported path, relative to the declaring module.
+    path: Vec<String>,
+    /// The name the import is visible under; `None` for a glob import.
+    alias: Option<String>,
+    public: bool,
+}
+
+/// An item defined in the crate being analyzed.
+#[derive(Debug)]
+struct Definition {
+    name: String,
+    kind: ApiItemKind,
+    module: usize,
+    /// Whether the item is declared plain `pub`, or is an exported macro.
+    public: bool,
+    /// Whether the item is a `#[macro_export]` macro, public at the crate root.
+    exported: bool,
+    file: PathBuf,
+    start_line: usize,
+    end_line: usize,
+    /// Name of the implementing type, for methods.
+    self_type: Option<String>,
+    pub_fields: usize,
+    /// Paths named in the item's public signature.
+    references: Vec<Vec<String>>,
+}
+
+/// What a path resolves to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Target {
+    Module(usize),
+    Definition(usize),
+}
+
+/// Statements of one file of the crate.
+struct FileStatements {
+    file: PathBuf,
+    counter: StmtCounter,
+}
+
+/// The module tree and items of one library crate.
+struct CrateTree {
+    modules: Vec<Module>,
+    definitions: Vec<Definition>,
+    files: Vec<FileStatements>,
+    root_file: PathBuf,
+}
+
+impl CrateTree {
+    /// Loads the crate rooted at `root_file`, following `mod` declarations.
+    fn load(crate_name: &str, root_file: &Path) -> Result<Self> {
+        let mut tree = Self {
+            modules: vec![Module {
+                path: crate_name.to_string(),
+                public: true,
+                parent: None,
+                children: BTreeMap::new(),
+                definitions: Vec::new(),
+                uses: Vec::new(),
+            }],
+            definitions: Vec::new(),
+            files: Vec::new(),
+            root_file: root_file.to_path_buf(),
+        };
+        tree.load_file(root_file, 0)?;
+        Ok(tree)
+    }
+
+    fn load_file(&mut self, file: &Path, module: usize) -> Result<()> {
+        let content = fs::read_to_string(file)
+            .map_err(|e| RaffError::io_error_with_source("read source file", file.into(), e))?;
+        let ast = syn::parse_file(&content)
+            .map_err(|e| RaffError::parse_error_with_file(file.into(), e.to_string()))?;
+        let mut counter = StmtCounter::new();
+        counter.visit_file(&ast);
+        self.files.push(FileStatements {
+            file: file.to_path_buf(),
+            counter,
+        });
+        self.add_items(&ast.items, module, file, &child_module_dir(file), false)
+    }
+
+    /// Records `items`, declared in `module` in `file`. Out-of-line child
+    /// modules live in `module_dir`; `inline` is set inside a `mod { .. }`.
+    fn add_items(
+        &mut self,
+        items: &[Item],
+        module: usize,
+        file: &Path,
+        module_dir: &Path,
+        inline: bool,
+    ) -> Result<()> {
+        for item in items {
+            if item_attrs(item).iter().any(is_test_attr) {
+                continue;
+            }
+            match item {
+                Item::Mod(item_mod) => {
+                    let name = item_mod.ident.to_string();
+                    let child = self.modules.len();
+                    self.modules.push(Module {
+                        path: format!("{}::{name}", self.modules[module].path),
+                        public: is_pub(&item_mod.vis),
+                        parent: Some(module),
+                        children: BTreeMap::new(),
+                        definitions: Vec::new(),
+                        uses: Vec::new(),
+                    });
+                    self.modules[module].children.insert(name.clone(), child);
+                    if let Some((_, content)) = &item_mod.content {
+                        self.add_items(content, child, file, &module_dir.join(&name), true)?;
+                        continue;
+                    }
+                    let candidates = match path_attr(&item_mod.attrs) {
+                        Some(path) if inline => vec![module_dir.join(path)],
+                        Some(path) => vec![file.parent().unwrap_or(Path::new("")).join(path)],
+                        None => vec![
+                            module_dir.join(format!("{name}.rs")),
+                            module_dir.join(&name).join("mod.rs"),
+                        ],
+                    };
+                    match candidates.into_iter().find(|candidate| candidate.is_file()) {
+                        Some(child_file) => self.load_file(&child_file, child)?,
+                        None => tracing::debug!(
+                            module = %self.modules[child].path,
+                            "Module file not found, skipping"
+                        ),
+                    }
+                }
+                Item::Use(item_use) => {
+                    let public = is_pub(&item_use.vis);
+                    if item_use.leading_colon.is_none() {
+                        flatten_use(&item_use.tree, &mut Vec::new(), public, &mut |decl| {
+                            self.modules[module].uses.push(decl)
+                        });
+                    }
+                }
+                Item::Impl(item_impl) if item_impl.trait_.is_none() => {
+                    let self_type = type_name(&item_impl.self_ty);
+                    let impl_generics: HashSet<String> = item_impl
+                        .generics
+                        .type_params()
+                        .map(|param| param.ident.to_string())
+                        .collect();
+                    for impl_item in &item_impl.items {
+                        let ImplItem::Fn(method) = impl_item else {
+                            continue;
+                        };
+                        if method.attrs.iter().any(is_test_attr) {
+                            continue;
+                        }
+                        let mut references = TypeReferences {
+                            generics: impl_generics.clone(),
+                            ..TypeReferences::default()
+                        };
+                        references.visit_signature(&method.sig);
+                        self.define(Definition {
+                            name: method.sig.ident.to_string(),
+                            kind: ApiItemKind::Method,
+                            module,
+                            public: is_pub(&method.vis),
+                            exported: false,
+                            file: file.to_path_buf(),
+                            start_line: method.span().start().line,
+                            end_line: method.span().end().line,
+                            self_type: Some(self_type.clone()),
+                            pub_fields: 0,
+                            references: references.finish(),
+                        });
+                    }
+                }
+                Item::ForeignMod(foreign) => {
+                    for foreign_item in &foreign.items {
+                        let (name, kind, vis) = match foreign_item {
+                            syn::ForeignItem::Fn(f) => {
+                                (&f.sig.ident, ApiItemKind::Function, &f.vis)
+                            }
+                            syn::ForeignItem::Static(s) => (&s.ident, ApiItemKind::Static, &s.vis),
+                            _ => continue,
+                        };
+                        let mut references = TypeReferences::default();
+                        references.visit_foreign_item(foreign_item);
+                        self.define(Definition {
+                            name: name.to_string(),
+                            kind,
+                            module,
+                            public: is_pub(vis),
+                            exported: false,
+                            file: file.to_path_buf(),
+                            start_line: foreign_item.span().start().line,
+                            end_line: foreign_item.span().end().line,
+                            self_type: None,
+                            pub_fields: 0,
+                            references: references.finish(),
+                        });
+                    }
+                }
+                _ => {
+                    if let Some(definition) = definition(item, module, file) {
+                        self.define(definition);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn define(&mut self, definition: Definition) {
+        self.modules[definition.module]
+            .definitions
+            .push(self.definitions.len());
+        self.definitions.push(definition);
+    }
+
+    /// Resolves `path` as written in `module` to every module or item it
+    /// may name. Paths leaving the crate resolve to nothing.
+    fn resolve(&self, module: usize, path: &[String], depth: usize) -> Vec<Target> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return Vec::new();
+        }
+        let Some((first, rest)) = path.split_first() else {
+            return vec![Target::Module(module)];
+        };
+        let mut targets = match first.as_str() {
+            "crate" => vec![Target::Module(0)],
+            _ => self.member(module, first, depth),
+        };
+        for segment in rest {
+            targets = targets
+                .into_iter()
+                .filter_map(|target| match target {
+                    Target::Module(module) => Some(module),
+                    Target::Definition(_) => None,
+                })
+                .flat_map(|module| self.member(module, segment, depth))
+                .collect();
+        }
+        targets.sort();
+        targets.dedup();
+        targets
+    }
+
+    /// Resolves a single path segment within `module`.
+    fn member(&self, module: usize, name: &str, depth: usize) -> Vec<Target> {
+        match name {
+            "self" => vec![Target::Module(module)],
+            "super" => self.modules[module]
+                .parent
+                .map(Target::Module)
+                .into_iter()
+                .collect(),
+            _ => {
+                let found = self.lookup(module, name, depth);
+                if !found.is_empty() {
+                    return found;
+                }
+                let mut visited = HashSet::from([module]);
+                self.lookup_glob(module, name, depth, &mut visited)
+            }
+        }
+    }
+
+    /// Looks up `name` among the child modules, items and named imports of
+    /// `module`.
+    fn lookup(&self, module: usize, name: &str, depth: usize) -> Vec<Target> {
+        let scope = &self.modules[module];
+        let mut found: Vec<Target> = scope
+            .children
+            .get(name)
+            .map(|&child| Target::Module(child))
+            .into_iter()
+            .collect();
+        found.extend(
+            scope
+                .definitions
+                .iter()
+                .filter(|&&index| {
+                    let definition = &self.definitions[index];
+                    definition.kind != ApiItemKind::Method && definition.name == name
+                })
+                .map(|&index| Target::Definition(index)),
+        );
+        for decl in &scope.uses {
+            if decl.alias.as_deref() == Some(name) {
+                found.extend(self.resolve(module, &decl.path, depth + 1));
+            }
+        }
+        found
+    }
+
+    /// Looks up `name` through the glob imports of `module`.
+    fn lookup_glob(
+        &self,
+        module: usize,
+        name: &str,
+        depth: usize,
+        visited: &mut HashSet<usize>,
+    ) -> Vec<Target> {
+        for decl in self.modules[module]
+            .uses
+            .iter()
+            .filter(|d| d.alias.is_none())
+        {
+            for target in self.resolve(module, &decl.path, depth + 1) {
+                let Target::Module(glob) = target else {
+                    continue;
+                };
+                if !visited.insert(glob) {
+                    continue;
+                }
+                let found = self.lookup(glob, name, depth + 1);
+                if !found.is_empty() {
+                    return found;
+                }
+                let found = self.lookup_glob(glob, name, depth + 1, visited);
+                if !found.is_empty() {
+                    return found;
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Returns, per item, whether it is reachable from the crate root.
+    fn reachable(&self) -> Vec<bool> {
+        let mut exposed = vec![false; self.modules.len()];
+        exposed[0] = true;
+        let mut reachable = vec![false; self.definitions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, module) in self.modules.iter().enumerate() {
+                if !exposed[index] {
+                    continue;
+                }
+                let mut targets: Vec<Target> = module
+                    .children
+                    .values()
+                    .filter(|&&child| self.modules[child].public)
+                    .map(|&child| Target::Module(child))
+                    .collect();
+                targets.extend(
+                    module
+                        .definitions
+                        .iter()
+                        .filter(|&&d| {
+                            self.definitions[d].public
+                                && self.definitions[d].kind != ApiItemKind::Method
+                        })
+                        .map(|&d| Target::Definition(d)),
+                );
+                for decl in module.uses.iter().filter(|decl| decl.public) {
+                    targets.extend(self.resolve(index, &decl.path, 0));
+                }
+                for target in targets {
+                    match target {
+                        Target::Module(module) if !exposed[module] => {
+                            exposed[module] = true;
+                            changed = true;
+                        }
+                        Target::Definition(d) if self.definitions[d].public && !reachable[d] => {
+                            reachable[d] = true;
+                            changed = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        for (index, definition) in self.definitions.iter().enumerate() {
+            reachable[index] |= definition.exported;
+        }
+        // Methods are reachable through their reachable self type
+        let types: HashSet<&str> = self
+            .definitions
+            .iter()
+            .zip(&reachable)
+            .filter(|(definition, reachable)| **reachable && definition.kind.is_type())
+            .map(|(definition, _)| definition.name.as_str())
+            .collect();
+        for (index, definition) in self.definitions.iter().enumerate() {
+            if definition.kind == ApiItemKind::Method
+                && definition.public
+                && definition
+                    .self_type
+                    .as_deref()
+                    .is_some_and(|name| types.contains(name))
+            {
+                reachable[index] = true;
+            }
+        }
+        reachable
+    }
+
+    /// Returns the path of `definition` where it is defined.
+    fn definition_path(&self, definition: &Definition) -> String {
+        let module = if definition.exported {
+            &self.modules[0]
+        } else {
+            &self.modules[definition.module]
+        };
+        match &definition.self_type {
+            Some(self_type) => format!("{}::{self_type}::{}", module.path, definition.name),
+            None => format!("{}::{}", module.path, definition.name),
+        }
+    }
+
+    /// Summarizes the crate's surface, with paths relative to `analysis_path`.
+    fn surface(&self, name: &str, analysis_path: &Path) -> CrateApiSurface {
+        let relative = |path: &Path| {
+            path.strip_prefix(analysis_path)
+                .unwrap_or(path)
+                .to_path_buf()
+        };
+        let reachable = self.reachable();
+
+        let mut items = Vec::new();
+        let mut public_by_kind = BTreeMap::new();
+        let mut leaks = Vec::new();
+        let mut seen_leaks = BTreeSet::new();
+        for (index, definition) in self.definitions.iter().enumerate() {
+            if !reachable[index] {
+                continue;
+            }
+            let path = self.definition_path(definition);
+            *public_by_kind.entry(definition.kind).or_insert(0) += 1;
+            for reference in &definition.references {
+                for target in self.resolve(definition.module, reference, 0) {
+                    let Target::Definition(target) = target else {
+                        continue;
+                    };
+                    let internal = &self.definitions[target];
+                    if reachable[target] || !internal.kind.is_type() {
+                        continue;
+                    }
+                    let internal_type = self.definition_path(internal);
+                    if seen_leaks.insert((path.clone(), internal_type.clone())) {
+                        leaks.push(LeakedType {
+                            item: path.clone(),
+                            internal_type,
+                            file: relative(&definition.file),
+                            line: definition.start_line,
+                        });
+                    }
+                }
+            }
+            items.push(PublicItem {
+                path,
+                kind: definition.kind,
+                file: relative(&definition.file),
+                line: definition.start_line,
+                pub_fields: definition.pub_fields,
+            });
+        }
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut statements = 0;
+        let mut public_statements = 0;
+        for file in &self.files {
+            statements += file.counter.count - file.counter.test_count;
+            let spans: Vec<(usize, usize)> = self
+                .definitions
+                .iter()
+                .zip(&reachable)
+                .filter(|(definition, reachable)| {
+                    **reachable
+                        && definition.file == file.file
+                        && matches!(definition.kind, ApiItemKind::Function | ApiItemKind::Method)
+                })
+                .map(|(definition, _)| (definition.start_line, definition.end_line))
+                .collect();
+            public_statements += file
+                .counter
+                .items
+                .iter()
+                .filter(|item| !item.is_test && item.kind != ItemKind::Module)
+                .filter(|item| {
+                    spans
+                        .iter()
+                        .any(|&(start, end)| item.start_line >= start && item.end_line <= end)
+                })
+                .map(|item| item.statements)
+                .sum::<usize>();
+        }
+
+        let public_items = items.len();
+        let private_items = self.definitions.len() - public_items;
+        CrateApiSurface {
+            name: name.to_string(),
+            root_file: relative(&self.root_file),
+            public_items,
+            private_items,
+            pub_to_private_ratio: public_items as f64 / private_items.max(1) as f64,
+            public_by_kind,
+            pub_fields: items.iter().map(|item| item.pub_fields).sum(),
+            statements,
+            public_statements,
+            public_statement_share: percentage(public_statements, statements),
+            leaks,
+            items,
+        }
+    }
+}
+
+/// Builds the definition of a plain item, or `None` for items that are not
+/// counted.
+fn definition(item: &Item, module: usize, file: &Path) -> Option<Definition> {
+    let mut references = TypeReferences::default();
+    let mut pub_fields = 0;
+    let mut exported = false;
+    let (ident, kind, public) = match item {
+        Item::Fn(f) => {
+            references.visit_signature(&f.sig);
+            (&f.sig.ident, ApiItemKind::Function, is_pub(&f.vis))
+        }
+        Item::Struct(s) => {
+            references.visit_generics(&s.generics);
+            pub_fields = public_fields(&s.fields, &mut references);
+            (&s.ident, ApiItemKind::Struct, is_pub(&s.vis))
+        }
+        Item::Union(u) => {
+            references.visit_generics(&u.generics);
+            pub_fields = public_fields(&Fields::Named(u.fields.clone()), &mut references);
+            (&u.ident, ApiItemKind::Union, is_pub(&u.vis))
+        }
+        Item::Enum(e) => {
+            references.visit_item_enum(e);
+            (&e.ident, ApiItemKind::Enum, is_pub(&e.vis))
+        }
+        Item::Trait(t) => {
+            references.visit_item_trait(t);
+            (&t.ident, ApiItemKind::Trait, is_pub(&t.vis))
+        }
+        Item::Type(t) => {
+            references.visit_item_type(t);
+            (&t.ident, ApiItemKind::TypeAlias, is_pub(&t.vis))
+        }
+        Item::Const(c) => {
+            references.visit_type(&c.ty);
+            (&c.ident, ApiItemKind::Const, is_pub(&c.vis))
+        }
+        Item::Static(s) => {
+            references.visit_type(&s.ty);
+            (&s.ident, ApiItemKind::Static, is_pub(&s.vis))
+        }
+        Item::Macro(m) => {
+            let ident = m.ident.as_ref()?;
+            exported = m
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("macro_export"));
+            (ident, ApiItemKind::Macro, exported)
+        }
+        _ => return None,
+    };
+    Some(Definition {
+        name: ident.to_string(),
+        kind,
+        module,
+        public,
+        exported,
+        file: file.to_path_buf(),
+        start_line: item.span().start().line,
+        end_line: item.span().end().line,
+        self_type: None,
+        pub_fields,
+        references: references.finish(),
+    })
+}
+
+/// Counts the `pub` fields in `fields`, recording the types they name.
+fn public_fields(fields: &Fields, references: &mut TypeReferences) -> usize {
+    let mut count = 0;
+    for field in fields.iter().filter(|field| is_pub(&field.vis)) {
+        references.visit_type(&field.ty);
+        count += 1;
+    }
+    count
+}
+
+/// Collects the paths named in an item's signature, skipping bodies,
+/// attributes and generic parameters.
+#[derive(Default)]
+struct TypeReferences {
+    paths: Vec<Vec<String>>,
+    generics: HashSet<String>,
+}
+
+impl TypeReferences {
+    fn finish(self) -> Vec<Vec<String>> {
+        let mut paths: Vec<Vec<String>> = self
+            .paths
+            .into_iter()
+            .filter(|path| {
+                path.first().is_some_and(|first| first != "Self")
+                    && !(path.len() == 1 && self.generics.contains(&path[0]))
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+impl<'ast> Visit<'ast> for TypeReferences {
+    fn visit_attribute(&mut self, _: &'ast Attribute) {}
+
+    fn visit_block(&mut self, _: &'ast syn::Block) {}
+
+    fn visit_expr(&mut self, _: &'ast Expr) {}
+
+    fn visit_macro(&mut self, _: &'ast syn::Macro) {}
+
+    fn visit_visibility(&mut self, _: &'ast Visibility) {}
+
+    fn visit_generic_param(&mut self, param: &'ast GenericParam) {
+        if let GenericParam::Type(param) = param {
+            self.generics.insert(param.ident.to_string());
+        }
+        syn::visit::visit_generic_param(self, param);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path.leading_colon.is_none() {
+            self.paths.push(
+                path.segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect(),
+            );
+        }
+        syn::visit::visit_path(self, path);
+    }
+}
+
+/// Flattens a `use` tree into one declaration per imported name. `prefix`
+/// holds the segments leading to `tree`.
+fn flatten_use(
+    tree: &UseTree,
+    prefix: &mut Vec<String>,
+    public: bool,
+    push: &mut impl FnMut(UseDecl),
+) {
+    match tree {
+        UseTree::Path(path) => {
+            prefix.push(path.ident.to_string());
+            flatten_use(&path.tree, prefix, public, push);
+            prefix.pop();
+        }
+        UseTree::Name(name) => {
+            let ident = name.ident.to_string();
+            // `use module::{self}` imports the module itself
+            let (path, alias) = if ident == "self" {
+                (prefix.clone(), prefix.last().cloned())
+            } else {
+                (
+                    [prefix.as_slice(), std::slice::from_ref(&ident)].concat(),
+                    Some(ident),
+                )
+            };
+            push(UseDecl {
+                path,
+                alias,
+                public,
+            });
+        }
+        UseTree::Rename(rename) => {
+            let alias = rename.rename.to_string();
+            if alias != "_" {
+                let ident = rename.ident.to_string();
+                let path = if ident == "self" {
+                    prefix.clone()
+                } else {
+                    [prefix.as_slice(), &[ident]].concat()
+                };
+                push(UseDecl {
+                    path,
+                    alias: Some(alias),
+                    public,
+                });
+            }
+        }
+        UseTree::Glob(_) => push(UseDecl {
+            path: prefix.clone(),
+            alias: None,
+            public,
+        }),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use(tree, prefix, public, push);
+            }
+        }
+    }
+}
+
+/// Returns `true` only for plain `pub`, not `pub(crate)` and friends.
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// Returns the file named by a `#[path = "..."]` attribute.
+fn path_attr(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("path") => match &meta.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(path) => Some(path.value()),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Formats the public item counts of `krate` per kind, e.g. `fn 3, struct 1`.
+fn kind_summary(krate: &CrateApiSurface) -> String {
+    krate
+        .public_by_kind
+        .iter()
+        .map(|(kind, count)| format!("{kind} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn args(path: &Path) -> ApiSurfaceArgs {
+        ApiSurfaceArgs {
+            path: path.to_path_buf(),
+            max_public_items: None,
+            max_public_statement_share: None,
+            output: ApiSurfaceOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    /// A crate with a public module, a private module re-exported in part,
+    /// and a public function returning a private type.
+    fn write_crate(dir: &Path) {
+        write(dir, "Cargo.toml", "[package]\nname = \"demo-lib\"\n");
+        write(
+            dir,
+            "src/lib.rs",
+            "pub mod api;\nmod internal;\n\npub use internal::Engine;\n\n#[macro_export]\nmacro_rules! demo { () => {}; }\n\npub(crate) fn helper() -> u32 {\n    1\n}\n\n#[cfg(test)]\nmod tests {\n    pub fn ignored() {}\n}\n",
+        );
+        write(
+            dir,
+            "src/api.rs",
+            "use crate::internal::Secret;\n\npub struct Config {\n    pub name: String,\n    pub retries: u32,\n    secret: u32,\n}\n\npub fn secret() -> Secret {\n    let value = 1;\n    Secret(value)\n}\n\nfn private() {}\n",
+        );
+        write(
+            dir,
+            "src/internal.rs",
+            "pub struct Engine;\n\npub struct Secret(pub u32);\n\nimpl Engine {\n    pub fn start(&self) {\n        let a = 1;\n        let b = a + 1;\n        drop(b);\n    }\n\n    fn stop(&self) {}\n}\n\nimpl Secret {\n    pub fn reveal(&self) {}\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_flatten_use_handles_groups_renames_self_and_globs() {
+        let item: syn::ItemUse =
+            syn::parse_str("use crate::a::{b, c as d, e::{self, *}, f as _};").unwrap();
+        let mut decls = Vec::new();
+        flatten_use(&item.tree, &mut Vec::new(), false, &mut |decl| {
+            decls.push((decl.path.join("::"), decl.alias))
+        });
+        assert_eq!(
+            decls,
+            vec![
+                ("crate::a::b".to_string(), Some("b".to_string())),
+                ("crate::a::c".to_string(), Some("d".to_string())),
+                ("crate::a::e".to_string(), Some("e".to_string())),
+                ("crate::a::e".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_follows_reexports_and_reports_leaks() {
+        let temp_dir = TempDir::new().unwrap();
+        write_crate(temp_dir.path());
+
+        let data = ApiSurfaceRule::new()
+            .analyze(&args(temp_dir.path()))
+            .unwrap();
+
+        assert_eq!(data.crates.len(), 1);
+        let krate = &data.crates[0];
+        assert_eq!(krate.name, "demo-lib");
+        let paths: Vec<&str> = krate.items.iter().map(|item| item.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "demo_lib::api::Config",
+                "demo_lib::api::secret",
+                "demo_lib::demo",
+                "demo_lib::internal::Engine",
+                "demo_lib::internal::Engine::start",
+            ]
+        );
+        // helper, private, Secret, Engine::stop and Secret::reveal
+        assert_eq!((krate.public_items, krate.private_items), (5, 5));
+        assert_eq!(krate.pub_fields, 2);
+        assert_eq!(krate.public_by_kind[&ApiItemKind::Method], 1);
+
+        assert_eq!(krate.leaks.len(), 1);
+        assert_eq!(krate.leaks[0].item, "demo_lib::api::secret");
+        assert_eq!(krate.leaks[0].internal_type, "demo_lib::internal::Secret");
+        assert_eq!(krate.leaks[0].line, 9);
+
+        // helper 1, secret 2, start 3; the test module is ignored
+        assert_eq!(krate.statements, 6);
+        assert_eq!(krate.public_statements, 5);
+
+        let findings = data.to_findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(data.thresholds_error().is_none());
+    }
+
+    #[test]
+    fn test_thresholds_produce_error_findings() {
+        let temp_dir = TempDir::new().unwrap();
+        write_crate(temp_dir.path());
+        let mut args = args(temp_dir.path());
+        args.max_public_items = Some(4);
+        args.max_public_statement_share = Some(50.0);
+
+        let data = ApiSurfaceRule::new().analyze(&args).unwrap();
+
+        assert!(data.thresholds_error().is_some());
+        let errors: Vec<_> = data
+            .to_findings()
+            .into_iter()
+            .filter(|f| f.severity == Severity::Error)
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].location.as_ref().unwrap().uri,
+            "src/lib.rs".to_string()
+        );
+    }
+
+    #[test]
+    fn test_analyze_rejects_workspace_without_library_crates() {
+        let temp_dir = TempDir::new().unwrap();
+        write(
+            temp_dir.path(),
+            "Cargo.toml",
+            "[package]\nname = \"tool\"\n",
+        );
+        write(temp_dir.path(), "src/main.rs", "fn main() {}\n");
+        assert!(
+            ApiSurfaceRule::new()
+                .analyze(&args(temp_dir.path()))
+                .is_err()
+        );
+    }
+}
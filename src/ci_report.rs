@@ -26,6 +26,7 @@
 //!         severity: Severity::Error,
 //!         message: "Component too large".to_string(),
 //!         location: None,
+//!         related_locations: Vec::new(),
 //!         help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
 //!         fingerprint: Some("unique-id".to_string()),
 //!     }
@@ -99,6 +100,11 @@ pub struct Finding {
     /// Optional location information for the finding.
     pub location: Option<Location>,
 
+    /// Other locations involved in the finding, such as the remaining copies
+    /// of a duplicated fragment. Maps to SARIF `relatedLocations`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<Location>,
+
     /// Optional URI to documentation about this rule/finding.
     pub help_uri: Option<String>,
 
@@ -214,6 +220,7 @@ pub fn normalize_repo_relative(path: &Path, repo_root: &Path) -> String {
 /// - `results[].level` = error/warning/note based on severity
 /// - `results[].message.text` = finding's message
 /// - `results[].locations[]` = location info if present
+/// - `results[].relatedLocations[]` = related locations, if any
 /// - `results[].partialFingerprints["primaryLocation"]` = finding's fingerprint
 pub fn to_sarif(findings: &[Finding]) -> Result<String> {
     // Group findings by rule_id for de-duplicated tool.driver.rules
@@ -432,6 +439,9 @@ struct SarifResult {
     message: SarifMessage,
     #[serde(skip_serializing_if = "Option::is_none")]
     locations: Option<Vec<SarifLocation>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(rename = "relatedLocations")]
+    related_locations: Vec<SarifLocation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "partialFingerprints")]
     partial_fingerprints: Option<HashMap<String, String>>,
@@ -444,6 +454,8 @@ struct SarifMessage {
 
 #[derive(Debug, Serialize)]
 struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
     physical_location: SarifPhysicalLocation,
 }
 
@@ -475,25 +487,39 @@ struct SarifInvocation {
     end_time_utc: String,
 }
 
+impl SarifLocation {
+    fn from_location(loc: &Location, id: Option<usize>) -> Self {
+        Self {
+            id,
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: loc.uri.clone(),
+                },
+                region: if loc.start_line.is_some() || loc.end_line.is_some() {
+                    Some(SarifRegion {
+                        start_line: loc.start_line,
+                        end_line: loc.end_line,
+                    })
+                } else {
+                    None
+                },
+            },
+        }
+    }
+}
+
 impl SarifResult {
     fn from_finding(finding: &Finding) -> Self {
-        let locations = finding.location.as_ref().map(|loc| {
-            vec![SarifLocation {
-                physical_location: SarifPhysicalLocation {
-                    artifact_location: SarifArtifactLocation {
-                        uri: loc.uri.clone(),
-                    },
-                    region: if loc.start_line.is_some() || loc.end_line.is_some() {
-                        Some(SarifRegion {
-                            start_line: loc.start_line,
-                            end_line: loc.end_line,
-                        })
-                    } else {
-                        None
-                    },
-                },
-            }]
-        });
+        let locations = finding
+            .location
+            .as_ref()
+            .map(|loc| vec![SarifLocation::from_location(loc, None)]);
+        let related_locations = finding
+            .related_locations
+            .iter()
+            .enumerate()
+            .map(|(id, loc)| SarifLocation::from_location(loc, Some(id)))
+            .collect();
 
         let partial_fingerprints = finding.fingerprint.as_ref().map(|fp| {
             let mut map = HashMap::new();
@@ -508,6 +534,7 @@ impl SarifResult {
                 text: finding.message.clone(),
             },
             locations,
+            related_locations,
             partial_fingerprints,
         }
     }
@@ -622,6 +649,7 @@ mod tests {
             severity: Severity::Error,
             message: "Test finding".to_string(),
             location: Some(Location::new("src/test.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://example.com/docs".to_string()),
             fingerprint: Some("test-fingerprint".to_string()),
         }];
@@ -636,6 +664,38 @@ mod tests {
             parsed["runs"][0]["results"][0]["message"]["text"],
             "Test finding"
         );
+        assert!(
+            parsed["runs"][0]["results"][0]
+                .get("relatedLocations")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_to_sarif_includes_related_locations() {
+        let findings = vec![Finding {
+            rule_id: "duplication".to_string(),
+            rule_name: "Duplication Rule".to_string(),
+            severity: Severity::Warning,
+            message: "Duplicated fragment".to_string(),
+            location: Some(Location::with_lines("src/a.rs".to_string(), 1, 10)),
+            related_locations: vec![Location::with_lines("src/b.rs".to_string(), 5, 14)],
+            help_uri: None,
+            fingerprint: None,
+        }];
+
+        let json = to_sarif(&findings).expect("SARIF generation should succeed");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&json).expect("Generated SARIF should be valid JSON");
+
+        let related = &parsed["runs"][0]["results"][0]["relatedLocations"];
+        assert_eq!(related.as_array().map_or(0, |v| v.len()), 1);
+        assert_eq!(related[0]["id"], 0);
+        assert_eq!(
+            related[0]["physical_location"]["artifact_location"]["uri"],
+            "src/b.rs"
+        );
+        assert_eq!(related[0]["physical_location"]["region"]["start_line"], 5);
     }
 
     #[test]
@@ -647,6 +707,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Finding 1".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -656,6 +717,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Finding 2".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -698,6 +760,7 @@ mod tests {
             severity: Severity::Error,
             message: "Test error".to_string(),
             location: Some(Location::new("src/test.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -719,6 +782,7 @@ mod tests {
             severity: Severity::Warning,
             message: "Test warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -739,6 +803,7 @@ mod tests {
             severity: Severity::Note,
             message: "Test note".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -759,6 +824,7 @@ mod tests {
             severity: Severity::Error,
             message: "Error: x < y & y > z".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -778,6 +844,7 @@ mod tests {
             severity: Severity::Warning,
             message: "Test message".to_string(),
             location: Some(Location::with_lines("src/test.rs".to_string(), 10, 20)),
+            related_locations: Vec::new(),
             help_uri: Some("https://example.com".to_string()),
             fingerprint: Some("abc123".to_string()),
         };
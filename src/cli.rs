@@ -1,5 +1,6 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Main CLI structure for `rust-ff`.
 /// This structure will be augmented by subcommands provided by different rules.
@@ -56,12 +57,28 @@ pub enum Commands {
     Volatility(VolatilityArgs),
     /// Analyzes code coupling between components.
     Coupling(CouplingArgs),
-    /// Perform extended code analysis using rust-code-analysis-cli on all src/ folders.
+    /// Perform extended code analysis with the builtin metrics engine or rust-code-analysis-cli.
     RustCodeAnalysis(RustCodeAnalysisArgs),
     /// Runs all analyses and generates a consolidated report.
     All(AllArgs),
     /// Produces a contributor report, ranking committers by their contribution stats.
     ContributorReport(ContributorReportArgs),
+    /// Reports the age of the current code in each crate, directory or file using git blame.
+    CodeAge(CodeAgeArgs),
+    /// Reports who owns each crate or module, its bus factor, and inactive owners.
+    Ownership(OwnershipArgs),
+    /// Suggests a CODEOWNERS file from history, or checks an existing one for drift.
+    Codeowners(CodeownersArgs),
+    /// Finds duplicated code fragments across the workspace, including clones shared between crates.
+    Duplication(DuplicationArgs),
+    /// Measures the public API surface of each library crate and flags internal types it leaks.
+    ApiSurface(ApiSurfaceArgs),
+    /// Locates unsafe code and FFI boundaries, and checks they stay inside the allowed modules.
+    UnsafeCode(UnsafeCodeArgs),
+    /// Counts unwrap, expect, panicking macros and indexing per module, and checks module budgets.
+    ErrorHandling(ErrorHandlingArgs),
+    /// Measures generic parameters, trait bounds and impls in type signatures, and flags overly generic APIs.
+    Generics(GenericsArgs),
 }
 
 /// Shared output format for CI/CD platforms.
@@ -86,6 +103,28 @@ pub enum StatementCountOutputFormat {
     // Potentially Json, Yaml in the future if detailed data is useful
 }
 
+/// Defines how files are grouped into components. Shared by all rules.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+pub enum ComponentMode {
+    /// The top-level directory under the analysis path.
+    #[default]
+    Directory,
+    /// The Cargo workspace member containing the file.
+    Crate,
+    /// The workspace member and its top-level module, e.g. `my-crate::parser`.
+    Module,
+}
+
+/// Defines the granularity level for the statement count report.
+#[derive(ValueEnum, Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub enum StatementCountGranularity {
+    /// Show statement totals per component.
+    #[default]
+    Component,
+    /// Also list the largest functions, methods and closures.
+    Item,
+}
+
 /// Arguments for the `statement-count` subcommand.
 #[derive(Args, Clone, Debug)]
 pub struct StatementCountArgs {
@@ -98,6 +137,62 @@ pub struct StatementCountArgs {
     #[clap(long, default_value_t = 10)]
     pub threshold: usize,
 
+    /// How files are grouped into components.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
+    /// Report granularity. `item` also lists the largest functions.
+    #[clap(long, value_enum, default_value_t = StatementCountGranularity::default())]
+    pub granularity: StatementCountGranularity,
+
+    /// Number of functions listed by `--granularity item`.
+    #[clap(long, default_value_t = 20)]
+    pub top: usize,
+
+    /// Maximum number of statements allowed in a single function or method.
+    /// Functions over this limit are reported with their location.
+    #[clap(long)]
+    pub max_function_statements: Option<usize>,
+
+    /// Apply thresholds to test code as well as production code. Test code is
+    /// `#[cfg(test)]` and `#[test]` items and files under `tests/`, `benches/`
+    /// and `examples/`; it is always reported in its own column.
+    #[clap(long)]
+    pub include_tests: bool,
+
+    /// Count statements inside macro invocations and `macro_rules!`
+    /// definitions. Bodies that parse as Rust are counted exactly; other
+    /// bodies, such as HTML templates, are approximated by their blocks and
+    /// semicolons. Files whose statements mostly come from macros are listed
+    /// separately.
+    #[clap(long)]
+    pub parse_macros: bool,
+
+    /// Maximum number of statements allowed in a single component,
+    /// independent of the size of the rest of the codebase.
+    #[clap(long)]
+    pub max_component_statements: Option<usize>,
+
+    /// Minimum number of statements expected in a component. Smaller
+    /// components are reported as anemic.
+    #[clap(long)]
+    pub min_component_statements: Option<usize>,
+
+    /// Maximum number of statements allowed in a single file.
+    #[clap(long)]
+    pub max_file_statements: Option<usize>,
+
+    /// Maximum number of statements allowed in a single module, including
+    /// its submodules.
+    #[clap(long)]
+    pub max_module_statements: Option<usize>,
+
+    /// Maximum Gini coefficient of component sizes (0.0-1.0). 0 means all
+    /// components are the same size; values near 1 mean one component holds
+    /// most of the code.
+    #[clap(long)]
+    pub max_gini: Option<f64>,
+
     /// Output format for the report.
     #[clap(long, value_enum, default_value_t = StatementCountOutputFormat::default())]
     pub output: StatementCountOutputFormat,
@@ -115,6 +210,73 @@ pub struct StatementCountArgs {
     /// Analyze only git-staged files (pre-commit mode).
     #[clap(long)]
     pub staged: bool,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
+}
+
+/// Revision and date selection shared by all Git history-based rules.
+///
+/// The walk starts at `--to` (or `--branch`, or `HEAD` when neither is given)
+/// and stops at `--from`, mirroring `git log <from>..<to>`. A `--range` such
+/// as `v1.0..release/2.x` can be used in place of `--from`/`--to`.
+#[derive(Args, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HistoryArgs {
+    /// Analyze commits since this date (YYYY-MM-DD).
+    #[clap(long)]
+    pub since: Option<String>,
+
+    /// Analyze commits up to and including this date (YYYY-MM-DD).
+    #[clap(long)]
+    pub until: Option<String>,
+
+    /// Exclude this revision and its ancestors from the analysis.
+    #[clap(long)]
+    pub from: Option<String>,
+
+    /// Revision to start walking history from (defaults to HEAD).
+    #[clap(long)]
+    pub to: Option<String>,
+
+    /// Revision range to analyze (e.g., `v1.0..v2.0`).
+    /// Cannot be combined with `--from` or `--to`.
+    #[clap(long)]
+    pub range: Option<String>,
+
+    /// Branch to analyze instead of the currently checked-out one.
+    #[clap(long)]
+    pub branch: Option<String>,
+
+    /// Only count changes to paths matching these globs (repeatable).
+    /// Patterns without a `/` match file names at any depth.
+    #[clap(long = "include-path", value_name = "GLOB")]
+    pub include_paths: Vec<String>,
+
+    /// Ignore changes to paths matching these globs (repeatable),
+    /// e.g. `Cargo.lock` or `src/generated/**`.
+    #[clap(long = "exclude-path", value_name = "GLOB")]
+    pub exclude_paths: Vec<String>,
+
+    /// Ignore commits whose author name or email matches (repeatable).
+    /// `*` matches any sequence of characters, e.g. `*[bot]*`.
+    #[clap(long = "exclude-author", value_name = "PATTERN")]
+    pub exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches this regex (repeatable).
+    #[clap(long = "exclude-message", value_name = "REGEX")]
+    pub exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, one per line, in the format of
+    /// `.git-blame-ignore-revs`. Defaults to `.git-blame-ignore-revs` in the
+    /// repository root when that file exists.
+    #[clap(long)]
+    pub ignore_revs_file: Option<std::path::PathBuf>,
+
+    /// Number of threads used to diff commits (defaults to the number of CPUs).
+    #[clap(long)]
+    pub jobs: Option<usize>,
 }
 
 /// Enum representing the supported output formats for the volatility report.
@@ -139,9 +301,9 @@ pub struct VolatilityArgs {
     #[clap(long, default_value_t = 0.01)]
     pub alpha: f64,
 
-    /// Analyze commits since this date (YYYY-MM-DD).
-    #[clap(long)]
-    pub since: Option<String>,
+    /// Revision range and date window to analyze.
+    #[clap(flatten)]
+    pub history: HistoryArgs,
 
     /// Normalize volatility scores by the total lines of code in each crate.
     #[clap(long)]
@@ -164,6 +326,11 @@ pub struct VolatilityArgs {
     /// When specified, writes output to the file instead of stdout.
     #[clap(long)]
     pub output_file: Option<std::path::PathBuf>,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
 }
 
 /// Enum representing the supported output formats for the coupling report.
@@ -217,6 +384,11 @@ pub struct CouplingArgs {
     /// Analyze only git-staged files (pre-commit mode).
     #[clap(long)]
     pub staged: bool,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
 }
 
 /// Output format for the rust-code-analysis subcommand.
@@ -229,6 +401,17 @@ pub enum RustCodeAnalysisOutputFormat {
     Html,
 }
 
+/// Engine that computes the rust-code-analysis metrics.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RustCodeAnalysisBackend {
+    /// Built-in engine working on the `syn` AST. Needs no external tools and
+    /// supports Rust only.
+    #[default]
+    Builtin,
+    /// The external `rust-code-analysis-cli` tool, which must be on PATH.
+    External,
+}
+
 /// Arguments for the `rust-code-analysis` subcommand.
 #[derive(Args, Clone, Debug)]
 pub struct RustCodeAnalysisArgs {
@@ -236,7 +419,7 @@ pub struct RustCodeAnalysisArgs {
     #[clap(long, short, default_value = ".")]
     pub path: std::path::PathBuf,
 
-    /// Extra flags to pass directly to rust-code-analysis-cli.
+    /// Extra flags to pass directly to rust-code-analysis-cli (external backend only).
     #[clap(short = 'f', long = "flag", num_args = 0..)]
     pub extra_flags: Vec<String>,
 
@@ -258,6 +441,45 @@ pub struct RustCodeAnalysisArgs {
     #[clap(short = 'l', long, default_value = "rust")]
     pub language: String,
 
+    /// Engine used to compute the metrics. `external` runs
+    /// rust-code-analysis-cli and is needed for languages other than Rust.
+    #[clap(long, value_enum, default_value_t = RustCodeAnalysisBackend::default())]
+    pub backend: RustCodeAnalysisBackend,
+
+    /// How files are grouped into components for the complexity roll-ups.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
+    /// Maximum total cognitive complexity of a single file.
+    #[clap(long)]
+    pub max_file_cognitive: Option<f64>,
+
+    /// Minimum mean maintainability index of the functions in a file, on the
+    /// 0-100 Visual Studio scale. Files below it are reported.
+    #[clap(long)]
+    pub min_file_mi: Option<f64>,
+
+    /// Maximum ABC (assignments, branches, conditions) magnitude of a single
+    /// file.
+    #[clap(long)]
+    pub max_file_abc: Option<f64>,
+
+    /// Maximum cyclomatic complexity of a single function, method or closure.
+    #[clap(long)]
+    pub max_function_cyclomatic: Option<f64>,
+
+    /// Maximum cognitive complexity of a single function, method or closure.
+    #[clap(long)]
+    pub max_function_cognitive: Option<f64>,
+
+    /// Maximum number of arguments of a single function, method or closure.
+    #[clap(long)]
+    pub max_function_args: Option<usize>,
+
+    /// Maximum length, in lines, of a single function, method or closure.
+    #[clap(long)]
+    pub max_function_length: Option<usize>,
+
     /// CI output format (overrides --output for CI platforms).
     /// Use this for SARIF or JUnit output in CI/CD pipelines.
     #[clap(long, value_enum)]
@@ -268,6 +490,15 @@ pub struct RustCodeAnalysisArgs {
     /// Precedence: CLI flag > config file > stdout.
     #[clap(long, global = false)]
     pub output_file: Option<std::path::PathBuf>,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
+
+    /// Skip the per-file result cache. Set from the global `--no-cache` flag.
+    #[clap(skip)]
+    pub no_cache: bool,
 }
 
 /// Enum representing the supported output formats for the full report.
@@ -306,6 +537,10 @@ pub struct AllArgs {
     #[clap(long)]
     pub fail_on_warnings: bool,
 
+    /// How files are grouped into components for every rule.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
     /// Percentage threshold for component size (0-100).
     #[clap(long, default_value_t = 10)]
     pub sc_threshold: usize,
@@ -318,6 +553,51 @@ pub struct AllArgs {
     #[clap(long)]
     pub vol_since: Option<String>,
 
+    /// Analyze commits up to and including this date (YYYY-MM-DD).
+    #[clap(long)]
+    pub vol_until: Option<String>,
+
+    /// Exclude this revision and its ancestors from volatility analysis.
+    #[clap(long)]
+    pub vol_from: Option<String>,
+
+    /// Revision to start walking history from (defaults to HEAD).
+    #[clap(long)]
+    pub vol_to: Option<String>,
+
+    /// Revision range for volatility analysis (e.g., `v1.0..v2.0`).
+    /// Cannot be combined with `--vol-from` or `--vol-to`.
+    #[clap(long)]
+    pub vol_range: Option<String>,
+
+    /// Branch to analyze instead of the currently checked-out one.
+    #[clap(long)]
+    pub vol_branch: Option<String>,
+
+    /// Only count changes to paths matching these globs (repeatable).
+    #[clap(long = "vol-include-path", value_name = "GLOB")]
+    pub vol_include_paths: Vec<String>,
+
+    /// Ignore changes to paths matching these globs (repeatable).
+    #[clap(long = "vol-exclude-path", value_name = "GLOB")]
+    pub vol_exclude_paths: Vec<String>,
+
+    /// Ignore commits whose author name or email matches (repeatable).
+    #[clap(long = "vol-exclude-author", value_name = "PATTERN")]
+    pub vol_exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches this regex (repeatable).
+    #[clap(long = "vol-exclude-message", value_name = "REGEX")]
+    pub vol_exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, in `.git-blame-ignore-revs` format.
+    #[clap(long)]
+    pub vol_ignore_revs_file: Option<std::path::PathBuf>,
+
+    /// Number of threads used to diff commits for volatility analysis.
+    #[clap(long)]
+    pub vol_jobs: Option<usize>,
+
     /// Normalize volatility scores by the total lines of code in each crate.
     #[clap(long)]
     pub vol_normalize: bool,
@@ -346,6 +626,38 @@ pub struct AllArgs {
     #[clap(short = 'l', long = "rca_language", default_value = "rust")]
     pub rca_language: String,
 
+    /// Engine used to compute the rust-code-analysis metrics.
+    #[clap(long, value_enum, default_value_t = RustCodeAnalysisBackend::default())]
+    pub rca_backend: RustCodeAnalysisBackend,
+
+    /// Maximum total cognitive complexity of a single file.
+    #[clap(long)]
+    pub rca_max_file_cognitive: Option<f64>,
+
+    /// Minimum mean maintainability index of the functions in a file.
+    #[clap(long)]
+    pub rca_min_file_mi: Option<f64>,
+
+    /// Maximum ABC magnitude of a single file.
+    #[clap(long)]
+    pub rca_max_file_abc: Option<f64>,
+
+    /// Maximum cyclomatic complexity of a single function.
+    #[clap(long)]
+    pub rca_max_function_cyclomatic: Option<f64>,
+
+    /// Maximum cognitive complexity of a single function.
+    #[clap(long)]
+    pub rca_max_function_cognitive: Option<f64>,
+
+    /// Maximum number of arguments of a single function.
+    #[clap(long)]
+    pub rca_max_function_args: Option<usize>,
+
+    /// Maximum length, in lines, of a single function.
+    #[clap(long)]
+    pub rca_max_function_length: Option<usize>,
+
     /// CI output format (overrides --output for CI platforms).
     /// Use this for SARIF or JUnit output in CI/CD pipelines.
     #[clap(long, value_enum)]
@@ -360,6 +672,16 @@ pub struct AllArgs {
     /// Analyze only git-staged files (pre-commit mode).
     #[clap(long)]
     pub staged: bool,
+
+    /// Skip the rust-code-analysis result cache. Set from the global
+    /// `--no-cache` flag.
+    #[clap(skip)]
+    pub no_cache: bool,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
 }
 
 /// Enum representing the supported output formats for the contributor report.
@@ -370,6 +692,17 @@ pub enum ContributorReportOutputFormat {
     Html,
     Json,
     Yaml,
+    Csv,
+}
+
+/// Time period that contributor activity is bucketed by.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContributorReportPeriod {
+    /// Calendar months, labelled `YYYY-MM`.
+    #[default]
+    Month,
+    /// Calendar quarters, labelled `YYYY-Qn`.
+    Quarter,
 }
 
 /// Arguments for the `contributor-report` subcommand.
@@ -379,14 +712,23 @@ pub struct ContributorReportArgs {
     #[clap(long, short, default_value = ".")]
     pub path: std::path::PathBuf,
 
-    /// Analyze commits since this date (YYYY-MM-DD).
-    #[clap(long)]
-    pub since: Option<String>,
+    /// Revision range and date window to analyze.
+    #[clap(flatten)]
+    pub history: HistoryArgs,
 
     /// Exponential decay factor for recency weighting (e.g., 0.01).
     #[clap(long, default_value_t = 0.01)]
     pub decay: f64,
 
+    /// Alias groups mapping a canonical email to other emails or names of the
+    /// same person. Set through `[contributor_report.aliases]` in the config file.
+    #[clap(skip)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+
+    /// Time period that each contributor's activity timeline is bucketed by.
+    #[clap(long, value_enum, default_value_t = ContributorReportPeriod::default())]
+    pub period: ContributorReportPeriod,
+
     /// Output format for the report.
     #[clap(long, value_enum, default_value_t = ContributorReportOutputFormat::default())]
     pub output: ContributorReportOutputFormat,
@@ -401,3 +743,518 @@ pub struct ContributorReportArgs {
     #[clap(long)]
     pub output_file: Option<std::path::PathBuf>,
 }
+
+/// Enum representing the supported output formats for the code age report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum CodeAgeOutputFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Defines the level at which line ages are aggregated in the code age report.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+pub enum CodeAgeGranularity {
+    /// Aggregate per crate.
+    #[default]
+    Crate,
+    /// Aggregate per directory.
+    Module,
+    /// Report every file separately.
+    File,
+}
+
+/// Arguments for the `code-age` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct CodeAgeArgs {
+    /// Path to the Git repository to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Level at which line ages are aggregated.
+    #[clap(long, value_enum, default_value_t = CodeAgeGranularity::default())]
+    pub granularity: CodeAgeGranularity,
+
+    /// Lines unchanged for more than this many months count as stale.
+    #[clap(long, default_value_t = 12)]
+    pub stale_months: u32,
+
+    /// Authors without a commit in this many months count as inactive.
+    #[clap(long, default_value_t = 6)]
+    pub inactive_months: u32,
+
+    /// Alias groups used to unify author identities. Taken from
+    /// `[contributor_report.aliases]` in the config file.
+    #[clap(skip)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+
+    /// Number of threads used to blame files (defaults to the number of CPUs).
+    #[clap(long)]
+    pub jobs: Option<usize>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = CodeAgeOutputFormat::default())]
+    pub output: CodeAgeOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the ownership report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum OwnershipOutputFormat {
+    #[default]
+    Table,
+    Csv,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Defines the components that ownership is computed for.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OwnershipGranularity {
+    /// Compute ownership per crate.
+    #[default]
+    Crate,
+    /// Compute ownership per module.
+    Module,
+}
+
+/// Defines how lines are attributed to authors in the ownership report.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OwnershipSource {
+    /// Lines added and deleted by each author within the history window.
+    #[default]
+    Churn,
+    /// Lines of the current code each author last changed, via git blame.
+    Blame,
+}
+
+/// Arguments for the `ownership` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct OwnershipArgs {
+    /// Path to the Git repository to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Revision range and date window to analyze.
+    /// With `--source blame`, the code is blamed as of the end of the window.
+    #[clap(flatten)]
+    pub history: HistoryArgs,
+
+    /// Components to compute ownership for.
+    #[clap(long, value_enum, default_value_t = OwnershipGranularity::default())]
+    pub granularity: OwnershipGranularity,
+
+    /// How lines are attributed to authors.
+    #[clap(long, value_enum, default_value_t = OwnershipSource::default())]
+    pub source: OwnershipSource,
+
+    /// Owners without a commit in this many months count as inactive.
+    #[clap(long, default_value_t = 6)]
+    pub inactive_months: u32,
+
+    /// Alias groups used to unify author identities. Taken from
+    /// `[contributor_report.aliases]` in the config file.
+    #[clap(skip)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = OwnershipOutputFormat::default())]
+    pub output: OwnershipOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Action performed by the `codeowners` subcommand.
+#[derive(ValueEnum, Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CodeownersAction {
+    /// Generate a CODEOWNERS file from contribution data.
+    Suggest,
+    /// Compare an existing CODEOWNERS file against contribution data.
+    Check,
+}
+
+/// Enum representing the supported output formats for the codeowners subcommand.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum CodeownersOutputFormat {
+    /// A CODEOWNERS file for `suggest`, or a table of drift for `check`.
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+/// Arguments for the `codeowners` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct CodeownersArgs {
+    /// Whether to suggest a CODEOWNERS file or check an existing one.
+    #[clap(value_enum)]
+    pub action: CodeownersAction,
+
+    /// Path to the Git repository to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Revision range and date window to analyze.
+    #[clap(flatten)]
+    pub history: HistoryArgs,
+
+    /// Components to suggest owners for, and to report unowned paths by.
+    #[clap(long, value_enum, default_value_t = OwnershipGranularity::default())]
+    pub granularity: OwnershipGranularity,
+
+    /// CODEOWNERS file to check (defaults to `.github/CODEOWNERS`, `CODEOWNERS` or
+    /// `docs/CODEOWNERS`, whichever exists first).
+    #[clap(long)]
+    pub codeowners_file: Option<std::path::PathBuf>,
+
+    /// Maximum number of owners suggested per entry.
+    #[clap(long, default_value_t = 2)]
+    pub max_owners: usize,
+
+    /// Minimum percentage of an entry's churn an owner must account for. Owners
+    /// below it are not suggested, and are flagged by `check`.
+    #[clap(long, default_value_t = 10.0)]
+    pub min_share: f64,
+
+    /// Maps canonical emails to the CODEOWNERS handles (e.g. `@alice`) to write and
+    /// match. Set through `[codeowners.handles]` in the config file.
+    #[clap(skip)]
+    pub handles: BTreeMap<String, String>,
+
+    /// Alias groups used to unify author identities. Taken from
+    /// `[contributor_report.aliases]` in the config file.
+    #[clap(skip)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = CodeownersOutputFormat::default())]
+    pub output: CodeownersOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the duplication report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum DuplicationOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Arguments for the `duplication` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct DuplicationArgs {
+    /// Path to the directory/project to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Minimum length, in normalized tokens, of a reported clone.
+    #[clap(long, default_value_t = 50)]
+    pub min_tokens: usize,
+
+    /// Also look for clones in test code: `#[cfg(test)]` modules, `#[test]`
+    /// functions and the `tests`, `benches` and `examples` directories.
+    #[clap(long)]
+    pub include_tests: bool,
+
+    /// How files are grouped into components for the duplicated-lines percentages.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = DuplicationOutputFormat::default())]
+    pub output: DuplicationOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the API surface report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum ApiSurfaceOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Arguments for the `api-surface` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct ApiSurfaceArgs {
+    /// Path to the directory/project to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Maximum number of public items a crate may expose.
+    #[clap(long)]
+    pub max_public_items: Option<usize>,
+
+    /// Maximum percentage of a crate's statements that may sit in public
+    /// functions and methods.
+    #[clap(long)]
+    pub max_public_statement_share: Option<f64>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = ApiSurfaceOutputFormat::default())]
+    pub output: ApiSurfaceOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the unsafe code report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum UnsafeCodeOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Arguments for the `unsafe-code` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct UnsafeCodeArgs {
+    /// Path to the directory/project to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Module in which unsafe code is allowed, e.g. `crate::ffi` (repeatable).
+    /// `crate` matches every crate; a crate name matches only that crate.
+    /// When set, unsafe code in any other module is reported as an error.
+    #[clap(long = "allow-module", value_name = "MODULE")]
+    pub allowed_modules: Vec<String>,
+
+    /// Also report unsafe code in test code: `#[cfg(test)]` modules, `#[test]`
+    /// functions and the `tests`, `benches` and `examples` directories.
+    #[clap(long)]
+    pub include_tests: bool,
+
+    /// How files are grouped into components for the unsafe density.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
+
+    /// Also measure the unsafe density of each component at the last commit
+    /// of every period in the history window.
+    #[clap(long)]
+    pub trend: bool,
+
+    /// Time period the trend is bucketed by.
+    #[clap(long, value_enum, default_value_t = ContributorReportPeriod::default())]
+    pub period: ContributorReportPeriod,
+
+    /// Revision range and date window for the trend.
+    #[clap(flatten)]
+    pub history: HistoryArgs,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = UnsafeCodeOutputFormat::default())]
+    pub output: UnsafeCodeOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the error handling report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum ErrorHandlingOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Maximum number of panic sites allowed in a module and its submodules.
+///
+/// `total` limits all kinds together; the other fields limit a single kind.
+/// Unset fields are unlimited.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PanicBudget {
+    pub total: Option<usize>,
+    pub unwrap: Option<usize>,
+    pub expect: Option<usize>,
+    pub panic: Option<usize>,
+    pub todo: Option<usize>,
+    pub unimplemented: Option<usize>,
+    pub index: Option<usize>,
+}
+
+/// Arguments for the `error-handling` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct ErrorHandlingArgs {
+    /// Path to the directory/project to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Module in which no panic sites are allowed at all, e.g. `crate::domain`
+    /// (repeatable). `crate` matches every crate; a crate name matches only
+    /// that crate.
+    #[clap(long = "ban", value_name = "MODULE")]
+    pub banned_modules: Vec<String>,
+
+    /// Per-module budgets, keyed by module. Set through
+    /// `[error_handling.budgets."crate::module"]` in the config file.
+    #[clap(skip)]
+    pub budgets: BTreeMap<String, PanicBudget>,
+
+    /// Also count panic sites in test code: `#[cfg(test)]` modules, `#[test]`
+    /// functions and the `tests`, `benches` and `examples` directories.
+    #[clap(long)]
+    pub include_tests: bool,
+
+    /// How files are grouped into components in the report.
+    #[clap(long, value_enum, default_value_t = ComponentMode::default())]
+    pub components: ComponentMode,
+
+    /// Named components mapped to path globs or module paths. Set through
+    /// `[components.define]` in the config file.
+    #[clap(skip)]
+    pub component_definitions: BTreeMap<String, Vec<String>>,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = ErrorHandlingOutputFormat::default())]
+    pub output: ErrorHandlingOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
+
+/// Enum representing the supported output formats for the generics report.
+#[derive(ValueEnum, Clone, Debug, Default)]
+pub enum GenericsOutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Html,
+}
+
+/// Arguments for the `generics` subcommand.
+#[derive(Args, Clone, Debug)]
+pub struct GenericsArgs {
+    /// Path to the directory/project to analyze.
+    #[clap(long, short, default_value = ".")]
+    pub path: std::path::PathBuf,
+
+    /// Maximum number of generic type and const parameters of a single item,
+    /// counting `impl Trait` arguments.
+    #[clap(long)]
+    pub max_generic_params: Option<usize>,
+
+    /// Maximum number of where-clause predicates of a single item.
+    #[clap(long)]
+    pub max_where_predicates: Option<usize>,
+
+    /// Maximum nesting depth of trait bounds in a single item, e.g. 2 for
+    /// `F: Fn() -> Box<dyn Display>`.
+    #[clap(long)]
+    pub max_bound_depth: Option<usize>,
+
+    /// Maximum number of trait impls for a single type.
+    #[clap(long)]
+    pub max_trait_impls: Option<usize>,
+
+    /// Maximum number of blanket impls (`impl<T> Trait for T`) in a single
+    /// module.
+    #[clap(long)]
+    pub max_blanket_impls: Option<usize>,
+
+    /// Only measure items declared `pub`.
+    #[clap(long)]
+    pub public_only: bool,
+
+    /// Also measure test code: `#[cfg(test)]` modules, `#[test]` functions and
+    /// the `tests`, `benches` and `examples` directories.
+    #[clap(long)]
+    pub include_tests: bool,
+
+    /// Number of items and types listed in the table output.
+    #[clap(long, default_value_t = 20)]
+    pub top: usize,
+
+    /// Output format for the report.
+    #[clap(long, value_enum, default_value_t = GenericsOutputFormat::default())]
+    pub output: GenericsOutputFormat,
+
+    /// CI output format (overrides --output for CI platforms).
+    /// Use this for SARIF or JUnit output in CI/CD pipelines.
+    #[clap(long, value_enum)]
+    pub ci_output: Option<CiOutputFormat>,
+
+    /// Output file path for the report.
+    /// When specified, writes output to the file instead of stdout.
+    #[clap(long)]
+    pub output_file: Option<std::path::PathBuf>,
+}
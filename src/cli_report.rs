@@ -75,6 +75,7 @@ const fn severity_label(severity: Severity) -> &'static str {
 ///         severity: Severity::Error,
 ///         message: "Component too large".to_string(),
 ///         location: Some(Location::new("src/main.rs".to_string())),
+///         related_locations: Vec::new(),
 ///         help_uri: Some("https://example.com/docs".to_string()),
 ///         fingerprint: None,
 ///     }
@@ -159,6 +160,7 @@ const fn severity_color(severity: Severity) -> Attr {
 ///         severity: Severity::Error,
 ///         message: "Component too large".to_string(),
 ///         location: Some(Location::new("src/main.rs".to_string())),
+///         related_locations: Vec::new(),
 ///         help_uri: Some("https://example.com/docs".to_string()),
 ///         fingerprint: None,
 ///     }
@@ -352,6 +354,7 @@ mod tests {
             severity: Severity::Error,
             message: "Test error message".to_string(),
             location: Some(Location::new("src/test.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://example.com/docs".to_string()),
             fingerprint: None,
         }];
@@ -385,6 +388,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error message".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -394,6 +398,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Warning message".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -403,6 +408,7 @@ mod tests {
                 severity: Severity::Note,
                 message: "Note message".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -439,6 +445,7 @@ mod tests {
             severity: Severity::Warning,
             message: long_message.to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -459,6 +466,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Warning".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -468,6 +476,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -477,6 +486,7 @@ mod tests {
                 severity: Severity::Note,
                 message: "Note".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -503,6 +513,7 @@ mod tests {
             severity: Severity::Error,
             message: "Component too large".to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: None,
         }];
@@ -522,6 +533,7 @@ mod tests {
             severity: Severity::Warning,
             message: "Test warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -550,6 +562,7 @@ mod tests {
             severity: Severity::Error,
             message: "Test error".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -566,6 +579,7 @@ mod tests {
             severity: Severity::Warning,
             message: "Test warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -582,6 +596,7 @@ mod tests {
             severity: Severity::Note,
             message: "Test note".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         }];
@@ -599,6 +614,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -608,6 +624,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error 2".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -617,6 +634,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Warning".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -626,6 +644,7 @@ mod tests {
                 severity: Severity::Note,
                 message: "Note".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -644,6 +663,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error 1".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -653,6 +673,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "Error 2".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -662,6 +683,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Warning 1".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -671,6 +693,7 @@ mod tests {
                 severity: Severity::Warning,
                 message: "Warning 2".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -680,6 +703,7 @@ mod tests {
                 severity: Severity::Note,
                 message: "Note 1".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -689,6 +713,7 @@ mod tests {
                 severity: Severity::Note,
                 message: "Note 2".to_string(),
                 location: None,
+                related_locations: Vec::new(),
                 help_uri: None,
                 fingerprint: None,
             },
@@ -0,0 +1,765 @@
+//! Code Age Rule
+//!
+//! This module provides the code age rule, which uses `git blame` to work out
+//! when each line currently in the repository was last changed, and by whom.
+//! Line ages are aggregated per crate, directory or file.
+//!
+//! # Overview
+//!
+//! Volatility shows where code is changing; code age shows where it is not.
+//! Old code is not a problem in itself, but old code whose last authors have
+//! left the project is likely to be poorly understood. The report therefore
+//! separates stale code from abandoned code:
+//!
+//! - **Median Age**: Median age in days of the lines in the group, weighted by line
+//! - **Stale**: Share of lines unchanged for more than `--stale-months` months
+//! - **Inactive Owners**: Files whose most recent change was made by an author
+//!   without any commit in the last `--inactive-months` months
+//!
+//! A month is counted as 30 days. Only `.rs` files inside a crate are
+//! analyzed, and authors are identified by email address after `.mailmap`
+//! resolution and the alias groups in `[contributor_report.aliases]`.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::code_age_rule::CodeAgeRule;
+//! use raff_core::{CodeAgeArgs, CodeAgeGranularity, CodeAgeOutputFormat};
+//! use std::path::PathBuf;
+//!
+//! let rule = CodeAgeRule::new();
+//! let args = CodeAgeArgs {
+//!     path: PathBuf::from("."),
+//!     granularity: CodeAgeGranularity::Crate,
+//!     stale_months: 12,
+//!     inactive_months: 6,
+//!     aliases: Default::default(),
+//!     jobs: None,
+//!     output: CodeAgeOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path is not a valid Git repository
+//! - No crates (Cargo.toml files) are found
+//! - Git operations fail (e.g., a file cannot be blamed)
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use git2::{BlameOptions, ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult};
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{CiOutputFormat, CodeAgeArgs, CodeAgeGranularity, CodeAgeOutputFormat};
+use crate::error::{RaffError, Result};
+use crate::git_utils::map_with_repository;
+use crate::html_utils::{self, MetricRanges};
+use crate::identity::IdentityResolver;
+use crate::output_utils::percentage;
+use crate::rule::Rule;
+use crate::volatility_rule::{CrateStatsMap, VolatilityRule};
+
+/// Seconds in the 30-day month used for the stale and inactive thresholds.
+const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60;
+
+/// Seconds per day, used to express ages in days.
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+
+/// Age statistics for a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAge {
+    /// Path of the file relative to the repository root.
+    pub path: PathBuf,
+    /// Name of the group (crate, directory or file) the file belongs to.
+    pub group: String,
+    /// Number of lines in the file.
+    pub lines: usize,
+    /// Median line age in days.
+    pub median_age_days: f64,
+    /// Number of lines older than the stale threshold.
+    pub stale_lines: usize,
+    /// Timestamp of the most recent change to the file.
+    pub last_modified: i64,
+    /// Author of the most recent change to the file.
+    pub last_author: String,
+    /// Whether that author has committed within the inactive threshold.
+    pub last_author_active: bool,
+}
+
+/// Age statistics for a crate, directory or file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeAgeStats {
+    /// Name of the group.
+    pub name: String,
+    /// Number of files in the group.
+    pub file_count: usize,
+    /// Number of lines in the group.
+    pub total_lines: usize,
+    /// Median line age in days, weighted by line.
+    pub median_age_days: f64,
+    /// Number of lines older than the stale threshold.
+    pub stale_lines: usize,
+    /// Percentage of lines older than the stale threshold.
+    pub stale_percentage: f64,
+    /// Files whose most recent change was made by an inactive author.
+    pub inactive_owner_files: Vec<PathBuf>,
+}
+
+/// Data type for code age analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeAgeData {
+    /// Per-group statistics, with the highest stale percentage first.
+    pub stats: Vec<CodeAgeStats>,
+    /// Per-file statistics, sorted by path.
+    pub files: Vec<FileAge>,
+    /// Months after which a line counts as stale.
+    pub stale_months: u32,
+    /// Months without a commit after which an author counts as inactive.
+    pub inactive_months: u32,
+    /// The analyzed repository.
+    pub analysis_path: PathBuf,
+}
+
+impl ToFindings for CodeAgeData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.files
+            .iter()
+            .filter(|file| !file.last_author_active)
+            .map(|file| {
+                let uri = file.path.to_string_lossy().replace('\\', "/");
+                Finding {
+                    rule_id: "code-age".to_string(),
+                    rule_name: "Code Age Rule".to_string(),
+                    severity: Severity::Warning,
+                    message: format!(
+                        "File '{}' was last changed {} by '{}', who has not committed in over {} months ({:.0}% of its {} lines are stale)",
+                        uri,
+                        format_date(file.last_modified),
+                        file.last_author,
+                        self.inactive_months,
+                        percentage(file.stale_lines, file.lines),
+                        file.lines
+                    ),
+                    location: Some(Location::new(uri.clone())),
+                    related_locations: Vec::new(),
+                    help_uri: Some("https://github.com/liamwh/raff/docs/code-age".to_string()),
+                    fingerprint: Some(format!("code-age:{}:{}", uri, file.last_author)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rule to report the age of the code in a Git repository using blame.
+#[derive(Debug, Default)]
+pub struct CodeAgeRule;
+
+impl Rule for CodeAgeRule {
+    type Config = CodeAgeArgs;
+    type Data = CodeAgeData;
+
+    fn name() -> &'static str {
+        "code_age"
+    }
+
+    fn description() -> &'static str {
+        "Uses git blame to report the age of code and files owned by inactive authors"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+/// A blamed run of lines sharing the same last change.
+pub(crate) struct BlameRun {
+    pub(crate) time: i64,
+    pub(crate) lines: usize,
+    pub(crate) author: String,
+    pub(crate) email: String,
+}
+
+impl CodeAgeRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &CodeAgeArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &CodeAgeArgs) -> Result<CodeAgeData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &CodeAgeArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "code-age")?,
+            };
+            // Code age findings are warnings, which don't fail CI
+            return crate::output_utils::write_output(&output, args.output_file.as_deref());
+        }
+
+        let output = match args.output {
+            CodeAgeOutputFormat::Table => {
+                self.print_table(&data);
+                return Ok(());
+            }
+            CodeAgeOutputFormat::Json => serde_json::to_string_pretty(&data)?,
+            CodeAgeOutputFormat::Yaml => serde_yaml::to_string(&data)?,
+            CodeAgeOutputFormat::Csv => self.render_csv(&data)?,
+            CodeAgeOutputFormat::Html => html_utils::render_html_doc(
+                &format!("Code Age Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            ),
+        };
+        crate::output_utils::write_output(&output, args.output_file.as_deref())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &CodeAgeArgs) -> Result<CodeAgeData> {
+        let analysis_path = args.path.canonicalize()?;
+        let repo = Repository::open(&analysis_path).map_err(|e| {
+            RaffError::git_error_with_repo(
+                format!("open Git repository: {}", e),
+                analysis_path.clone(),
+            )
+        })?;
+        let head = repo.head()?.peel_to_commit()?;
+
+        let crates = VolatilityRule::new().discover_crates_and_init_stats(&analysis_path)?;
+        let files = rust_files_by_crate(&repo, head.id(), &crates)?;
+        tracing::info!(files = files.len(), "Blaming files for code age analysis");
+
+        let jobs = args.jobs.unwrap_or_else(num_cpus::get);
+        let head_id = head.id();
+        let blamed = map_with_repository(repo.path(), &files, jobs, |repo, (path, _)| {
+            blame_runs(repo, head_id, path)
+        })?;
+
+        let identities = IdentityResolver::new(&repo, &args.aliases)?;
+        let last_seen = last_commit_by_author(&repo, head_id, &identities)?;
+        Ok(summarize(
+            files.into_iter().zip(blamed).collect(),
+            &last_seen,
+            &identities,
+            args,
+            Utc::now().timestamp(),
+            analysis_path,
+        ))
+    }
+
+    fn print_table(&self, data: &CodeAgeData) {
+        println!("\nCode Age Report Interpretation:");
+        println!("-----------------------------------");
+        println!("- Median Age: Median age in days of the lines in the group.");
+        println!(
+            "- Stale: Share of lines unchanged for more than {} months.",
+            data.stale_months
+        );
+        println!(
+            "- Inactive Owners: Files last changed by an author with no commit in {} months.",
+            data.inactive_months
+        );
+        println!("-----------------------------------");
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Name",
+            "Files",
+            "Lines",
+            "Median Age (days)",
+            "Stale %",
+            "Inactive Owners"
+        ]);
+        for stats in &data.stats {
+            table.add_row(row![
+                stats.name,
+                r->stats.file_count,
+                r->stats.total_lines,
+                r->format!("{:.0}", stats.median_age_days),
+                r->format!("{:.1}", stats.stale_percentage),
+                r->stats.inactive_owner_files.len()
+            ]);
+        }
+        println!("\nCode Age Report:");
+        table.printstd();
+    }
+
+    fn render_csv(&self, data: &CodeAgeData) -> Result<String> {
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(vec![]);
+        wtr.write_record([
+            "name",
+            "file_count",
+            "total_lines",
+            "median_age_days",
+            "stale_lines",
+            "stale_percentage",
+            "inactive_owner_files",
+        ])?;
+        for stats in &data.stats {
+            wtr.write_record([
+                stats.name.clone(),
+                stats.file_count.to_string(),
+                stats.total_lines.to_string(),
+                format!("{:.1}", stats.median_age_days),
+                stats.stale_lines.to_string(),
+                format!("{:.1}", stats.stale_percentage),
+                stats.inactive_owner_files.len().to_string(),
+            ])?;
+        }
+        let bytes = wtr
+            .into_inner()
+            .map_err(|e| RaffError::parse_error(format!("Failed to get CSV bytes: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| RaffError::parse_error(format!("Failed to convert CSV to UTF-8: {}", e)))
+    }
+
+    fn render_html_body(&self, data: &CodeAgeData) -> Markup {
+        let explanations = [
+            (
+                "Median Age",
+                "Median age in days of the lines in the group, weighted by line.",
+            ),
+            (
+                "Stale %",
+                "Share of lines that have not changed within the stale threshold.",
+            ),
+            (
+                "Inactive Owners",
+                "Files whose most recent change was made by an author who has not committed within the inactive threshold.",
+            ),
+        ];
+        let age_values: Vec<f64> = data.stats.iter().map(|s| s.median_age_days).collect();
+        let stale_values: Vec<f64> = data.stats.iter().map(|s| s.stale_percentage).collect();
+        let age_ranges = MetricRanges::from_values(&age_values, false);
+        let stale_ranges = MetricRanges::from_values(&stale_values, false);
+        let abandoned: Vec<&FileAge> = data
+            .files
+            .iter()
+            .filter(|file| !file.last_author_active)
+            .collect();
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { (format!("Stale after {} months, authors inactive after {} months", data.stale_months, data.inactive_months)) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Name" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="number" { "Files" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Lines" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Median Age (days)" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Stale %" }
+                        th class="sortable-header" data-column-index="5" data-sort-type="number" { "Inactive Owners" }
+                    }
+                }
+                tbody {
+                    @for stats in &data.stats {
+                        tr {
+                            td { (stats.name) }
+                            td { (stats.file_count) }
+                            td { (stats.total_lines) }
+                            td style=({age_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(stats.median_age_days, r))}) { (format!("{:.0}", stats.median_age_days)) }
+                            td style=({stale_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(stats.stale_percentage, r))}) { (format!("{:.1}", stats.stale_percentage)) }
+                            td { (stats.inactive_owner_files.len()) }
+                        }
+                    }
+                }
+            }
+            @if !abandoned.is_empty() {
+                h2 { "Files Last Changed by Inactive Authors" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "File" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Last Author" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Last Changed" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Lines" }
+                        }
+                    }
+                    tbody {
+                        @for file in &abandoned {
+                            tr {
+                                td { (file.path.display()) }
+                                td { (file.last_author) }
+                                td { (format_date(file.last_modified)) }
+                                td { (file.lines) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Lists the `.rs` files at `commit_id` that belong to a crate, together
+/// with the name of the owning crate, sorted by path.
+pub(crate) fn rust_files_by_crate(
+    repo: &Repository,
+    commit_id: Oid,
+    crates: &CrateStatsMap,
+) -> Result<Vec<(PathBuf, String)>> {
+    let tree = repo.find_commit(commit_id)?.tree()?;
+    let mut files = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        let name = entry.name().unwrap_or_default();
+        if entry.kind() == Some(ObjectType::Blob) && name.ends_with(".rs") {
+            let path = Path::new(dir).join(name);
+            if let Some((crate_name, _)) = VolatilityRule::new().find_owning_crate(&path, crates) {
+                files.push((path, crate_name));
+            }
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(|e| RaffError::git_error(format!("walk tree of commit {}: {}", commit_id, e)))?;
+    files.sort();
+    Ok(files)
+}
+
+/// Blames `path` as of `commit_id`, returning one run per blame hunk with
+/// authors resolved through `.mailmap`.
+pub(crate) fn blame_runs(repo: &Repository, commit_id: Oid, path: &Path) -> Result<Vec<BlameRun>> {
+    let mut opts = BlameOptions::new();
+    opts.newest_commit(commit_id).use_mailmap(true);
+    let blame = repo
+        .blame_file(path, Some(&mut opts))
+        .map_err(|e| RaffError::git_error(format!("blame {}: {}", path.display(), e)))?;
+    Ok(blame
+        .iter()
+        .map(|hunk| {
+            let signature = hunk.final_signature();
+            BlameRun {
+                time: signature.when().seconds(),
+                lines: hunk.lines_in_hunk(),
+                author: signature.name().unwrap_or("Unknown").to_string(),
+                email: signature.email().unwrap_or_default().to_lowercase(),
+            }
+        })
+        .collect())
+}
+
+/// Returns the time of each author's most recent commit reachable from `tip`,
+/// keyed by the canonical email of the author's identity.
+pub(crate) fn last_commit_by_author(
+    repo: &Repository,
+    tip: Oid,
+    identities: &IdentityResolver,
+) -> Result<HashMap<String, i64>> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(tip)?;
+    let mut last_seen: HashMap<String, i64> = HashMap::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let author = commit.author();
+        let time = author.when().seconds();
+        let entry = last_seen
+            .entry(identities.resolve(&author).email)
+            .or_insert(time);
+        *entry = (*entry).max(time);
+    }
+    Ok(last_seen)
+}
+
+/// Line ages and member files collected for one group.
+#[derive(Default)]
+struct GroupAges {
+    /// `(age_days, lines)` for every blame run in the group.
+    ages: Vec<(f64, usize)>,
+    /// Indices of the group's files in the file list.
+    files: Vec<usize>,
+}
+
+/// Aggregates blamed files into per-file and per-group statistics as of `now`.
+fn summarize(
+    blamed: Vec<((PathBuf, String), Vec<BlameRun>)>,
+    last_commit_by_author: &HashMap<String, i64>,
+    identities: &IdentityResolver,
+    args: &CodeAgeArgs,
+    now: i64,
+    analysis_path: PathBuf,
+) -> CodeAgeData {
+    let stale_before = now - i64::from(args.stale_months) * SECONDS_PER_MONTH;
+    let active_since = now - i64::from(args.inactive_months) * SECONDS_PER_MONTH;
+    let age_days = |time: i64| (now - time).max(0) as f64 / SECONDS_PER_DAY;
+
+    let mut files = Vec::new();
+    let mut groups: HashMap<String, GroupAges> = HashMap::new();
+    for ((path, crate_name), runs) in blamed {
+        let Some(last) = runs.iter().max_by_key(|run| run.time) else {
+            continue;
+        };
+        let mut ages: Vec<(f64, usize)> = runs
+            .iter()
+            .map(|run| (age_days(run.time), run.lines))
+            .collect();
+        let last_author = identities.resolve_name_email(&last.author, &last.email);
+        let group = match args.granularity {
+            CodeAgeGranularity::Crate => crate_name,
+            CodeAgeGranularity::Module => path
+                .parent()
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default(),
+            CodeAgeGranularity::File => path.to_string_lossy().replace('\\', "/"),
+        };
+        let file = FileAge {
+            lines: runs.iter().map(|run| run.lines).sum(),
+            median_age_days: weighted_median(&mut ages),
+            stale_lines: runs
+                .iter()
+                .filter(|run| run.time < stale_before)
+                .map(|run| run.lines)
+                .sum(),
+            last_modified: last.time,
+            last_author: last_author.name,
+            last_author_active: last_commit_by_author
+                .get(&last_author.email)
+                .is_some_and(|time| *time >= active_since),
+            path,
+            group: group.clone(),
+        };
+        let group_ages = groups.entry(group).or_default();
+        group_ages.ages.extend(ages);
+        group_ages.files.push(files.len());
+        files.push(file);
+    }
+
+    let mut stats: Vec<CodeAgeStats> = groups
+        .into_iter()
+        .map(|(name, mut group)| {
+            let members: Vec<&FileAge> = group.files.iter().map(|idx| &files[*idx]).collect();
+            let total_lines = members.iter().map(|file| file.lines).sum();
+            let stale_lines = members.iter().map(|file| file.stale_lines).sum();
+            CodeAgeStats {
+                name,
+                file_count: members.len(),
+                total_lines,
+                median_age_days: weighted_median(&mut group.ages),
+                stale_lines,
+                stale_percentage: percentage(stale_lines, total_lines),
+                inactive_owner_files: members
+                    .iter()
+                    .filter(|file| !file.last_author_active)
+                    .map(|file| file.path.clone())
+                    .collect(),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| {
+        b.stale_percentage
+            .partial_cmp(&a.stale_percentage)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    CodeAgeData {
+        stats,
+        files,
+        stale_months: args.stale_months,
+        inactive_months: args.inactive_months,
+        analysis_path,
+    }
+}
+
+/// Returns the median of `values`, where each `(value, weight)` pair counts
+/// `weight` times.
+fn weighted_median(values: &mut [(f64, usize)]) -> f64 {
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    let total: usize = values.iter().map(|(_, weight)| weight).sum();
+    let mut seen = 0;
+    for (value, weight) in values.iter() {
+        seen += weight;
+        if seen * 2 >= total {
+            return *value;
+        }
+    }
+    0.0
+}
+
+pub(crate) fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str], author: (&str, &str), date: &str) {
+        let output = Command::new("git")
+            .args(args)
+            .env("GIT_AUTHOR_NAME", author.0)
+            .env("GIT_AUTHOR_EMAIL", author.1)
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_NAME", author.0)
+            .env("GIT_COMMITTER_EMAIL", author.1)
+            .env("GIT_COMMITTER_DATE", date)
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit(dir: &Path, file: &str, content: &str, author: (&str, &str), date: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        git(dir, &["add", "-A"], author, date);
+        git(dir, &["commit", "-q", "-m", file], author, date);
+    }
+
+    /// Creates a crate with an old file by a departed author and a fresh
+    /// file by an active one.
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let old = ("Old Author", "old@example.com");
+        let new = ("New Author", "new@example.com");
+        let recent = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        git(
+            dir,
+            &["init", "-q", "-b", "main"],
+            old,
+            "2020-01-01T00:00:00Z",
+        );
+        commit(
+            dir,
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n",
+            old,
+            "2020-01-01T00:00:00Z",
+        );
+        commit(
+            dir,
+            "src/legacy.rs",
+            "fn a() {}\nfn b() {}\nfn c() {}\n",
+            old,
+            "2020-01-01T00:00:00Z",
+        );
+        commit(dir, "src/lib.rs", "mod legacy;\n", new, &recent);
+        temp_dir
+    }
+
+    fn args(path: &Path, granularity: CodeAgeGranularity) -> CodeAgeArgs {
+        CodeAgeArgs {
+            path: path.to_path_buf(),
+            granularity,
+            stale_months: 12,
+            inactive_months: 6,
+            aliases: BTreeMap::new(),
+            jobs: Some(2),
+            output: CodeAgeOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    #[test]
+    fn test_analyze_reports_stale_lines_and_inactive_owners() {
+        let temp_dir = create_repo();
+        let data = CodeAgeRule::new()
+            .analyze(&args(temp_dir.path(), CodeAgeGranularity::Crate))
+            .unwrap();
+
+        assert_eq!(data.stats.len(), 1);
+        let stats = &data.stats[0];
+        assert_eq!(stats.name, "demo");
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.total_lines, 4);
+        assert_eq!(stats.stale_lines, 3);
+        assert_eq!(stats.stale_percentage, 75.0);
+        assert_eq!(
+            stats.inactive_owner_files,
+            vec![PathBuf::from("src/legacy.rs")]
+        );
+
+        let findings = data.to_findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0].message.contains("Old Author"));
+    }
+
+    #[test]
+    fn test_aliases_unify_authors() {
+        let temp_dir = create_repo();
+        let mut args = args(temp_dir.path(), CodeAgeGranularity::Crate);
+        args.aliases = BTreeMap::from([(
+            "new@example.com".to_string(),
+            vec!["old@example.com".to_string()],
+        )]);
+        let data = CodeAgeRule::new().analyze(&args).unwrap();
+
+        // The departed author's address belongs to the active author
+        assert!(data.stats[0].inactive_owner_files.is_empty());
+        assert!(data.files.iter().all(|file| file.last_author_active));
+        assert!(data.to_findings().is_empty());
+    }
+
+    #[test]
+    fn test_file_granularity_reports_each_file() {
+        let temp_dir = create_repo();
+        let data = CodeAgeRule::new()
+            .analyze(&args(temp_dir.path(), CodeAgeGranularity::File))
+            .unwrap();
+
+        let names: Vec<&str> = data.stats.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["src/legacy.rs", "src/lib.rs"]);
+        assert!(data.stats[1].median_age_days < 1.0);
+    }
+
+    #[test]
+    fn test_weighted_median_counts_weights() {
+        let mut values = vec![(10.0, 1), (1.0, 3), (100.0, 1)];
+        assert_eq!(weighted_median(&mut values), 1.0);
+        assert_eq!(weighted_median(&mut Vec::new()), 0.0);
+    }
+
+    #[test]
+    fn test_analyze_fails_with_non_git_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = CodeAgeRule::new().analyze(&args(temp_dir.path(), CodeAgeGranularity::Crate));
+        assert!(result.is_err());
+    }
+}
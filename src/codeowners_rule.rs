@@ -0,0 +1,894 @@
+//! CODEOWNERS Rule
+//!
+//! This module provides the codeowners rule, which suggests a CODEOWNERS file
+//! from contribution history and checks an existing one for drift.
+//!
+//! # Overview
+//!
+//! Contributions are measured as churn (lines added plus deleted) within the
+//! history window, using the shared [`commit_stats`](crate::commit_stats)
+//! cache. Without `--since`, `--from` or `--range` the window covers the last
+//! 12 months, so that owners reflect recent contributions. Authors are
+//! resolved through `.mailmap` and the alias groups in
+//! `[contributor_report.aliases]`.
+//!
+//! - **`suggest`**: Writes one entry per crate (or module with
+//!   `--granularity module`) listing its top contributors. A module's entry
+//!   is its directory when it is defined by a `lib.rs`, `main.rs` or `mod.rs`,
+//!   and its file otherwise. Up to
+//!   `--max-owners` authors are listed, skipping those below `--min-share`
+//!   percent of the entry's churn, but always keeping the top contributor.
+//! - **`check`**: Matches every changed file against the existing CODEOWNERS
+//!   file (the last matching entry wins, as on GitHub) and reports:
+//!   - listed owners who account for less than `--min-share` percent of
+//!     their entry's churn
+//!   - crates or modules with churn that no entry covers
+//!
+//! Owners are written as emails unless `[codeowners.handles]` maps the email to
+//! a handle such as `@alice`. When checking, handles are mapped back to emails
+//! the same way. Handles and emails are matched ignoring case; team handles
+//! (`@org/team`) and unmapped handles are listed but not evaluated.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::codeowners_rule::CodeownersRule;
+//! use raff_core::{
+//!     CodeownersAction, CodeownersArgs, CodeownersOutputFormat, HistoryArgs, OwnershipGranularity,
+//! };
+//! use std::path::PathBuf;
+//!
+//! let rule = CodeownersRule::new();
+//! let args = CodeownersArgs {
+//!     action: CodeownersAction::Check,
+//!     path: PathBuf::from("."),
+//!     history: HistoryArgs {
+//!         since: Some("2024-01-01".to_string()),
+//!         ..HistoryArgs::default()
+//!     },
+//!     granularity: OwnershipGranularity::Crate,
+//!     codeowners_file: None,
+//!     max_owners: 2,
+//!     min_share: 10.0,
+//!     handles: Default::default(),
+//!     aliases: Default::default(),
+//!     output: CodeownersOutputFormat::Text,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path is not a valid Git repository
+//! - No crates (Cargo.toml files) are found
+//! - `check` finds no CODEOWNERS file, or an entry has an invalid pattern
+//! - `check` finds drift (returned as an analysis error after reporting)
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use git2::Repository;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{
+    CiOutputFormat, CodeownersAction, CodeownersArgs, CodeownersOutputFormat, OwnershipGranularity,
+};
+use crate::code_age_rule::format_date;
+use crate::error::{RaffError, Result};
+use crate::history::HistoryWindow;
+use crate::identity::IdentityResolver;
+use crate::output_utils::percentage;
+use crate::ownership_rule::churn_by_file;
+use crate::rule::Rule;
+use crate::statement_count_rule::child_module_dir;
+use crate::volatility_rule::{CrateStatsMap, VolatilityRule};
+
+/// Months of history considered when no start of the window is given.
+const DEFAULT_RECENT_MONTHS: i64 = 12;
+
+/// Seconds in the 30-day month used for the default window.
+const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60;
+
+/// Locations searched for a CODEOWNERS file, in order.
+const DEFAULT_CODEOWNERS_FILES: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// An owner of a CODEOWNERS entry and their share of its churn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerShare {
+    /// The owner as written in CODEOWNERS (an email or `@handle`).
+    pub owner: String,
+    /// The owner's canonical email, if it could be determined.
+    pub email: Option<String>,
+    /// Lines of churn by the owner in paths matched by the entry.
+    pub lines: u64,
+    /// Percentage of the entry's churn by the owner.
+    pub percentage: f64,
+}
+
+/// A CODEOWNERS entry with the contribution data behind it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeownersEntry {
+    /// The path pattern of the entry.
+    pub pattern: String,
+    /// Line of the entry in the checked CODEOWNERS file.
+    pub line: Option<usize>,
+    /// Owners listed (or suggested) for the entry.
+    pub owners: Vec<OwnerShare>,
+    /// Lines of churn in paths matched by the entry.
+    pub total_lines: u64,
+}
+
+/// A crate or directory with churn that no CODEOWNERS entry covers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnownedPath {
+    /// Repository-relative directory.
+    pub path: String,
+    /// Lines of churn in the unowned files.
+    pub lines: u64,
+}
+
+/// Data type for codeowners analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeownersData {
+    /// Whether the entries were suggested or checked.
+    pub action: CodeownersAction,
+    /// Suggested entries, or the checked file's entries in file order.
+    pub entries: Vec<CodeownersEntry>,
+    /// Paths no entry covers (only filled by `check`).
+    pub unowned: Vec<UnownedPath>,
+    /// The checked CODEOWNERS file, relative to the repository root.
+    pub codeowners_file: Option<PathBuf>,
+    /// Minimum share of churn expected from each owner.
+    pub min_share: f64,
+    /// The analyzed repository.
+    pub analysis_path: PathBuf,
+}
+
+impl CodeownersData {
+    /// Owners of checked entries whose share is below the minimum.
+    fn low_share_owners(&self) -> impl Iterator<Item = (&CodeownersEntry, &OwnerShare)> {
+        self.entries
+            .iter()
+            .filter(|entry| self.action == CodeownersAction::Check && entry.total_lines > 0)
+            .flat_map(|entry| entry.owners.iter().map(move |owner| (entry, owner)))
+            .filter(|(_, owner)| owner.email.is_some() && owner.percentage < self.min_share)
+    }
+
+    /// Renders the entries as a CODEOWNERS file.
+    fn to_codeowners(&self) -> String {
+        let mut output =
+            String::from("# Generated by `raff codeowners suggest` from contribution history.\n");
+        for entry in &self.entries {
+            let owners: Vec<&str> = entry.owners.iter().map(|o| o.owner.as_str()).collect();
+            output.push_str(&format!("{} {}\n", entry.pattern, owners.join(" ")));
+        }
+        output
+    }
+}
+
+impl ToFindings for CodeownersData {
+    fn to_findings(&self) -> Vec<Finding> {
+        let file = self
+            .codeowners_file
+            .as_ref()
+            .map(|path| path.to_string_lossy().replace('\\', "/"));
+        let mut findings: Vec<Finding> = self
+            .low_share_owners()
+            .map(|(entry, owner)| Finding {
+                rule_id: "codeowners".to_string(),
+                rule_name: "CODEOWNERS Rule".to_string(),
+                severity: Severity::Error,
+                message: format!(
+                    "CODEOWNERS entry '{}' lists '{}', who accounts for {:.1}% of its churn (minimum {:.1}%)",
+                    entry.pattern, owner.owner, owner.percentage, self.min_share
+                ),
+                location: file.clone().zip(entry.line).map(|(uri, line)| Location::with_lines(uri, line, line)),
+                related_locations: Vec::new(),
+                help_uri: Some("https://github.com/liamwh/raff/docs/codeowners".to_string()),
+                fingerprint: Some(format!("codeowners:owner:{}:{}", entry.pattern, owner.owner)),
+            })
+            .collect();
+        findings.extend(self.unowned.iter().map(|unowned| Finding {
+            rule_id: "codeowners".to_string(),
+            rule_name: "CODEOWNERS Rule".to_string(),
+            severity: Severity::Error,
+            message: format!(
+                "'{}' has {} lines of churn but no CODEOWNERS entry",
+                display_dir(&unowned.path),
+                unowned.lines
+            ),
+            location: file.clone().map(Location::new),
+            related_locations: Vec::new(),
+            help_uri: Some("https://github.com/liamwh/raff/docs/codeowners".to_string()),
+            fingerprint: Some(format!("codeowners:unowned:{}", unowned.path)),
+        }));
+        findings
+    }
+}
+
+/// Rule to suggest CODEOWNERS entries and detect CODEOWNERS drift.
+#[derive(Debug, Default)]
+pub struct CodeownersRule;
+
+impl Rule for CodeownersRule {
+    type Config = CodeownersArgs;
+    type Data = CodeownersData;
+
+    fn name() -> &'static str {
+        "codeowners"
+    }
+
+    fn description() -> &'static str {
+        "Suggests a CODEOWNERS file from contribution history and checks an existing one for drift"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+/// A parsed CODEOWNERS entry.
+struct ParsedEntry {
+    pattern: String,
+    line: usize,
+    owners: Vec<String>,
+    matcher: GlobSet,
+}
+
+impl CodeownersRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &CodeownersArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &CodeownersArgs) -> Result<CodeownersData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &CodeownersArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+        let findings = data.to_findings();
+
+        // Check for CI output first (takes precedence)
+        let output = if let Some(ci_format) = &args.ci_output {
+            match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "codeowners")?,
+            }
+        } else {
+            match args.output {
+                CodeownersOutputFormat::Text => match data.action {
+                    CodeownersAction::Suggest => data.to_codeowners(),
+                    CodeownersAction::Check => self.render_check_table(&data),
+                },
+                CodeownersOutputFormat::Json => serde_json::to_string_pretty(&data)?,
+                CodeownersOutputFormat::Yaml => serde_yaml::to_string(&data)?,
+            }
+        };
+        crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+
+        if !findings.is_empty() {
+            return Err(RaffError::analysis_error(
+                "codeowners",
+                format!("Found {} CODEOWNERS drift issue(s)", findings.len()),
+            ));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &CodeownersArgs) -> Result<CodeownersData> {
+        let analysis_path = args.path.canonicalize()?;
+        let repo = Repository::open(&analysis_path).map_err(|e| {
+            RaffError::git_error_with_repo(
+                format!("open Git repository: {}", e),
+                analysis_path.clone(),
+            )
+        })?;
+        let mut history = args.history.clone();
+        if history.since.is_none() && history.from.is_none() && history.range.is_none() {
+            history.since = Some(format_date(
+                Utc::now().timestamp() - DEFAULT_RECENT_MONTHS * SECONDS_PER_MONTH,
+            ));
+        }
+        let window = HistoryWindow::resolve(&repo, &history, false)?;
+        let crates = VolatilityRule::new().discover_crates_and_init_stats(&analysis_path)?;
+        let identities = IdentityResolver::new(&repo, &args.aliases)?;
+        let jobs = args.history.jobs.unwrap_or_else(num_cpus::get);
+        let churn = churn_by_file(&repo, &window, jobs, &identities)?;
+
+        let mut data = CodeownersData {
+            action: args.action,
+            entries: Vec::new(),
+            unowned: Vec::new(),
+            codeowners_file: None,
+            min_share: args.min_share,
+            analysis_path,
+        };
+        match args.action {
+            CodeownersAction::Suggest => {
+                // Lines per component path, then per author email
+                let mut by_dir: BTreeMap<String, HashMap<String, u64>> = BTreeMap::new();
+                for change in &churn {
+                    if let Some(dir) = component_dir(&change.path, &crates, args.granularity) {
+                        *by_dir
+                            .entry(dir)
+                            .or_default()
+                            .entry(change.identity.email.clone())
+                            .or_default() += change.lines;
+                    }
+                }
+                // Handles are looked up by canonical email, whatever its case
+                let email_handles: HashMap<String, &String> = args
+                    .handles
+                    .iter()
+                    .map(|(email, handle)| (identities.canonical_email(email), handle))
+                    .collect();
+                data.entries = by_dir
+                    .into_iter()
+                    .map(|(dir, by_author)| suggest_entry(&dir, by_author, &email_handles, args))
+                    .collect();
+            }
+            CodeownersAction::Check => {
+                let (file, content) = read_codeowners(&data.analysis_path, args)?;
+                let rules = parse_codeowners(&content)?;
+                let handle_emails: HashMap<String, &String> = args
+                    .handles
+                    .iter()
+                    .map(|(email, handle)| (handle.to_lowercase(), email))
+                    .collect();
+
+                let mut by_rule: Vec<HashMap<String, u64>> = vec![HashMap::new(); rules.len()];
+                let mut unowned: BTreeMap<String, u64> = BTreeMap::new();
+                for change in &churn {
+                    match rules
+                        .iter()
+                        .rposition(|rule| rule.matcher.is_match(&change.path))
+                    {
+                        Some(idx) => {
+                            *by_rule[idx]
+                                .entry(change.identity.email.clone())
+                                .or_default() += change.lines;
+                        }
+                        None => {
+                            if let Some(dir) =
+                                component_dir(&change.path, &crates, args.granularity)
+                            {
+                                *unowned.entry(dir).or_default() += change.lines;
+                            }
+                        }
+                    }
+                }
+
+                data.entries = rules
+                    .into_iter()
+                    .zip(by_rule)
+                    .map(|(rule, by_author)| {
+                        let total_lines: u64 = by_author.values().sum();
+                        let owners = rule
+                            .owners
+                            .into_iter()
+                            .map(|owner| {
+                                let email = owner_email(&owner, &handle_emails, &identities);
+                                let lines = email
+                                    .as_ref()
+                                    .and_then(|email| by_author.get(email))
+                                    .copied()
+                                    .unwrap_or(0);
+                                OwnerShare {
+                                    owner,
+                                    email,
+                                    lines,
+                                    percentage: percentage(lines as usize, total_lines as usize),
+                                }
+                            })
+                            .collect();
+                        CodeownersEntry {
+                            pattern: rule.pattern,
+                            line: Some(rule.line),
+                            owners,
+                            total_lines,
+                        }
+                    })
+                    .collect();
+                data.unowned = unowned
+                    .into_iter()
+                    .map(|(path, lines)| UnownedPath { path, lines })
+                    .collect();
+                data.codeowners_file = Some(file);
+            }
+        }
+        Ok(data)
+    }
+
+    fn render_check_table(&self, data: &CodeownersData) -> String {
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Line",
+            "Pattern",
+            "Owner",
+            "Owner Churn",
+            "Share %",
+            "Status"
+        ]);
+        for entry in &data.entries {
+            for owner in &entry.owners {
+                let status = match &owner.email {
+                    None => "not evaluated",
+                    Some(_) if entry.total_lines == 0 => "no churn",
+                    Some(_) if owner.percentage < data.min_share => "low share",
+                    Some(_) => "ok",
+                };
+                table.add_row(row![
+                    r->entry.line.map_or_else(String::new, |line| line.to_string()),
+                    entry.pattern,
+                    owner.owner,
+                    r->owner.lines,
+                    r->format!("{:.1}", owner.percentage),
+                    status
+                ]);
+            }
+        }
+
+        let mut output = format!(
+            "\nCODEOWNERS Check ({}):\n{}",
+            data.codeowners_file
+                .as_ref()
+                .map_or_else(String::new, |path| path.display().to_string()),
+            table
+        );
+        if !data.unowned.is_empty() {
+            let mut unowned = Table::new();
+            unowned.set_format(crate::table_utils::get_default_table_format());
+            unowned.set_titles(row!["Unowned Path", "Churn"]);
+            for path in &data.unowned {
+                unowned.add_row(row![display_dir(&path.path), r->path.lines]);
+            }
+            output.push_str(&format!("\nPaths Without Owners:\n{}", unowned));
+        }
+        output
+    }
+}
+
+/// Builds a suggested entry for a component path from its per-author churn.
+fn suggest_entry(
+    dir: &str,
+    by_author: HashMap<String, u64>,
+    email_handles: &HashMap<String, &String>,
+    args: &CodeownersArgs,
+) -> CodeownersEntry {
+    let total_lines: u64 = by_author.values().sum();
+    let mut authors: Vec<(String, u64)> = by_author.into_iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let owners = authors
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, (_, lines))| {
+            *idx == 0 || percentage(*lines as usize, total_lines as usize) >= args.min_share
+        })
+        .take(args.max_owners.max(1))
+        .map(|(_, (email, lines))| OwnerShare {
+            owner: email_handles
+                .get(&email)
+                .map_or_else(|| email.clone(), |handle| handle.to_string()),
+            email: Some(email),
+            lines,
+            percentage: percentage(lines as usize, total_lines as usize),
+        })
+        .collect();
+    CodeownersEntry {
+        pattern: if dir.is_empty() {
+            "*".to_string()
+        } else if dir.ends_with(".rs") {
+            format!("/{dir}")
+        } else {
+            format!("/{dir}/")
+        },
+        line: None,
+        owners,
+        total_lines,
+    }
+}
+
+/// Returns the repository-relative path of the component containing `path`:
+/// the crate root, or for module granularity the `.rs` file defining the
+/// module. Modules defined by a `lib.rs`, `main.rs` or `mod.rs`, and files
+/// other than `.rs` files, map to their directory.
+fn component_dir(
+    path: &Path,
+    crates: &CrateStatsMap,
+    granularity: OwnershipGranularity,
+) -> Option<String> {
+    let (_, root_path) = VolatilityRule::new().find_owning_crate(path, crates)?;
+    let dir = match granularity {
+        OwnershipGranularity::Crate => root_path,
+        OwnershipGranularity::Module => {
+            let parent = path.parent().unwrap_or(Path::new(""));
+            if path.extension().is_some_and(|ext| ext == "rs") && child_module_dir(path) != parent {
+                path.to_path_buf()
+            } else {
+                parent.to_path_buf()
+            }
+        }
+    };
+    Some(dir.to_string_lossy().replace('\\', "/"))
+}
+
+/// Formats a component directory for messages, showing the root as `/`.
+fn display_dir(dir: &str) -> &str {
+    if dir.is_empty() { "/" } else { dir }
+}
+
+/// Reads the CODEOWNERS file to check, returning its repository-relative path.
+fn read_codeowners(repo_root: &Path, args: &CodeownersArgs) -> Result<(PathBuf, String)> {
+    let candidates: Vec<PathBuf> = match &args.codeowners_file {
+        Some(file) => vec![file.clone()],
+        None => DEFAULT_CODEOWNERS_FILES.iter().map(PathBuf::from).collect(),
+    };
+    for candidate in candidates {
+        let full_path = if candidate.is_absolute() {
+            candidate.clone()
+        } else {
+            repo_root.join(&candidate)
+        };
+        if args.codeowners_file.is_none() && !full_path.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&full_path).map_err(|e| {
+            RaffError::io_error_with_source("read CODEOWNERS file", full_path.clone(), e)
+        })?;
+        let relative = full_path
+            .strip_prefix(repo_root)
+            .map(Path::to_path_buf)
+            .unwrap_or(candidate);
+        return Ok((relative, content));
+    }
+    Err(RaffError::invalid_input_with_arg(
+        format!(
+            "No CODEOWNERS file found (looked for {})",
+            DEFAULT_CODEOWNERS_FILES.join(", ")
+        ),
+        "--codeowners-file",
+    ))
+}
+
+/// Parses CODEOWNERS content into entries, in file order.
+fn parse_codeowners(content: &str) -> Result<Vec<ParsedEntry>> {
+    let mut rules = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut tokens = line
+            .split_whitespace()
+            .take_while(|token| !token.starts_with('#'));
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+        rules.push(ParsedEntry {
+            pattern: pattern.to_string(),
+            line: idx + 1,
+            owners: tokens.map(str::to_string).collect(),
+            matcher: compile_pattern(pattern)?,
+        });
+    }
+    Ok(rules)
+}
+
+/// Compiles a CODEOWNERS pattern, which follows `.gitignore` rules: patterns
+/// with a leading or inner `/` are anchored at the repository root, a trailing
+/// `/` only matches directories, and a match on a directory covers everything
+/// beneath it. As in GitHub's CODEOWNERS, a wildcard in the last segment does
+/// not reach into subdirectories: `docs/*` matches `docs/a.md` but not
+/// `docs/a/b.md`.
+fn compile_pattern(pattern: &str) -> Result<GlobSet> {
+    let trimmed = pattern.trim_start_matches('/');
+    let dir_only = trimmed.ends_with('/');
+    let trimmed = trimmed.trim_end_matches('/');
+    let anchored = pattern.starts_with('/') || trimmed.contains('/');
+    let base = if anchored {
+        trimmed.to_string()
+    } else {
+        format!("**/{trimmed}")
+    };
+
+    let last_segment = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    let mut globs = Vec::new();
+    if dir_only || !last_segment.contains(['*', '?', '[']) {
+        globs.push(format!("{base}/**"));
+    }
+    if !dir_only {
+        globs.push(base);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(
+            GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .map_err(|e| {
+                    RaffError::invalid_input_with_arg(
+                        format!("Invalid CODEOWNERS pattern '{}': {}", pattern, e),
+                        pattern,
+                    )
+                })?,
+        );
+    }
+    builder.build().map_err(|e| {
+        RaffError::invalid_input_with_arg(
+            format!("Invalid CODEOWNERS pattern '{}': {}", pattern, e),
+            pattern,
+        )
+    })
+}
+
+/// Returns the canonical email of a CODEOWNERS owner, or `None` for team and
+/// unmapped handles.
+fn owner_email(
+    owner: &str,
+    handle_emails: &HashMap<String, &String>,
+    identities: &IdentityResolver,
+) -> Option<String> {
+    if owner.starts_with('@') {
+        handle_emails
+            .get(&owner.to_lowercase())
+            .map(|email| identities.canonical_email(email))
+    } else {
+        Some(identities.canonical_email(owner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::HistoryArgs;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn commit(dir: &Path, file: &str, content: &str, author: (&str, &str)) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        for args in [vec!["add", "-A"], vec!["commit", "-q", "-m", file]] {
+            let output = Command::new("git")
+                .args(&args)
+                .env("GIT_AUTHOR_NAME", author.0)
+                .env("GIT_AUTHOR_EMAIL", author.1)
+                .env("GIT_COMMITTER_NAME", author.0)
+                .env("GIT_COMMITTER_EMAIL", author.1)
+                .current_dir(dir)
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success(), "git {:?} failed", args);
+        }
+    }
+
+    /// Creates a workspace where Alice writes `core` and Bob writes `app`.
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let alice = ("Alice", "alice@example.com");
+        let bob = ("Bob", "bob@example.com");
+        let output = Command::new("git")
+            .args(["init", "-q", "-b", "main"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        commit(
+            dir,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
+            alice,
+        );
+        commit(
+            dir,
+            "crates/core/src/lib.rs",
+            "a\nb\nc\nd\ne\nf\ng\nh\n",
+            alice,
+        );
+        commit(dir, "crates/core/src/util.rs", "a\n", bob);
+        commit(
+            dir,
+            "crates/app/Cargo.toml",
+            "[package]\nname = \"app\"\n",
+            bob,
+        );
+        commit(dir, "crates/app/src/main.rs", "a\nb\nc\n", bob);
+        temp_dir
+    }
+
+    fn args(path: &Path, action: CodeownersAction) -> CodeownersArgs {
+        CodeownersArgs {
+            action,
+            path: path.to_path_buf(),
+            history: HistoryArgs {
+                jobs: Some(2),
+                ..HistoryArgs::default()
+            },
+            granularity: OwnershipGranularity::Crate,
+            codeowners_file: None,
+            max_owners: 2,
+            min_share: 20.0,
+            handles: BTreeMap::from([("bob@example.com".to_string(), "@bob".to_string())]),
+            aliases: BTreeMap::new(),
+            output: CodeownersOutputFormat::Text,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    #[test]
+    fn test_suggest_lists_top_contributors_per_crate() {
+        let temp_dir = create_repo();
+        let data = CodeownersRule::new()
+            .analyze(&args(temp_dir.path(), CodeownersAction::Suggest))
+            .unwrap();
+
+        // Bob's single line in core is below the 20% minimum share
+        assert_eq!(
+            data.to_codeowners(),
+            "# Generated by `raff codeowners suggest` from contribution history.\n\
+             /crates/app/ @bob\n\
+             /crates/core/ alice@example.com\n"
+        );
+        assert!(data.to_findings().is_empty());
+    }
+
+    #[test]
+    fn test_check_flags_low_share_owners_and_unowned_paths() {
+        let temp_dir = create_repo();
+        fs::create_dir_all(temp_dir.path().join(".github")).unwrap();
+        fs::write(
+            temp_dir.path().join(".github/CODEOWNERS"),
+            "# Owners\n/crates/core/ alice@example.com @bob @org/team\n",
+        )
+        .unwrap();
+
+        let data = CodeownersRule::new()
+            .analyze(&args(temp_dir.path(), CodeownersAction::Check))
+            .unwrap();
+
+        assert_eq!(
+            data.codeowners_file,
+            Some(PathBuf::from(".github/CODEOWNERS"))
+        );
+        let entry = &data.entries[0];
+        assert_eq!(entry.line, Some(2));
+        assert_eq!(entry.owners[1].email.as_deref(), Some("bob@example.com"));
+        assert_eq!(entry.owners[2].email, None);
+        assert_eq!(data.unowned.len(), 1);
+        assert_eq!(data.unowned[0].path, "crates/app");
+
+        let findings = data.to_findings();
+        assert_eq!(findings.len(), 2);
+        assert!(findings[0].message.contains("'@bob'"));
+        assert_eq!(findings[0].location.as_ref().unwrap().start_line, Some(2));
+        assert!(findings[1].message.contains("'crates/app'"));
+    }
+
+    #[test]
+    fn test_handles_and_emails_match_case_insensitively() {
+        let temp_dir = create_repo();
+        let mut suggest = args(temp_dir.path(), CodeownersAction::Suggest);
+        suggest.handles = BTreeMap::from([("Bob@Example.com".to_string(), "@bob".to_string())]);
+        let data = CodeownersRule::new().analyze(&suggest).unwrap();
+        assert!(data.to_codeowners().contains("/crates/app/ @bob\n"));
+
+        fs::write(
+            temp_dir.path().join("CODEOWNERS"),
+            "/crates/app/ @Bob\n/crates/core/ Alice@Example.com\n",
+        )
+        .unwrap();
+        let data = CodeownersRule::new()
+            .analyze(&args(temp_dir.path(), CodeownersAction::Check))
+            .unwrap();
+        assert_eq!(
+            data.entries[0].owners[0].email.as_deref(),
+            Some("bob@example.com")
+        );
+        assert!(data.to_findings().is_empty());
+    }
+
+    #[test]
+    fn test_suggest_module_granularity_follows_module_files() {
+        let temp_dir = create_repo();
+        let mut suggest = args(temp_dir.path(), CodeownersAction::Suggest);
+        suggest.granularity = OwnershipGranularity::Module;
+        let data = CodeownersRule::new().analyze(&suggest).unwrap();
+
+        // `util.rs` defines its own module, `lib.rs` and `main.rs` their directory's
+        assert_eq!(
+            data.to_codeowners(),
+            "# Generated by `raff codeowners suggest` from contribution history.\n\
+             /crates/app/ @bob\n\
+             /crates/app/src/ @bob\n\
+             /crates/core/ alice@example.com\n\
+             /crates/core/src/ alice@example.com\n\
+             /crates/core/src/util.rs @bob\n"
+        );
+    }
+
+    #[test]
+    fn test_suggest_defaults_to_recent_history() {
+        let temp_dir = create_repo();
+        fs::write(temp_dir.path().join("crates/app/src/old.rs"), "a\n").unwrap();
+        for args in [vec!["add", "-A"], vec!["commit", "-q", "-m", "old"]] {
+            let output = Command::new("git")
+                .args(&args)
+                .env("GIT_AUTHOR_NAME", "Old")
+                .env("GIT_AUTHOR_EMAIL", "old@example.com")
+                .env("GIT_AUTHOR_DATE", "2020-01-01T00:00:00Z")
+                .env("GIT_COMMITTER_NAME", "Old")
+                .env("GIT_COMMITTER_EMAIL", "old@example.com")
+                .env("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z")
+                .current_dir(temp_dir.path())
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success(), "git {:?} failed", args);
+        }
+        let mut suggest = args(temp_dir.path(), CodeownersAction::Suggest);
+        suggest.granularity = OwnershipGranularity::Module;
+
+        let data = CodeownersRule::new().analyze(&suggest).unwrap();
+        assert!(!data.to_codeowners().contains("old.rs"));
+
+        suggest.history.since = Some("2019-01-01".to_string());
+        let data = CodeownersRule::new().analyze(&suggest).unwrap();
+        assert!(
+            data.to_codeowners()
+                .contains("/crates/app/src/old.rs old@example.com\n")
+        );
+    }
+
+    #[test]
+    fn test_check_requires_codeowners_file() {
+        let temp_dir = create_repo();
+        let result = CodeownersRule::new().analyze(&args(temp_dir.path(), CodeownersAction::Check));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compile_pattern_follows_codeowners_rules() {
+        let matches =
+            |pattern: &str, path: &str| compile_pattern(pattern).unwrap().is_match(Path::new(path));
+        assert!(matches("*", "crates/core/src/lib.rs"));
+        assert!(matches("*.rs", "crates/core/src/lib.rs"));
+        assert!(!matches("*.rs", "Cargo.toml"));
+        assert!(matches("/crates/core/", "crates/core/src/lib.rs"));
+        assert!(!matches("/crates/core/", "other/crates/core/lib.rs"));
+        assert!(matches("docs", "crates/core/docs/readme.md"));
+        assert!(matches("crates/*", "crates/core"));
+        assert!(matches("docs/*", "docs/a.md"));
+        assert!(!matches("docs/*", "docs/a/b.md"));
+        assert!(matches("docs/*/", "docs/a/b.md"));
+        assert!(!matches("/src/*.rs", "src/nested/lib.rs"));
+    }
+}
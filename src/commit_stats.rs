@@ -0,0 +1,372 @@
+//! Per-commit diff statistics shared by history-based rules.
+//!
+//! Diffing every commit in the analysis window is the dominant cost of the
+//! volatility and contributor report rules. A commit's diff against its first
+//! parent never changes, so the per-file line counts are persisted in the
+//! [`cache`](crate::cache) keyed by commit id. A later run only diffs commits
+//! it has not seen before and folds them into the stored set.
+//!
+//! The stored statistics are unfiltered: path, author and message filters,
+//! date bounds and crate ownership are applied by each rule when it
+//! aggregates, so one store serves every rule and every configuration.
+//!
+//! Commits missing from the store can be diffed in parallel with
+//! [`CommitStatsStore::prefetch`]. Each worker thread opens its own
+//! [`Repository`] handle, since libgit2 handles must not be shared across
+//! threads. Results land in the keyed store and rules aggregate them in
+//! history order afterwards, so the output matches a sequential run exactly.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> raff_core::error::Result<()> {
+//! use git2::Repository;
+//! use raff_core::commit_stats::CommitStatsStore;
+//!
+//! let repo = Repository::open(".")?;
+//! let mut store = CommitStatsStore::open(&repo)?;
+//! let head = repo.head()?.peel_to_commit()?;
+//! let diff = store.get_or_compute(&repo, &head)?;
+//! println!("{} files changed", diff.files.len());
+//! store.save()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::path::PathBuf;
+
+use git2::{Commit, DiffOptions, Oid, Patch, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{CacheEntry, CacheKey, CacheManager};
+use crate::error::{RaffError, Result};
+use crate::git_utils::map_with_repository;
+
+/// Cache version for stored commit statistics.
+/// Increment this when [`CommitDiff`] changes to invalidate old stores.
+const COMMIT_STATS_CACHE_VERSION: &str = "1";
+
+/// Rewrite an unchanged store once it is this old, so that the cache's
+/// expiry does not discard statistics that are still in use.
+const REFRESH_AFTER_SECONDS: u64 = 24 * 60 * 60;
+
+/// Lines changed in a single file by a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// Path of the file relative to the repository root. For deletions this
+    /// is the old path.
+    pub path: PathBuf,
+    /// Lines added to the file.
+    pub lines_added: u32,
+    /// Lines removed from the file.
+    pub lines_deleted: u32,
+}
+
+/// Diff statistics of a commit against its first parent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitDiff {
+    /// Every file changed by the commit.
+    pub files: Vec<FileChange>,
+}
+
+/// A persistent map from commit id to [`CommitDiff`] for one repository.
+pub struct CommitStatsStore {
+    cache_manager: CacheManager,
+    cache_key: CacheKey,
+    diffs: HashMap<String, CommitDiff>,
+    dirty: bool,
+    stored_age_seconds: u64,
+}
+
+impl CommitStatsStore {
+    /// Opens the store for `repo` in the default cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be created or read.
+    pub fn open(repo: &Repository) -> Result<Self> {
+        Self::with_cache_manager(repo, CacheManager::new()?)
+    }
+
+    /// Opens the store for `repo` using the given cache manager.
+    ///
+    /// A missing, expired or unreadable store starts out empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache entry cannot be read.
+    pub fn with_cache_manager(repo: &Repository, cache_manager: CacheManager) -> Result<Self> {
+        let repo_path = repo.path().canonicalize()?;
+        let cache_key = CacheKey::new(
+            format!("commit_stats:{}", repo_path.display()),
+            None,
+            vec![(
+                "cache_version".to_string(),
+                COMMIT_STATS_CACHE_VERSION.to_string(),
+            )],
+        );
+
+        let (diffs, stored_age_seconds) = match cache_manager.get(&cache_key)? {
+            Some(entry) => match bincode::deserialize(&entry.data) {
+                Ok(diffs) => (diffs, entry.age_seconds()),
+                Err(e) => {
+                    tracing::debug!(error = %e, "Discarding unreadable commit statistics store");
+                    (HashMap::new(), 0)
+                }
+            },
+            None => (HashMap::new(), 0),
+        };
+        tracing::debug!(commits = diffs.len(), "Loaded commit statistics store");
+
+        Ok(Self {
+            cache_manager,
+            cache_key,
+            diffs,
+            dirty: false,
+            stored_age_seconds,
+        })
+    }
+
+    /// Returns the diff statistics for `commit`, diffing it only if it has
+    /// not been seen before.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the commit cannot be diffed against its parent.
+    pub fn get_or_compute(
+        &mut self,
+        repo: &Repository,
+        commit: &Commit<'_>,
+    ) -> Result<&CommitDiff> {
+        match self.diffs.entry(commit.id().to_string()) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let diff = diff_commit(repo, commit)?;
+                self.dirty = true;
+                Ok(entry.insert(diff))
+            }
+        }
+    }
+
+    /// Diffs every commit in `oids` that is not yet stored, spread across
+    /// `jobs` worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a worker cannot open the repository or diff a
+    /// commit, or if a worker thread panics.
+    pub fn prefetch(&mut self, repo: &Repository, oids: &[Oid], jobs: usize) -> Result<()> {
+        let missing: Vec<Oid> = oids
+            .iter()
+            .copied()
+            .filter(|oid| !self.contains(*oid))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(
+            commits = missing.len(),
+            jobs,
+            "Diffing commits missing from store"
+        );
+
+        let diffs = map_with_repository(repo.path(), &missing, jobs, |repo, oid| {
+            diff_commit(repo, &repo.find_commit(*oid)?)
+        })?;
+        for (oid, diff) in missing.into_iter().zip(diffs) {
+            self.diffs.insert(oid.to_string(), diff);
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Returns `true` if statistics for the commit with id `oid` are stored.
+    #[must_use]
+    pub fn contains(&self, oid: Oid) -> bool {
+        self.diffs.contains_key(&oid.to_string())
+    }
+
+    /// Returns the number of commits in the store.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.diffs.len()
+    }
+
+    /// Returns `true` if the store holds no commits.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.diffs.is_empty()
+    }
+
+    /// Persists the store if new commits were added or it is due a refresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be serialized or written.
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty && self.stored_age_seconds < REFRESH_AFTER_SECONDS {
+            return Ok(());
+        }
+        let data = bincode::serialize(&self.diffs).map_err(|e| {
+            RaffError::parse_error(format!("Failed to serialize commit statistics: {}", e))
+        })?;
+        self.cache_manager
+            .put(&self.cache_key, CacheEntry::new(data))?;
+        tracing::debug!(commits = self.diffs.len(), "Saved commit statistics store");
+        Ok(())
+    }
+}
+
+/// Diffs `commit` against its first parent, or the empty tree for a root commit.
+fn diff_commit(repo: &Repository, commit: &Commit<'_>) -> Result<CommitDiff> {
+    let tree = commit.tree()?;
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+    let mut diff_opts = DiffOptions::new();
+    diff_opts.context_lines(0);
+    diff_opts.interhunk_lines(0);
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let (lines_added, lines_deleted) = match Patch::from_diff(&diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions as u32, deletions as u32)
+            }
+            None => (0, 0),
+        };
+        files.push(FileChange {
+            path: path.to_path_buf(),
+            lines_added,
+            lines_deleted,
+        });
+    }
+    Ok(CommitDiff { files })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit_file(dir: &Path, file: &str, content: &str) {
+        fs::write(dir.join(file), content).expect("Failed to write file");
+        git(dir, &["add", "-A"]);
+        git(dir, &["commit", "-q", "-m", file]);
+    }
+
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        commit_file(dir, "a.rs", "one\ntwo\n");
+        commit_file(dir, "a.rs", "one\nthree\nfour\n");
+        temp_dir
+    }
+
+    fn head(repo: &Repository) -> Commit<'_> {
+        repo.head().unwrap().peel_to_commit().unwrap()
+    }
+
+    #[test]
+    fn test_diff_counts_lines_against_first_parent() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(Some(cache_dir.path().to_path_buf())).unwrap();
+        let mut store = CommitStatsStore::with_cache_manager(&repo, cache).unwrap();
+
+        let diff = store.get_or_compute(&repo, &head(&repo)).unwrap();
+        assert_eq!(
+            diff.files,
+            vec![FileChange {
+                path: PathBuf::from("a.rs"),
+                lines_added: 2,
+                lines_deleted: 1,
+            }]
+        );
+
+        let root = head(&repo).parent(0).unwrap();
+        let diff = store.get_or_compute(&repo, &root).unwrap();
+        assert_eq!(diff.files[0].lines_added, 2);
+        assert_eq!(diff.files[0].lines_deleted, 0);
+    }
+
+    #[test]
+    fn test_saved_store_only_diffs_new_commits() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        let cache_dir = TempDir::new().unwrap();
+        let open = |repo: &Repository| {
+            let cache = CacheManager::with_dir(Some(cache_dir.path().to_path_buf())).unwrap();
+            CommitStatsStore::with_cache_manager(repo, cache).unwrap()
+        };
+
+        let repo = Repository::open(dir).unwrap();
+        let first_head = head(&repo).id();
+        let mut store = open(&repo);
+        store.get_or_compute(&repo, &head(&repo)).unwrap();
+        store.save().unwrap();
+
+        commit_file(dir, "b.rs", "new\n");
+        let mut store = open(&repo);
+        assert_eq!(store.len(), 1);
+        assert!(store.contains(first_head));
+        assert!(!store.contains(head(&repo).id()));
+
+        store.get_or_compute(&repo, &head(&repo)).unwrap();
+        store.save().unwrap();
+        assert_eq!(open(&repo).len(), 2);
+    }
+
+    #[test]
+    fn test_parallel_prefetch_matches_sequential_diffs() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        for i in 0..6 {
+            commit_file(dir, &format!("f{i}.rs"), &"line\n".repeat(i + 1));
+        }
+        let repo = Repository::open(dir).unwrap();
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        let oids: Vec<Oid> = revwalk.map(|oid| oid.unwrap()).collect();
+
+        let sequential_dir = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(Some(sequential_dir.path().to_path_buf())).unwrap();
+        let mut sequential = CommitStatsStore::with_cache_manager(&repo, cache).unwrap();
+        sequential.prefetch(&repo, &oids, 1).unwrap();
+
+        let parallel_dir = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(Some(parallel_dir.path().to_path_buf())).unwrap();
+        let mut parallel = CommitStatsStore::with_cache_manager(&repo, cache).unwrap();
+        parallel.prefetch(&repo, &oids, 3).unwrap();
+
+        assert_eq!(parallel.len(), oids.len());
+        assert_eq!(parallel.diffs, sequential.diffs);
+    }
+}
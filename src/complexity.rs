@@ -0,0 +1,1046 @@
+//! Built-in complexity metrics engine.
+//!
+//! This module computes the metrics reported by the rust-code-analysis rule
+//! directly on the `syn` AST, so the rule does not need the external
+//! `rust-code-analysis-cli` tool. The results use the same [`AnalysisUnit`]
+//! and [`CodeSpace`] shapes that the tool emits: one unit per file with a
+//! nested space for every function, method, closure, `impl` block and trait.
+//!
+//! # Metrics
+//!
+//! Every space reports metrics for itself and all spaces nested in it:
+//!
+//! - **Lines**: SLOC is every line the space spans, PLOC the lines holding
+//!   code, CLOC the lines holding a comment and blank the lines holding
+//!   neither. LLOC is the number of statements.
+//! - **Cyclomatic**: one per space, plus one for each `if`, `else if`,
+//!   loop, extra `match` arm, match guard, `&&`, `||` and `?`.
+//! - **Cognitive**: control flow structures cost one plus their nesting
+//!   depth, `else` branches, labelled jumps and each run of like boolean
+//!   operators cost one.
+//! - **Halstead**: keywords, punctuation and delimiters are operators;
+//!   identifiers, lifetimes and literals are operands. Attributes, including
+//!   doc comments, are skipped.
+//! - **NOM/NARGS/NEXITS**: functions and closures, their parameters, and
+//!   their `return` and `?` exit points.
+//! - **ABC**: assignments (`let` with a value, `=` and compound
+//!   assignment), branches (function and method calls) and conditions
+//!   (comparisons, `else`, `match` arms and `?`), with the magnitude
+//!   `sqrt(A² + B² + C²)`.
+//! - **Maintainability index**: the original, SEI and Visual Studio
+//!   variants, derived from Halstead volume, cyclomatic complexity, SLOC
+//!   and the share of comment lines.
+//!
+//! Macro bodies are opaque token streams, so control flow inside them does
+//! not count towards cyclomatic or cognitive complexity. Their tokens do
+//! count towards Halstead metrics.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use proc_macro2::{Delimiter, LineColumn, Spacing, TokenStream, TokenTree};
+use syn::{
+    BinOp, Expr, ExprAssign, ExprBinary, ExprBreak, ExprCall, ExprClosure, ExprContinue,
+    ExprForLoop, ExprIf, ExprLoop, ExprMatch, ExprMethodCall, ExprReturn, ExprTry, ExprWhile,
+    ImplItemFn, ItemFn, ItemImpl, ItemTrait, Local, Stmt, TraitItemFn, spanned::Spanned,
+    visit::Visit,
+};
+
+use crate::counter::type_name;
+use crate::error::{RaffError, Result};
+use crate::rust_code_analysis_rule::{
+    AbcMetrics, AnalysisUnit, CodeSpace, CognitiveMetrics, CyclomaticMetrics, HalsteadMetrics,
+    ItemMetrics, LocMetrics, MaintainabilityMetrics, NargsMetrics, NexitsMetrics, NomMetrics,
+};
+
+/// Rust keywords, counted as Halstead operators rather than operands.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "super", "trait", "type", "unsafe", "use", "where", "while",
+];
+
+/// Reads and analyzes a Rust source file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not valid Rust.
+pub fn analyze_file(path: &Path) -> Result<AnalysisUnit> {
+    let source = std::fs::read_to_string(path).map_err(|e| {
+        RaffError::io_error_with_source("Failed to read source file", path.to_path_buf(), e)
+    })?;
+    analyze_source(&path.display().to_string(), &source).map_err(|e| match e {
+        RaffError::ParseError { context, .. } => {
+            RaffError::parse_error_with_file(path.to_path_buf(), context)
+        }
+        other => other,
+    })
+}
+
+/// Analyzes Rust source code, naming the resulting unit `name`.
+///
+/// # Errors
+///
+/// Returns an error if `source` is not valid Rust.
+pub fn analyze_source(name: &str, source: &str) -> Result<AnalysisUnit> {
+    let ast = syn::parse_file(source)
+        .map_err(|e| RaffError::parse_error(format!("Failed to parse {name}: {e}")))?;
+
+    let lines = classify_lines(source);
+    let mut tokens = Vec::new();
+    match source.parse::<TokenStream>() {
+        Ok(stream) => lex_tokens(stream, &mut tokens),
+        Err(e) => tracing::debug!("Could not tokenize {} for Halstead metrics: {}", name, e),
+    }
+
+    let unit_end = LineColumn {
+        line: lines.len(),
+        column: usize::MAX,
+    };
+    let mut engine = Engine {
+        lines: &lines,
+        tokens: &tokens,
+        current: SpaceBuilder::new(
+            name.to_string(),
+            "unit",
+            LineColumn { line: 1, column: 0 },
+            unit_end,
+            0.0,
+        ),
+        parents: Vec::new(),
+    };
+    engine.visit_file(&ast);
+
+    let Engine { current: root, .. } = engine;
+    let metrics = metrics_for(&lines, &tokens, &root);
+    Ok(AnalysisUnit {
+        name: name.to_string(),
+        kind: "unit".to_string(),
+        spaces: root.children,
+        metrics: Some(metrics),
+    })
+}
+
+/// Whether a source line holds code, a comment, or both.
+#[derive(Debug, Default, Clone, Copy)]
+struct LineKind {
+    code: bool,
+    comment: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    Code,
+    LineComment,
+    BlockComment(usize),
+    Str,
+    RawStr(usize),
+}
+
+/// Classifies every line of `source`, skipping over string and character
+/// literals so that `//` inside them is not mistaken for a comment.
+fn classify_lines(source: &str) -> Vec<LineKind> {
+    let mut lines = vec![LineKind::default(); source.lines().count()];
+    let chars: Vec<char> = source.chars().collect();
+    let mut state = ScanState::Code;
+    let mut line = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            line += 1;
+            if state == ScanState::LineComment {
+                state = ScanState::Code;
+            }
+            i += 1;
+            continue;
+        }
+        let Some(kind) = lines.get_mut(line) else {
+            break;
+        };
+
+        match state {
+            ScanState::Code => {
+                if c.is_whitespace() {
+                    i += 1;
+                    continue;
+                }
+                if c == '/' && next == Some('/') {
+                    kind.comment = true;
+                    state = ScanState::LineComment;
+                    i += 2;
+                    continue;
+                }
+                if c == '/' && next == Some('*') {
+                    kind.comment = true;
+                    state = ScanState::BlockComment(1);
+                    i += 2;
+                    continue;
+                }
+                kind.code = true;
+                if c == '"' {
+                    state = ScanState::Str;
+                } else if c == 'r' && starts_raw_string(&chars, i) {
+                    let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                    state = ScanState::RawStr(hashes);
+                    i += hashes + 2;
+                    continue;
+                } else if c == '\'' {
+                    i = skip_char_literal(&chars, i);
+                    continue;
+                }
+            }
+            ScanState::LineComment => kind.comment = true,
+            ScanState::BlockComment(depth) => {
+                kind.comment = true;
+                if c == '/' && next == Some('*') {
+                    state = ScanState::BlockComment(depth + 1);
+                    i += 2;
+                    continue;
+                }
+                if c == '*' && next == Some('/') {
+                    state = if depth == 1 {
+                        ScanState::Code
+                    } else {
+                        ScanState::BlockComment(depth - 1)
+                    };
+                    i += 2;
+                    continue;
+                }
+            }
+            ScanState::Str => {
+                if !c.is_whitespace() {
+                    kind.code = true;
+                }
+                if c == '\\' && next != Some('\n') {
+                    i += 2;
+                    continue;
+                }
+                if c == '"' {
+                    state = ScanState::Code;
+                }
+            }
+            ScanState::RawStr(hashes) => {
+                if !c.is_whitespace() {
+                    kind.code = true;
+                }
+                if c == '"' && chars[i + 1..].iter().take_while(|&&c| c == '#').count() >= hashes {
+                    state = ScanState::Code;
+                    i += hashes + 1;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+    lines
+}
+
+/// Returns `true` if the `r` at `i` opens a raw string such as `r"..."`,
+/// `r#"..."#` or `br"..."`.
+fn starts_raw_string(chars: &[char], i: usize) -> bool {
+    let is_ident = |index: Option<usize>| {
+        index
+            .and_then(|index| chars.get(index))
+            .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+    };
+    let prefix_ok =
+        !is_ident(i.checked_sub(1)) || (chars[i - 1] == 'b' && !is_ident(i.checked_sub(2)));
+    let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+    prefix_ok && chars.get(i + 1 + hashes) == Some(&'"')
+}
+
+/// Skips a character literal starting at `i`, or just the quote of a
+/// lifetime, returning the index to continue scanning from.
+fn skip_char_literal(chars: &[char], i: usize) -> usize {
+    match chars.get(i + 1) {
+        Some('\\') => chars[i + 2..]
+            .iter()
+            .skip(1)
+            .position(|&c| c == '\'')
+            .map_or(i + 1, |offset| i + offset + 4),
+        Some(_) if chars.get(i + 2) == Some(&'\'') => i + 3,
+        _ => i + 1,
+    }
+}
+
+/// A Halstead operator or operand and where it starts.
+#[derive(Debug)]
+struct Token {
+    start: LineColumn,
+    text: String,
+    is_operator: bool,
+}
+
+/// Flattens `stream` into Halstead tokens in source order. Joint punctuation
+/// such as `::` or `+=` forms a single operator, and a delimited group counts
+/// as one operator for its delimiter pair.
+fn lex_tokens(stream: TokenStream, out: &mut Vec<Token>) {
+    let trees: Vec<TokenTree> = stream.into_iter().collect();
+    let mut i = 0;
+    while i < trees.len() {
+        match &trees[i] {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                let mut group = i + 1;
+                if matches!(trees.get(group), Some(TokenTree::Punct(bang)) if bang.as_char() == '!')
+                {
+                    group += 1;
+                }
+                if matches!(trees.get(group), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket)
+                {
+                    i = group + 1;
+                    continue;
+                }
+                out.push(Token {
+                    start: punct.span().start(),
+                    text: "#".to_string(),
+                    is_operator: true,
+                });
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == '\''
+                    && matches!(trees.get(i + 1), Some(TokenTree::Ident(_))) =>
+            {
+                out.push(Token {
+                    start: punct.span().start(),
+                    text: format!("'{}", trees[i + 1]),
+                    is_operator: false,
+                });
+                i += 1;
+            }
+            TokenTree::Punct(punct) => {
+                let mut text = punct.as_char().to_string();
+                let mut spacing = punct.spacing();
+                while spacing == Spacing::Joint
+                    && let Some(TokenTree::Punct(next)) = trees.get(i + 1)
+                    && next.as_char() != '\''
+                {
+                    text.push(next.as_char());
+                    spacing = next.spacing();
+                    i += 1;
+                }
+                out.push(Token {
+                    start: punct.span().start(),
+                    text,
+                    is_operator: true,
+                });
+            }
+            TokenTree::Ident(ident) => {
+                let text = ident.to_string();
+                out.push(Token {
+                    start: ident.span().start(),
+                    is_operator: KEYWORDS.contains(&text.as_str()),
+                    text,
+                });
+            }
+            TokenTree::Literal(literal) => out.push(Token {
+                start: literal.span().start(),
+                text: literal.to_string(),
+                is_operator: false,
+            }),
+            TokenTree::Group(group) => {
+                let delimiters = match group.delimiter() {
+                    Delimiter::Parenthesis => Some("()"),
+                    Delimiter::Brace => Some("{}"),
+                    Delimiter::Bracket => Some("[]"),
+                    Delimiter::None => None,
+                };
+                if let Some(delimiters) = delimiters {
+                    out.push(Token {
+                        start: group.span_open().start(),
+                        text: delimiters.to_string(),
+                        is_operator: true,
+                    });
+                }
+                lex_tokens(group.stream(), out);
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Counts accumulated over a space and the spaces nested in it.
+#[derive(Debug, Default, Clone, Copy)]
+struct Totals {
+    spaces: f64,
+    cyclomatic: f64,
+    cognitive: f64,
+    lloc: f64,
+    functions: f64,
+    closures: f64,
+    nargs: f64,
+    exits: f64,
+    assignments: f64,
+    branches: f64,
+    conditions: f64,
+}
+
+impl Totals {
+    fn add(&mut self, other: &Totals) {
+        self.spaces += other.spaces;
+        self.cyclomatic += other.cyclomatic;
+        self.cognitive += other.cognitive;
+        self.lloc += other.lloc;
+        self.functions += other.functions;
+        self.closures += other.closures;
+        self.nargs += other.nargs;
+        self.exits += other.exits;
+        self.assignments += other.assignments;
+        self.branches += other.branches;
+        self.conditions += other.conditions;
+    }
+
+    fn methods(&self) -> f64 {
+        self.functions + self.closures
+    }
+}
+
+/// A space whose metrics are still being collected.
+#[derive(Debug)]
+struct SpaceBuilder {
+    name: String,
+    kind: &'static str,
+    start: LineColumn,
+    end: LineColumn,
+    /// Nesting depth of control flow, for cognitive complexity.
+    nesting: usize,
+    totals: Totals,
+    children: Vec<CodeSpace>,
+}
+
+impl SpaceBuilder {
+    fn new(
+        name: String,
+        kind: &'static str,
+        start: LineColumn,
+        end: LineColumn,
+        nargs: f64,
+    ) -> Self {
+        let totals = Totals {
+            spaces: 1.0,
+            cyclomatic: 1.0,
+            functions: f64::from(u8::from(kind == "function")),
+            closures: f64::from(u8::from(kind == "closure")),
+            nargs,
+            ..Totals::default()
+        };
+        Self {
+            name,
+            kind,
+            start,
+            end,
+            nesting: 0,
+            totals,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Builds the metrics of a finished space from its totals, its lines and
+/// the tokens within its span.
+fn metrics_for(lines: &[LineKind], tokens: &[Token], space: &SpaceBuilder) -> ItemMetrics {
+    let first = space.start.line.max(1);
+    let last = space.end.line.min(lines.len());
+    let span_lines = lines.get(first - 1..last).unwrap_or_default();
+    let sloc = span_lines.len() as f64;
+    let ploc = span_lines.iter().filter(|l| l.code).count() as f64;
+    let cloc = span_lines.iter().filter(|l| l.comment).count() as f64;
+    let blank = span_lines.iter().filter(|l| !l.code && !l.comment).count() as f64;
+
+    let position = |pos: LineColumn| (pos.line, pos.column);
+    let lo = tokens.partition_point(|t| position(t.start) < position(space.start));
+    let hi = tokens.partition_point(|t| position(t.start) < position(space.end));
+    let halstead = halstead(tokens.get(lo..hi).unwrap_or_default());
+
+    let totals = &space.totals;
+    let per_method = |value: f64| {
+        if totals.methods() > 0.0 {
+            value / totals.methods()
+        } else {
+            0.0
+        }
+    };
+
+    ItemMetrics {
+        loc: Some(LocMetrics {
+            sloc,
+            ploc,
+            lloc: totals.lloc,
+            cloc,
+            blank,
+        }),
+        cyclomatic: Some(CyclomaticMetrics {
+            sum: totals.cyclomatic,
+            average: totals.cyclomatic / totals.spaces,
+        }),
+        mi: Some(maintainability(
+            halstead.volume,
+            totals.cyclomatic,
+            sloc,
+            cloc,
+        )),
+        halstead: Some(halstead),
+        cognitive: Some(CognitiveMetrics {
+            sum: totals.cognitive,
+            average: per_method(totals.cognitive),
+        }),
+        nom: Some(NomMetrics {
+            functions: totals.functions,
+            closures: totals.closures,
+            total: totals.methods(),
+        }),
+        nargs: Some(NargsMetrics {
+            total: totals.nargs,
+            average: per_method(totals.nargs),
+        }),
+        nexits: Some(NexitsMetrics {
+            sum: totals.exits,
+            average: per_method(totals.exits),
+        }),
+        abc: Some(AbcMetrics {
+            assignments: totals.assignments,
+            branches: totals.branches,
+            conditions: totals.conditions,
+            magnitude: (totals.assignments.powi(2)
+                + totals.branches.powi(2)
+                + totals.conditions.powi(2))
+            .sqrt(),
+        }),
+    }
+}
+
+fn halstead(tokens: &[Token]) -> HalsteadMetrics {
+    let mut operators: HashMap<&str, usize> = HashMap::new();
+    let mut operands: HashMap<&str, usize> = HashMap::new();
+    for token in tokens {
+        let counts = if token.is_operator {
+            &mut operators
+        } else {
+            &mut operands
+        };
+        *counts.entry(token.text.as_str()).or_default() += 1;
+    }
+
+    let n1 = operators.len() as f64;
+    let n2 = operands.len() as f64;
+    let total_operands = operands.values().sum::<usize>() as f64;
+    let length = tokens.len() as f64;
+    let vocabulary = n1 + n2;
+    let volume = if vocabulary > 0.0 {
+        length * vocabulary.log2()
+    } else {
+        0.0
+    };
+    let difficulty = if n2 > 0.0 {
+        n1 / 2.0 * total_operands / n2
+    } else {
+        0.0
+    };
+    let effort = difficulty * volume;
+    HalsteadMetrics {
+        n1,
+        n2,
+        length,
+        vocabulary,
+        volume,
+        difficulty,
+        effort,
+        time: effort / 18.0,
+        bugs: effort.powf(2.0 / 3.0) / 3000.0,
+    }
+}
+
+fn maintainability(volume: f64, cyclomatic: f64, sloc: f64, cloc: f64) -> MaintainabilityMetrics {
+    let volume = volume.max(1.0);
+    let sloc = sloc.max(1.0);
+    let mi_original = 171.0 - 5.2 * volume.ln() - 0.23 * cyclomatic - 16.2 * sloc.ln();
+    let mi_sei = 171.0 - 5.2 * volume.log2() - 0.23 * cyclomatic - 16.2 * sloc.log2()
+        + 50.0 * (cloc / sloc * 2.4).sqrt().sin();
+    MaintainabilityMetrics {
+        mi_original,
+        mi_sei,
+        mi_visual_studio: (mi_original * 100.0 / 171.0).max(0.0),
+    }
+}
+
+/// Walks the AST, opening a space for every function-like item, `impl`
+/// block and trait.
+struct Engine<'a> {
+    lines: &'a [LineKind],
+    tokens: &'a [Token],
+    current: SpaceBuilder,
+    parents: Vec<SpaceBuilder>,
+}
+
+impl Engine<'_> {
+    fn enter(
+        &mut self,
+        name: String,
+        kind: &'static str,
+        start: LineColumn,
+        end: LineColumn,
+        nargs: usize,
+    ) {
+        let mut space = SpaceBuilder::new(name, kind, start, end, nargs as f64);
+        // closures are nested inside their enclosing function's control flow
+        if kind == "closure" {
+            space.nesting = self.current.nesting + 1;
+        }
+        let parent = std::mem::replace(&mut self.current, space);
+        self.parents.push(parent);
+    }
+
+    fn leave(&mut self) {
+        let Some(parent) = self.parents.pop() else {
+            return;
+        };
+        let space = std::mem::replace(&mut self.current, parent);
+        let metrics = metrics_for(self.lines, self.tokens, &space);
+        self.current.totals.add(&space.totals);
+        self.current.children.push(CodeSpace {
+            name: space.name,
+            kind: space.kind.to_string(),
+            start_line: space.start.line,
+            end_line: space.end.line,
+            metrics,
+            spaces: space.children,
+        });
+    }
+
+    /// Adds a structural increment, which costs more the deeper it is nested.
+    fn structural(&mut self) {
+        self.current.totals.cyclomatic += 1.0;
+        self.current.totals.cognitive += 1.0 + self.current.nesting as f64;
+    }
+
+    fn nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.current.nesting += 1;
+        visit(self);
+        self.current.nesting -= 1;
+    }
+
+    /// Visits an `if` and its `else if` chain; the caller accounts for the
+    /// leading `if`.
+    fn visit_if_chain(&mut self, node: &ExprIf) {
+        self.visit_expr(&node.cond);
+        self.nested(|this| this.visit_block(&node.then_branch));
+        if let Some((_, else_branch)) = &node.else_branch {
+            self.current.totals.cognitive += 1.0;
+            self.current.totals.conditions += 1.0;
+            match &**else_branch {
+                Expr::If(else_if) => {
+                    self.current.totals.cyclomatic += 1.0;
+                    self.visit_if_chain(else_if);
+                }
+                other => self.nested(|this| this.visit_expr(other)),
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Engine<'_> {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let end = node.block.span().end();
+        self.enter(
+            node.sig.ident.to_string(),
+            "function",
+            node.sig.span().start(),
+            end,
+            node.sig.inputs.len(),
+        );
+        syn::visit::visit_item_fn(self, node);
+        self.leave();
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let end = node.block.span().end();
+        self.enter(
+            node.sig.ident.to_string(),
+            "function",
+            node.sig.span().start(),
+            end,
+            node.sig.inputs.len(),
+        );
+        syn::visit::visit_impl_item_fn(self, node);
+        self.leave();
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        let Some(block) = &node.default else {
+            return syn::visit::visit_trait_item_fn(self, node);
+        };
+        self.enter(
+            node.sig.ident.to_string(),
+            "function",
+            node.sig.span().start(),
+            block.span().end(),
+            node.sig.inputs.len(),
+        );
+        syn::visit::visit_trait_item_fn(self, node);
+        self.leave();
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        let span = node.span();
+        self.enter(
+            "{closure}".to_string(),
+            "closure",
+            span.start(),
+            span.end(),
+            node.inputs.len(),
+        );
+        syn::visit::visit_expr_closure(self, node);
+        self.leave();
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let name = match &node.trait_ {
+            Some((_, path, _)) => {
+                let trait_name = path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string())
+                    .unwrap_or_default();
+                format!("{trait_name} for {}", type_name(&node.self_ty))
+            }
+            None => type_name(&node.self_ty),
+        };
+        self.enter(
+            name,
+            "impl",
+            node.impl_token.span.start(),
+            node.brace_token.span.close().end(),
+            0,
+        );
+        syn::visit::visit_item_impl(self, node);
+        self.leave();
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        self.enter(
+            node.ident.to_string(),
+            "trait",
+            node.trait_token.span.start(),
+            node.brace_token.span.close().end(),
+            0,
+        );
+        syn::visit::visit_item_trait(self, node);
+        self.leave();
+    }
+
+    fn visit_stmt(&mut self, node: &'ast Stmt) {
+        self.current.totals.lloc += 1.0;
+        syn::visit::visit_stmt(self, node);
+    }
+
+    fn visit_expr_if(&mut self, node: &'ast ExprIf) {
+        self.structural();
+        self.visit_if_chain(node);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast ExprMatch) {
+        self.structural();
+        let extra_arms = node.arms.len().saturating_sub(1);
+        let guards = node.arms.iter().filter(|arm| arm.guard.is_some()).count();
+        // `structural` already counted one decision
+        self.current.totals.cyclomatic += (extra_arms + guards) as f64 - 1.0;
+        self.current.totals.conditions += node.arms.len() as f64;
+        self.visit_expr(&node.expr);
+        self.nested(|this| {
+            for arm in &node.arms {
+                this.visit_arm(arm);
+            }
+        });
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast ExprWhile) {
+        self.structural();
+        self.visit_expr(&node.cond);
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast ExprForLoop) {
+        self.structural();
+        self.visit_pat(&node.pat);
+        self.visit_expr(&node.expr);
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast ExprLoop) {
+        self.structural();
+        self.nested(|this| this.visit_block(&node.body));
+    }
+
+    fn visit_expr_binary(&mut self, node: &'ast ExprBinary) {
+        match node.op {
+            BinOp::Eq(_)
+            | BinOp::Ne(_)
+            | BinOp::Lt(_)
+            | BinOp::Le(_)
+            | BinOp::Gt(_)
+            | BinOp::Ge(_) => {
+                self.current.totals.conditions += 1.0;
+            }
+            BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_) => self.current.totals.assignments += 1.0,
+            _ => {}
+        }
+        if matches!(node.op, BinOp::And(_) | BinOp::Or(_)) {
+            self.current.totals.cyclomatic += 1.0;
+            // `a && b && c` is one run of like operators and costs one
+            let continues_run = matches!(
+                &*node.left,
+                Expr::Binary(left)
+                    if std::mem::discriminant(&left.op) == std::mem::discriminant(&node.op)
+            );
+            if !continues_run {
+                self.current.totals.cognitive += 1.0;
+            }
+        }
+        syn::visit::visit_expr_binary(self, node);
+    }
+
+    fn visit_expr_try(&mut self, node: &'ast ExprTry) {
+        self.current.totals.cyclomatic += 1.0;
+        self.current.totals.exits += 1.0;
+        self.current.totals.conditions += 1.0;
+        syn::visit::visit_expr_try(self, node);
+    }
+
+    fn visit_local(&mut self, node: &'ast Local) {
+        if node.init.is_some() {
+            self.current.totals.assignments += 1.0;
+        }
+        syn::visit::visit_local(self, node);
+    }
+
+    fn visit_expr_assign(&mut self, node: &'ast ExprAssign) {
+        self.current.totals.assignments += 1.0;
+        syn::visit::visit_expr_assign(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast ExprCall) {
+        self.current.totals.branches += 1.0;
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        self.current.totals.branches += 1.0;
+        syn::visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_return(&mut self, node: &'ast ExprReturn) {
+        self.current.totals.exits += 1.0;
+        syn::visit::visit_expr_return(self, node);
+    }
+
+    fn visit_expr_break(&mut self, node: &'ast ExprBreak) {
+        if node.label.is_some() {
+            self.current.totals.cognitive += 1.0;
+        }
+        syn::visit::visit_expr_break(self, node);
+    }
+
+    fn visit_expr_continue(&mut self, node: &'ast ExprContinue) {
+        if node.label.is_some() {
+            self.current.totals.cognitive += 1.0;
+        }
+        syn::visit::visit_expr_continue(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"// Sample module
+use std::fmt;
+
+/// Classifies a number.
+fn classify(n: i32, strict: bool) -> &'static str {
+    if n < 0 && strict {
+        return "negative";
+    } else if n == 0 {
+        "zero"
+    } else {
+        for i in 0..n {
+            if i % 2 == 0 || i > 10 {
+                continue;
+            }
+        }
+        "positive"
+    }
+}
+
+struct Wrapper(u8);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = "// not a comment";
+
+        write!(f, "{}{}", label, self.0)?;
+        Ok(())
+    }
+}
+
+fn apply(values: &[u8]) -> Vec<u8> {
+    values.iter().map(|v| match v {
+        0 => 1,
+        n if *n > 100 => 2,
+        _ => 3,
+    }).collect()
+}
+"#;
+
+    fn find<'a>(spaces: &'a [CodeSpace], name: &str) -> &'a CodeSpace {
+        spaces
+            .iter()
+            .find(|space| space.name == name)
+            .unwrap_or_else(|| panic!("no space named {name}"))
+    }
+
+    #[test]
+    fn test_analyze_source_builds_space_tree() {
+        let unit = analyze_source("sample.rs", SAMPLE).unwrap();
+        assert_eq!(unit.kind, "unit");
+        let names: Vec<_> = unit.spaces.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["classify", "Display for Wrapper", "apply"]);
+
+        let display = find(&unit.spaces, "Display for Wrapper");
+        assert_eq!(display.kind, "impl");
+        assert_eq!((display.start_line, display.end_line), (22, 29));
+        assert_eq!(display.spaces[0].name, "fmt");
+        assert_eq!(display.spaces[0].kind, "function");
+
+        let apply = find(&unit.spaces, "apply");
+        assert_eq!(apply.spaces[0].kind, "closure");
+    }
+
+    #[test]
+    fn test_analyze_source_lines() {
+        let unit = analyze_source("sample.rs", SAMPLE).unwrap();
+        let loc = unit.metrics.as_ref().unwrap().loc.as_ref().unwrap();
+        assert_eq!(loc.sloc, 37.0);
+        assert_eq!(loc.cloc, 2.0, "the comment inside a string is code");
+        assert_eq!(loc.blank, 5.0);
+        assert_eq!(loc.ploc, 30.0);
+
+        let fmt = &find(&unit.spaces, "Display for Wrapper").spaces[0];
+        let loc = fmt.metrics.loc.as_ref().unwrap();
+        assert_eq!(
+            (loc.sloc, loc.ploc, loc.blank, loc.lloc),
+            (6.0, 5.0, 1.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_analyze_source_complexity() {
+        let unit = analyze_source("sample.rs", SAMPLE).unwrap();
+
+        let classify = find(&unit.spaces, "classify");
+        // 1 + if + && + else if + for + if + ||
+        assert_eq!(classify.metrics.cyclomatic.as_ref().unwrap().sum, 7.0);
+        // if(1) + &&(1) + else if(1) + else(1) + for(2) + if(3) + ||(1)
+        assert_eq!(classify.metrics.cognitive.as_ref().unwrap().sum, 10.0);
+        assert_eq!(classify.metrics.nargs.as_ref().unwrap().total, 2.0);
+        assert_eq!(classify.metrics.nexits.as_ref().unwrap().sum, 1.0);
+
+        let fmt = &find(&unit.spaces, "Display for Wrapper").spaces[0];
+        assert_eq!(fmt.metrics.nexits.as_ref().unwrap().sum, 1.0);
+        assert_eq!(fmt.metrics.cyclomatic.as_ref().unwrap().sum, 2.0);
+
+        let apply = find(&unit.spaces, "apply");
+        let closure = &apply.spaces[0];
+        // 1 + two extra arms + guard
+        assert_eq!(closure.metrics.cyclomatic.as_ref().unwrap().sum, 4.0);
+        // the match is nested once inside the closure
+        assert_eq!(closure.metrics.cognitive.as_ref().unwrap().sum, 2.0);
+        let nom = apply.metrics.nom.as_ref().unwrap();
+        assert_eq!((nom.functions, nom.closures, nom.total), (1.0, 1.0, 2.0));
+
+        let unit_nom = unit.metrics.as_ref().unwrap().nom.as_ref().unwrap();
+        assert_eq!(unit_nom.total, 4.0);
+    }
+
+    #[test]
+    fn test_analyze_source_abc() {
+        let unit = analyze_source("sample.rs", SAMPLE).unwrap();
+
+        let fmt = &find(&unit.spaces, "Display for Wrapper").spaces[0];
+        let abc = fmt.metrics.abc.as_ref().unwrap();
+        // let label = ..; Ok(()); the `?` on write!
+        assert_eq!(
+            (abc.assignments, abc.branches, abc.conditions),
+            (1.0, 1.0, 1.0)
+        );
+        assert!((abc.magnitude - 3f64.sqrt()).abs() < 1e-9);
+
+        let classify = find(&unit.spaces, "classify");
+        let abc = classify.metrics.abc.as_ref().unwrap();
+        // four comparisons and two `else` branches
+        assert_eq!(abc.conditions, 6.0);
+
+        let apply = find(&unit.spaces, "apply");
+        let abc = apply.metrics.abc.as_ref().unwrap();
+        // iter, map, collect; three arms and a `>` guard
+        assert_eq!((abc.branches, abc.conditions), (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_analyze_source_halstead_and_mi() {
+        let unit =
+            analyze_source("t.rs", "fn add(a: u32, b: u32) -> u32 {\n    a + b\n}\n").unwrap();
+        let halstead = unit.spaces[0].metrics.halstead.as_ref().unwrap();
+        // operators: fn () : , -> {} +   operands: add a u32 b
+        assert_eq!((halstead.n1, halstead.n2), (7.0, 4.0));
+        assert_eq!(halstead.length, 16.0);
+        assert!((halstead.volume - 16.0 * 11f64.log2()).abs() < 1e-9);
+
+        let mi = unit.spaces[0].metrics.mi.as_ref().unwrap();
+        assert!(mi.mi_visual_studio > 0.0 && mi.mi_visual_studio <= 100.0);
+        assert!(mi.mi_original > mi.mi_visual_studio);
+    }
+
+    #[test]
+    fn test_analyze_source_skips_attributes_in_halstead() {
+        let plain = analyze_source("a.rs", "fn f() {}\n").unwrap();
+        let documented = analyze_source("b.rs", "/// Docs.\n#[inline]\nfn f() {}\n").unwrap();
+        let length = |unit: &AnalysisUnit| {
+            unit.metrics
+                .as_ref()
+                .unwrap()
+                .halstead
+                .as_ref()
+                .unwrap()
+                .length
+        };
+        assert_eq!(length(&plain), length(&documented));
+    }
+
+    #[test]
+    fn test_classify_lines_handles_block_comments_and_literals() {
+        let lines =
+            classify_lines("let a = '\"'; /* start\n\n  end */ let b = r#\"x // y\"#;\n// c\n");
+        let kinds: Vec<_> = lines.iter().map(|l| (l.code, l.comment)).collect();
+        assert_eq!(
+            kinds,
+            [(true, true), (false, false), (true, true), (false, true)]
+        );
+    }
+
+    #[test]
+    fn test_analyze_source_rejects_invalid_rust() {
+        let err = analyze_source("bad.rs", "fn (").unwrap_err();
+        assert!(matches!(err, RaffError::ParseError { .. }));
+    }
+}
@@ -0,0 +1,569 @@
+//! Component resolution shared by all rules.
+//!
+//! Rules aggregate their results by *component*. This module defines what a
+//! component is, so that it means the same thing in every report:
+//!
+//! - [`ComponentMode::Directory`]: the top-level directory under the analysis path
+//! - [`ComponentMode::Crate`]: the Cargo workspace member that contains the file
+//! - [`ComponentMode::Module`]: the workspace member and its top-level module,
+//!   e.g. `my-crate::parser`
+//!
+//! Workspace members are read from `cargo metadata`. When that fails, for
+//! example outside a Cargo project or with an invalid manifest, every
+//! `Cargo.toml` with a `[package].name` under the analysis path counts as a
+//! crate instead. Files that belong to no crate fall back to their top-level
+//! directory.
+//!
+//! Logical components that do not follow the file-system layout can be
+//! defined in the `[components.define]` config section, which maps a
+//! component name to path globs or module paths:
+//!
+//! ```toml
+//! [components.define]
+//! billing = ["crates/billing-*", "src/payments/"]
+//! parsing = ["my_crate::parser", "my_crate::lexer"]
+//! ```
+//!
+//! A pattern containing `::` is a module path and matches that module and its
+//! submodules; anything else is a glob matched against the path relative to
+//! the analysis path and its parent directories. When several patterns match,
+//! the closest match to the file wins. Defined components take precedence
+//! over the mode-based component.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use globset::{GlobBuilder, GlobMatcher};
+use serde::Deserialize;
+use toml::Value as TomlValue;
+use walkdir::WalkDir;
+
+use crate::cli::ComponentMode;
+use crate::error::{RaffError, Result};
+use crate::file_utils::{relative_namespace, top_level_component};
+
+/// A crate discovered under, or enclosing, the analysis path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceCrate {
+    /// Package name from the crate manifest.
+    pub name: String,
+    /// Crate root relative to the analysis path. Empty when the crate is at,
+    /// or encloses, the analysis path.
+    pub root: PathBuf,
+    /// The analysis path relative to the crate root, when the crate encloses it.
+    prefix: PathBuf,
+}
+
+impl WorkspaceCrate {
+    /// Returns the directory holding the crate manifest, given the canonical
+    /// analysis path the crate was discovered from.
+    #[must_use]
+    pub fn directory(&self, analysis_path: &Path) -> PathBuf {
+        let mut directory = analysis_path.join(&self.root);
+        for _ in self.prefix.components() {
+            directory.pop();
+        }
+        directory
+    }
+
+    /// Returns `path`, relative to the analysis path, relative to this
+    /// crate's root instead.
+    fn crate_relative(&self, path: &Path) -> PathBuf {
+        self.prefix
+            .join(path.strip_prefix(&self.root).unwrap_or(path))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+}
+
+/// Discovers the crates that make up the project at `analysis_path`.
+///
+/// Prefers the workspace members reported by `cargo metadata`, and falls back
+/// to walking the directory tree for `Cargo.toml` files.
+///
+/// # Errors
+///
+/// Returns an error if `analysis_path` cannot be canonicalized, or if a
+/// `Cargo.toml` found by the fallback walk cannot be read or parsed.
+pub fn discover_crates(analysis_path: &Path) -> Result<Vec<WorkspaceCrate>> {
+    let analysis_path = analysis_path.canonicalize()?;
+    match crates_from_metadata(&analysis_path) {
+        Ok(crates) if !crates.is_empty() => return Ok(crates),
+        Ok(_) => tracing::debug!("cargo metadata found no workspace members under the path"),
+        Err(e) => {
+            tracing::debug!(error = %e, "cargo metadata unavailable, walking Cargo.toml files")
+        }
+    }
+    crates_from_manifests(&analysis_path)
+}
+
+fn crates_from_metadata(analysis_path: &Path) -> Result<Vec<WorkspaceCrate>> {
+    let output = Command::new("cargo")
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--offline",
+        ])
+        .current_dir(analysis_path)
+        .output()?;
+    if !output.status.success() {
+        return Err(RaffError::parse_error(format!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)?;
+
+    let mut crates = Vec::new();
+    for package in metadata.packages {
+        if !metadata.workspace_members.contains(&package.id) {
+            continue;
+        }
+        let Some(root) = package.manifest_path.parent() else {
+            continue;
+        };
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        if let Ok(relative) = root.strip_prefix(analysis_path) {
+            crates.push(WorkspaceCrate {
+                name: package.name,
+                root: relative.to_path_buf(),
+                prefix: PathBuf::new(),
+            });
+        } else if let Ok(prefix) = analysis_path.strip_prefix(&root) {
+            crates.push(WorkspaceCrate {
+                name: package.name,
+                root: PathBuf::new(),
+                prefix: prefix.to_path_buf(),
+            });
+        }
+    }
+    Ok(crates)
+}
+
+fn crates_from_manifests(analysis_path: &Path) -> Result<Vec<WorkspaceCrate>> {
+    let mut crates: Vec<WorkspaceCrate> = Vec::new();
+    for entry in WalkDir::new(analysis_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy() == "Cargo.toml")
+    {
+        let manifest = entry.path();
+        let Some(root) = manifest.parent() else {
+            continue;
+        };
+        let root = root
+            .strip_prefix(analysis_path)
+            .map_err(|e| {
+                RaffError::parse_error_with_file(
+                    root.to_path_buf(),
+                    format!("Failed to make crate root path relative: {}", e),
+                )
+            })?
+            .to_path_buf();
+
+        let toml_value = fs::read_to_string(manifest)?.parse::<TomlValue>()?;
+        let Some(name) = toml_value
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            tracing::warn!(
+                path = %manifest.display(),
+                "Could not extract [package].name from Cargo.toml. Skipping."
+            );
+            continue;
+        };
+
+        if let Some(existing) = crates.iter_mut().find(|c| c.name == name) {
+            tracing::warn!(
+                crate_name = name,
+                new_path_relative = %root.display(),
+                "Duplicate crate name found. Overwriting with new path."
+            );
+            existing.root = root;
+        } else {
+            crates.push(WorkspaceCrate {
+                name: name.to_string(),
+                root,
+                prefix: PathBuf::new(),
+            });
+        }
+    }
+    Ok(crates)
+}
+
+/// A pattern from `[components.define]`.
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// Matches a path relative to the analysis path, or one of its parents.
+    Path(GlobMatcher),
+    /// Matches a module path, written with `/` separators, or one of its parents.
+    Module(GlobMatcher),
+}
+
+#[derive(Debug, Clone)]
+struct Definition {
+    name: String,
+    pattern: Pattern,
+}
+
+/// Maps files to the component they belong to.
+#[derive(Debug, Clone)]
+pub struct ComponentResolver {
+    mode: ComponentMode,
+    crates: Vec<WorkspaceCrate>,
+    definitions: Vec<Definition>,
+}
+
+impl ComponentResolver {
+    /// Creates a resolver for the project at `analysis_path`. `definitions`
+    /// maps component names to path globs or module paths. Crates are only
+    /// discovered when `mode` or a module path pattern needs them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is not a valid glob, or if crate
+    /// discovery fails.
+    pub fn new(
+        analysis_path: &Path,
+        mode: ComponentMode,
+        definitions: &BTreeMap<String, Vec<String>>,
+    ) -> Result<Self> {
+        let mut compiled = Vec::new();
+        for (name, patterns) in definitions {
+            for pattern in patterns {
+                compiled.push(Definition {
+                    name: name.clone(),
+                    pattern: compile_pattern(pattern)?,
+                });
+            }
+        }
+        let needs_crates = mode != ComponentMode::Directory
+            || compiled
+                .iter()
+                .any(|d| matches!(d.pattern, Pattern::Module(_)));
+        let crates = if needs_crates {
+            discover_crates(analysis_path)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            mode,
+            crates,
+            definitions: compiled,
+        })
+    }
+
+    /// Returns `true` if any components are defined by name.
+    #[must_use]
+    pub fn has_definitions(&self) -> bool {
+        !self.definitions.is_empty()
+    }
+
+    /// The names of the defined components, sorted and deduplicated.
+    #[must_use]
+    pub fn defined_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.definitions.iter().map(|d| d.name.as_str()).collect();
+        names.dedup();
+        names
+    }
+
+    /// Returns the defined component `path` belongs to, if any. `path` is
+    /// relative to the analysis path and may be a file or a directory.
+    #[must_use]
+    pub fn defined_component(&self, path: &Path) -> Option<&str> {
+        if self.definitions.is_empty() {
+            return None;
+        }
+        let module = self.module_path(path);
+        let mut best: Option<(usize, &str)> = None;
+        for definition in &self.definitions {
+            let distance = match &definition.pattern {
+                Pattern::Path(glob) => closest_match(glob, path),
+                Pattern::Module(glob) => module
+                    .as_deref()
+                    .and_then(|module| closest_match(glob, Path::new(module))),
+            };
+            if let Some(distance) = distance
+                && best.is_none_or(|(best_distance, _)| distance < best_distance)
+            {
+                best = Some((distance, &definition.name));
+            }
+        }
+        best.map(|(_, name)| name)
+    }
+
+    /// Returns the module path of `path` as `crate_name/module/submodule`,
+    /// with the crate name in its Rust identifier form.
+    fn module_path(&self, path: &Path) -> Option<String> {
+        let krate = self.owning_crate(path)?;
+        let in_crate = krate.crate_relative(path);
+        let in_src = in_crate.strip_prefix("src").unwrap_or(&in_crate);
+        let namespace = relative_namespace(in_src, Path::new(""));
+        let mut module = krate.name.replace('-', "_");
+        if !matches!(namespace.as_str(), "lib" | "main" | "root" | "") {
+            module.push('/');
+            module.push_str(&namespace.replace("::", "/"));
+        }
+        Some(module)
+    }
+
+    /// Returns the Rust module path of the file `path`, which is relative to
+    /// the analysis path, e.g. `my_crate::parser::lexer`. Returns `None` when
+    /// no known crate contains the file.
+    #[must_use]
+    pub fn module_of(&self, path: &Path) -> Option<String> {
+        self.module_path(path)
+            .map(|module| module.replace('/', "::"))
+    }
+
+    /// The crates known to this resolver.
+    #[must_use]
+    pub fn crates(&self) -> &[WorkspaceCrate] {
+        &self.crates
+    }
+
+    /// Returns the innermost crate containing `path`, which is relative to
+    /// the analysis path.
+    #[must_use]
+    pub fn owning_crate(&self, path: &Path) -> Option<&WorkspaceCrate> {
+        self.crates
+            .iter()
+            .filter(|c| path.starts_with(&c.root))
+            .max_by_key(|c| c.root.components().count())
+    }
+
+    /// Returns the component of `path`, which is relative to the analysis path.
+    #[must_use]
+    pub fn component_of(&self, path: &Path) -> String {
+        if let Some(name) = self.defined_component(path) {
+            return name.to_string();
+        }
+        let directory = || top_level_component(&relative_namespace(path, Path::new("")));
+        if self.mode == ComponentMode::Directory {
+            return directory();
+        }
+        let Some(krate) = self.owning_crate(path) else {
+            return directory();
+        };
+        if self.mode == ComponentMode::Crate {
+            return krate.name.clone();
+        }
+
+        let in_crate = krate.crate_relative(path);
+        let in_src = in_crate.strip_prefix("src").unwrap_or(&in_crate);
+        let module = top_level_component(&relative_namespace(in_src, Path::new("")));
+        match module.as_str() {
+            "lib" | "main" | "root" | "" => krate.name.clone(),
+            _ => format!("{}::{}", krate.name, module),
+        }
+    }
+}
+
+/// Compiles a `[components.define]` pattern.
+fn compile_pattern(pattern: &str) -> Result<Pattern> {
+    let is_module = pattern.contains("::");
+    let normalized = if is_module {
+        pattern.replace("::", "/").replace('-', "_")
+    } else {
+        pattern.replace('\\', "/")
+    };
+    let normalized = normalized.trim_matches('/');
+    let glob = GlobBuilder::new(normalized)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| {
+            RaffError::invalid_input_with_arg(
+                format!("invalid component pattern '{pattern}': {e}"),
+                "components",
+            )
+        })?
+        .compile_matcher();
+    Ok(if is_module {
+        Pattern::Module(glob)
+    } else {
+        Pattern::Path(glob)
+    })
+}
+
+/// Returns how many levels above `path` the closest match of `glob` is: 0
+/// when `path` itself matches, 1 for its parent, and so on.
+fn closest_match(glob: &GlobMatcher, path: &Path) -> Option<usize> {
+    path.ancestors()
+        .take_while(|ancestor| !ancestor.as_os_str().is_empty())
+        .position(|ancestor| glob.is_match(ancestor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn workspace() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n",
+        );
+        for name in ["core", "app"] {
+            write(
+                root,
+                &format!("crates/{name}/Cargo.toml"),
+                &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+            );
+            write(root, &format!("crates/{name}/src/lib.rs"), "");
+        }
+        // Not a workspace member, so ignored when cargo metadata is available
+        write(
+            root,
+            "fuzz/Cargo.toml",
+            "[package]\nname = \"fuzz\"\nversion = \"0.1.0\"\n",
+        );
+        temp_dir
+    }
+
+    #[test]
+    fn test_discover_crates_uses_workspace_members() {
+        let temp_dir = workspace();
+        let mut crates = discover_crates(temp_dir.path()).unwrap();
+        crates.sort_by(|a, b| a.name.cmp(&b.name));
+        let names: Vec<_> = crates.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "core"]);
+        assert_eq!(crates[1].root, PathBuf::from("crates/core"));
+    }
+
+    #[test]
+    fn test_component_of_by_mode() {
+        let temp_dir = workspace();
+        let file = Path::new("crates/core/src/parser/lexer.rs");
+        let component = |mode| {
+            ComponentResolver::new(temp_dir.path(), mode, &BTreeMap::new())
+                .unwrap()
+                .component_of(file)
+        };
+        assert_eq!(component(ComponentMode::Directory), "crates");
+        assert_eq!(component(ComponentMode::Crate), "core");
+        assert_eq!(component(ComponentMode::Module), "core::parser");
+
+        let resolver =
+            ComponentResolver::new(temp_dir.path(), ComponentMode::Module, &BTreeMap::new())
+                .unwrap();
+        assert_eq!(
+            resolver.component_of(Path::new("crates/app/src/lib.rs")),
+            "app"
+        );
+        assert_eq!(
+            resolver.component_of(Path::new("crates/app/tests/it.rs")),
+            "app::tests"
+        );
+        assert_eq!(
+            resolver.component_of(Path::new("scripts/gen.rs")),
+            "scripts"
+        );
+        assert_eq!(
+            resolver.module_of(file).as_deref(),
+            Some("core::parser::lexer")
+        );
+        assert_eq!(resolver.module_of(Path::new("scripts/gen.rs")), None);
+    }
+
+    #[test]
+    fn test_component_of_inside_a_crate() {
+        let temp_dir = workspace();
+        let resolver = ComponentResolver::new(
+            &temp_dir.path().join("crates/core/src"),
+            ComponentMode::Module,
+            &BTreeMap::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            resolver.component_of(Path::new("parser/mod.rs")),
+            "core::parser"
+        );
+
+        let analysis_path = temp_dir
+            .path()
+            .join("crates/core/src")
+            .canonicalize()
+            .unwrap();
+        assert_eq!(
+            resolver.crates()[0].directory(&analysis_path),
+            analysis_path.parent().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_discover_crates_falls_back_to_manifests() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "a/Cargo.toml", "[package]\nname = \"a\"\n");
+        write(temp_dir.path(), "b/Cargo.toml", "[workspace]\n");
+        let crates = discover_crates(temp_dir.path()).unwrap();
+        assert_eq!(crates.len(), 1);
+        assert_eq!(crates[0].name, "a");
+        assert_eq!(crates[0].root, PathBuf::from("a"));
+    }
+
+    #[test]
+    fn test_defined_components_take_precedence() {
+        let temp_dir = workspace();
+        write(temp_dir.path(), "crates/core/src/payments/stripe.rs", "");
+        let definitions = BTreeMap::from([
+            (
+                "billing".to_string(),
+                vec![
+                    "crates/app".to_string(),
+                    "crates/core/src/payments/".to_string(),
+                ],
+            ),
+            ("parsing".to_string(), vec!["core::parser".to_string()]),
+            (
+                "stripe".to_string(),
+                vec!["core::payments::stripe".to_string()],
+            ),
+        ]);
+        let resolver =
+            ComponentResolver::new(temp_dir.path(), ComponentMode::Crate, &definitions).unwrap();
+
+        let component = |path: &str| resolver.component_of(Path::new(path));
+        assert_eq!(component("crates/app/src/lib.rs"), "billing");
+        assert_eq!(component("crates/core/src/payments/mod.rs"), "billing");
+        assert_eq!(component("crates/core/src/parser/lexer.rs"), "parsing");
+        // The module path match is closer to the file than the directory match
+        assert_eq!(component("crates/core/src/payments/stripe.rs"), "stripe");
+        assert_eq!(component("crates/core/src/lib.rs"), "core");
+        assert_eq!(
+            resolver.defined_names(),
+            vec!["billing", "parsing", "stripe"]
+        );
+
+        let invalid = BTreeMap::from([("bad".to_string(), vec!["src/[".to_string()])]);
+        assert!(
+            ComponentResolver::new(temp_dir.path(), ComponentMode::Directory, &invalid).is_err()
+        );
+    }
+}
@@ -6,6 +6,7 @@
 
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -26,6 +27,10 @@ pub struct RaffConfig {
     #[serde(default)]
     pub general: GeneralConfig,
 
+    /// Component definition shared by all rules.
+    #[serde(default)]
+    pub components: ComponentsConfig,
+
     /// Statement count rule configuration.
     #[serde(default)]
     pub statement_count: StatementCountConfig,
@@ -46,6 +51,38 @@ pub struct RaffConfig {
     #[serde(default)]
     pub contributor_report: ContributorReportConfig,
 
+    /// Code age rule configuration.
+    #[serde(default)]
+    pub code_age: CodeAgeConfig,
+
+    /// Ownership rule configuration.
+    #[serde(default)]
+    pub ownership: OwnershipConfig,
+
+    /// CODEOWNERS suggestion and check configuration.
+    #[serde(default)]
+    pub codeowners: CodeownersConfig,
+
+    /// Duplication rule configuration.
+    #[serde(default)]
+    pub duplication: DuplicationConfig,
+
+    /// API surface rule configuration.
+    #[serde(default)]
+    pub api_surface: ApiSurfaceConfig,
+
+    /// Unsafe code rule configuration.
+    #[serde(default)]
+    pub unsafe_code: UnsafeCodeConfig,
+
+    /// Error handling rule configuration.
+    #[serde(default)]
+    pub error_handling: ErrorHandlingConfig,
+
+    /// Generics rule configuration.
+    #[serde(default)]
+    pub generics: GenericsConfig,
+
     /// Profile configurations for different usage scenarios.
     #[serde(default)]
     pub profile: ProfileConfig,
@@ -77,6 +114,20 @@ fn default_exclude() -> Vec<String> {
     vec!["target".to_string()]
 }
 
+/// Component configuration shared by all rules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct ComponentsConfig {
+    /// How files are grouped into components ("directory", "crate" or "module").
+    pub mode: Option<String>,
+
+    /// Named components mapped to path globs or module paths, e.g.
+    /// `billing = ["crates/billing-*", "src/payments/"]`.
+    #[serde(default)]
+    pub define: BTreeMap<String, Vec<String>>,
+}
+
 /// Statement count rule configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -88,6 +139,37 @@ pub struct StatementCountConfig {
     #[serde(default = "default_statement_count_threshold")]
     pub threshold: usize,
 
+    /// Report granularity ("component" or "item").
+    pub granularity: Option<String>,
+
+    /// Number of functions listed at item granularity.
+    pub top: Option<usize>,
+
+    /// Maximum number of statements allowed in a single function or method.
+    pub max_function_statements: Option<usize>,
+
+    /// Apply thresholds to test code as well as production code.
+    pub include_tests: Option<bool>,
+
+    /// Count statements inside macro invocations and definitions.
+    pub parse_macros: Option<bool>,
+
+    /// Maximum number of statements allowed in a single component.
+    pub max_component_statements: Option<usize>,
+
+    /// Minimum number of statements expected in a component.
+    pub min_component_statements: Option<usize>,
+
+    /// Maximum number of statements allowed in a single file.
+    pub max_file_statements: Option<usize>,
+
+    /// Maximum number of statements allowed in a single module, including
+    /// its submodules.
+    pub max_module_statements: Option<usize>,
+
+    /// Maximum Gini coefficient of component sizes (0.0-1.0).
+    pub max_gini: Option<f64>,
+
     /// Output format for the report.
     pub output: Option<String>,
 }
@@ -97,6 +179,16 @@ impl Default for StatementCountConfig {
         Self {
             path: None,
             threshold: 10,
+            granularity: None,
+            top: None,
+            max_function_statements: None,
+            include_tests: None,
+            parse_macros: None,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: None,
         }
     }
@@ -120,6 +212,22 @@ pub struct VolatilityConfig {
     /// Analyze commits since this date (YYYY-MM-DD).
     pub since: Option<String>,
 
+    /// Analyze commits up to and including this date (YYYY-MM-DD).
+    pub until: Option<String>,
+
+    /// Exclude this revision and its ancestors from the analysis.
+    pub from: Option<String>,
+
+    /// Revision to start walking history from (defaults to HEAD).
+    pub to: Option<String>,
+
+    /// Revision range to analyze (e.g., `v1.0..v2.0`). Cannot be combined
+    /// with `from` or `to`.
+    pub range: Option<String>,
+
+    /// Branch to analyze instead of the currently checked-out one.
+    pub branch: Option<String>,
+
     /// Normalize volatility scores by total lines of code.
     #[serde(default)]
     pub normalize: bool,
@@ -128,6 +236,28 @@ pub struct VolatilityConfig {
     #[serde(default)]
     pub skip_merges: bool,
 
+    /// Only count changes to paths matching these globs.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+
+    /// Ignore changes to paths matching these globs (e.g., "Cargo.lock").
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// Ignore commits by these authors (name or email, `*` wildcards allowed).
+    #[serde(default)]
+    pub exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches any of these regexes.
+    #[serde(default)]
+    pub exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, in `.git-blame-ignore-revs` format.
+    pub ignore_revs_file: Option<PathBuf>,
+
+    /// Number of threads used to diff commits (defaults to the number of CPUs).
+    pub jobs: Option<usize>,
+
     /// Output format for the report.
     pub output: Option<String>,
 }
@@ -138,8 +268,19 @@ impl Default for VolatilityConfig {
             path: None,
             alpha: 0.01,
             since: None,
+            until: None,
+            from: None,
+            to: None,
+            range: None,
+            branch: None,
             normalize: false,
             skip_merges: false,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            exclude_authors: Vec::new(),
+            exclude_messages: Vec::new(),
+            ignore_revs_file: None,
+            jobs: None,
             output: None,
         }
     }
@@ -188,6 +329,30 @@ pub struct RustCodeAnalysisConfig {
     /// Language to analyze.
     #[serde(default = "default_rca_language")]
     pub language: String,
+
+    /// Metrics engine ("builtin" or "external").
+    pub backend: Option<String>,
+
+    /// Maximum total cognitive complexity of a single file.
+    pub max_file_cognitive: Option<f64>,
+
+    /// Minimum mean maintainability index (0-100) of the functions in a file.
+    pub min_file_mi: Option<f64>,
+
+    /// Maximum ABC magnitude of a single file.
+    pub max_file_abc: Option<f64>,
+
+    /// Maximum cyclomatic complexity of a single function.
+    pub max_function_cyclomatic: Option<f64>,
+
+    /// Maximum cognitive complexity of a single function.
+    pub max_function_cognitive: Option<f64>,
+
+    /// Maximum number of arguments of a single function.
+    pub max_function_args: Option<usize>,
+
+    /// Maximum length, in lines, of a single function.
+    pub max_function_length: Option<usize>,
 }
 
 impl Default for RustCodeAnalysisConfig {
@@ -199,6 +364,14 @@ impl Default for RustCodeAnalysisConfig {
             output: None,
             metrics: true,
             language: "rust".to_string(),
+            backend: None,
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
         }
     }
 }
@@ -221,10 +394,46 @@ pub struct ContributorReportConfig {
     /// Analyze commits since this date (YYYY-MM-DD).
     pub since: Option<String>,
 
+    /// Analyze commits up to and including this date (YYYY-MM-DD).
+    pub until: Option<String>,
+
+    /// Branch to analyze instead of the currently checked-out one.
+    pub branch: Option<String>,
+
     /// Exponential decay factor for recency weighting.
     #[serde(default = "default_contributor_decay")]
     pub decay: f64,
 
+    /// Alias groups: each canonical email maps to the other emails or names
+    /// the same person has committed under. Applied after `.mailmap`.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Vec<String>>,
+
+    /// Period the activity timeline is bucketed by ("month" or "quarter").
+    pub period: Option<String>,
+
+    /// Only count changes to paths matching these globs.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+
+    /// Ignore changes to paths matching these globs (e.g., "Cargo.lock").
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// Ignore commits by these authors (name or email, `*` wildcards allowed).
+    #[serde(default)]
+    pub exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches any of these regexes.
+    #[serde(default)]
+    pub exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, in `.git-blame-ignore-revs` format.
+    pub ignore_revs_file: Option<PathBuf>,
+
+    /// Number of threads used to diff commits (defaults to the number of CPUs).
+    pub jobs: Option<usize>,
+
     /// Output format for the report.
     pub output: Option<String>,
 }
@@ -272,7 +481,17 @@ impl Default for ContributorReportConfig {
         Self {
             path: None,
             since: None,
+            until: None,
+            branch: None,
             decay: 0.01,
+            aliases: BTreeMap::new(),
+            period: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            exclude_authors: Vec::new(),
+            exclude_messages: Vec::new(),
+            ignore_revs_file: None,
+            jobs: None,
             output: None,
         }
     }
@@ -282,6 +501,270 @@ fn default_contributor_decay() -> f64 {
     0.01
 }
 
+/// Code age rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct CodeAgeConfig {
+    /// Default path for code age analysis.
+    pub path: Option<PathBuf>,
+
+    /// Level at which line ages are aggregated ("crate", "module" or "file").
+    pub granularity: Option<String>,
+
+    /// Lines unchanged for more than this many months count as stale.
+    pub stale_months: Option<u32>,
+
+    /// Authors without a commit in this many months count as inactive.
+    pub inactive_months: Option<u32>,
+
+    /// Number of threads used to blame files (defaults to the number of CPUs).
+    pub jobs: Option<usize>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// Ownership rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct OwnershipConfig {
+    /// Default path for ownership analysis.
+    pub path: Option<PathBuf>,
+
+    /// Level at which ownership is reported ("crate" or "module").
+    pub granularity: Option<String>,
+
+    /// How lines are attributed to authors ("churn" or "blame").
+    pub source: Option<String>,
+
+    /// Start date for churn analysis (YYYY-MM-DD format).
+    pub since: Option<String>,
+
+    /// End date for churn analysis (YYYY-MM-DD format).
+    pub until: Option<String>,
+
+    /// Branch to analyze when no revision is given on the command line.
+    pub branch: Option<String>,
+
+    /// Only count files matching these glob patterns.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+
+    /// Ignore files matching these glob patterns.
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// Ignore commits by these authors (name or email, `*` wildcards allowed).
+    #[serde(default)]
+    pub exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches any of these regexes.
+    #[serde(default)]
+    pub exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, in `.git-blame-ignore-revs` format.
+    pub ignore_revs_file: Option<PathBuf>,
+
+    /// Owners without a commit in this many months count as inactive.
+    pub inactive_months: Option<u32>,
+
+    /// Number of threads used to diff commits or blame files (defaults to the number of CPUs).
+    pub jobs: Option<usize>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// CODEOWNERS suggestion and check configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct CodeownersConfig {
+    /// Default path of the repository to analyze.
+    pub path: Option<PathBuf>,
+
+    /// Level at which owners are suggested ("crate" or "module").
+    pub granularity: Option<String>,
+
+    /// CODEOWNERS file to check, relative to the repository root.
+    pub codeowners_file: Option<PathBuf>,
+
+    /// Start date for the contribution window (YYYY-MM-DD format).
+    pub since: Option<String>,
+
+    /// End date for the contribution window (YYYY-MM-DD format).
+    pub until: Option<String>,
+
+    /// Branch to analyze when no revision is given on the command line.
+    pub branch: Option<String>,
+
+    /// Only count files matching these glob patterns.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+
+    /// Ignore files matching these glob patterns.
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// Ignore commits by these authors (name or email, `*` wildcards allowed).
+    #[serde(default)]
+    pub exclude_authors: Vec<String>,
+
+    /// Ignore commits whose message matches any of these regexes.
+    #[serde(default)]
+    pub exclude_messages: Vec<String>,
+
+    /// File listing commits to ignore, in `.git-blame-ignore-revs` format.
+    pub ignore_revs_file: Option<PathBuf>,
+
+    /// Maximum number of owners suggested per entry.
+    pub max_owners: Option<usize>,
+
+    /// Minimum percentage of an entry's churn each owner must account for.
+    pub min_share: Option<f64>,
+
+    /// Maps canonical emails to CODEOWNERS handles (e.g. `"alice@example.com" = "@alice"`).
+    #[serde(default)]
+    pub handles: BTreeMap<String, String>,
+
+    /// Number of threads used to diff commits (defaults to the number of CPUs).
+    pub jobs: Option<usize>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// Duplication rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct DuplicationConfig {
+    /// Default path for duplication analysis.
+    pub path: Option<PathBuf>,
+
+    /// Minimum length, in normalized tokens, of a reported clone.
+    pub min_tokens: Option<usize>,
+
+    /// Whether to look for clones in test code.
+    pub include_tests: Option<bool>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// API surface rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct ApiSurfaceConfig {
+    /// Default path for API surface analysis.
+    pub path: Option<PathBuf>,
+
+    /// Maximum number of public items a crate may expose.
+    pub max_public_items: Option<usize>,
+
+    /// Maximum percentage of a crate's statements in public functions and methods.
+    pub max_public_statement_share: Option<f64>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// Unsafe code rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct UnsafeCodeConfig {
+    /// Default path for unsafe code analysis.
+    pub path: Option<PathBuf>,
+
+    /// Modules in which unsafe code is allowed (e.g., "crate::ffi").
+    #[serde(default)]
+    pub allowed_modules: Vec<String>,
+
+    /// Whether to report unsafe code in test code.
+    pub include_tests: Option<bool>,
+
+    /// Whether to measure the unsafe density over the history window.
+    pub trend: Option<bool>,
+
+    /// Period the trend is bucketed by ("month" or "quarter").
+    pub period: Option<String>,
+
+    /// Measure the trend from this date (YYYY-MM-DD).
+    pub since: Option<String>,
+
+    /// Measure the trend up to and including this date (YYYY-MM-DD).
+    pub until: Option<String>,
+
+    /// Branch to measure the trend on instead of the currently checked-out one.
+    pub branch: Option<String>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// Error handling rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct ErrorHandlingConfig {
+    /// Default path for error handling analysis.
+    pub path: Option<PathBuf>,
+
+    /// Modules in which no panic sites are allowed (e.g., "crate::domain").
+    #[serde(default)]
+    pub banned_modules: Vec<String>,
+
+    /// Panic site budgets keyed by module (e.g., `[error_handling.budgets."crate::app"]`).
+    #[serde(default)]
+    pub budgets: BTreeMap<String, crate::cli::PanicBudget>,
+
+    /// Whether to count panic sites in test code.
+    pub include_tests: Option<bool>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
+/// Generics rule configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[derive(Default)]
+pub struct GenericsConfig {
+    /// Default path for generics analysis.
+    pub path: Option<PathBuf>,
+
+    /// Maximum number of generic parameters of a single item.
+    pub max_generic_params: Option<usize>,
+
+    /// Maximum number of where-clause predicates of a single item.
+    pub max_where_predicates: Option<usize>,
+
+    /// Maximum nesting depth of trait bounds in a single item.
+    pub max_bound_depth: Option<usize>,
+
+    /// Maximum number of trait impls for a single type.
+    pub max_trait_impls: Option<usize>,
+
+    /// Maximum number of blanket impls in a single module.
+    pub max_blanket_impls: Option<usize>,
+
+    /// Whether to only measure items declared `pub`.
+    pub public_only: Option<bool>,
+
+    /// Whether to measure test code.
+    pub include_tests: Option<bool>,
+
+    /// Number of items and types listed in the table output.
+    pub top: Option<usize>,
+
+    /// Output format for the report.
+    pub output: Option<String>,
+}
+
 /// Pre-commit profile runtime settings.
 ///
 /// This struct contains both the modified configuration and runtime flags
@@ -470,6 +953,61 @@ pub fn merge_statement_count_args(
         merged.threshold = config.statement_count.threshold;
     }
 
+    // Merge components: CLI arg OR shared components mode OR default Directory
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
+    {
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
+    }
+
+    // Merge granularity: CLI arg OR config granularity OR default Component
+    if let Some(config_granularity) = &config.statement_count.granularity
+        && matches!(
+            merged.granularity,
+            crate::cli::StatementCountGranularity::Component
+        )
+    {
+        merged.granularity = parse_statement_count_granularity(config_granularity)
+            .unwrap_or(crate::cli::StatementCountGranularity::Component);
+    }
+
+    // Merge top: CLI arg OR config top OR default 20
+    if let Some(top) = config.statement_count.top
+        && merged.top == 20
+    {
+        merged.top = top;
+    }
+
+    // Merge max_function_statements: CLI arg OR config value
+    if merged.max_function_statements.is_none() {
+        merged.max_function_statements = config.statement_count.max_function_statements;
+    }
+
+    // Merge include_tests: CLI flag OR config value
+    if !merged.include_tests {
+        merged.include_tests = config.statement_count.include_tests.unwrap_or(false);
+    }
+
+    // Merge parse_macros: CLI flag OR config value
+    if !merged.parse_macros {
+        merged.parse_macros = config.statement_count.parse_macros.unwrap_or(false);
+    }
+
+    // Merge absolute size limits: CLI arg OR config value
+    let limits = &config.statement_count;
+    merged.max_component_statements = merged
+        .max_component_statements
+        .or(limits.max_component_statements);
+    merged.min_component_statements = merged
+        .min_component_statements
+        .or(limits.min_component_statements);
+    merged.max_file_statements = merged.max_file_statements.or(limits.max_file_statements);
+    merged.max_module_statements = merged
+        .max_module_statements
+        .or(limits.max_module_statements);
+    merged.max_gini = merged.max_gini.or(limits.max_gini);
+
     // Merge output: CLI arg OR config output OR default Table
     if let Some(config_output) = &config.statement_count.output {
         // Only use config output if CLI is using default (Table)
@@ -479,6 +1017,11 @@ pub fn merge_statement_count_args(
         }
     }
 
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
     // Merge output_file: CLI arg OR general config output_file
     if merged.output_file.is_none() {
         merged.output_file = config.general.output_file.clone();
@@ -488,6 +1031,23 @@ pub fn merge_statement_count_args(
 }
 
 /// Parse output format string for statement count.
+fn parse_component_mode(s: &str) -> Option<crate::cli::ComponentMode> {
+    match s.to_lowercase().as_str() {
+        "directory" => Some(crate::cli::ComponentMode::Directory),
+        "crate" => Some(crate::cli::ComponentMode::Crate),
+        "module" => Some(crate::cli::ComponentMode::Module),
+        _ => None,
+    }
+}
+
+fn parse_statement_count_granularity(s: &str) -> Option<crate::cli::StatementCountGranularity> {
+    match s.to_lowercase().as_str() {
+        "component" => Some(crate::cli::StatementCountGranularity::Component),
+        "item" => Some(crate::cli::StatementCountGranularity::Item),
+        _ => None,
+    }
+}
+
 fn parse_statement_count_output_format(s: &str) -> Option<crate::cli::StatementCountOutputFormat> {
     match s.to_lowercase().as_str() {
         "table" => Some(crate::cli::StatementCountOutputFormat::Table),
@@ -513,12 +1073,46 @@ pub fn merge_volatility_args(
         merged.alpha = config.volatility.alpha;
     }
 
-    // Merge since: optional, use CLI if set, otherwise config
-    if merged.since.is_none() {
-        merged.since = config.volatility.since.clone();
+    // Merge history window: optional, use CLI if set, otherwise config
+    if merged.history.since.is_none() {
+        merged.history.since = config.volatility.since.clone();
     }
-
-    // Merge normalize: CLI default is false
+    if merged.history.until.is_none() {
+        merged.history.until = config.volatility.until.clone();
+    }
+    // A configured revision window only applies when none was requested
+    if merged.history.from.is_none()
+        && merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.from = config.volatility.from.clone();
+        merged.history.to = config.volatility.to.clone();
+        merged.history.range = config.volatility.range.clone();
+    }
+    // A configured branch only applies when no explicit revision was requested
+    if merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.branch = config.volatility.branch.clone();
+    }
+
+    merge_history_filters(
+        &mut merged.history,
+        HistoryFilterConfig {
+            include_paths: &config.volatility.include_paths,
+            exclude_paths: &config.volatility.exclude_paths,
+            exclude_authors: &config.volatility.exclude_authors,
+            exclude_messages: &config.volatility.exclude_messages,
+            ignore_revs_file: config.volatility.ignore_revs_file.as_ref(),
+        },
+    );
+    if merged.history.jobs.is_none() {
+        merged.history.jobs = config.volatility.jobs;
+    }
+
+    // Merge normalize: CLI default is false
     if config.volatility.normalize && !merged.normalize {
         merged.normalize = true;
     }
@@ -536,6 +1130,11 @@ pub fn merge_volatility_args(
             .unwrap_or(crate::cli::VolatilityOutputFormat::Table);
     }
 
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
     // Merge output_file: CLI takes precedence if set, otherwise use config
     if merged.output_file.is_none() {
         merged.output_file = config.general.output_file.clone();
@@ -544,6 +1143,34 @@ pub fn merge_volatility_args(
     merged
 }
 
+/// History filter settings from a rule's config section.
+struct HistoryFilterConfig<'a> {
+    include_paths: &'a [String],
+    exclude_paths: &'a [String],
+    exclude_authors: &'a [String],
+    exclude_messages: &'a [String],
+    ignore_revs_file: Option<&'a PathBuf>,
+}
+
+/// Merge history filters from config into CLI history args.
+///
+/// Pattern lists are combined, with config patterns first, so that CLI flags
+/// add to the configured filters rather than replacing them.
+fn merge_history_filters(history: &mut crate::cli::HistoryArgs, config: HistoryFilterConfig<'_>) {
+    history.include_paths = concat(config.include_paths, &history.include_paths);
+    history.exclude_paths = concat(config.exclude_paths, &history.exclude_paths);
+    history.exclude_authors = concat(config.exclude_authors, &history.exclude_authors);
+    history.exclude_messages = concat(config.exclude_messages, &history.exclude_messages);
+    if history.ignore_revs_file.is_none() {
+        history.ignore_revs_file = config.ignore_revs_file.cloned();
+    }
+}
+
+/// Concatenate two pattern lists.
+fn concat(first: &[String], second: &[String]) -> Vec<String> {
+    first.iter().chain(second).cloned().collect()
+}
+
 /// Parse output format string for volatility.
 fn parse_volatility_output_format(s: &str) -> Option<crate::cli::VolatilityOutputFormat> {
     match s.to_lowercase().as_str() {
@@ -563,25 +1190,746 @@ pub fn merge_coupling_args(
 ) -> crate::cli::CouplingArgs {
     let mut merged = cli_args.clone();
 
-    // Merge path
-    if config.coupling.path.is_some() && merged.path.as_os_str() == "." {
-        merged.path = resolve_path(&config.coupling.path, &PathBuf::from("."));
+    // Merge path
+    if config.coupling.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.coupling.path, &PathBuf::from("."));
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.coupling.output
+        && matches!(merged.output, crate::cli::CouplingOutputFormat::Table)
+    {
+        merged.output = parse_coupling_output_format(config_output)
+            .unwrap_or(crate::cli::CouplingOutputFormat::Table);
+    }
+
+    // Merge granularity: CLI default is Both
+    if let Some(config_granularity) = &config.coupling.granularity
+        && matches!(merged.granularity, crate::cli::CouplingGranularity::Both)
+    {
+        merged.granularity = parse_coupling_granularity(config_granularity)
+            .unwrap_or(crate::cli::CouplingGranularity::Both);
+    }
+
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse output format string for coupling.
+fn parse_coupling_output_format(s: &str) -> Option<crate::cli::CouplingOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::CouplingOutputFormat::Table),
+        "json" => Some(crate::cli::CouplingOutputFormat::Json),
+        "yaml" => Some(crate::cli::CouplingOutputFormat::Yaml),
+        "html" => Some(crate::cli::CouplingOutputFormat::Html),
+        "dot" => Some(crate::cli::CouplingOutputFormat::Dot),
+        _ => None,
+    }
+}
+
+/// Parse granularity string for coupling.
+fn parse_coupling_granularity(s: &str) -> Option<crate::cli::CouplingGranularity> {
+    match s.to_lowercase().as_str() {
+        "both" => Some(crate::cli::CouplingGranularity::Both),
+        "crate" => Some(crate::cli::CouplingGranularity::Crate),
+        "module" => Some(crate::cli::CouplingGranularity::Module),
+        _ => None,
+    }
+}
+
+/// Merge rust-code-analysis CLI args with config file values.
+pub fn merge_rust_code_analysis_args(
+    cli_args: &crate::cli::RustCodeAnalysisArgs,
+    config: &RaffConfig,
+) -> crate::cli::RustCodeAnalysisArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.rust_code_analysis.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.rust_code_analysis.path, &PathBuf::from("."));
+    }
+
+    // Merge extra_flags: CLI flags should append to config, not replace
+    if !config.rust_code_analysis.extra_flags.is_empty() {
+        let mut combined_flags = config.rust_code_analysis.extra_flags.clone();
+        combined_flags.extend(merged.extra_flags.clone());
+        merged.extra_flags = combined_flags;
+    }
+
+    // Merge jobs: CLI default is num_cpus::get(), config is None
+    if let Some(config_jobs) = config.rust_code_analysis.jobs {
+        merged.jobs = config_jobs;
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.rust_code_analysis.output
+        && matches!(
+            merged.output,
+            crate::cli::RustCodeAnalysisOutputFormat::Table
+        )
+    {
+        merged.output = parse_rca_output_format(config_output)
+            .unwrap_or(crate::cli::RustCodeAnalysisOutputFormat::Table);
+    }
+
+    // Merge metrics: CLI default is true
+    // If config has false and CLI is default true, use config
+    if !config.rust_code_analysis.metrics && merged.metrics {
+        merged.metrics = false;
+    }
+
+    // Merge language: CLI default is "rust"
+    if merged.language == "rust" && config.rust_code_analysis.language != "rust" {
+        merged.language = config.rust_code_analysis.language.clone();
+    }
+
+    // Merge backend: CLI default is Builtin
+    if let Some(config_backend) = &config.rust_code_analysis.backend
+        && merged.backend == crate::cli::RustCodeAnalysisBackend::Builtin
+    {
+        merged.backend = parse_rca_backend(config_backend)
+            .unwrap_or(crate::cli::RustCodeAnalysisBackend::Builtin);
+    }
+
+    // Merge components: CLI arg OR shared components mode OR default Directory
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
+    {
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
+    }
+
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
+    // Merge complexity thresholds: CLI arg OR config value
+    let thresholds = &config.rust_code_analysis;
+    merged.max_file_cognitive = merged.max_file_cognitive.or(thresholds.max_file_cognitive);
+    merged.min_file_mi = merged.min_file_mi.or(thresholds.min_file_mi);
+    merged.max_file_abc = merged.max_file_abc.or(thresholds.max_file_abc);
+    merged.max_function_cyclomatic = merged
+        .max_function_cyclomatic
+        .or(thresholds.max_function_cyclomatic);
+    merged.max_function_cognitive = merged
+        .max_function_cognitive
+        .or(thresholds.max_function_cognitive);
+    merged.max_function_args = merged.max_function_args.or(thresholds.max_function_args);
+    merged.max_function_length = merged
+        .max_function_length
+        .or(thresholds.max_function_length);
+
+    // Merge output_file: Use general.output_file if CLI arg is not set
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse metrics engine string for rust-code-analysis.
+fn parse_rca_backend(s: &str) -> Option<crate::cli::RustCodeAnalysisBackend> {
+    match s.to_lowercase().as_str() {
+        "builtin" => Some(crate::cli::RustCodeAnalysisBackend::Builtin),
+        "external" => Some(crate::cli::RustCodeAnalysisBackend::External),
+        _ => None,
+    }
+}
+
+/// Parse output format string for rust-code-analysis.
+fn parse_rca_output_format(s: &str) -> Option<crate::cli::RustCodeAnalysisOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::RustCodeAnalysisOutputFormat::Table),
+        "json" => Some(crate::cli::RustCodeAnalysisOutputFormat::Json),
+        "yaml" => Some(crate::cli::RustCodeAnalysisOutputFormat::Yaml),
+        "html" => Some(crate::cli::RustCodeAnalysisOutputFormat::Html),
+        _ => None,
+    }
+}
+
+/// Merge contributor-report CLI args with config file values.
+pub fn merge_contributor_report_args(
+    cli_args: &crate::cli::ContributorReportArgs,
+    config: &RaffConfig,
+) -> crate::cli::ContributorReportArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.contributor_report.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.contributor_report.path, &PathBuf::from("."));
+    }
+
+    // Merge history window: optional
+    if merged.history.since.is_none() {
+        merged.history.since = config.contributor_report.since.clone();
+    }
+    if merged.history.until.is_none() {
+        merged.history.until = config.contributor_report.until.clone();
+    }
+    // A configured branch only applies when no explicit revision was requested
+    if merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.branch = config.contributor_report.branch.clone();
+    }
+
+    merge_history_filters(
+        &mut merged.history,
+        HistoryFilterConfig {
+            include_paths: &config.contributor_report.include_paths,
+            exclude_paths: &config.contributor_report.exclude_paths,
+            exclude_authors: &config.contributor_report.exclude_authors,
+            exclude_messages: &config.contributor_report.exclude_messages,
+            ignore_revs_file: config.contributor_report.ignore_revs_file.as_ref(),
+        },
+    );
+    if merged.history.jobs.is_none() {
+        merged.history.jobs = config.contributor_report.jobs;
+    }
+
+    // Merge decay: CLI default is 0.01, same as config default
+    if config.contributor_report.decay != 0.01 {
+        merged.decay = config.contributor_report.decay;
+    }
+
+    // Merge aliases: only configurable through the config file
+    merged
+        .aliases
+        .extend(config.contributor_report.aliases.clone());
+
+    // Merge period: CLI default is Month
+    if let Some(config_period) = &config.contributor_report.period
+        && matches!(merged.period, crate::cli::ContributorReportPeriod::Month)
+    {
+        merged.period = parse_contributor_report_period(config_period)
+            .unwrap_or(crate::cli::ContributorReportPeriod::Month);
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.contributor_report.output
+        && matches!(
+            merged.output,
+            crate::cli::ContributorReportOutputFormat::Table
+        )
+    {
+        merged.output = parse_contributor_report_output_format(config_output)
+            .unwrap_or(crate::cli::ContributorReportOutputFormat::Table);
+    }
+
+    // Merge output_file: from general config if not set on CLI
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse period string for contributor report.
+fn parse_contributor_report_period(s: &str) -> Option<crate::cli::ContributorReportPeriod> {
+    match s.to_lowercase().as_str() {
+        "month" => Some(crate::cli::ContributorReportPeriod::Month),
+        "quarter" => Some(crate::cli::ContributorReportPeriod::Quarter),
+        _ => None,
+    }
+}
+
+/// Parse output format string for contributor report.
+fn parse_contributor_report_output_format(
+    s: &str,
+) -> Option<crate::cli::ContributorReportOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::ContributorReportOutputFormat::Table),
+        "html" => Some(crate::cli::ContributorReportOutputFormat::Html),
+        "json" => Some(crate::cli::ContributorReportOutputFormat::Json),
+        "yaml" => Some(crate::cli::ContributorReportOutputFormat::Yaml),
+        "csv" => Some(crate::cli::ContributorReportOutputFormat::Csv),
+        _ => None,
+    }
+}
+
+/// Merge code-age CLI args with config file values.
+pub fn merge_code_age_args(
+    cli_args: &crate::cli::CodeAgeArgs,
+    config: &RaffConfig,
+) -> crate::cli::CodeAgeArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.code_age.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.code_age.path, &PathBuf::from("."));
+    }
+
+    // Merge granularity: CLI default is Crate
+    if let Some(config_granularity) = &config.code_age.granularity
+        && matches!(merged.granularity, crate::cli::CodeAgeGranularity::Crate)
+    {
+        merged.granularity = parse_code_age_granularity(config_granularity)
+            .unwrap_or(crate::cli::CodeAgeGranularity::Crate);
+    }
+
+    // Merge thresholds: CLI defaults are 12 and 6 months
+    if let Some(stale_months) = config.code_age.stale_months
+        && merged.stale_months == 12
+    {
+        merged.stale_months = stale_months;
+    }
+    if let Some(inactive_months) = config.code_age.inactive_months
+        && merged.inactive_months == 6
+    {
+        merged.inactive_months = inactive_months;
+    }
+
+    // Merge aliases: only configurable through the config file
+    merged
+        .aliases
+        .extend(config.contributor_report.aliases.clone());
+
+    // Merge jobs: optional
+    if merged.jobs.is_none() {
+        merged.jobs = config.code_age.jobs;
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.code_age.output
+        && matches!(merged.output, crate::cli::CodeAgeOutputFormat::Table)
+    {
+        merged.output = parse_code_age_output_format(config_output)
+            .unwrap_or(crate::cli::CodeAgeOutputFormat::Table);
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse granularity string for code age.
+fn parse_code_age_granularity(s: &str) -> Option<crate::cli::CodeAgeGranularity> {
+    match s.to_lowercase().as_str() {
+        "crate" => Some(crate::cli::CodeAgeGranularity::Crate),
+        "module" => Some(crate::cli::CodeAgeGranularity::Module),
+        "file" => Some(crate::cli::CodeAgeGranularity::File),
+        _ => None,
+    }
+}
+
+/// Parse output format string for code age.
+fn parse_code_age_output_format(s: &str) -> Option<crate::cli::CodeAgeOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::CodeAgeOutputFormat::Table),
+        "csv" => Some(crate::cli::CodeAgeOutputFormat::Csv),
+        "json" => Some(crate::cli::CodeAgeOutputFormat::Json),
+        "yaml" => Some(crate::cli::CodeAgeOutputFormat::Yaml),
+        "html" => Some(crate::cli::CodeAgeOutputFormat::Html),
+        _ => None,
+    }
+}
+
+/// Merge ownership CLI args with config file values.
+pub fn merge_ownership_args(
+    cli_args: &crate::cli::OwnershipArgs,
+    config: &RaffConfig,
+) -> crate::cli::OwnershipArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.ownership.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.ownership.path, &PathBuf::from("."));
+    }
+
+    // Merge history window: optional
+    if merged.history.since.is_none() {
+        merged.history.since = config.ownership.since.clone();
+    }
+    if merged.history.until.is_none() {
+        merged.history.until = config.ownership.until.clone();
+    }
+    // A configured branch only applies when no explicit revision was requested
+    if merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.branch = config.ownership.branch.clone();
+    }
+
+    merge_history_filters(
+        &mut merged.history,
+        HistoryFilterConfig {
+            include_paths: &config.ownership.include_paths,
+            exclude_paths: &config.ownership.exclude_paths,
+            exclude_authors: &config.ownership.exclude_authors,
+            exclude_messages: &config.ownership.exclude_messages,
+            ignore_revs_file: config.ownership.ignore_revs_file.as_ref(),
+        },
+    );
+    if merged.history.jobs.is_none() {
+        merged.history.jobs = config.ownership.jobs;
+    }
+
+    // Merge granularity: CLI default is Crate
+    if let Some(config_granularity) = &config.ownership.granularity
+        && matches!(merged.granularity, crate::cli::OwnershipGranularity::Crate)
+    {
+        merged.granularity = parse_ownership_granularity(config_granularity)
+            .unwrap_or(crate::cli::OwnershipGranularity::Crate);
+    }
+
+    // Merge source: CLI default is Churn
+    if let Some(config_source) = &config.ownership.source
+        && matches!(merged.source, crate::cli::OwnershipSource::Churn)
+    {
+        merged.source =
+            parse_ownership_source(config_source).unwrap_or(crate::cli::OwnershipSource::Churn);
+    }
+
+    // Merge inactive threshold: CLI default is 6 months
+    if let Some(inactive_months) = config.ownership.inactive_months
+        && merged.inactive_months == 6
+    {
+        merged.inactive_months = inactive_months;
+    }
+
+    // Merge aliases: only configurable through the config file
+    merged
+        .aliases
+        .extend(config.contributor_report.aliases.clone());
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.ownership.output
+        && matches!(merged.output, crate::cli::OwnershipOutputFormat::Table)
+    {
+        merged.output = parse_ownership_output_format(config_output)
+            .unwrap_or(crate::cli::OwnershipOutputFormat::Table);
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse granularity string for ownership.
+fn parse_ownership_granularity(s: &str) -> Option<crate::cli::OwnershipGranularity> {
+    match s.to_lowercase().as_str() {
+        "crate" => Some(crate::cli::OwnershipGranularity::Crate),
+        "module" => Some(crate::cli::OwnershipGranularity::Module),
+        _ => None,
+    }
+}
+
+/// Parse attribution source string for ownership.
+fn parse_ownership_source(s: &str) -> Option<crate::cli::OwnershipSource> {
+    match s.to_lowercase().as_str() {
+        "churn" => Some(crate::cli::OwnershipSource::Churn),
+        "blame" => Some(crate::cli::OwnershipSource::Blame),
+        _ => None,
+    }
+}
+
+/// Parse output format string for ownership.
+fn parse_ownership_output_format(s: &str) -> Option<crate::cli::OwnershipOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::OwnershipOutputFormat::Table),
+        "csv" => Some(crate::cli::OwnershipOutputFormat::Csv),
+        "json" => Some(crate::cli::OwnershipOutputFormat::Json),
+        "yaml" => Some(crate::cli::OwnershipOutputFormat::Yaml),
+        "html" => Some(crate::cli::OwnershipOutputFormat::Html),
+        _ => None,
+    }
+}
+
+/// Merge codeowners CLI args with config file values.
+///
+/// Author aliases are shared with the contributor report and are taken from
+/// `[contributor_report.aliases]`.
+pub fn merge_codeowners_args(
+    cli_args: &crate::cli::CodeownersArgs,
+    config: &RaffConfig,
+) -> crate::cli::CodeownersArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.codeowners.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.codeowners.path, &PathBuf::from("."));
+    }
+
+    // Merge history window: optional
+    if merged.history.since.is_none() {
+        merged.history.since = config.codeowners.since.clone();
+    }
+    if merged.history.until.is_none() {
+        merged.history.until = config.codeowners.until.clone();
+    }
+    // A configured branch only applies when no explicit revision was requested
+    if merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.branch = config.codeowners.branch.clone();
+    }
+
+    merge_history_filters(
+        &mut merged.history,
+        HistoryFilterConfig {
+            include_paths: &config.codeowners.include_paths,
+            exclude_paths: &config.codeowners.exclude_paths,
+            exclude_authors: &config.codeowners.exclude_authors,
+            exclude_messages: &config.codeowners.exclude_messages,
+            ignore_revs_file: config.codeowners.ignore_revs_file.as_ref(),
+        },
+    );
+    if merged.history.jobs.is_none() {
+        merged.history.jobs = config.codeowners.jobs;
+    }
+
+    // Merge granularity: CLI default is Crate
+    if let Some(config_granularity) = &config.codeowners.granularity
+        && matches!(merged.granularity, crate::cli::OwnershipGranularity::Crate)
+    {
+        merged.granularity = parse_ownership_granularity(config_granularity)
+            .unwrap_or(crate::cli::OwnershipGranularity::Crate);
+    }
+
+    // Merge codeowners_file: optional
+    if merged.codeowners_file.is_none() {
+        merged.codeowners_file = config.codeowners.codeowners_file.clone();
+    }
+
+    // Merge thresholds: CLI defaults are 2 owners and 10%
+    if let Some(max_owners) = config.codeowners.max_owners
+        && merged.max_owners == 2
+    {
+        merged.max_owners = max_owners;
+    }
+    if let Some(min_share) = config.codeowners.min_share
+        && merged.min_share == 10.0
+    {
+        merged.min_share = min_share;
+    }
+
+    // Merge handles and aliases: only configurable through the config file
+    merged.handles.extend(config.codeowners.handles.clone());
+    merged
+        .aliases
+        .extend(config.contributor_report.aliases.clone());
+
+    // Merge output: CLI default is Text
+    if let Some(config_output) = &config.codeowners.output
+        && matches!(merged.output, crate::cli::CodeownersOutputFormat::Text)
+    {
+        merged.output = parse_codeowners_output_format(config_output)
+            .unwrap_or(crate::cli::CodeownersOutputFormat::Text);
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse output format string for codeowners.
+fn parse_codeowners_output_format(s: &str) -> Option<crate::cli::CodeownersOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "text" => Some(crate::cli::CodeownersOutputFormat::Text),
+        "json" => Some(crate::cli::CodeownersOutputFormat::Json),
+        "yaml" => Some(crate::cli::CodeownersOutputFormat::Yaml),
+        _ => None,
+    }
+}
+
+/// Merge duplication CLI args with config file values.
+pub fn merge_duplication_args(
+    cli_args: &crate::cli::DuplicationArgs,
+    config: &RaffConfig,
+) -> crate::cli::DuplicationArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.duplication.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.duplication.path, &PathBuf::from("."));
+    }
+
+    // Merge min_tokens: CLI default is 50
+    if let Some(min_tokens) = config.duplication.min_tokens
+        && merged.min_tokens == 50
+    {
+        merged.min_tokens = min_tokens;
+    }
+
+    // Merge include_tests: CLI flag OR config value
+    if !merged.include_tests {
+        merged.include_tests = config.duplication.include_tests.unwrap_or(false);
+    }
+
+    // Merge components: CLI arg OR shared components mode OR default Directory
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
+    {
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
+    }
+
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.duplication.output
+        && matches!(merged.output, crate::cli::DuplicationOutputFormat::Table)
+    {
+        merged.output = parse_duplication_output_format(config_output)
+            .unwrap_or(crate::cli::DuplicationOutputFormat::Table);
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse output format string for duplication.
+fn parse_duplication_output_format(s: &str) -> Option<crate::cli::DuplicationOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::DuplicationOutputFormat::Table),
+        "json" => Some(crate::cli::DuplicationOutputFormat::Json),
+        "yaml" => Some(crate::cli::DuplicationOutputFormat::Yaml),
+        "html" => Some(crate::cli::DuplicationOutputFormat::Html),
+        _ => None,
+    }
+}
+
+/// Merge API surface CLI args with config file values.
+pub fn merge_api_surface_args(
+    cli_args: &crate::cli::ApiSurfaceArgs,
+    config: &RaffConfig,
+) -> crate::cli::ApiSurfaceArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.api_surface.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.api_surface.path, &PathBuf::from("."));
+    }
+
+    // Merge thresholds: CLI arg OR config value
+    merged.max_public_items = merged
+        .max_public_items
+        .or(config.api_surface.max_public_items);
+    merged.max_public_statement_share = merged
+        .max_public_statement_share
+        .or(config.api_surface.max_public_statement_share);
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.api_surface.output
+        && matches!(merged.output, crate::cli::ApiSurfaceOutputFormat::Table)
+    {
+        merged.output = parse_api_surface_output_format(config_output)
+            .unwrap_or(crate::cli::ApiSurfaceOutputFormat::Table);
+    }
+
+    // Merge output_file: CLI takes precedence if set, otherwise use config
+    if merged.output_file.is_none() {
+        merged.output_file = config.general.output_file.clone();
+    }
+
+    merged
+}
+
+/// Parse output format string for API surface.
+fn parse_api_surface_output_format(s: &str) -> Option<crate::cli::ApiSurfaceOutputFormat> {
+    match s.to_lowercase().as_str() {
+        "table" => Some(crate::cli::ApiSurfaceOutputFormat::Table),
+        "json" => Some(crate::cli::ApiSurfaceOutputFormat::Json),
+        "yaml" => Some(crate::cli::ApiSurfaceOutputFormat::Yaml),
+        "html" => Some(crate::cli::ApiSurfaceOutputFormat::Html),
+        _ => None,
+    }
+}
+
+/// Merge unsafe code CLI args with config file values.
+pub fn merge_unsafe_code_args(
+    cli_args: &crate::cli::UnsafeCodeArgs,
+    config: &RaffConfig,
+) -> crate::cli::UnsafeCodeArgs {
+    let mut merged = cli_args.clone();
+
+    // Merge path
+    if config.unsafe_code.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.unsafe_code.path, &PathBuf::from("."));
+    }
+
+    // Merge allowed modules: config modules first, then CLI modules
+    merged.allowed_modules = concat(&config.unsafe_code.allowed_modules, &merged.allowed_modules);
+
+    // Merge include_tests and trend: CLI flag OR config value
+    if !merged.include_tests {
+        merged.include_tests = config.unsafe_code.include_tests.unwrap_or(false);
+    }
+    if !merged.trend {
+        merged.trend = config.unsafe_code.trend.unwrap_or(false);
+    }
+
+    // Merge period: CLI default is Month
+    if let Some(config_period) = &config.unsafe_code.period
+        && matches!(merged.period, crate::cli::ContributorReportPeriod::Month)
+    {
+        merged.period = parse_contributor_report_period(config_period)
+            .unwrap_or(crate::cli::ContributorReportPeriod::Month);
+    }
+
+    // Merge history window: optional
+    if merged.history.since.is_none() {
+        merged.history.since = config.unsafe_code.since.clone();
+    }
+    if merged.history.until.is_none() {
+        merged.history.until = config.unsafe_code.until.clone();
+    }
+    // A configured branch only applies when no explicit revision was requested
+    if merged.history.to.is_none()
+        && merged.history.range.is_none()
+        && merged.history.branch.is_none()
+    {
+        merged.history.branch = config.unsafe_code.branch.clone();
     }
 
-    // Merge output: CLI default is Table
-    if let Some(config_output) = &config.coupling.output
-        && matches!(merged.output, crate::cli::CouplingOutputFormat::Table)
+    // Merge components: CLI arg OR shared components mode OR default Directory
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
     {
-        merged.output = parse_coupling_output_format(config_output)
-            .unwrap_or(crate::cli::CouplingOutputFormat::Table);
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
     }
 
-    // Merge granularity: CLI default is Both
-    if let Some(config_granularity) = &config.coupling.granularity
-        && matches!(merged.granularity, crate::cli::CouplingGranularity::Both)
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.unsafe_code.output
+        && matches!(merged.output, crate::cli::UnsafeCodeOutputFormat::Table)
     {
-        merged.granularity = parse_coupling_granularity(config_granularity)
-            .unwrap_or(crate::cli::CouplingGranularity::Both);
+        merged.output = parse_unsafe_code_output_format(config_output)
+            .unwrap_or(crate::cli::UnsafeCodeOutputFormat::Table);
     }
 
     // Merge output_file: CLI takes precedence if set, otherwise use config
@@ -592,75 +1940,71 @@ pub fn merge_coupling_args(
     merged
 }
 
-/// Parse output format string for coupling.
-fn parse_coupling_output_format(s: &str) -> Option<crate::cli::CouplingOutputFormat> {
-    match s.to_lowercase().as_str() {
-        "table" => Some(crate::cli::CouplingOutputFormat::Table),
-        "json" => Some(crate::cli::CouplingOutputFormat::Json),
-        "yaml" => Some(crate::cli::CouplingOutputFormat::Yaml),
-        "html" => Some(crate::cli::CouplingOutputFormat::Html),
-        "dot" => Some(crate::cli::CouplingOutputFormat::Dot),
-        _ => None,
-    }
-}
-
-/// Parse granularity string for coupling.
-fn parse_coupling_granularity(s: &str) -> Option<crate::cli::CouplingGranularity> {
+/// Parse output format string for unsafe code.
+fn parse_unsafe_code_output_format(s: &str) -> Option<crate::cli::UnsafeCodeOutputFormat> {
     match s.to_lowercase().as_str() {
-        "both" => Some(crate::cli::CouplingGranularity::Both),
-        "crate" => Some(crate::cli::CouplingGranularity::Crate),
-        "module" => Some(crate::cli::CouplingGranularity::Module),
+        "table" => Some(crate::cli::UnsafeCodeOutputFormat::Table),
+        "json" => Some(crate::cli::UnsafeCodeOutputFormat::Json),
+        "yaml" => Some(crate::cli::UnsafeCodeOutputFormat::Yaml),
+        "html" => Some(crate::cli::UnsafeCodeOutputFormat::Html),
         _ => None,
     }
 }
 
-/// Merge rust-code-analysis CLI args with config file values.
-pub fn merge_rust_code_analysis_args(
-    cli_args: &crate::cli::RustCodeAnalysisArgs,
+/// Merge error handling CLI args with config file values.
+///
+/// CLI args take precedence over config file values. Banned modules from both
+/// sources are combined, and a CLI budget replaces the configured budget for
+/// the same module.
+pub fn merge_error_handling_args(
+    cli_args: &crate::cli::ErrorHandlingArgs,
     config: &RaffConfig,
-) -> crate::cli::RustCodeAnalysisArgs {
+) -> crate::cli::ErrorHandlingArgs {
     let mut merged = cli_args.clone();
 
     // Merge path
-    if config.rust_code_analysis.path.is_some() && merged.path.as_os_str() == "." {
-        merged.path = resolve_path(&config.rust_code_analysis.path, &PathBuf::from("."));
+    if config.error_handling.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.error_handling.path, &PathBuf::from("."));
     }
 
-    // Merge extra_flags: CLI flags should append to config, not replace
-    if !config.rust_code_analysis.extra_flags.is_empty() {
-        let mut combined_flags = config.rust_code_analysis.extra_flags.clone();
-        combined_flags.extend(merged.extra_flags.clone());
-        merged.extra_flags = combined_flags;
-    }
+    // Merge banned modules: config modules first, then CLI modules
+    merged.banned_modules = concat(
+        &config.error_handling.banned_modules,
+        &merged.banned_modules,
+    );
 
-    // Merge jobs: CLI default is num_cpus::get(), config is None
-    if let Some(config_jobs) = config.rust_code_analysis.jobs {
-        merged.jobs = config_jobs;
+    // Merge budgets: configured budgets, overridden per module by CLI budgets
+    let mut budgets = config.error_handling.budgets.clone();
+    budgets.extend(std::mem::take(&mut merged.budgets));
+    merged.budgets = budgets;
+
+    // Merge include_tests: CLI flag OR config value
+    if !merged.include_tests {
+        merged.include_tests = config.error_handling.include_tests.unwrap_or(false);
     }
 
-    // Merge output: CLI default is Table
-    if let Some(config_output) = &config.rust_code_analysis.output
-        && matches!(
-            merged.output,
-            crate::cli::RustCodeAnalysisOutputFormat::Table
-        )
+    // Merge components: CLI arg OR shared components mode OR default Directory
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
     {
-        merged.output = parse_rca_output_format(config_output)
-            .unwrap_or(crate::cli::RustCodeAnalysisOutputFormat::Table);
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
     }
 
-    // Merge metrics: CLI default is true
-    // If config has false and CLI is default true, use config
-    if !config.rust_code_analysis.metrics && merged.metrics {
-        merged.metrics = false;
+    // Merge component definitions: shared [components.define] section
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
     }
 
-    // Merge language: CLI default is "rust"
-    if merged.language == "rust" && config.rust_code_analysis.language != "rust" {
-        merged.language = config.rust_code_analysis.language.clone();
+    // Merge output: CLI default is Table
+    if let Some(config_output) = &config.error_handling.output
+        && matches!(merged.output, crate::cli::ErrorHandlingOutputFormat::Table)
+    {
+        merged.output = parse_error_handling_output_format(config_output)
+            .unwrap_or(crate::cli::ErrorHandlingOutputFormat::Table);
     }
 
-    // Merge output_file: Use general.output_file if CLI arg is not set
+    // Merge output_file: CLI arg OR general config
     if merged.output_file.is_none() {
         merged.output_file = config.general.output_file.clone();
     }
@@ -668,51 +2012,68 @@ pub fn merge_rust_code_analysis_args(
     merged
 }
 
-/// Parse output format string for rust-code-analysis.
-fn parse_rca_output_format(s: &str) -> Option<crate::cli::RustCodeAnalysisOutputFormat> {
+/// Parse output format string for error handling.
+fn parse_error_handling_output_format(s: &str) -> Option<crate::cli::ErrorHandlingOutputFormat> {
     match s.to_lowercase().as_str() {
-        "table" => Some(crate::cli::RustCodeAnalysisOutputFormat::Table),
-        "json" => Some(crate::cli::RustCodeAnalysisOutputFormat::Json),
-        "yaml" => Some(crate::cli::RustCodeAnalysisOutputFormat::Yaml),
-        "html" => Some(crate::cli::RustCodeAnalysisOutputFormat::Html),
+        "table" => Some(crate::cli::ErrorHandlingOutputFormat::Table),
+        "json" => Some(crate::cli::ErrorHandlingOutputFormat::Json),
+        "yaml" => Some(crate::cli::ErrorHandlingOutputFormat::Yaml),
+        "html" => Some(crate::cli::ErrorHandlingOutputFormat::Html),
         _ => None,
     }
 }
 
-/// Merge contributor-report CLI args with config file values.
-pub fn merge_contributor_report_args(
-    cli_args: &crate::cli::ContributorReportArgs,
+/// Merge generics CLI args with config file values.
+///
+/// CLI args take precedence over config file values.
+pub fn merge_generics_args(
+    cli_args: &crate::cli::GenericsArgs,
     config: &RaffConfig,
-) -> crate::cli::ContributorReportArgs {
+) -> crate::cli::GenericsArgs {
     let mut merged = cli_args.clone();
 
     // Merge path
-    if config.contributor_report.path.is_some() && merged.path.as_os_str() == "." {
-        merged.path = resolve_path(&config.contributor_report.path, &PathBuf::from("."));
-    }
-
-    // Merge since: optional
-    if merged.since.is_none() {
-        merged.since = config.contributor_report.since.clone();
-    }
-
-    // Merge decay: CLI default is 0.01, same as config default
-    if config.contributor_report.decay != 0.01 {
-        merged.decay = config.contributor_report.decay;
+    if config.generics.path.is_some() && merged.path.as_os_str() == "." {
+        merged.path = resolve_path(&config.generics.path, &PathBuf::from("."));
+    }
+
+    // Merge thresholds: CLI arg OR config value
+    merged.max_generic_params = merged
+        .max_generic_params
+        .or(config.generics.max_generic_params);
+    merged.max_where_predicates = merged
+        .max_where_predicates
+        .or(config.generics.max_where_predicates);
+    merged.max_bound_depth = merged.max_bound_depth.or(config.generics.max_bound_depth);
+    merged.max_trait_impls = merged.max_trait_impls.or(config.generics.max_trait_impls);
+    merged.max_blanket_impls = merged
+        .max_blanket_impls
+        .or(config.generics.max_blanket_impls);
+
+    // Merge public_only and include_tests: CLI flag OR config value
+    if !merged.public_only {
+        merged.public_only = config.generics.public_only.unwrap_or(false);
+    }
+    if !merged.include_tests {
+        merged.include_tests = config.generics.include_tests.unwrap_or(false);
+    }
+
+    // Merge top: CLI arg OR config top OR default 20
+    if let Some(top) = config.generics.top
+        && merged.top == 20
+    {
+        merged.top = top;
     }
 
     // Merge output: CLI default is Table
-    if let Some(config_output) = &config.contributor_report.output
-        && matches!(
-            merged.output,
-            crate::cli::ContributorReportOutputFormat::Table
-        )
+    if let Some(config_output) = &config.generics.output
+        && matches!(merged.output, crate::cli::GenericsOutputFormat::Table)
     {
-        merged.output = parse_contributor_report_output_format(config_output)
-            .unwrap_or(crate::cli::ContributorReportOutputFormat::Table);
+        merged.output = parse_generics_output_format(config_output)
+            .unwrap_or(crate::cli::GenericsOutputFormat::Table);
     }
 
-    // Merge output_file: from general config if not set on CLI
+    // Merge output_file: CLI takes precedence if set, otherwise use config
     if merged.output_file.is_none() {
         merged.output_file = config.general.output_file.clone();
     }
@@ -720,15 +2081,13 @@ pub fn merge_contributor_report_args(
     merged
 }
 
-/// Parse output format string for contributor report.
-fn parse_contributor_report_output_format(
-    s: &str,
-) -> Option<crate::cli::ContributorReportOutputFormat> {
+/// Parse output format string for generics.
+fn parse_generics_output_format(s: &str) -> Option<crate::cli::GenericsOutputFormat> {
     match s.to_lowercase().as_str() {
-        "table" => Some(crate::cli::ContributorReportOutputFormat::Table),
-        "html" => Some(crate::cli::ContributorReportOutputFormat::Html),
-        "json" => Some(crate::cli::ContributorReportOutputFormat::Json),
-        "yaml" => Some(crate::cli::ContributorReportOutputFormat::Yaml),
+        "table" => Some(crate::cli::GenericsOutputFormat::Table),
+        "json" => Some(crate::cli::GenericsOutputFormat::Json),
+        "yaml" => Some(crate::cli::GenericsOutputFormat::Yaml),
+        "html" => Some(crate::cli::GenericsOutputFormat::Html),
         _ => None,
     }
 }
@@ -756,6 +2115,17 @@ pub fn merge_all_args(cli_args: &crate::cli::AllArgs, config: &RaffConfig) -> cr
         }
     }
 
+    // Merge components: shared [components] section
+    if let Some(config_mode) = &config.components.mode
+        && matches!(merged.components, crate::cli::ComponentMode::Directory)
+    {
+        merged.components =
+            parse_component_mode(config_mode).unwrap_or(crate::cli::ComponentMode::Directory);
+    }
+    if merged.component_definitions.is_empty() {
+        merged.component_definitions = config.components.define.clone();
+    }
+
     // Merge statement count threshold
     if config.statement_count.threshold != 10 {
         merged.sc_threshold = config.statement_count.threshold;
@@ -766,10 +2136,44 @@ pub fn merge_all_args(cli_args: &crate::cli::AllArgs, config: &RaffConfig) -> cr
         merged.vol_alpha = config.volatility.alpha;
     }
 
-    // Merge volatility since
+    // Merge volatility history window
     if merged.vol_since.is_none() {
         merged.vol_since = config.volatility.since.clone();
     }
+    if merged.vol_until.is_none() {
+        merged.vol_until = config.volatility.until.clone();
+    }
+    // A configured revision window only applies when none was requested
+    if merged.vol_from.is_none()
+        && merged.vol_to.is_none()
+        && merged.vol_range.is_none()
+        && merged.vol_branch.is_none()
+    {
+        merged.vol_from = config.volatility.from.clone();
+        merged.vol_to = config.volatility.to.clone();
+        merged.vol_range = config.volatility.range.clone();
+    }
+    if merged.vol_to.is_none() && merged.vol_range.is_none() && merged.vol_branch.is_none() {
+        merged.vol_branch = config.volatility.branch.clone();
+    }
+
+    // Merge volatility history filters: config patterns first, then CLI patterns
+    merged.vol_include_paths = concat(&config.volatility.include_paths, &merged.vol_include_paths);
+    merged.vol_exclude_paths = concat(&config.volatility.exclude_paths, &merged.vol_exclude_paths);
+    merged.vol_exclude_authors = concat(
+        &config.volatility.exclude_authors,
+        &merged.vol_exclude_authors,
+    );
+    merged.vol_exclude_messages = concat(
+        &config.volatility.exclude_messages,
+        &merged.vol_exclude_messages,
+    );
+    if merged.vol_ignore_revs_file.is_none() {
+        merged.vol_ignore_revs_file = config.volatility.ignore_revs_file.clone();
+    }
+    if merged.vol_jobs.is_none() {
+        merged.vol_jobs = config.volatility.jobs;
+    }
 
     // Merge volatility normalize
     if config.volatility.normalize && !merged.vol_normalize {
@@ -814,6 +2218,33 @@ pub fn merge_all_args(cli_args: &crate::cli::AllArgs, config: &RaffConfig) -> cr
         merged.rca_language = config.rust_code_analysis.language.clone();
     }
 
+    if let Some(config_backend) = &config.rust_code_analysis.backend
+        && merged.rca_backend == crate::cli::RustCodeAnalysisBackend::Builtin
+    {
+        merged.rca_backend = parse_rca_backend(config_backend)
+            .unwrap_or(crate::cli::RustCodeAnalysisBackend::Builtin);
+    }
+
+    // Merge RCA complexity thresholds: CLI arg OR config value
+    let thresholds = &config.rust_code_analysis;
+    merged.rca_max_file_cognitive = merged
+        .rca_max_file_cognitive
+        .or(thresholds.max_file_cognitive);
+    merged.rca_min_file_mi = merged.rca_min_file_mi.or(thresholds.min_file_mi);
+    merged.rca_max_file_abc = merged.rca_max_file_abc.or(thresholds.max_file_abc);
+    merged.rca_max_function_cyclomatic = merged
+        .rca_max_function_cyclomatic
+        .or(thresholds.max_function_cyclomatic);
+    merged.rca_max_function_cognitive = merged
+        .rca_max_function_cognitive
+        .or(thresholds.max_function_cognitive);
+    merged.rca_max_function_args = merged
+        .rca_max_function_args
+        .or(thresholds.max_function_args);
+    merged.rca_max_function_length = merged
+        .rca_max_function_length
+        .or(thresholds.max_function_length);
+
     // Merge output_file: Use general.output_file if CLI arg is not set
     if merged.output_file.is_none() {
         merged.output_file = config.general.output_file.clone();
@@ -1232,10 +2663,22 @@ verbose = true
         let cli_args = crate::cli::StatementCountArgs {
             path: PathBuf::from("."),
             threshold: 10,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: crate::cli::StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_statement_count_args(&cli_args, &config);
@@ -1258,10 +2701,22 @@ verbose = true
         let cli_args = crate::cli::StatementCountArgs {
             path: PathBuf::from("."),
             threshold: 10,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: crate::cli::StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_statement_count_args(&cli_args, &config);
@@ -1275,6 +2730,249 @@ verbose = true
         ));
     }
 
+    #[test]
+    fn test_merge_statement_count_args_item_settings_from_config() {
+        let mut config = RaffConfig::default();
+        config.statement_count.granularity = Some("item".to_string());
+        config.statement_count.top = Some(5);
+        config.statement_count.max_function_statements = Some(60);
+        config.statement_count.include_tests = Some(true);
+        config.statement_count.parse_macros = Some(true);
+        config.statement_count.max_file_statements = Some(300);
+        config.statement_count.max_gini = Some(0.6);
+
+        let cli_args = crate::cli::StatementCountArgs {
+            path: PathBuf::from("."),
+            threshold: 10,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: Some(40),
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
+            output: crate::cli::StatementCountOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+            staged: false,
+            component_definitions: Default::default(),
+        };
+
+        let merged = merge_statement_count_args(&cli_args, &config);
+
+        assert_eq!(
+            merged.granularity,
+            crate::cli::StatementCountGranularity::Item
+        );
+        assert_eq!(merged.top, 5);
+        assert_eq!(merged.max_function_statements, Some(40));
+        assert!(merged.include_tests);
+        assert!(merged.parse_macros);
+        assert_eq!(merged.max_file_statements, Some(300));
+        assert_eq!(merged.max_gini, Some(0.6));
+        assert_eq!(merged.max_component_statements, None);
+    }
+
+    #[test]
+    fn test_merge_statement_count_args_uses_shared_component_mode() {
+        let mut config = RaffConfig::default();
+        config.components.mode = Some("crate".to_string());
+        config.components.define.insert(
+            "billing".to_string(),
+            vec!["crates/billing-*".to_string(), "app::payments".to_string()],
+        );
+
+        let mut cli_args = crate::cli::StatementCountArgs {
+            path: PathBuf::from("."),
+            threshold: 10,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
+            output: crate::cli::StatementCountOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+            staged: false,
+            component_definitions: Default::default(),
+        };
+        let merged = merge_statement_count_args(&cli_args, &config);
+        assert_eq!(merged.components, crate::cli::ComponentMode::Crate);
+        assert_eq!(
+            merged.component_definitions.get("billing").map(Vec::len),
+            Some(2)
+        );
+
+        cli_args.components = crate::cli::ComponentMode::Module;
+        let merged = merge_statement_count_args(&cli_args, &config);
+        assert_eq!(merged.components, crate::cli::ComponentMode::Module);
+    }
+
+    #[test]
+    fn test_merge_duplication_args_with_config_values() {
+        let mut config = RaffConfig::default();
+        config.components.mode = Some("crate".to_string());
+        config.duplication.min_tokens = Some(80);
+        config.duplication.include_tests = Some(true);
+        config.duplication.output = Some("json".to_string());
+
+        let mut cli_args = crate::cli::DuplicationArgs {
+            path: PathBuf::from("."),
+            min_tokens: 50,
+            include_tests: false,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            output: crate::cli::DuplicationOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let merged = merge_duplication_args(&cli_args, &config);
+        assert_eq!(merged.min_tokens, 80);
+        assert!(merged.include_tests);
+        assert_eq!(merged.components, crate::cli::ComponentMode::Crate);
+        assert!(matches!(
+            merged.output,
+            crate::cli::DuplicationOutputFormat::Json
+        ));
+
+        cli_args.min_tokens = 30;
+        let merged = merge_duplication_args(&cli_args, &config);
+        assert_eq!(merged.min_tokens, 30);
+    }
+
+    #[test]
+    fn test_merge_api_surface_args_thresholds_cli_wins() {
+        let mut config = RaffConfig::default();
+        config.api_surface.max_public_items = Some(100);
+        config.api_surface.max_public_statement_share = Some(40.0);
+        config.api_surface.output = Some("yaml".to_string());
+
+        let cli_args = crate::cli::ApiSurfaceArgs {
+            path: PathBuf::from("."),
+            max_public_items: Some(20),
+            max_public_statement_share: None,
+            output: crate::cli::ApiSurfaceOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let merged = merge_api_surface_args(&cli_args, &config);
+        assert_eq!(merged.max_public_items, Some(20));
+        assert_eq!(merged.max_public_statement_share, Some(40.0));
+        assert!(matches!(
+            merged.output,
+            crate::cli::ApiSurfaceOutputFormat::Yaml
+        ));
+    }
+
+    #[test]
+    fn test_merge_unsafe_code_args_with_config_values() {
+        let mut config = RaffConfig::default();
+        config.unsafe_code.allowed_modules = vec!["crate::ffi".to_string()];
+        config.unsafe_code.trend = Some(true);
+        config.unsafe_code.period = Some("quarter".to_string());
+        config.unsafe_code.since = Some("2024-01-01".to_string());
+
+        let cli_args = crate::cli::UnsafeCodeArgs {
+            path: PathBuf::from("."),
+            allowed_modules: vec!["crate::sys".to_string()],
+            include_tests: false,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            trend: false,
+            period: crate::cli::ContributorReportPeriod::Month,
+            history: Default::default(),
+            output: crate::cli::UnsafeCodeOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let merged = merge_unsafe_code_args(&cli_args, &config);
+        assert_eq!(merged.allowed_modules, vec!["crate::ffi", "crate::sys"]);
+        assert!(merged.trend);
+        assert!(matches!(
+            merged.period,
+            crate::cli::ContributorReportPeriod::Quarter
+        ));
+        assert_eq!(merged.history.since.as_deref(), Some("2024-01-01"));
+    }
+
+    #[test]
+    fn test_merge_error_handling_args_with_config_values() {
+        let config: RaffConfig = toml::from_str(
+            r#"
+            [error_handling]
+            banned_modules = ["crate::domain"]
+            output = "json"
+
+            [error_handling.budgets."crate::app"]
+            unwrap = 2
+            total = 10
+            "#,
+        )
+        .unwrap();
+
+        let cli_args = crate::cli::ErrorHandlingArgs {
+            path: PathBuf::from("."),
+            banned_modules: vec!["crate::core".to_string()],
+            budgets: Default::default(),
+            include_tests: false,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            output: crate::cli::ErrorHandlingOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let merged = merge_error_handling_args(&cli_args, &config);
+        assert_eq!(merged.banned_modules, vec!["crate::domain", "crate::core"]);
+        let budget = &merged.budgets["crate::app"];
+        assert_eq!(
+            (budget.unwrap, budget.total, budget.expect),
+            (Some(2), Some(10), None)
+        );
+        assert!(matches!(
+            merged.output,
+            crate::cli::ErrorHandlingOutputFormat::Json
+        ));
+    }
+
+    #[test]
+    fn test_merge_generics_args_with_config_values() {
+        let mut config = RaffConfig::default();
+        config.generics.max_generic_params = Some(4);
+        config.generics.max_bound_depth = Some(2);
+        config.generics.public_only = Some(true);
+        config.generics.top = Some(5);
+
+        let cli_args = crate::cli::GenericsArgs {
+            path: PathBuf::from("."),
+            max_generic_params: Some(6),
+            max_where_predicates: None,
+            max_bound_depth: None,
+            max_trait_impls: None,
+            max_blanket_impls: None,
+            public_only: false,
+            include_tests: false,
+            top: 20,
+            output: crate::cli::GenericsOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let merged = merge_generics_args(&cli_args, &config);
+        assert_eq!(merged.max_generic_params, Some(6));
+        assert_eq!(merged.max_bound_depth, Some(2));
+        assert!(merged.public_only);
+        assert_eq!(merged.top, 5);
+    }
+
     #[test]
     fn test_merge_statement_count_args_cli_overrides_config() {
         let mut config = RaffConfig::default();
@@ -1284,10 +2982,22 @@ verbose = true
         let cli_args = crate::cli::StatementCountArgs {
             path: PathBuf::from("/cli/path"),
             threshold: 50,
+            components: crate::cli::ComponentMode::Directory,
+            granularity: crate::cli::StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: crate::cli::StatementCountOutputFormat::Html,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_statement_count_args(&cli_args, &config);
@@ -1315,18 +3025,19 @@ verbose = true
         let cli_args = crate::cli::VolatilityArgs {
             path: PathBuf::from("."),
             alpha: 0.01,
-            since: None,
+            history: crate::cli::HistoryArgs::default(),
             normalize: false,
             skip_merges: false,
             output: crate::cli::VolatilityOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_volatility_args(&cli_args, &config);
 
         assert_eq!(merged.alpha, 0.05);
-        assert_eq!(merged.since, Some("2024-01-01".to_string()));
+        assert_eq!(merged.history.since, Some("2024-01-01".to_string()));
         assert!(merged.normalize);
         assert!(merged.skip_merges);
         assert!(matches!(
@@ -1344,12 +3055,16 @@ verbose = true
         let cli_args = crate::cli::VolatilityArgs {
             path: PathBuf::from("."),
             alpha: 0.1,
-            since: Some("2023-01-01".to_string()),
+            history: crate::cli::HistoryArgs {
+                since: Some("2023-01-01".to_string()),
+                ..crate::cli::HistoryArgs::default()
+            },
             normalize: false,
             skip_merges: false,
             output: crate::cli::VolatilityOutputFormat::Json,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_volatility_args(&cli_args, &config);
@@ -1360,13 +3075,41 @@ verbose = true
         // This is documented behavior; improving this would require clap's Id to detect
         // explicitly provided flags.
         assert_eq!(merged.alpha, 0.05); // Config overrides because it's non-default
-        assert_eq!(merged.since, Some("2023-01-01".to_string())); // CLI takes precedence for Option
+        assert_eq!(merged.history.since, Some("2023-01-01".to_string())); // CLI takes precedence for Option
         assert!(matches!(
             merged.output,
             crate::cli::VolatilityOutputFormat::Json
         ));
     }
 
+    #[test]
+    fn test_merge_volatility_revision_window_from_config() {
+        let mut config = RaffConfig::default();
+        config.volatility.range = Some("v1.0..v2.0".to_string());
+        config.volatility.branch = Some("develop".to_string());
+
+        let mut cli_args = crate::cli::VolatilityArgs {
+            path: PathBuf::from("."),
+            alpha: 0.01,
+            history: crate::cli::HistoryArgs::default(),
+            normalize: false,
+            skip_merges: false,
+            output: crate::cli::VolatilityOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+            component_definitions: Default::default(),
+        };
+        let merged = merge_volatility_args(&cli_args, &config);
+        assert_eq!(merged.history.range, Some("v1.0..v2.0".to_string()));
+        assert_eq!(merged.history.branch, None, "a range excludes the branch");
+
+        // An explicit CLI revision replaces the configured window
+        cli_args.history.to = Some("HEAD~3".to_string());
+        let merged = merge_volatility_args(&cli_args, &config);
+        assert_eq!(merged.history.range, None);
+        assert_eq!(merged.history.to, Some("HEAD~3".to_string()));
+    }
+
     #[test]
     fn test_merge_coupling_args_with_config_values() {
         let mut config = RaffConfig::default();
@@ -1380,6 +3123,7 @@ verbose = true
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let merged = merge_coupling_args(&cli_args, &config);
@@ -1401,6 +3145,12 @@ verbose = true
         config.rust_code_analysis.jobs = Some(4);
         config.rust_code_analysis.metrics = false;
         config.rust_code_analysis.language = "python".to_string();
+        config.rust_code_analysis.backend = Some("external".to_string());
+        config.components.mode = Some("crate".to_string());
+        config
+            .components
+            .define
+            .insert("parsing".to_string(), vec!["src/parser/**".to_string()]);
 
         let cli_args = crate::cli::RustCodeAnalysisArgs {
             path: PathBuf::from("."),
@@ -1409,8 +3159,19 @@ verbose = true
             output: crate::cli::RustCodeAnalysisOutputFormat::Table,
             metrics: true,
             language: "rust".to_string(),
+            backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             ci_output: None,
             output_file: None,
+            no_cache: false,
         };
 
         let merged = merge_rust_code_analysis_args(&cli_args, &config);
@@ -1420,6 +3181,49 @@ verbose = true
         assert_eq!(merged.jobs, 4);
         assert!(!merged.metrics);
         assert_eq!(merged.language, "python");
+        assert_eq!(
+            merged.backend,
+            crate::cli::RustCodeAnalysisBackend::External
+        );
+        assert_eq!(merged.components, crate::cli::ComponentMode::Crate);
+        assert!(merged.component_definitions.contains_key("parsing"));
+    }
+
+    #[test]
+    fn test_merge_rust_code_analysis_args_thresholds_cli_wins() {
+        let mut config = RaffConfig::default();
+        config.rust_code_analysis.max_file_cognitive = Some(50.0);
+        config.rust_code_analysis.min_file_mi = Some(40.0);
+        config.rust_code_analysis.max_function_args = Some(5);
+
+        let cli_args = crate::cli::RustCodeAnalysisArgs {
+            path: PathBuf::from("."),
+            extra_flags: vec![],
+            jobs: num_cpus::get(),
+            output: crate::cli::RustCodeAnalysisOutputFormat::Table,
+            metrics: true,
+            language: "rust".to_string(),
+            backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            components: crate::cli::ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: Some(25.0),
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
+            ci_output: None,
+            output_file: None,
+            no_cache: false,
+        };
+
+        let merged = merge_rust_code_analysis_args(&cli_args, &config);
+        assert_eq!(merged.max_file_cognitive, Some(25.0));
+        assert_eq!(merged.min_file_mi, Some(40.0));
+        assert_eq!(merged.max_file_abc, None);
+        assert_eq!(merged.max_function_args, Some(5));
+        assert_eq!(merged.max_function_length, None);
     }
 
     #[test]
@@ -1431,8 +3235,10 @@ verbose = true
 
         let cli_args = crate::cli::ContributorReportArgs {
             path: PathBuf::from("."),
-            since: None,
+            history: crate::cli::HistoryArgs::default(),
             decay: 0.01,
+            period: crate::cli::ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: crate::cli::ContributorReportOutputFormat::Table,
             ci_output: None,
             output_file: None,
@@ -1441,7 +3247,7 @@ verbose = true
         let merged = merge_contributor_report_args(&cli_args, &config);
 
         assert_eq!(merged.decay, 0.02);
-        assert_eq!(merged.since, Some("2023-01-01".to_string()));
+        assert_eq!(merged.history.since, Some("2023-01-01".to_string()));
         assert!(matches!(
             merged.output,
             crate::cli::ContributorReportOutputFormat::Html
@@ -1457,6 +3263,12 @@ verbose = true
         config.volatility.normalize = true;
         config.coupling.granularity = Some("crate".to_string());
         config.rust_code_analysis.extra_flags = vec!["--rca-flag".to_string()];
+        config.volatility.range = Some("v1.0..v2.0".to_string());
+        config.rust_code_analysis.min_file_mi = Some(20.0);
+        config.rust_code_analysis.max_function_args = Some(6);
+        config.components.mode = Some("crate".to_string());
+        config.components.define =
+            BTreeMap::from([("billing".to_string(), vec!["src/payments/".to_string()])]);
 
         let cli_args = crate::cli::AllArgs {
             path: PathBuf::from("."),
@@ -1464,9 +3276,21 @@ verbose = true
             fast: false,
             quiet: false,
             fail_on_warnings: false,
+            components: crate::cli::ComponentMode::Directory,
             sc_threshold: 10,
             vol_alpha: 0.01,
             vol_since: None,
+            vol_until: None,
+            vol_from: None,
+            vol_to: None,
+            vol_range: None,
+            vol_branch: None,
+            vol_include_paths: vec![],
+            vol_exclude_paths: vec![],
+            vol_exclude_authors: vec![],
+            vol_exclude_messages: vec![],
+            vol_ignore_revs_file: None,
+            vol_jobs: None,
             vol_normalize: false,
             vol_skip_merges: false,
             coup_granularity: crate::cli::CouplingGranularity::Both,
@@ -1474,9 +3298,19 @@ verbose = true
             rca_jobs: num_cpus::get(),
             rca_metrics: true,
             rca_language: "rust".to_string(),
+            rca_backend: crate::cli::RustCodeAnalysisBackend::Builtin,
+            rca_max_file_cognitive: None,
+            rca_min_file_mi: None,
+            rca_max_file_abc: None,
+            rca_max_function_cyclomatic: None,
+            rca_max_function_cognitive: None,
+            rca_max_function_args: None,
+            rca_max_function_length: None,
             ci_output: None,
             output_file: None,
             staged: false,
+            no_cache: false,
+            component_definitions: BTreeMap::new(),
         };
 
         let merged = merge_all_args(&cli_args, &config);
@@ -1485,11 +3319,18 @@ verbose = true
         assert_eq!(merged.sc_threshold, 30);
         assert_eq!(merged.vol_alpha, 0.03);
         assert!(merged.vol_normalize);
+        assert_eq!(merged.vol_range, Some("v1.0..v2.0".to_string()));
+        assert_eq!(merged.components, crate::cli::ComponentMode::Crate);
+        assert_eq!(merged.component_definitions, config.components.define);
         assert!(matches!(
             merged.coup_granularity,
             crate::cli::CouplingGranularity::Crate
         ));
         assert_eq!(merged.rca_extra_flags, vec!["--rca-flag"]);
+        assert_eq!(merged.rca_min_file_mi, Some(20.0));
+        assert_eq!(merged.rca_max_file_cognitive, None);
+        assert_eq!(merged.rca_max_function_args, Some(6));
+        assert_eq!(merged.rca_max_function_length, None);
     }
 
     #[test]
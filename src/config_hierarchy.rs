@@ -301,11 +301,20 @@ pub fn load_hierarchical_config(cli_explicit_path: Option<&Path>) -> Result<Hier
 pub fn merge_configs(base: &RaffConfig, override_: &RaffConfig) -> RaffConfig {
     RaffConfig {
         general: base.general.merge(&override_.general),
+        components: base.components.merge(&override_.components),
         statement_count: base.statement_count.merge(&override_.statement_count),
         volatility: base.volatility.merge(&override_.volatility),
         coupling: base.coupling.merge(&override_.coupling),
         rust_code_analysis: base.rust_code_analysis.merge(&override_.rust_code_analysis),
         contributor_report: base.contributor_report.merge(&override_.contributor_report),
+        code_age: base.code_age.merge(&override_.code_age),
+        ownership: base.ownership.merge(&override_.ownership),
+        codeowners: base.codeowners.merge(&override_.codeowners),
+        duplication: base.duplication.merge(&override_.duplication),
+        api_surface: base.api_surface.merge(&override_.api_surface),
+        unsafe_code: base.unsafe_code.merge(&override_.unsafe_code),
+        error_handling: base.error_handling.merge(&override_.error_handling),
+        generics: base.generics.merge(&override_.generics),
         profile: base.profile.merge(&override_.profile),
     }
 }
@@ -346,11 +355,44 @@ impl Mergeable for crate::config::GeneralConfig {
     }
 }
 
+impl Mergeable for crate::config::ComponentsConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            mode: other.mode.clone().or_else(|| self.mode.clone()),
+            define: self
+                .define
+                .iter()
+                .chain(&other.define)
+                .map(|(name, patterns)| (name.clone(), patterns.clone()))
+                .collect(),
+        }
+    }
+}
+
 impl Mergeable for crate::config::StatementCountConfig {
     fn merge(&self, other: &Self) -> Self {
         Self {
             path: other.path.clone().or_else(|| self.path.clone()),
             threshold: other.threshold,
+            granularity: other
+                .granularity
+                .clone()
+                .or_else(|| self.granularity.clone()),
+            top: other.top.or(self.top),
+            max_function_statements: other
+                .max_function_statements
+                .or(self.max_function_statements),
+            include_tests: other.include_tests.or(self.include_tests),
+            parse_macros: other.parse_macros.or(self.parse_macros),
+            max_component_statements: other
+                .max_component_statements
+                .or(self.max_component_statements),
+            min_component_statements: other
+                .min_component_statements
+                .or(self.min_component_statements),
+            max_file_statements: other.max_file_statements.or(self.max_file_statements),
+            max_module_statements: other.max_module_statements.or(self.max_module_statements),
+            max_gini: other.max_gini.or(self.max_gini),
             output: other.output.clone().or_else(|| self.output.clone()),
         }
     }
@@ -362,8 +404,22 @@ impl Mergeable for crate::config::VolatilityConfig {
             path: other.path.clone().or_else(|| self.path.clone()),
             alpha: other.alpha,
             since: other.since.clone().or_else(|| self.since.clone()),
+            until: other.until.clone().or_else(|| self.until.clone()),
+            from: other.from.clone().or_else(|| self.from.clone()),
+            to: other.to.clone().or_else(|| self.to.clone()),
+            range: other.range.clone().or_else(|| self.range.clone()),
+            branch: other.branch.clone().or_else(|| self.branch.clone()),
             normalize: other.normalize || self.normalize,
             skip_merges: other.skip_merges || self.skip_merges,
+            include_paths: union(&self.include_paths, &other.include_paths),
+            exclude_paths: union(&self.exclude_paths, &other.exclude_paths),
+            exclude_authors: union(&self.exclude_authors, &other.exclude_authors),
+            exclude_messages: union(&self.exclude_messages, &other.exclude_messages),
+            ignore_revs_file: other
+                .ignore_revs_file
+                .clone()
+                .or_else(|| self.ignore_revs_file.clone()),
+            jobs: other.jobs.or(self.jobs),
             output: other.output.clone().or_else(|| self.output.clone()),
         }
     }
@@ -404,6 +460,16 @@ impl Mergeable for crate::config::RustCodeAnalysisConfig {
             } else {
                 self.language.clone()
             },
+            backend: other.backend.clone().or_else(|| self.backend.clone()),
+            max_file_cognitive: other.max_file_cognitive.or(self.max_file_cognitive),
+            min_file_mi: other.min_file_mi.or(self.min_file_mi),
+            max_file_abc: other.max_file_abc.or(self.max_file_abc),
+            max_function_cyclomatic: other
+                .max_function_cyclomatic
+                .or(self.max_function_cyclomatic),
+            max_function_cognitive: other.max_function_cognitive.or(self.max_function_cognitive),
+            max_function_args: other.max_function_args.or(self.max_function_args),
+            max_function_length: other.max_function_length.or(self.max_function_length),
         }
     }
 }
@@ -413,7 +479,25 @@ impl Mergeable for crate::config::ContributorReportConfig {
         Self {
             path: other.path.clone().or_else(|| self.path.clone()),
             since: other.since.clone().or_else(|| self.since.clone()),
+            until: other.until.clone().or_else(|| self.until.clone()),
+            branch: other.branch.clone().or_else(|| self.branch.clone()),
             decay: other.decay,
+            aliases: self
+                .aliases
+                .iter()
+                .chain(&other.aliases)
+                .map(|(email, identities)| (email.clone(), identities.clone()))
+                .collect(),
+            period: other.period.clone().or_else(|| self.period.clone()),
+            include_paths: union(&self.include_paths, &other.include_paths),
+            exclude_paths: union(&self.exclude_paths, &other.exclude_paths),
+            exclude_authors: union(&self.exclude_authors, &other.exclude_authors),
+            exclude_messages: union(&self.exclude_messages, &other.exclude_messages),
+            ignore_revs_file: other
+                .ignore_revs_file
+                .clone()
+                .or_else(|| self.ignore_revs_file.clone()),
+            jobs: other.jobs.or(self.jobs),
             output: other.output.clone().or_else(|| self.output.clone()),
         }
     }
@@ -430,6 +514,160 @@ impl Mergeable for crate::config::PreCommitProfile {
     }
 }
 
+impl Mergeable for crate::config::CodeAgeConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            granularity: other
+                .granularity
+                .clone()
+                .or_else(|| self.granularity.clone()),
+            stale_months: other.stale_months.or(self.stale_months),
+            inactive_months: other.inactive_months.or(self.inactive_months),
+            jobs: other.jobs.or(self.jobs),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::OwnershipConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            granularity: other
+                .granularity
+                .clone()
+                .or_else(|| self.granularity.clone()),
+            source: other.source.clone().or_else(|| self.source.clone()),
+            since: other.since.clone().or_else(|| self.since.clone()),
+            until: other.until.clone().or_else(|| self.until.clone()),
+            branch: other.branch.clone().or_else(|| self.branch.clone()),
+            include_paths: union(&self.include_paths, &other.include_paths),
+            exclude_paths: union(&self.exclude_paths, &other.exclude_paths),
+            exclude_authors: union(&self.exclude_authors, &other.exclude_authors),
+            exclude_messages: union(&self.exclude_messages, &other.exclude_messages),
+            ignore_revs_file: other
+                .ignore_revs_file
+                .clone()
+                .or_else(|| self.ignore_revs_file.clone()),
+            inactive_months: other.inactive_months.or(self.inactive_months),
+            jobs: other.jobs.or(self.jobs),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::CodeownersConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            granularity: other
+                .granularity
+                .clone()
+                .or_else(|| self.granularity.clone()),
+            codeowners_file: other
+                .codeowners_file
+                .clone()
+                .or_else(|| self.codeowners_file.clone()),
+            since: other.since.clone().or_else(|| self.since.clone()),
+            until: other.until.clone().or_else(|| self.until.clone()),
+            branch: other.branch.clone().or_else(|| self.branch.clone()),
+            include_paths: union(&self.include_paths, &other.include_paths),
+            exclude_paths: union(&self.exclude_paths, &other.exclude_paths),
+            exclude_authors: union(&self.exclude_authors, &other.exclude_authors),
+            exclude_messages: union(&self.exclude_messages, &other.exclude_messages),
+            ignore_revs_file: other
+                .ignore_revs_file
+                .clone()
+                .or_else(|| self.ignore_revs_file.clone()),
+            max_owners: other.max_owners.or(self.max_owners),
+            min_share: other.min_share.or(self.min_share),
+            handles: self
+                .handles
+                .iter()
+                .chain(&other.handles)
+                .map(|(email, handle)| (email.clone(), handle.clone()))
+                .collect(),
+            jobs: other.jobs.or(self.jobs),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::DuplicationConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            min_tokens: other.min_tokens.or(self.min_tokens),
+            include_tests: other.include_tests.or(self.include_tests),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::ApiSurfaceConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            max_public_items: other.max_public_items.or(self.max_public_items),
+            max_public_statement_share: other
+                .max_public_statement_share
+                .or(self.max_public_statement_share),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::UnsafeCodeConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            allowed_modules: union(&self.allowed_modules, &other.allowed_modules),
+            include_tests: other.include_tests.or(self.include_tests),
+            trend: other.trend.or(self.trend),
+            period: other.period.clone().or_else(|| self.period.clone()),
+            since: other.since.clone().or_else(|| self.since.clone()),
+            until: other.until.clone().or_else(|| self.until.clone()),
+            branch: other.branch.clone().or_else(|| self.branch.clone()),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::ErrorHandlingConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            banned_modules: union(&self.banned_modules, &other.banned_modules),
+            budgets: self
+                .budgets
+                .iter()
+                .chain(&other.budgets)
+                .map(|(module, budget)| (module.clone(), *budget))
+                .collect(),
+            include_tests: other.include_tests.or(self.include_tests),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
+impl Mergeable for crate::config::GenericsConfig {
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            path: other.path.clone().or_else(|| self.path.clone()),
+            max_generic_params: other.max_generic_params.or(self.max_generic_params),
+            max_where_predicates: other.max_where_predicates.or(self.max_where_predicates),
+            max_bound_depth: other.max_bound_depth.or(self.max_bound_depth),
+            max_trait_impls: other.max_trait_impls.or(self.max_trait_impls),
+            max_blanket_impls: other.max_blanket_impls.or(self.max_blanket_impls),
+            public_only: other.public_only.or(self.public_only),
+            include_tests: other.include_tests.or(self.include_tests),
+            top: other.top.or(self.top),
+            output: other.output.clone().or_else(|| self.output.clone()),
+        }
+    }
+}
+
 impl Mergeable for crate::config::ProfileConfig {
     fn merge(&self, other: &Self) -> Self {
         // If other has pre_commit, merge with self's pre_commit (if any)
@@ -454,6 +692,21 @@ impl Mergeable for RaffConfig {
     }
 }
 
+/// Combines two pattern lists, keeping the order of `first` and appending
+/// the entries of `second` that are not already present.
+///
+/// Unlike plain concatenation this keeps merging idempotent and associative.
+fn union(first: &[String], second: &[String]) -> Vec<String> {
+    let mut combined = first.to_vec();
+    combined.extend(
+        second
+            .iter()
+            .filter(|pattern| !first.contains(pattern))
+            .cloned(),
+    );
+    combined
+}
+
 /// Returns the user's home directory.
 ///
 /// This is a platform-independent helper that tries multiple
@@ -630,11 +883,14 @@ mod tests {
             path: Some(PathBuf::from("/base")),
             threshold: 10,
             output: Some("table".to_string()),
+            ..Default::default()
         };
         let override_ = StatementCountConfig {
             path: Some(PathBuf::from("/override")),
             threshold: 25,
+            max_function_statements: Some(40),
             output: Some("html".to_string()),
+            ..Default::default()
         };
 
         let merged = base.merge(&override_);
@@ -642,6 +898,7 @@ mod tests {
         assert_eq!(merged.path, Some(PathBuf::from("/override")));
         assert_eq!(merged.threshold, 25);
         assert_eq!(merged.output, Some("html".to_string()));
+        assert_eq!(merged.max_function_statements, Some(40));
     }
 
     #[test]
@@ -25,19 +25,47 @@
 //!
 //! A higher decay factor causes older contributions to be weighted less heavily.
 //!
+//! # Identity Resolution
+//!
+//! Authors are resolved through the repository's `.mailmap` and then through
+//! the alias groups in `[contributor_report.aliases]`, and are keyed on their
+//! canonical email. Commits made under several names or emails are credited
+//! to one contributor, while different people who share a name stay separate.
+//!
+//! Every author named in a `Co-authored-by:` trailer is credited with the
+//! commit in full, as if they had authored it. The committer is only credited
+//! separately (as **Committed**) when they differ from the author, for example
+//! when a maintainer applies or rebases someone else's patch. Identities that
+//! only ever commit, such as GitHub's web-flow committer, are reported with
+//! their committed commits only.
+//!
+//! # Activity Timeline
+//!
+//! Each contributor's commits, churn and active days (distinct author dates)
+//! are also bucketed by `--period` (month or quarter). The timeline is shown
+//! under the table, charted in the HTML report, and exported as one row per
+//! contributor and period with `--output csv`.
+//!
 //! # Usage
 //!
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use raff_core::contributor_report::ContributorReportRule;
-//! use raff_core::{ContributorReportArgs, ContributorReportOutputFormat};
+//! use raff_core::{
+//!     ContributorReportArgs, ContributorReportOutputFormat, ContributorReportPeriod, HistoryArgs,
+//! };
 //! use std::path::PathBuf;
 //!
 //! let rule = ContributorReportRule::new();
 //! let args = ContributorReportArgs {
 //!     path: PathBuf::from("."),
-//!     since: Some("2023-01-01".to_string()),
+//!     history: HistoryArgs {
+//!         since: Some("2023-01-01".to_string()),
+//!         ..HistoryArgs::default()
+//!     },
 //!     decay: 0.01,
+//!     period: ContributorReportPeriod::Month,
+//!     aliases: Default::default(),
 //!     output: ContributorReportOutputFormat::Table,
 //!     ci_output: None,
 //!     output_file: None,
@@ -57,11 +85,16 @@
 //!
 //! # Metrics Per Contributor
 //!
-//! - **Author**: The git author name
+//! - **Author**: The git author name, after `.mailmap` resolution
+//! - **Email**: The canonical email the contributor is keyed on
+//! - **Identities**: Every name and email the contributor committed under
 //! - **Commit Count**: Total number of commits
 //! - **Lines Added**: Total lines of code added
 //! - **Lines Deleted**: Total lines of code deleted (considered positive contribution)
 //! - **Files Touched**: Number of unique files modified
+//! - **Co-authored**: Commits credited through a `Co-authored-by:` trailer
+//! - **Committed**: Commits committed on behalf of another author
+//! - **Active Days**: Number of distinct days with a credited commit
 //! - **Score**: Weighted sum considering recency decay
 //!
 //! # Output Formats
@@ -69,8 +102,10 @@
 //! The rule supports multiple output formats:
 //! - `Table`: Human-readable table format
 //! - `Html`: Interactive HTML report saved to `contributor-report.html`
+//!   unless `--output-file` is given
 //! - `Json`: Machine-readable JSON
 //! - `Yaml`: Machine-readable YAML
+//! - `Csv`: The activity timeline, one row per contributor and period
 //!
 //! # Errors
 //!
@@ -78,43 +113,80 @@
 //! - The provided path is not a valid Git repository
 //! - Git operations fail (e.g., corrupted repository)
 
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::Write;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 
 use crate::ci_report::{Finding, Severity, ToFindings};
+use crate::commit_stats::{CommitDiff, CommitStatsStore};
 use crate::error::{RaffError, Result};
+use crate::history::{HistoryFilter, HistoryWindow};
+use crate::identity::{Identity, IdentityResolver};
 use crate::rule::Rule;
-use chrono::{DateTime, Utc};
-use git2::{Commit, Repository};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use git2::Repository;
 use maud::{Markup, html};
 use prettytable::{Table, row};
 use serde::{Deserialize, Serialize};
 
-use crate::cli::{CiOutputFormat, ContributorReportArgs, ContributorReportOutputFormat};
+use crate::cli::{
+    CiOutputFormat, ContributorReportArgs, ContributorReportOutputFormat, ContributorReportPeriod,
+};
 use crate::html_utils::{self, MetricRanges};
 
+/// File the HTML report is written to when no output file is given.
+const DEFAULT_HTML_FILE: &str = "contributor-report.html";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContributorStats {
     pub author: String,
+    /// Canonical email the contributor is keyed on.
+    pub email: String,
+    /// Every `Name <email>` identity seen for this contributor, in order of first appearance.
+    pub identities: Vec<String>,
     pub commit_count: u32,
     pub lines_added: u32,
     pub lines_deleted: u32,
     pub files_touched: u32,
+    /// Commits credited through a `Co-authored-by:` trailer.
+    pub co_authored_count: u32,
+    /// Commits this contributor committed on behalf of another author.
+    pub committed_count: u32,
+    /// Distinct days with a credited commit.
+    pub active_days: u32,
     pub last_commit_date: DateTime<Utc>,
     pub score: f64,
+    /// Activity per period, oldest first.
+    pub activity: Vec<ContributorActivity>,
+}
+
+/// A contributor's activity within one period of the timeline.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContributorActivity {
+    /// Period label, `YYYY-MM` for months or `YYYY-Qn` for quarters.
+    pub period: String,
+    pub commits: u32,
+    pub lines_added: u32,
+    pub lines_deleted: u32,
+    /// Distinct days with a credited commit in the period.
+    pub active_days: u32,
 }
 
 impl ContributorStats {
-    pub fn new(author: String) -> Self {
+    pub fn new(author: String, email: String) -> Self {
         Self {
             author,
+            email,
+            identities: Vec::new(),
             commit_count: 0,
             lines_added: 0,
             lines_deleted: 0,
             files_touched: 0,
+            co_authored_count: 0,
+            committed_count: 0,
+            active_days: 0,
             last_commit_date: Utc::now(),
             score: 0.0,
+            activity: Vec::new(),
         }
     }
 }
@@ -144,6 +216,7 @@ impl ToFindings for ContributorReportData {
                     stat.score
                 ),
                 location: None, // Contributor report is aggregate data, not file-specific
+                related_locations: Vec::new(),
                 help_uri: Some("https://github.com/liamwh/raff/docs/contributor-report".to_string()),
                 fingerprint: Some(format!(
                     "contributor-report:{}:{}:{}",
@@ -206,25 +279,7 @@ impl ContributorReportRule {
                 }
             };
 
-            // Write to file if specified, otherwise stdout
-            if let Some(ref output_file) = args.output_file {
-                let mut file = File::create(output_file).map_err(|e| {
-                    RaffError::io_error(format!(
-                        "Failed to create output file {}: {}",
-                        output_file.display(),
-                        e
-                    ))
-                })?;
-                file.write_all(output.as_bytes()).map_err(|e| {
-                    RaffError::io_error(format!(
-                        "Failed to write to output file {}: {}",
-                        output_file.display(),
-                        e
-                    ))
-                })?;
-            } else {
-                println!("{output}");
-            }
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
 
             // Note findings are informational - don't fail CI
             return Ok(());
@@ -236,42 +291,112 @@ impl ContributorReportRule {
     fn analyze_impl(&self, args: &ContributorReportArgs) -> Result<ContributorReportData> {
         let repo = Repository::open(&args.path)
             .map_err(|_e| RaffError::git_error_with_repo("open repository", args.path.clone()))?;
-        let mut revwalk = repo.revwalk()?;
-        revwalk.push_head()?;
-
+        let window = HistoryWindow::resolve(&repo, &args.history, false)?;
+
+        let commits = window.commits(&repo)?;
+        let mut store = CommitStatsStore::open(&repo)?;
+        store.prefetch(
+            &repo,
+            &commits,
+            args.history.jobs.unwrap_or_else(num_cpus::get),
+        )?;
+        let identities = IdentityResolver::new(&repo, &args.aliases)?;
         let mut stats: HashMap<String, ContributorStats> = HashMap::new();
+        let mut activity: HashMap<String, ActivityLog> = HashMap::new();
+        // Commits committed on behalf of others, merged in after the walk so
+        // that committer names never rename authors
+        let mut committers: HashMap<String, ContributorStats> = HashMap::new();
         let now = Utc::now();
 
-        for oid in revwalk {
-            let oid = oid?;
+        for oid in commits {
             let commit = repo.find_commit(oid)?;
-            let author = commit.author().name().unwrap_or("Unknown").to_string();
+            let diff = store.get_or_compute(&repo, &commit)?;
+            let Some((lines_added, lines_deleted, files_touched)) =
+                self.get_commit_stats(diff, &window.filter)
+            else {
+                tracing::trace!(commit_id = %oid, "Commit only touches excluded paths, skipping.");
+                continue;
+            };
 
-            let contributor = stats
-                .entry(author.clone())
-                .or_insert_with(|| ContributorStats::new(author));
+            let author = identities.resolve(&commit.author());
+            let committer = identities.resolve(&commit.committer());
+            let author_email = author.email.clone();
+            let mut credited = vec![author];
+            for (name, email) in co_authors(commit.message().unwrap_or_default()) {
+                let co_author = identities.resolve_name_email(&name, &email);
+                if credited.iter().all(|c| c.email != co_author.email) {
+                    credited.push(co_author);
+                }
+            }
 
             let commit_time = DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or(now);
             let days_since_commit = now.signed_duration_since(commit_time).num_days() as f64;
             let weight = (-args.decay * days_since_commit).exp();
-
-            let (lines_added, lines_deleted, files_touched) =
-                self.get_commit_stats(&repo, &commit)?;
-
-            contributor.commit_count += 1;
-            contributor.lines_added += lines_added;
-            contributor.lines_deleted += lines_deleted;
-            contributor.files_touched += files_touched;
-
             let churn = (lines_added + lines_deleted) as f64;
             let commit_score = (1.0 + churn + files_touched as f64) * weight;
-            contributor.score += commit_score;
+            let day = commit_time.date_naive();
+            let period = period_label(day, args.period);
+
+            for (idx, identity) in credited.into_iter().enumerate() {
+                let log = activity.entry(identity.email.clone()).or_default();
+                log.days.insert(day);
+                let bucket = log.periods.entry(period.clone()).or_default();
+                bucket.commits += 1;
+                bucket.lines_added += lines_added;
+                bucket.lines_deleted += lines_deleted;
+                bucket.days.insert(day);
+
+                let contributor = contributor_entry(&mut stats, identity);
+                if idx > 0 {
+                    contributor.co_authored_count += 1;
+                }
+                if contributor.commit_count == 0 || commit_time > contributor.last_commit_date {
+                    contributor.last_commit_date = commit_time;
+                }
+                contributor.commit_count += 1;
+                contributor.lines_added += lines_added;
+                contributor.lines_deleted += lines_deleted;
+                contributor.files_touched += files_touched;
+                contributor.score += commit_score;
+            }
+
+            if committer.email != author_email {
+                let contributor = contributor_entry(&mut committers, committer);
+                if contributor.committed_count == 0 || commit_time > contributor.last_commit_date {
+                    contributor.last_commit_date = commit_time;
+                }
+                contributor.committed_count += 1;
+            }
+        }
 
-            if commit_time > contributor.last_commit_date {
-                contributor.last_commit_date = commit_time;
+        for (email, committer) in committers {
+            match stats.get_mut(&email) {
+                Some(contributor) => contributor.committed_count = committer.committed_count,
+                None => {
+                    stats.insert(email, committer);
+                }
             }
         }
 
+        for (email, log) in activity {
+            if let Some(contributor) = stats.get_mut(&email) {
+                contributor.active_days = log.days.len() as u32;
+                contributor.activity = log
+                    .periods
+                    .into_iter()
+                    .map(|(period, bucket)| ContributorActivity {
+                        period,
+                        commits: bucket.commits,
+                        lines_added: bucket.lines_added,
+                        lines_deleted: bucket.lines_deleted,
+                        active_days: bucket.days.len() as u32,
+                    })
+                    .collect();
+            }
+        }
+
+        store.save()?;
+
         let mut sorted_stats: Vec<ContributorStats> = stats.into_values().collect();
         sorted_stats.sort_by(|a, b| {
             b.score
@@ -290,10 +415,23 @@ impl ContributorReportRule {
         args: &ContributorReportArgs,
     ) -> Result<()> {
         match args.output {
-            ContributorReportOutputFormat::Table => self.print_table(&data.stats),
-            ContributorReportOutputFormat::Html => self.print_html(&data.stats),
-            ContributorReportOutputFormat::Json => self.print_json(&data.stats),
-            ContributorReportOutputFormat::Yaml => self.print_yaml(&data.stats),
+            ContributorReportOutputFormat::Table => {
+                self.print_table(&data.stats)?;
+                self.print_timeline_table(&data.stats)
+            }
+            ContributorReportOutputFormat::Html => {
+                self.print_html(&data.stats, args.output_file.as_deref())
+            }
+            ContributorReportOutputFormat::Json => {
+                self.print_json(&data.stats, args.output_file.as_deref())
+            }
+            ContributorReportOutputFormat::Yaml => {
+                self.print_yaml(&data.stats, args.output_file.as_deref())
+            }
+            ContributorReportOutputFormat::Csv => crate::output_utils::write_output(
+                &self.render_timeline_csv(&data.stats)?,
+                args.output_file.as_deref(),
+            ),
         }
     }
 
@@ -307,39 +445,58 @@ impl ContributorReportRule {
         self.analyze_impl(args)
     }
 
-    fn get_commit_stats(&self, repo: &Repository, commit: &Commit) -> Result<(u32, u32, u32)> {
-        let parent = commit.parent(0);
-        let tree = commit.tree()?;
-        let parent_tree = parent.ok().and_then(|p| p.tree().ok());
-
-        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
-        let diff_stats = diff.stats()?;
-
-        Ok((
-            diff_stats.insertions() as u32,
-            diff_stats.deletions() as u32,
-            diff_stats.files_changed() as u32,
-        ))
+    /// Returns `(lines_added, lines_deleted, files_touched)` for a commit,
+    /// counting only paths accepted by `filter`.
+    ///
+    /// Returns `None` when the commit changed files but every one of them was
+    /// filtered out, so that such commits are not credited at all.
+    fn get_commit_stats(
+        &self,
+        diff: &CommitDiff,
+        filter: &HistoryFilter,
+    ) -> Option<(u32, u32, u32)> {
+        let (mut added, mut deleted, mut files) = (0, 0, 0);
+        for change in diff
+            .files
+            .iter()
+            .filter(|change| filter.includes_path(&change.path))
+        {
+            files += 1;
+            added += change.lines_added;
+            deleted += change.lines_deleted;
+        }
+        if files == 0 && !diff.files.is_empty() {
+            return None;
+        }
+        Some((added, deleted, files))
     }
 
     fn print_table(&self, stats: &[ContributorStats]) -> Result<()> {
         let mut table = Table::new();
         table.add_row(row![
             "Author",
+            "Email",
             "Commit Count",
             "Lines Added",
             "Lines Deleted",
             "Files Touched",
+            "Co-authored",
+            "Committed",
+            "Active Days",
             "Score"
         ]);
 
         for stat in stats {
             table.add_row(row![
                 stat.author,
+                stat.email,
                 stat.commit_count.to_string(),
                 stat.lines_added.to_string(),
                 stat.lines_deleted.to_string(),
                 stat.files_touched.to_string(),
+                stat.co_authored_count.to_string(),
+                stat.committed_count.to_string(),
+                stat.active_days.to_string(),
                 format!("{:.2}", stat.score)
             ]);
         }
@@ -348,24 +505,86 @@ impl ContributorReportRule {
         Ok(())
     }
 
-    fn print_json(&self, stats: &[ContributorStats]) -> Result<()> {
-        let json = serde_json::to_string_pretty(stats)?;
-        println!("{json}");
+    fn print_timeline_table(&self, stats: &[ContributorStats]) -> Result<()> {
+        let mut table = Table::new();
+        table.add_row(row![
+            "Author",
+            "Period",
+            "Commits",
+            "Lines Added",
+            "Lines Deleted",
+            "Active Days"
+        ]);
+        for stat in stats {
+            for bucket in &stat.activity {
+                table.add_row(row![
+                    stat.author,
+                    bucket.period,
+                    bucket.commits.to_string(),
+                    bucket.lines_added.to_string(),
+                    bucket.lines_deleted.to_string(),
+                    bucket.active_days.to_string()
+                ]);
+            }
+        }
+
+        println!("\nActivity Timeline:");
+        table.printstd();
         Ok(())
     }
 
-    fn print_yaml(&self, stats: &[ContributorStats]) -> Result<()> {
+    /// Renders the activity timeline as CSV, one row per contributor and period.
+    fn render_timeline_csv(&self, stats: &[ContributorStats]) -> Result<String> {
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(vec![]);
+        wtr.write_record([
+            "author",
+            "email",
+            "period",
+            "commits",
+            "lines_added",
+            "lines_deleted",
+            "active_days",
+        ])?;
+        for stat in stats {
+            for bucket in &stat.activity {
+                wtr.write_record([
+                    stat.author.clone(),
+                    stat.email.clone(),
+                    bucket.period.clone(),
+                    bucket.commits.to_string(),
+                    bucket.lines_added.to_string(),
+                    bucket.lines_deleted.to_string(),
+                    bucket.active_days.to_string(),
+                ])?;
+            }
+        }
+        let bytes = wtr
+            .into_inner()
+            .map_err(|e| RaffError::parse_error(format!("Failed to get CSV bytes: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| RaffError::parse_error(format!("Failed to convert CSV to UTF-8: {}", e)))
+    }
+
+    fn print_json(&self, stats: &[ContributorStats], output_file: Option<&Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(stats)?;
+        crate::output_utils::write_output(&json, output_file)
+    }
+
+    fn print_yaml(&self, stats: &[ContributorStats], output_file: Option<&Path>) -> Result<()> {
         let yaml = serde_yaml::to_string(stats)?;
-        println!("{yaml}");
-        Ok(())
+        crate::output_utils::write_output(&yaml, output_file)
     }
 
-    fn print_html(&self, stats: &[ContributorStats]) -> Result<()> {
+    /// Writes the HTML report to `output_file`, or to `contributor-report.html`
+    /// when it is `None`.
+    fn print_html(&self, stats: &[ContributorStats], output_file: Option<&Path>) -> Result<()> {
         let report_body = self.generate_report_body(stats);
         let html_content = html_utils::render_html_doc("Contributor Report", report_body);
-        let mut file = File::create("contributor-report.html")?;
-        file.write_all(html_content.as_bytes())?;
-        println!("HTML report generated: contributor-report.html");
+        let output_file = output_file.unwrap_or(Path::new(DEFAULT_HTML_FILE));
+        crate::output_utils::write_output(&html_content, Some(output_file))?;
+        println!("HTML report generated: {}", output_file.display());
         Ok(())
     }
 
@@ -388,17 +607,22 @@ impl ContributorReportRule {
                 thead {
                     tr {
                         th { "Author" }
+                        th { "Email" }
                         th { "Commit Count" }
                         th { "Lines Added" }
                         th { "Lines Deleted" }
                         th { "Files Touched" }
+                        th { "Co-authored" }
+                        th { "Committed" }
+                        th { "Active Days" }
                         th { "Score" }
                     }
                 }
                 tbody {
                     @for stat in stats {
                         tr {
-                            td { (stat.author) }
+                            td title=(stat.identities.join("\n")) { (stat.author) }
+                            td { (stat.email) }
                             @if let Some(ref ranges) = commit_ranges {
                                 td style=(html_utils::get_metric_cell_style(stat.commit_count as f64, ranges)) { (stat.commit_count) }
                             } @else {
@@ -419,6 +643,9 @@ impl ContributorReportRule {
                             } @else {
                                 td { (stat.files_touched) }
                             }
+                            td { (stat.co_authored_count) }
+                            td { (stat.committed_count) }
+                            td { (stat.active_days) }
                             @if let Some(ref ranges) = score_ranges {
                                 td style=(html_utils::get_metric_cell_style(stat.score, ranges)) { (format!("{:.2}", stat.score)) }
                             } @else {
@@ -428,6 +655,71 @@ impl ContributorReportRule {
                     }
                 }
             }
+            (self.render_timeline(stats))
+        }
+    }
+
+    /// Renders a bar chart of commits per period and a contributor × period
+    /// heat map of commits.
+    fn render_timeline(&self, stats: &[ContributorStats]) -> Markup {
+        let mut totals: BTreeMap<&str, u32> = BTreeMap::new();
+        for bucket in stats.iter().flat_map(|s| &s.activity) {
+            *totals.entry(bucket.period.as_str()).or_default() += bucket.commits;
+        }
+        let periods: Vec<&str> = totals.keys().copied().collect();
+        let max_commits = totals.values().copied().max().unwrap_or(0).max(1);
+        let commit_values: Vec<f64> = stats
+            .iter()
+            .flat_map(|s| &s.activity)
+            .map(|bucket| bucket.commits as f64)
+            .collect();
+        let commit_ranges = MetricRanges::from_values(&commit_values, true);
+
+        const CHART_HEIGHT: u32 = 160;
+        const BAR_WIDTH: usize = 24;
+        let chart_width = (periods.len() * (BAR_WIDTH + 4)).max(BAR_WIDTH);
+
+        html! {
+            h2 { "Activity Timeline" }
+            svg width=(chart_width) height=(CHART_HEIGHT + 20) role="img" aria-label="Commits per period" {
+                @for (idx, (period, commits)) in totals.iter().enumerate() {
+                    @let height = commits * CHART_HEIGHT / max_commits;
+                    rect x=(idx * (BAR_WIDTH + 4)) y=(CHART_HEIGHT - height) width=(BAR_WIDTH) height=(height) fill="#4a90d9" {
+                        title { (format!("{}: {} commits", period, commits)) }
+                    }
+                }
+                text x="0" y=(CHART_HEIGHT + 15) font-size="11" { (periods.first().copied().unwrap_or_default()) }
+                text x=(chart_width) y=(CHART_HEIGHT + 15) font-size="11" text-anchor="end" { (periods.last().copied().unwrap_or_default()) }
+            }
+            table class="sortable-table" {
+                thead {
+                    tr {
+                        th { "Author" }
+                        @for period in &periods {
+                            th { (period) }
+                        }
+                    }
+                }
+                tbody {
+                    @for stat in stats {
+                        tr {
+                            td { (stat.author) }
+                            @for period in &periods {
+                                @let bucket = stat.activity.iter().find(|b| b.period == *period);
+                                @let commits = bucket.map_or(0, |b| b.commits);
+                                @let tooltip = bucket.map_or_else(String::new, |b| format!(
+                                    "+{} -{} lines, {} active days", b.lines_added, b.lines_deleted, b.active_days
+                                ));
+                                @if let Some(ref ranges) = commit_ranges {
+                                    td title=(tooltip) style=(html_utils::get_metric_cell_style(commits as f64, ranges)) { (commits) }
+                                } @else {
+                                    td title=(tooltip) { (commits) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -435,7 +727,11 @@ impl ContributorReportRule {
         let explanations = vec![
             (
                 "Author",
-                "The name of the contributor, as extracted from the Git commit logs.",
+                "The name of the contributor, as extracted from the Git commit logs and resolved through `.mailmap`. Hover to see every identity they committed under.",
+            ),
+            (
+                "Email",
+                "The canonical email of the contributor. Commits are grouped by this email after `.mailmap` and configured aliases are applied.",
             ),
             (
                 "Commit Count",
@@ -453,6 +749,18 @@ impl ContributorReportRule {
                 "Files Touched",
                 "The total number of unique files modified by the contributor.",
             ),
+            (
+                "Co-authored",
+                "Commits credited to the contributor through a `Co-authored-by:` trailer. Co-authors receive full credit for the commit's churn and score.",
+            ),
+            (
+                "Committed",
+                "Commits the contributor committed on behalf of a different author, such as applied or rebased patches. These are not counted towards the score.",
+            ),
+            (
+                "Active Days",
+                "The number of distinct days on which the contributor has a credited commit.",
+            ),
             (
                 "Score",
                 "A calculated metric representing the overall contribution. It is a weighted sum of commits, lines added, lines deleted, and files touched, with an exponential decay factor applied to give more weight to recent contributions. The formula is: `Σ((1 + churn + files_touched) * e^(-decay * days_since_commit))` for each commit.",
@@ -462,9 +770,67 @@ impl ContributorReportRule {
     }
 }
 
+/// Days with credited commits, and activity per period, for one contributor.
+#[derive(Default)]
+struct ActivityLog {
+    days: HashSet<NaiveDate>,
+    periods: BTreeMap<String, PeriodActivity>,
+}
+
+#[derive(Default)]
+struct PeriodActivity {
+    commits: u32,
+    lines_added: u32,
+    lines_deleted: u32,
+    days: HashSet<NaiveDate>,
+}
+
+/// Returns the stats for `identity`, creating them on first sight and
+/// recording the name and raw identity used.
+fn contributor_entry(
+    stats: &mut HashMap<String, ContributorStats>,
+    identity: Identity,
+) -> &mut ContributorStats {
+    let contributor = stats
+        .entry(identity.email.clone())
+        .or_insert_with(|| ContributorStats::new(identity.name.clone(), identity.email));
+    // Commits are visited oldest first, so the most recent name wins
+    contributor.author = identity.name;
+    if !contributor.identities.contains(&identity.raw) {
+        contributor.identities.push(identity.raw);
+    }
+    contributor
+}
+
+/// Returns the label of the period containing `day`.
+pub(crate) fn period_label(day: NaiveDate, period: ContributorReportPeriod) -> String {
+    match period {
+        ContributorReportPeriod::Month => format!("{}-{:02}", day.year(), day.month()),
+        ContributorReportPeriod::Quarter => format!("{}-Q{}", day.year(), day.month0() / 3 + 1),
+    }
+}
+
+/// Returns the name and email of every `Co-authored-by:` trailer in `message`.
+fn co_authors(message: &str) -> Vec<(String, String)> {
+    message
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once(':')?;
+            if !key.trim().eq_ignore_ascii_case("co-authored-by") {
+                return None;
+            }
+            let (name, rest) = value.split_once('<')?;
+            let email = rest.strip_suffix('>')?.trim();
+            let name = name.trim();
+            (!name.is_empty() && !email.is_empty()).then(|| (name.to_string(), email.to_string()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::HistoryArgs;
 
     /// Helper function to create test ContributorStats.
     fn create_test_contributor_stats(
@@ -477,18 +843,31 @@ mod tests {
     ) -> ContributorStats {
         ContributorStats {
             author: author.to_string(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            identities: vec![format!("{author} <{}@example.com>", author.to_lowercase())],
             commit_count,
             lines_added,
             lines_deleted,
             files_touched,
+            co_authored_count: 0,
+            committed_count: 0,
+            active_days: commit_count,
             last_commit_date: Utc::now(),
             score,
+            activity: vec![ContributorActivity {
+                period: "2024-01".to_string(),
+                commits: commit_count,
+                lines_added,
+                lines_deleted,
+                active_days: commit_count,
+            }],
         }
     }
 
     #[test]
     fn test_contributor_stats_new_creates_default_instance() {
-        let stats = ContributorStats::new("Test Author".to_string());
+        let stats =
+            ContributorStats::new("Test Author".to_string(), "test@example.com".to_string());
         assert_eq!(stats.author, "Test Author", "author should match input");
         assert_eq!(stats.commit_count, 0, "commit_count should be 0");
         assert_eq!(stats.lines_added, 0, "lines_added should be 0");
@@ -688,8 +1067,9 @@ mod tests {
         ];
 
         // Redirect stdout to capture the output
-        let result =
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| rule.print_json(&stats)));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rule.print_json(&stats, None)
+        }));
 
         assert!(
             result.is_ok(),
@@ -705,8 +1085,9 @@ mod tests {
         )];
 
         // Redirect stdout to capture the output
-        let result =
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| rule.print_yaml(&stats)));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rule.print_yaml(&stats, None)
+        }));
 
         assert!(
             result.is_ok(),
@@ -733,7 +1114,7 @@ mod tests {
 
     #[test]
     fn test_contributor_stats_with_zero_values() {
-        let stats = ContributorStats::new("Zero Hero".to_string());
+        let stats = ContributorStats::new("Zero Hero".to_string(), "zero@example.com".to_string());
         assert_eq!(stats.commit_count, 0, "commit_count should be 0");
         assert_eq!(stats.lines_added, 0, "lines_added should be 0");
         assert_eq!(stats.lines_deleted, 0, "lines_deleted should be 0");
@@ -861,8 +1242,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: None,
             output_file: None,
@@ -889,6 +1272,214 @@ mod tests {
         );
     }
 
+    /// Commits `content` to `file.txt` on top of HEAD.
+    fn commit_file(
+        repo: &git2::Repository,
+        author: &git2::Signature,
+        committer: &git2::Signature,
+        message: &str,
+        content: &str,
+    ) {
+        let workdir = repo.workdir().expect("repository should have a workdir");
+        std::fs::write(workdir.join("file.txt"), content).expect("Failed to write test file");
+        let mut index = repo.index().expect("Failed to get index");
+        index
+            .add_path(std::path::Path::new("file.txt"))
+            .expect("Failed to add path");
+        index.write().expect("Failed to write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("Failed to write tree"))
+            .expect("Failed to find tree");
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), author, committer, message, &tree, &parents)
+            .expect("Failed to create commit");
+    }
+
+    #[test]
+    fn test_analyze_unifies_identities_by_mailmap_and_aliases() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+        let repo_path = temp_dir.path();
+        let repo = git2::Repository::init(repo_path).expect("Failed to initialize git repo");
+        std::fs::write(
+            repo_path.join(".mailmap"),
+            "Alice Smith <alice@example.com> <alice@old.example.com>\n",
+        )
+        .expect("Failed to write mailmap");
+
+        let authors = [
+            ("alice", "alice@old.example.com"),
+            ("Alice Smith", "alice@example.com"),
+            ("Ally", "ally@home.example.com"),
+            ("Sam", "sam@one.example.com"),
+            ("Sam", "sam@two.example.com"),
+        ];
+        for (idx, (name, email)) in authors.iter().enumerate() {
+            let sig = git2::Signature::now(name, email).expect("Failed to create signature");
+            commit_file(&repo, &sig, &sig, "change", &idx.to_string());
+        }
+
+        let args = ContributorReportArgs {
+            path: repo_path.to_path_buf(),
+            history: HistoryArgs::default(),
+            decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: [(
+                "alice@example.com".to_string(),
+                vec!["ally@home.example.com".to_string()],
+            )]
+            .into(),
+            output: ContributorReportOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let data = ContributorReportRule::new()
+            .analyze(&args)
+            .expect("analysis should succeed");
+
+        assert_eq!(
+            data.stats.len(),
+            3,
+            "Alice should be merged, the two Sams kept apart"
+        );
+        let alice = data
+            .stats
+            .iter()
+            .find(|s| s.email == "alice@example.com")
+            .expect("Alice should be reported");
+        assert_eq!(alice.commit_count, 3);
+        assert_eq!(alice.identities.len(), 3);
+        assert_eq!(data.stats.iter().filter(|s| s.author == "Sam").count(), 2);
+    }
+
+    #[test]
+    fn test_analyze_credits_co_authors_committers_and_periods() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+        let repo = git2::Repository::init(temp_dir.path()).expect("Failed to initialize git repo");
+        let at = |name: &str, email: &str, date: &str| {
+            let time = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp();
+            git2::Signature::new(name, email, &git2::Time::new(time, 0)).unwrap()
+        };
+
+        let alice = |date| at("Alice", "alice@example.com", date);
+        commit_file(
+            &repo,
+            &alice("2024-01-05"),
+            &alice("2024-01-05"),
+            "one",
+            "1",
+        );
+        commit_file(
+            &repo,
+            &alice("2024-01-05"),
+            &alice("2024-01-05"),
+            "pair\n\nCo-authored-by: Bob <bob@example.com>\nco-authored-by: Alice <alice@example.com>\n",
+            "2",
+        );
+        commit_file(
+            &repo,
+            &at("Bob", "bob@example.com", "2024-04-02"),
+            &at("GitHub", "noreply@github.com", "2024-04-02"),
+            "merged on the web",
+            "3",
+        );
+        let maintainer = at("Maintainer", "maint@example.com", "2024-04-03");
+        commit_file(&repo, &maintainer, &maintainer, "own", "4");
+        commit_file(
+            &repo,
+            &alice("2024-04-02"),
+            &at("Maint Bot", "maint@example.com", "2024-04-04"),
+            "applied",
+            "5",
+        );
+
+        let mut args = ContributorReportArgs {
+            path: temp_dir.path().to_path_buf(),
+            history: HistoryArgs::default(),
+            decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
+            output: ContributorReportOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        };
+        let data = ContributorReportRule::new().analyze(&args).unwrap();
+        let find = |data: &ContributorReportData, email: &str| {
+            data.stats
+                .iter()
+                .find(|s| s.email == email)
+                .cloned()
+                .expect("contributor should be reported")
+        };
+
+        let alice = find(&data, "alice@example.com");
+        assert_eq!(alice.commit_count, 3);
+        assert_eq!(
+            alice.co_authored_count, 0,
+            "listing yourself is not co-authoring"
+        );
+        assert_eq!(alice.active_days, 2);
+        assert_eq!(
+            alice
+                .activity
+                .iter()
+                .map(|b| (b.period.as_str(), b.commits, b.active_days))
+                .collect::<Vec<_>>(),
+            vec![("2024-01", 2, 1), ("2024-04", 1, 1)]
+        );
+
+        let bob = find(&data, "bob@example.com");
+        assert_eq!((bob.commit_count, bob.co_authored_count), (2, 1));
+
+        let maintainer = find(&data, "maint@example.com");
+        assert_eq!(
+            (maintainer.commit_count, maintainer.committed_count),
+            (1, 1)
+        );
+        assert_eq!(
+            maintainer.author, "Maintainer",
+            "committer names do not rename authors"
+        );
+        let web_flow = find(&data, "noreply@github.com");
+        assert_eq!(
+            (web_flow.commit_count, web_flow.committed_count),
+            (0, 1),
+            "committer-only identities are reported with their committed commits"
+        );
+        assert_eq!(web_flow.author, "GitHub");
+
+        args.period = ContributorReportPeriod::Quarter;
+        let data = ContributorReportRule::new().analyze(&args).unwrap();
+        let periods: Vec<String> = find(&data, "alice@example.com")
+            .activity
+            .into_iter()
+            .map(|b| b.period)
+            .collect();
+        assert_eq!(periods, vec!["2024-Q1", "2024-Q2"]);
+
+        let csv = ContributorReportRule::new()
+            .render_timeline_csv(&data.stats)
+            .unwrap();
+        assert!(
+            csv.starts_with("author,email,period,commits,lines_added,lines_deleted,active_days\n")
+        );
+        assert!(csv.contains("Bob,bob@example.com,2024-Q1,1,"));
+    }
+
+    #[test]
+    fn test_co_authors_parses_trailers() {
+        let message = "Fix bug\n\nCo-Authored-By: Jane Doe <jane@example.com>\nSigned-off-by: X <x@example.com>\nCo-authored-by: <nobody@example.com>\n";
+        assert_eq!(
+            co_authors(message),
+            vec![("Jane Doe".to_string(), "jane@example.com".to_string())]
+        );
+    }
+
     #[test]
     fn test_rule_trait_analyze_fails_with_non_git_repository() {
         let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
@@ -897,8 +1488,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: non_repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: None,
             output_file: None,
@@ -922,8 +1515,10 @@ mod tests {
         // Verify Config type is ContributorReportArgs
         let config = ContributorReportArgs {
             path: std::path::PathBuf::from("."),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: None,
             output_file: None,
@@ -1144,8 +1739,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
@@ -1192,8 +1789,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: Some(CiOutputFormat::JUnit),
             output_file: None,
@@ -1240,8 +1839,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
@@ -1289,8 +1890,10 @@ mod tests {
         let rule = ContributorReportRule::new();
         let args = ContributorReportArgs {
             path: repo_path.to_path_buf(),
-            since: None,
+            history: HistoryArgs::default(),
             decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
             output: ContributorReportOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: Some(output_file.clone()),
@@ -1305,4 +1908,43 @@ mod tests {
         );
         assert!(output_file.exists(), "output file should be created");
     }
+
+    #[test]
+    fn test_run_with_json_output_writes_output_file() {
+        let temp_dir = tempfile::TempDir::new().expect("Failed to create temp directory");
+        let repo_path = temp_dir.path();
+        let output_file = temp_dir.path().join("contributors.json");
+
+        let repo = git2::Repository::init(repo_path).expect("Failed to initialize git repo");
+        std::fs::write(repo_path.join("test.txt"), "test content")
+            .expect("Failed to write test file");
+        let mut index = repo.index().expect("Failed to get index");
+        index
+            .add_path(std::path::Path::new("test.txt"))
+            .expect("Failed to add path");
+        index.write().expect("Failed to write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("Failed to write tree"))
+            .expect("Failed to find tree");
+        let sig = git2::Signature::now("Test Author", "test@example.com")
+            .expect("Failed to create signature");
+        repo.commit(Some("HEAD"), &sig, &sig, "Test commit", &tree, &[])
+            .expect("Failed to create commit");
+
+        let args = ContributorReportArgs {
+            path: repo_path.to_path_buf(),
+            history: HistoryArgs::default(),
+            decay: 0.01,
+            period: ContributorReportPeriod::Month,
+            aliases: Default::default(),
+            output: ContributorReportOutputFormat::Json,
+            ci_output: None,
+            output_file: Some(output_file.clone()),
+        };
+        ContributorReportRule::new().run_impl(&args).unwrap();
+
+        let written = std::fs::read_to_string(&output_file).expect("output file should be created");
+        let stats: Vec<ContributorStats> = serde_json::from_str(&written).unwrap();
+        assert_eq!(stats[0].email, "test@example.com");
+    }
 }
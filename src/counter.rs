@@ -2,7 +2,8 @@
 //!
 //! This module provides [`StmtCounter`], a visitor that counts statement nodes
 //! in a Rust Abstract Syntax Tree (AST). It is used by the statement count rule
-//! to measure the size of code components.
+//! to measure the size of code components, and records per-item counts
+//! ([`ItemStmtCount`]) for functions, methods, closures and inline modules.
 //!
 //! # Example
 //!
@@ -13,15 +14,116 @@
 //! let mut counter = StmtCounter::new();
 //! counter.visit_file(&ast);  // ast: &syn::File
 //! // counter.count now contains the statement count
+//! // counter.items holds the count for each fn, method, closure and module
 //! ```
 //!
 //! The `visit_file` method requires the `Visit` trait from `syn` to be in scope.
+//!
+//! # Test code
+//!
+//! Statements inside `#[cfg(test)]` items and `#[test]` functions are also
+//! counted in [`StmtCounter::test_count`], and the items are marked with
+//! [`ItemStmtCount::is_test`]. Out-of-line `#[cfg(test)] mod tests;`
+//! declarations have no body to visit; their names are recorded in
+//! [`StmtCounter::test_modules`] so the caller can classify those files.
+//!
+//! # Macros
+//!
+//! Macro bodies are opaque token streams and are skipped by default. With
+//! [`StmtCounter::parse_macros`], a body is parsed as statements or as
+//! comma-separated expressions where possible, and its statements are
+//! counted like any other. Bodies that are not Rust, such as `macro_rules!`
+//! rules or HTML templates, are approximated by counting their braced blocks
+//! and semicolons. Statements found this way are also counted in
+//! [`StmtCounter::macro_count`].
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::{
+    Attribute, Block, Expr, ExprClosure, File as SynFile, ImplItemFn, Item, ItemFn, ItemImpl,
+    ItemMod, ItemTrait, Macro, Meta, Stmt, Token, TraitItemFn, Type, punctuated::Punctuated,
+    spanned::Spanned, visit::Visit,
+};
 
-use syn::{File as SynFile, Stmt, visit::Visit};
+/// The kind of item a statement count is attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemKind {
+    /// A free function.
+    Function,
+    /// A method in an `impl` block, or a trait method with a default body.
+    Method,
+    /// A closure expression.
+    Closure,
+    /// An inline `mod { ... }` item.
+    Module,
+}
+
+impl ItemKind {
+    /// Returns `true` for functions and methods, which are subject to the
+    /// per-function statement limit.
+    pub fn is_function(self) -> bool {
+        matches!(self, ItemKind::Function | ItemKind::Method)
+    }
+}
 
-/// A simple visitor that counts every `syn::Stmt` node in an AST.
+impl std::fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ItemKind::Function => "fn",
+            ItemKind::Method => "method",
+            ItemKind::Closure => "closure",
+            ItemKind::Module => "mod",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Statements attributed to a single item.
+///
+/// Functions, methods and closures own only the statements in their own
+/// body; statements in a nested closure or nested `fn` are attributed to that
+/// item instead. Modules own every statement inside them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStmtCount {
+    /// Path of the item within its file, e.g. `tests::helper` or `Foo::new`.
+    pub name: String,
+    /// What kind of item this is.
+    pub kind: ItemKind,
+    /// First line of the item, 1-based.
+    pub start_line: usize,
+    /// Last line of the item, 1-based.
+    pub end_line: usize,
+    /// Number of statements the item owns.
+    pub statements: usize,
+    /// Whether the item is test code.
+    pub is_test: bool,
+}
+
+/// A visitor that counts every `syn::Stmt` node in an AST, in total and per
+/// enclosing item.
 pub struct StmtCounter {
     pub count: usize,
+    /// Statements inside test code, also included in `count`.
+    pub test_count: usize,
+    /// Statements inside macro bodies, also included in `count`. Only
+    /// counted when macro parsing is enabled.
+    pub macro_count: usize,
+    /// Items in the order they were entered.
+    pub items: Vec<ItemStmtCount>,
+    /// Out-of-line `#[cfg(test)]` modules declared in the file, as
+    /// `/`-separated paths relative to the file's module, e.g. `tests` or
+    /// `inner/tests`.
+    pub test_modules: Vec<String>,
+    /// Names of the enclosing modules, impl and trait blocks.
+    scope: Vec<String>,
+    /// Indices into `items` of the items currently being visited.
+    open: Vec<usize>,
+    /// Number of enclosing test items.
+    test_depth: usize,
+    /// Whether macro bodies are counted.
+    parse_macros: bool,
+    /// Number of enclosing macro invocations.
+    macro_depth: usize,
 }
 
 impl Default for StmtCounter {
@@ -33,18 +135,291 @@ impl Default for StmtCounter {
 impl StmtCounter {
     /// Create a new, empty `StmtCounter`.
     pub fn new() -> Self {
-        StmtCounter { count: 0 }
+        StmtCounter {
+            count: 0,
+            test_count: 0,
+            macro_count: 0,
+            items: Vec::new(),
+            test_modules: Vec::new(),
+            scope: Vec::new(),
+            open: Vec::new(),
+            test_depth: 0,
+            parse_macros: false,
+            macro_depth: 0,
+        }
+    }
+
+    /// Enables or disables counting statements inside macro bodies.
+    pub fn parse_macros(mut self, enabled: bool) -> Self {
+        self.parse_macros = enabled;
+        self
+    }
+
+    /// Counts one statement and attributes it to the innermost function-like
+    /// item and every enclosing module.
+    fn record_stmt(&mut self) {
+        self.count += 1;
+        if self.test_depth > 0 {
+            self.test_count += 1;
+        }
+        if self.macro_depth > 0 {
+            self.macro_count += 1;
+        }
+        let mut attributed = false;
+        for &index in self.open.iter().rev() {
+            let item = &mut self.items[index];
+            if item.kind == ItemKind::Module {
+                item.statements += 1;
+            } else if !attributed {
+                item.statements += 1;
+                attributed = true;
+            }
+        }
+    }
+
+    /// Approximates the statements in a token stream that is not Rust code:
+    /// every braced block and every semicolon counts as one.
+    fn count_token_statements(&mut self, tokens: TokenStream) {
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    if group.delimiter() == Delimiter::Brace {
+                        self.record_stmt();
+                    }
+                    self.count_token_statements(group.stream());
+                }
+                TokenTree::Punct(punct) if punct.as_char() == ';' => self.record_stmt(),
+                _ => {}
+            }
+        }
+    }
+
+    fn qualified(&self, name: &str) -> String {
+        if self.scope.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}::{}", self.scope.join("::"), name)
+        }
+    }
+
+    /// Records a new item and makes it the innermost open item.
+    fn enter(&mut self, name: String, kind: ItemKind, span: proc_macro2::Span) {
+        self.open.push(self.items.len());
+        self.items.push(ItemStmtCount {
+            name,
+            kind,
+            start_line: span.start().line,
+            end_line: span.end().line,
+            statements: 0,
+            is_test: self.test_depth > 0,
+        });
+    }
+
+    /// Visits an item with `attrs`, treating it as test code if any
+    /// attribute marks it as such.
+    fn with_attrs(&mut self, attrs: &[Attribute], visit: impl FnOnce(&mut Self)) {
+        let is_test = attrs.iter().any(is_test_attr);
+        self.test_depth += usize::from(is_test);
+        visit(self);
+        self.test_depth -= usize::from(is_test);
+    }
+
+    fn leave(&mut self) {
+        self.open.pop();
+    }
+
+    /// Name of the innermost open function-like item, used to name closures.
+    fn enclosing_function(&self) -> Option<&str> {
+        self.open
+            .iter()
+            .rev()
+            .map(|&index| &self.items[index])
+            .find(|item| item.kind != ItemKind::Module)
+            .map(|item| item.name.as_str())
+    }
+}
+
+/// Returns the last path segment of a type, e.g. `Foo` for `crate::Foo<T>`.
+pub(crate) fn type_name(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        Type::Reference(reference) => type_name(&reference.elem),
+        _ => "_".to_string(),
+    }
+}
+
+/// Returns `true` for `#[test]`-like attributes (`#[test]`, `#[tokio::test]`,
+/// `#[bench]`) and for `#[cfg(..)]` predicates that only hold under `test`.
+pub(crate) fn is_test_attr(attr: &Attribute) -> bool {
+    let path = attr.path();
+    if path.is_ident("cfg") {
+        return attr
+            .parse_args::<Meta>()
+            .is_ok_and(|meta| cfg_requires_test(&meta));
+    }
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "test" || segment.ident == "bench")
+}
+
+/// Returns the outer attributes of `item`.
+pub(crate) fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Const(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::ExternCrate(item) => &item.attrs,
+        Item::Fn(item) => &item.attrs,
+        Item::ForeignMod(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::Static(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::TraitAlias(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Returns `true` if the cfg predicate can only be true when `test` is set.
+fn cfg_requires_test(meta: &Meta) -> bool {
+    match meta {
+        Meta::Path(path) => path.is_ident("test"),
+        Meta::List(list) => {
+            let Ok(nested) = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            else {
+                return false;
+            };
+            if list.path.is_ident("all") {
+                nested.iter().any(cfg_requires_test)
+            } else if list.path.is_ident("any") {
+                !nested.is_empty() && nested.iter().all(cfg_requires_test)
+            } else {
+                false
+            }
+        }
+        Meta::NameValue(_) => false,
     }
 }
 
 impl<'ast> Visit<'ast> for StmtCounter {
     /// Called for each `Stmt` in the AST.
-    /// We increment `count` and continue walking nested statements.
+    /// We increment `count`, attribute the statement to the innermost
+    /// function-like item and every enclosing module, and continue walking
+    /// nested statements.
     fn visit_stmt(&mut self, node: &'ast Stmt) {
-        self.count += 1;
+        self.record_stmt();
         syn::visit::visit_stmt(self, node);
     }
 
+    /// With macro parsing enabled, counts the statements in the macro body:
+    /// first as a list of statements, then as comma-separated expressions,
+    /// and otherwise by approximation.
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        if !self.parse_macros {
+            return syn::visit::visit_macro(self, node);
+        }
+        self.macro_depth += 1;
+        if let Ok(stmts) = node.parse_body_with(Block::parse_within) {
+            for stmt in &stmts {
+                self.visit_stmt(stmt);
+            }
+        } else if let Ok(exprs) =
+            node.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            for expr in &exprs {
+                self.visit_expr(expr);
+            }
+        } else {
+            self.count_token_statements(node.tokens.clone());
+        }
+        self.macro_depth -= 1;
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.with_attrs(&node.attrs, |this| {
+            let name = this.qualified(&node.sig.ident.to_string());
+            this.enter(name, ItemKind::Function, node.span());
+            syn::visit::visit_item_fn(this, node);
+            this.leave();
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.with_attrs(&node.attrs, |this| {
+            let name = this.qualified(&node.sig.ident.to_string());
+            this.enter(name, ItemKind::Method, node.span());
+            syn::visit::visit_impl_item_fn(this, node);
+            this.leave();
+        });
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        if node.default.is_none() {
+            return syn::visit::visit_trait_item_fn(self, node);
+        }
+        self.with_attrs(&node.attrs, |this| {
+            let name = this.qualified(&node.sig.ident.to_string());
+            this.enter(name, ItemKind::Method, node.span());
+            syn::visit::visit_trait_item_fn(this, node);
+            this.leave();
+        });
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast ExprClosure) {
+        let name = match self.enclosing_function() {
+            Some(parent) => format!("{parent}::{{closure}}"),
+            None => self.qualified("{closure}"),
+        };
+        self.enter(name, ItemKind::Closure, node.span());
+        syn::visit::visit_expr_closure(self, node);
+        self.leave();
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        // `mod foo;` declarations have no body; their file is counted on its own
+        if node.content.is_none() {
+            if self.test_depth > 0 || node.attrs.iter().any(is_test_attr) {
+                let mut path = self.scope.clone();
+                path.push(node.ident.to_string());
+                self.test_modules.push(path.join("/"));
+            }
+            return;
+        }
+        self.with_attrs(&node.attrs, |this| {
+            let name = this.qualified(&node.ident.to_string());
+            this.enter(name, ItemKind::Module, node.span());
+            this.scope.push(node.ident.to_string());
+            syn::visit::visit_item_mod(this, node);
+            this.scope.pop();
+            this.leave();
+        });
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        self.with_attrs(&node.attrs, |this| {
+            this.scope.push(type_name(&node.self_ty));
+            syn::visit::visit_item_impl(this, node);
+            this.scope.pop();
+        });
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        self.with_attrs(&node.attrs, |this| {
+            this.scope.push(node.ident.to_string());
+            syn::visit::visit_item_trait(this, node);
+            this.scope.pop();
+        });
+    }
+
     // We need to implement visit_file to allow StmtCounter to be used with visit_file(&ast)
     fn visit_file(&mut self, node: &'ast SynFile) {
         syn::visit::visit_file(self, node);
@@ -325,4 +700,185 @@ mod tests {
             "Counter should count let statement; static at item level is also counted as a statement by syn"
         );
     }
+
+    #[test]
+    fn test_counter_attributes_statements_to_items() {
+        let code = r#"
+struct Foo;
+
+impl Foo {
+    fn new() -> Self {
+        let x = 1;
+        Foo
+    }
+}
+
+fn outer() {
+    let a = 1;
+    let f = |x: i32| {
+        let y = x + 1;
+        y
+    };
+    f(a);
+}
+
+mod inner {
+    fn helper() {
+        let z = 3;
+    }
+}
+"#;
+        let ast: SynFile = syn::parse_file(code).expect("Failed to parse test code");
+        let mut counter = StmtCounter::new();
+        counter.visit_file(&ast);
+        assert_eq!(counter.count, 8);
+
+        let item = |name: &str| {
+            counter
+                .items
+                .iter()
+                .find(|item| item.name == name)
+                .unwrap_or_else(|| panic!("missing item {name}"))
+        };
+
+        let new = item("Foo::new");
+        assert_eq!(new.kind, ItemKind::Method);
+        assert_eq!(new.statements, 2);
+        assert_eq!((new.start_line, new.end_line), (5, 8));
+
+        // The closure's statements are not counted against `outer`
+        assert_eq!(item("outer").statements, 3);
+        let closure = item("outer::{closure}");
+        assert_eq!(closure.kind, ItemKind::Closure);
+        assert_eq!(closure.statements, 2);
+        assert_eq!(closure.start_line, 13);
+
+        let module = item("inner");
+        assert_eq!(module.kind, ItemKind::Module);
+        assert_eq!(module.statements, 1);
+        assert_eq!(item("inner::helper").statements, 1);
+    }
+
+    #[test]
+    fn test_counter_records_trait_default_methods_only() {
+        let code = r#"
+            trait Greet {
+                fn name(&self) -> String;
+                fn greet(&self) {
+                    println!("hi {}", self.name());
+                }
+            }
+        "#;
+        let ast: SynFile = syn::parse_file(code).expect("Failed to parse test code");
+        let mut counter = StmtCounter::new();
+        counter.visit_file(&ast);
+        let names: Vec<_> = counter
+            .items
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Greet::greet"]);
+        assert!(counter.items[0].kind.is_function());
+    }
+
+    #[test]
+    fn test_counter_separates_test_code() {
+        let code = r#"
+fn production() {
+    let a = 1;
+    let b = 2;
+}
+
+#[cfg(not(test))]
+fn also_production() {
+    let c = 3;
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper() {
+        let d = 4;
+    }
+
+    #[test]
+    fn it_works() {
+        let e = 5;
+    }
+}
+
+#[tokio::test]
+async fn integration() {
+    let f = 6;
+}
+
+#[cfg(all(test, feature = "slow"))]
+mod slow_tests;
+#[cfg(any(test, feature = "testing"))]
+mod fixtures;
+"#;
+        let ast: SynFile = syn::parse_file(code).expect("Failed to parse test code");
+        let mut counter = StmtCounter::new();
+        counter.visit_file(&ast);
+        assert_eq!(counter.count, 6);
+        assert_eq!(counter.test_count, 3);
+        assert_eq!(counter.test_modules, vec!["slow_tests"]);
+
+        let is_test = |name: &str| {
+            counter
+                .items
+                .iter()
+                .find(|item| item.name == name)
+                .unwrap_or_else(|| panic!("missing item {name}"))
+                .is_test
+        };
+        assert!(!is_test("production"));
+        assert!(!is_test("also_production"));
+        assert!(is_test("tests"));
+        assert!(is_test("tests::helper"));
+        assert!(is_test("integration"));
+    }
+
+    #[test]
+    fn test_counter_parses_macro_bodies_when_enabled() {
+        let code = r#"
+macro_rules! square {
+    ($x:expr) => {{
+        let value = $x;
+        value * value
+    }};
+}
+
+fn render() {
+    let items = vec![1, 2, 3];
+    let page = html! {
+        ul {
+            li { "one" }
+            li { "two" }
+        }
+    };
+    run! {
+        let a = 1;
+        let b = 2;
+    }
+}
+"#;
+        let ast: SynFile = syn::parse_file(code).expect("Failed to parse test code");
+        let mut plain = StmtCounter::new();
+        plain.visit_file(&ast);
+        assert_eq!(plain.count, 3);
+        assert_eq!(plain.macro_count, 0);
+
+        let mut counter = StmtCounter::new().parse_macros(true);
+        counter.visit_file(&ast);
+        // macro_rules!: two braced blocks and two semicolons; html!: three
+        // blocks; run!: two statements
+        assert_eq!(counter.macro_count, 9);
+        assert_eq!(counter.count, 12);
+        let render = counter
+            .items
+            .iter()
+            .find(|item| item.name == "render")
+            .expect("missing item render");
+        assert_eq!(render.statements, 8);
+    }
 }
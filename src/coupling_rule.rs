@@ -37,6 +37,7 @@
 //!     ci_output: None,
 //!     output_file: None,
 //!     staged: false,
+//!     component_definitions: Default::default(),
 //! };
 //!
 //! if let Err(e) = rule.run(&args) {
@@ -83,7 +84,10 @@
 //! - Git operations fail for repository-level analysis
 
 use crate::ci_report::{Finding, Severity, ToFindings};
-use crate::cli::{CiOutputFormat, CouplingArgs, CouplingGranularity, CouplingOutputFormat};
+use crate::cli::{
+    CiOutputFormat, ComponentMode, CouplingArgs, CouplingGranularity, CouplingOutputFormat,
+};
+use crate::components::ComponentResolver;
 use crate::error::{RaffError, Result};
 use crate::html_utils;
 use crate::rule::Rule;
@@ -91,7 +95,7 @@ use crate::table_utils::get_default_table_format;
 use maud::{Markup, html};
 use prettytable::{Cell, Row, Table};
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -159,9 +163,24 @@ pub struct ModuleCoupling {
     pub module_dependencies: HashSet<String>,
 }
 
+/// Coupling between user-defined components, aggregated from the crates
+/// they contain. Crates outside every defined component form a component of
+/// their own.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ComponentCoupling {
+    pub name: String,
+    pub crates: Vec<String>,
+    pub ce: usize,
+    pub ca: usize,
+    pub dependencies: BTreeSet<String>,
+}
+
 #[derive(Serialize, Debug, Default)]
 pub struct CouplingData {
     pub crates: Vec<CrateCoupling>,
+    /// Component-level coupling, present when `[components.define]` is set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentCoupling>,
     pub granularity: CouplingGranularity,
     pub analysis_path: PathBuf,
 }
@@ -188,6 +207,7 @@ impl ToFindings for CouplingData {
                             crate_data.name, instability, crate_data.ce, crate_data.ca
                         ),
                         location: None, // Coupling is crate-level, no specific file location
+                        related_locations: Vec::new(),
                         help_uri: Some(
                             "https://github.com/liamwh/raff/docs/coupling".to_string(),
                         ),
@@ -204,6 +224,51 @@ impl ToFindings for CouplingData {
     }
 }
 
+/// Aggregates crate coupling into component coupling. `crate_components`
+/// maps a crate name to its component; dependencies between crates of the
+/// same component are internal and not counted.
+pub(crate) fn aggregate_component_coupling(
+    crates: &[CrateCoupling],
+    crate_components: &HashMap<String, String>,
+) -> Vec<ComponentCoupling> {
+    let component_of = |name: &String| crate_components.get(name).unwrap_or(name).clone();
+    let mut components: BTreeMap<String, ComponentCoupling> = BTreeMap::new();
+    for krate in crates {
+        let name = component_of(&krate.name);
+        let component = components
+            .entry(name.clone())
+            .or_insert_with(|| ComponentCoupling {
+                name: name.clone(),
+                ..Default::default()
+            });
+        component.crates.push(krate.name.clone());
+        component.dependencies.extend(
+            krate
+                .dependencies
+                .iter()
+                .map(component_of)
+                .filter(|dependency| *dependency != name),
+        );
+    }
+    let mut afferent: HashMap<String, usize> = HashMap::new();
+    for component in components.values() {
+        for dependency in &component.dependencies {
+            *afferent.entry(dependency.clone()).or_insert(0) += 1;
+        }
+    }
+    let mut result: Vec<ComponentCoupling> = components
+        .into_values()
+        .map(|mut component| {
+            component.crates.sort();
+            component.ce = component.dependencies.len();
+            component.ca = afferent.get(&component.name).copied().unwrap_or(0);
+            component
+        })
+        .collect();
+    result.sort_by_key(|item| std::cmp::Reverse(item.ce + item.ca));
+    result
+}
+
 pub struct CouplingRule;
 
 impl Rule for CouplingRule {
@@ -318,6 +383,7 @@ impl CouplingRule {
 
         let mut full_report = CouplingData {
             crates: Vec::new(),
+            components: Vec::new(),
             granularity: args.granularity.clone(),
             analysis_path: args.path.clone(),
         };
@@ -377,9 +443,46 @@ impl CouplingRule {
             .crates
             .sort_by_key(|item| std::cmp::Reverse(item.ce + item.ca));
 
+        if !args.component_definitions.is_empty() {
+            let crate_components = self.map_crates_to_components(args, &workspace_packages_map)?;
+            full_report.components = aggregate_component_coupling(
+                &crate_couplings_map.into_values().collect::<Vec<_>>(),
+                &crate_components,
+            );
+        }
+
         Ok(full_report)
     }
 
+    /// Maps each workspace crate to the defined component containing its
+    /// root directory, or to itself when no component claims it.
+    fn map_crates_to_components(
+        &self,
+        args: &CouplingArgs,
+        workspace_packages_map: &HashMap<String, Package>,
+    ) -> Result<HashMap<String, String>> {
+        let analysis_path = args.path.canonicalize()?;
+        let components = ComponentResolver::new(
+            &analysis_path,
+            ComponentMode::Crate,
+            &args.component_definitions,
+        )?;
+        let mut crate_components = HashMap::new();
+        for pkg in workspace_packages_map.values() {
+            let manifest_path = PathBuf::from(&pkg.manifest_path);
+            let crate_root = manifest_path
+                .parent()
+                .and_then(|root| root.strip_prefix(&analysis_path).ok())
+                .unwrap_or(Path::new(""));
+            let component = components
+                .defined_component(crate_root)
+                .unwrap_or(&pkg.name)
+                .to_string();
+            crate_components.insert(pkg.name.clone(), component);
+        }
+        Ok(crate_components)
+    }
+
     /// Public wrapper that delegates to the Rule trait's run method
     pub fn run(&self, args: &CouplingArgs) -> Result<()> {
         self.run_impl(args)
@@ -677,6 +780,28 @@ impl CouplingRule {
             crate_table.printstd();
         }
 
+        if !report.components.is_empty() {
+            println!();
+            println!("[Component level]");
+            let mut component_table = Table::new();
+            component_table.set_format(get_default_table_format());
+            component_table.set_titles(Row::new(vec![
+                Cell::new("Component"),
+                Cell::new("Crates"),
+                Cell::new("Ce (Efferent)"),
+                Cell::new("Ca (Afferent)"),
+            ]));
+            for component in &report.components {
+                component_table.add_row(Row::new(vec![
+                    Cell::new(&component.name),
+                    Cell::new(&component.crates.join(", ")),
+                    Cell::new(&component.ce.to_string()),
+                    Cell::new(&component.ca.to_string()),
+                ]));
+            }
+            component_table.printstd();
+        }
+
         if matches!(
             granularity,
             CouplingGranularity::Module | CouplingGranularity::Both
@@ -793,6 +918,30 @@ impl CouplingRule {
                 }
             }
 
+            @if !report.components.is_empty() {
+                h2 { "Component Level Coupling" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Crates" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Ce" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Ca" }
+                        }
+                    }
+                    tbody {
+                        @for component in &report.components {
+                            tr {
+                                td { (component.name) }
+                                td { (component.crates.join(", ")) }
+                                td { (component.ce) }
+                                td { (component.ca) }
+                            }
+                        }
+                    }
+                }
+            }
+
             @if matches!(granularity, CouplingGranularity::Module | CouplingGranularity::Both) {
                 h2 { "Module Level Coupling" }
                 @for krate in &report.crates {
@@ -1394,10 +1543,46 @@ mod tests {
         assert_eq!(coupling.module_dependencies.len(), 1);
     }
 
+    #[test]
+    fn test_aggregate_component_coupling_merges_crates_and_drops_internal_edges() {
+        let krate = |name: &str, dependencies: &[&str]| CrateCoupling {
+            name: name.to_string(),
+            ce: dependencies.len(),
+            ca: 0,
+            modules: Vec::new(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        };
+        let crates = vec![
+            krate("billing-api", &["billing-core", "shared"]),
+            krate("billing-core", &["shared"]),
+            krate("app", &["billing-api", "shared"]),
+            krate("shared", &[]),
+        ];
+        let crate_components = HashMap::from([
+            ("billing-api".to_string(), "billing".to_string()),
+            ("billing-core".to_string(), "billing".to_string()),
+        ]);
+
+        let components = aggregate_component_coupling(&crates, &crate_components);
+        let find = |name: &str| components.iter().find(|c| c.name == name).unwrap();
+
+        let billing = find("billing");
+        assert_eq!(billing.crates, vec!["billing-api", "billing-core"]);
+        assert_eq!(billing.ce, 1);
+        assert_eq!(billing.ca, 1);
+        assert!(billing.dependencies.contains("shared"));
+
+        let shared = find("shared");
+        assert_eq!(shared.ce, 0);
+        assert_eq!(shared.ca, 2);
+        assert_eq!(find("app").ce, 2);
+    }
+
     #[test]
     fn test_coupling_data_new() {
         let data = CouplingData {
             crates: Vec::new(),
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1458,6 +1643,7 @@ mod tests {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1508,6 +1694,7 @@ mod tests {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Module,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1577,6 +1764,7 @@ mod tests {
                     deps
                 },
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1610,6 +1798,7 @@ mod tests {
                 }],
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Module,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1635,6 +1824,7 @@ mod tests {
                 }],
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Both,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1651,6 +1841,7 @@ mod tests {
         let rule = CouplingRule::new();
         let report = CouplingData {
             crates: Vec::new(),
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1674,6 +1865,7 @@ mod tests {
                     deps
                 },
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1690,6 +1882,7 @@ mod tests {
         let rule = CouplingRule::new();
         let report = CouplingData {
             crates: Vec::new(),
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1720,6 +1913,7 @@ mod tests {
                 }],
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Module,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1741,6 +1935,7 @@ mod tests {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Module,
             analysis_path: PathBuf::from("/test/path"),
         };
@@ -1836,6 +2031,7 @@ mod tests {
                 }],
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Both,
             analysis_path: PathBuf::from("/test"),
         };
@@ -1913,6 +2109,7 @@ fn main() {
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         // Call the Rule trait's run method
@@ -1960,6 +2157,7 @@ fn main() {
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         // Call the Rule trait's analyze method
@@ -1995,6 +2193,7 @@ fn main() {
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         // Call the Rule trait's analyze method
@@ -2020,6 +2219,7 @@ fn main() {
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         // Verify Data type is CouplingData
@@ -2043,6 +2243,7 @@ fn main() {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2069,6 +2270,7 @@ fn main() {
                     deps
                 },
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2112,6 +2314,7 @@ fn main() {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2136,6 +2339,7 @@ fn main() {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2173,6 +2377,7 @@ fn main() {
                     dependencies: HashSet::new(),
                 },
             ],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2200,6 +2405,7 @@ fn main() {
                 modules: Vec::new(),
                 dependencies: HashSet::new(),
             }],
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2216,6 +2422,7 @@ fn main() {
     fn test_to_findings_empty_crates() {
         let data = CouplingData {
             crates: Vec::new(),
+            components: Vec::new(),
             granularity: CouplingGranularity::Crate,
             analysis_path: PathBuf::from("/test"),
         };
@@ -2263,6 +2470,7 @@ fn main() {
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let result = rule.run(&args);
@@ -2305,6 +2513,7 @@ fn main() {
             ci_output: Some(CiOutputFormat::JUnit),
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let result = rule.run(&args);
@@ -2347,6 +2556,7 @@ fn main() {
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         let result = rule.run(&args);
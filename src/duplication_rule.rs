@@ -0,0 +1,1253 @@
+//! Duplication Rule
+//!
+//! This module provides the duplication rule, which finds code fragments that
+//! occur more than once in the workspace.
+//!
+//! # Overview
+//!
+//! Every `.rs` file is lexed with `proc_macro2` into a normalized token
+//! sequence, in which identifiers and literals are abstracted while keywords,
+//! punctuation and delimiters are kept. Any run of at least `--min-tokens`
+//! normalized tokens that occurs more than once is reported as a clone group,
+//! extended to the longest run all of its copies share:
+//!
+//! - **Exact** (type-1): the copies differ only in whitespace and comments
+//! - **Renamed** (type-2): the copies also differ in identifiers or literals
+//!
+//! Besides the clone groups, the report shows the share of each component's
+//! code lines that belong to a clone, and the clones whose copies live in
+//! different crates. Cross-crate duplication often signals a missing shared
+//! abstraction.
+//!
+//! Attributes, doc comments and `use` declarations are ignored, as is test
+//! code (`#[cfg(test)]` modules, `#[test]` functions and the `tests`,
+//! `benches` and `examples` directories) unless `--include-tests` is set.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::duplication_rule::DuplicationRule;
+//! use raff_core::{ComponentMode, DuplicationArgs, DuplicationOutputFormat};
+//! use std::path::PathBuf;
+//!
+//! let rule = DuplicationRule::new();
+//! let args = DuplicationArgs {
+//!     path: PathBuf::from("."),
+//!     min_tokens: 50,
+//!     include_tests: false,
+//!     components: ComponentMode::Crate,
+//!     component_definitions: Default::default(),
+//!     output: DuplicationOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path does not exist or is not a directory
+//! - No `.rs` files are found
+//! - A file cannot be read or is not valid Rust
+//! - `--min-tokens` is zero
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::Item;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use crate::cache::hash_bytes;
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{CiOutputFormat, ComponentMode, DuplicationArgs, DuplicationOutputFormat};
+use crate::components::ComponentResolver;
+use crate::counter::{is_test_attr, item_attrs};
+use crate::error::{RaffError, Result};
+use crate::html_utils::{self, MetricRanges};
+use crate::output_utils::percentage;
+use crate::rule::Rule;
+use crate::statement_count_rule::{child_module_dir, is_test_target};
+
+/// Keywords kept as they are in the normalized token sequence. Every other
+/// identifier is abstracted.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "type", "unsafe", "use",
+    "where", "while",
+];
+
+/// Normalized token standing in for any identifier or lifetime name.
+const IDENT: &str = "$id";
+
+/// Normalized token standing in for any literal, including `true` and `false`.
+const LITERAL: &str = "$lit";
+
+/// Base of the rolling hash over token windows.
+const HASH_BASE: u64 = 1_000_003;
+
+/// How the copies in a clone group differ from each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneKind {
+    /// The copies are identical apart from whitespace and comments (type-1).
+    Exact,
+    /// The copies differ in identifiers or literals (type-2).
+    Renamed,
+}
+
+impl std::fmt::Display for CloneKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exact => write!(f, "exact"),
+            Self::Renamed => write!(f, "renamed"),
+        }
+    }
+}
+
+/// One copy of a duplicated fragment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneOccurrence {
+    /// Path of the file relative to the analysis path.
+    pub path: PathBuf,
+    /// First line of the copy (1-indexed).
+    pub start_line: usize,
+    /// Last line of the copy (1-indexed).
+    pub end_line: usize,
+    /// Crate containing the file.
+    pub crate_name: String,
+    /// Component containing the file.
+    pub component: String,
+}
+
+/// A fragment that occurs more than once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneGroup {
+    /// Length of the fragment in normalized tokens.
+    pub tokens: usize,
+    /// Number of lines spanned by the longest copy.
+    pub lines: usize,
+    /// Whether the copies are exact or renamed.
+    pub kind: CloneKind,
+    /// The crates containing a copy, sorted.
+    pub crates: Vec<String>,
+    /// Every copy of the fragment, sorted by path and line.
+    pub occurrences: Vec<CloneOccurrence>,
+    /// Hash of the normalized token sequence, stable across runs.
+    pub fingerprint: String,
+}
+
+impl CloneGroup {
+    /// Returns `true` if the copies live in more than one crate.
+    #[must_use]
+    pub fn is_cross_crate(&self) -> bool {
+        self.crates.len() > 1
+    }
+}
+
+/// Duplication within a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentDuplication {
+    /// Name of the component.
+    pub name: String,
+    /// Number of analyzed files in the component.
+    pub files: usize,
+    /// Number of lines holding at least one analyzed token.
+    pub code_lines: usize,
+    /// Number of code lines that are part of a clone.
+    pub duplicated_lines: usize,
+    /// Percentage of code lines that are part of a clone.
+    pub duplicated_percentage: f64,
+    /// Number of clone groups with a copy in the component.
+    pub clone_groups: usize,
+}
+
+/// Clones shared between two crates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CratePairDuplication {
+    /// The crate that sorts first.
+    pub first: String,
+    /// The crate that sorts second.
+    pub second: String,
+    /// Number of clone groups with copies in both crates.
+    pub clone_groups: usize,
+    /// Total length in normalized tokens of those clone groups.
+    pub duplicated_tokens: usize,
+}
+
+/// Data type for duplication analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicationData {
+    /// Clone groups, longest first.
+    pub groups: Vec<CloneGroup>,
+    /// Per-component duplication, with the highest percentage first.
+    pub components: Vec<ComponentDuplication>,
+    /// Crate pairs sharing clones, with the most clone groups first.
+    pub cross_crate: Vec<CratePairDuplication>,
+    /// Number of analyzed files.
+    pub files: usize,
+    /// Number of code lines across all analyzed files.
+    pub code_lines: usize,
+    /// Number of code lines that are part of a clone.
+    pub duplicated_lines: usize,
+    /// Percentage of code lines that are part of a clone.
+    pub duplicated_percentage: f64,
+    /// Minimum clone length in normalized tokens.
+    pub min_tokens: usize,
+    /// The analyzed directory.
+    pub analysis_path: PathBuf,
+}
+
+impl ToFindings for DuplicationData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.groups
+            .iter()
+            .map(|group| {
+                let mut locations = group.occurrences.iter().map(|occurrence| {
+                    Location::with_lines(
+                        occurrence_uri(occurrence),
+                        occurrence.start_line,
+                        occurrence.end_line,
+                    )
+                });
+                let location = locations.next();
+                let copies: Vec<String> = group
+                    .occurrences
+                    .iter()
+                    .map(|occurrence| {
+                        format!(
+                            "{}:{}-{}",
+                            occurrence_uri(occurrence),
+                            occurrence.start_line,
+                            occurrence.end_line
+                        )
+                    })
+                    .collect();
+                let mut message = format!(
+                    "A fragment of {} tokens ({} lines) occurs {} times ({} clone): {}",
+                    group.tokens,
+                    group.lines,
+                    group.occurrences.len(),
+                    group.kind,
+                    copies.join(", ")
+                );
+                if group.is_cross_crate() {
+                    message.push_str(&format!(
+                        ". It is duplicated across crates {}, which suggests a missing shared abstraction",
+                        group.crates.join(", ")
+                    ));
+                }
+                Finding {
+                    rule_id: "duplication".to_string(),
+                    rule_name: "Duplication Rule".to_string(),
+                    severity: Severity::Warning,
+                    message,
+                    location,
+                    related_locations: locations.collect(),
+                    help_uri: Some("https://github.com/liamwh/raff/docs/duplication".to_string()),
+                    fingerprint: Some(format!("duplication:{}", group.fingerprint)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rule to find duplicated code fragments across a workspace.
+#[derive(Debug, Default)]
+pub struct DuplicationRule;
+
+impl Rule for DuplicationRule {
+    type Config = DuplicationArgs;
+    type Data = DuplicationData;
+
+    fn name() -> &'static str {
+        "duplication"
+    }
+
+    fn description() -> &'static str {
+        "Finds duplicated code fragments, per component and across crates"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+impl DuplicationRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &DuplicationArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &DuplicationArgs) -> Result<DuplicationData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &DuplicationArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "duplication")?,
+            };
+            // Duplication findings are warnings, which don't fail CI
+            return crate::output_utils::write_output(&output, args.output_file.as_deref());
+        }
+
+        let output = match args.output {
+            DuplicationOutputFormat::Table => {
+                self.print_table(&data);
+                return Ok(());
+            }
+            DuplicationOutputFormat::Json => serde_json::to_string_pretty(&data)?,
+            DuplicationOutputFormat::Yaml => serde_yaml::to_string(&data)?,
+            DuplicationOutputFormat::Html => html_utils::render_html_doc(
+                &format!("Duplication Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            ),
+        };
+        crate::output_utils::write_output(&output, args.output_file.as_deref())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &DuplicationArgs) -> Result<DuplicationData> {
+        let analysis_path = &args.path;
+        if args.min_tokens == 0 {
+            return Err(RaffError::invalid_input_with_arg(
+                "Minimum clone length must be at least one token",
+                "min_tokens",
+            ));
+        }
+        if !analysis_path.exists() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Path not found",
+                analysis_path.display().to_string(),
+            ));
+        }
+        if !analysis_path.is_dir() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Provided path is not a directory",
+                analysis_path.display().to_string(),
+            ));
+        }
+
+        let mut all_rs_files: Vec<PathBuf> = Vec::new();
+        crate::file_utils::collect_rs_files(analysis_path, false, &mut all_rs_files, None)?;
+        if all_rs_files.is_empty() {
+            return Err(RaffError::analysis_error(
+                "duplication",
+                format!("No `.rs` files found under {}", analysis_path.display()),
+            ));
+        }
+        all_rs_files.sort();
+
+        let mut interner = Interner::default();
+        let mut files = Vec::new();
+        let mut test_module_paths = Vec::new();
+        for path_buf in &all_rs_files {
+            let path = path_buf
+                .strip_prefix(analysis_path)
+                .unwrap_or(path_buf)
+                .to_path_buf();
+            if !args.include_tests && is_test_target(analysis_path, &path) {
+                continue;
+            }
+            let content = fs::read_to_string(path_buf).map_err(|e| {
+                RaffError::io_error_with_source("read source file", path_buf.clone(), e)
+            })?;
+            let (file, test_modules) =
+                SourceFile::lex(path, &content, args.include_tests, &mut interner)?;
+            let module_dir = child_module_dir(&file.path);
+            test_module_paths.extend(test_modules.iter().map(|module| module_dir.join(module)));
+            files.push(file);
+        }
+        // Files declared as `#[cfg(test)] mod name;` are test code in full
+        files.retain(|file| {
+            !test_module_paths.iter().any(|module| {
+                file.path.starts_with(module) || file.path == module.with_extension("rs")
+            })
+        });
+        tracing::info!(
+            files = files.len(),
+            "Searching for clones of at least {} tokens",
+            args.min_tokens
+        );
+
+        let clones = find_clones(&files, args.min_tokens);
+        let components =
+            ComponentResolver::new(analysis_path, args.components, &args.component_definitions)?;
+        let crates = ComponentResolver::new(analysis_path, ComponentMode::Crate, &BTreeMap::new())?;
+        Ok(summarize(
+            &files,
+            &clones,
+            &interner,
+            &components,
+            &crates,
+            args.min_tokens,
+            analysis_path.clone(),
+        ))
+    }
+
+    fn print_table(&self, data: &DuplicationData) {
+        println!("\nDuplication Report Interpretation:");
+        println!("-----------------------------------");
+        println!(
+            "- Clone: A fragment of at least {} tokens that occurs more than once, after identifiers and literals are abstracted.",
+            data.min_tokens
+        );
+        println!(
+            "- Kind: 'exact' copies match token for token, 'renamed' copies differ in identifiers or literals."
+        );
+        println!("- Duplicated %: Share of a component's code lines that are part of a clone.");
+        println!("- Cross-crate clones often signal a missing shared abstraction.");
+        println!("-----------------------------------");
+        println!(
+            "\n{} of {} code lines ({:.1}%) in {} files are duplicated, in {} clone groups.",
+            data.duplicated_lines,
+            data.code_lines,
+            data.duplicated_percentage,
+            data.files,
+            data.groups.len()
+        );
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Component",
+            "Files",
+            "Code Lines",
+            "Duplicated Lines",
+            "Duplicated %",
+            "Clone Groups"
+        ]);
+        for component in &data.components {
+            table.add_row(row![
+                component.name,
+                r->component.files,
+                r->component.code_lines,
+                r->component.duplicated_lines,
+                r->format!("{:.1}", component.duplicated_percentage),
+                r->component.clone_groups
+            ]);
+        }
+        println!("\nDuplication by Component:");
+        table.printstd();
+
+        if !data.cross_crate.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Crate", "Crate", "Clone Groups", "Duplicated Tokens"]);
+            for pair in &data.cross_crate {
+                table.add_row(row![
+                    pair.first,
+                    pair.second,
+                    r->pair.clone_groups,
+                    r->pair.duplicated_tokens
+                ]);
+            }
+            println!("\nCross-Crate Duplication:");
+            table.printstd();
+        }
+
+        if !data.groups.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Tokens", "Lines", "Kind", "Copies", "Locations"]);
+            for group in &data.groups {
+                table.add_row(row![
+                    r->group.tokens,
+                    r->group.lines,
+                    group.kind,
+                    r->group.occurrences.len(),
+                    occurrence_list(group).join("\n")
+                ]);
+            }
+            println!("\nClone Groups:");
+            table.printstd();
+        }
+    }
+
+    fn render_html_body(&self, data: &DuplicationData) -> Markup {
+        let explanations = [
+            (
+                "Clone",
+                "A fragment that occurs more than once, after identifiers and literals are abstracted.",
+            ),
+            (
+                "Kind",
+                "'exact' copies match token for token (type-1), 'renamed' copies differ in identifiers or literals (type-2).",
+            ),
+            (
+                "Duplicated %",
+                "Share of the component's code lines that are part of a clone.",
+            ),
+            (
+                "Cross-Crate Duplication",
+                "Clones with copies in two crates, which often signal a missing shared abstraction.",
+            ),
+        ];
+        let percentages: Vec<f64> = data
+            .components
+            .iter()
+            .map(|c| c.duplicated_percentage)
+            .collect();
+        let ranges = MetricRanges::from_values(&percentages, false);
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { (format!("{} of {} code lines ({:.1}%) duplicated, clones of at least {} tokens", data.duplicated_lines, data.code_lines, data.duplicated_percentage, data.min_tokens)) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="number" { "Files" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Code Lines" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Duplicated Lines" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Duplicated %" }
+                        th class="sortable-header" data-column-index="5" data-sort-type="number" { "Clone Groups" }
+                    }
+                }
+                tbody {
+                    @for component in &data.components {
+                        tr {
+                            td { (component.name) }
+                            td { (component.files) }
+                            td { (component.code_lines) }
+                            td { (component.duplicated_lines) }
+                            td style=({ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(component.duplicated_percentage, r))}) { (format!("{:.1}", component.duplicated_percentage)) }
+                            td { (component.clone_groups) }
+                        }
+                    }
+                }
+            }
+            @if !data.cross_crate.is_empty() {
+                h2 { "Cross-Crate Duplication" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Crate" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Crate" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Clone Groups" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Duplicated Tokens" }
+                        }
+                    }
+                    tbody {
+                        @for pair in &data.cross_crate {
+                            tr {
+                                td { (pair.first) }
+                                td { (pair.second) }
+                                td { (pair.clone_groups) }
+                                td { (pair.duplicated_tokens) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !data.groups.is_empty() {
+                h2 { "Clone Groups" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="number" { "Tokens" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="number" { "Lines" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Kind" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Copies" }
+                            th { "Locations" }
+                        }
+                    }
+                    tbody {
+                        @for group in &data.groups {
+                            tr {
+                                td { (group.tokens) }
+                                td { (group.lines) }
+                                td { (group.kind) }
+                                td { (group.occurrences.len()) }
+                                td {
+                                    @for location in occurrence_list(group) {
+                                        div { (location) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Maps token texts to dense ids, so token sequences compare as integers.
+#[derive(Debug, Default)]
+struct Interner {
+    ids: HashMap<String, u32>,
+    texts: Vec<String>,
+}
+
+impl Interner {
+    fn intern(&mut self, text: &str) -> u32 {
+        if let Some(id) = self.ids.get(text) {
+            return *id;
+        }
+        let id = self.texts.len() as u32;
+        self.texts.push(text.to_string());
+        self.ids.insert(text.to_string(), id);
+        id
+    }
+
+    fn text(&self, id: u32) -> &str {
+        &self.texts[id as usize]
+    }
+}
+
+/// The analyzed tokens of a single file, in source order.
+#[derive(Debug)]
+struct SourceFile {
+    /// Path of the file relative to the analysis path.
+    path: PathBuf,
+    /// Interned normalized token of each token.
+    kinds: Vec<u32>,
+    /// Interned source text of each token.
+    texts: Vec<u32>,
+    /// Line of each token (1-indexed).
+    lines: Vec<usize>,
+}
+
+impl SourceFile {
+    /// Lexes `content` into normalized tokens, leaving out the items
+    /// [`IgnoredItems`] skips. Also returns the out-of-line test modules the
+    /// file declares, relative to its child module directory.
+    fn lex(
+        path: PathBuf,
+        content: &str,
+        include_tests: bool,
+        interner: &mut Interner,
+    ) -> Result<(Self, Vec<PathBuf>)> {
+        let ast = syn::parse_file(content)
+            .map_err(|e| RaffError::parse_error_with_file(path.clone(), e.to_string()))?;
+        let mut ignored = IgnoredItems {
+            include_tests,
+            ..IgnoredItems::default()
+        };
+        ignored.visit_file(&ast);
+
+        let stream = TokenStream::from_str(content)
+            .map_err(|e| RaffError::parse_error_with_file(path.clone(), e.to_string()))?;
+        let mut lexer = Lexer {
+            ignored: &ignored.ranges,
+            interner,
+            file: SourceFile {
+                path,
+                kinds: Vec::new(),
+                texts: Vec::new(),
+                lines: Vec::new(),
+            },
+        };
+        lexer.lex(stream);
+        Ok((lexer.file, ignored.test_modules))
+    }
+
+    /// Number of distinct lines holding a token.
+    fn code_lines(&self) -> usize {
+        let mut lines = self.lines.clone();
+        lines.dedup();
+        lines.len()
+    }
+}
+
+/// Finds the line ranges of items left out of clone detection: `use` and
+/// `extern crate` declarations, and test items unless tests are included.
+#[derive(Debug, Default)]
+struct IgnoredItems {
+    include_tests: bool,
+    /// Inclusive line ranges of the skipped items.
+    ranges: Vec<(usize, usize)>,
+    /// Path of the inline module being visited.
+    scope: Vec<String>,
+    /// Out-of-line test modules, e.g. `tests` for `#[cfg(test)] mod tests;`.
+    test_modules: Vec<PathBuf>,
+}
+
+impl<'ast> Visit<'ast> for IgnoredItems {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let is_test = !self.include_tests && item_attrs(item).iter().any(is_test_attr);
+        if is_test || matches!(item, Item::Use(_) | Item::ExternCrate(_)) {
+            if is_test
+                && let Item::Mod(module) = item
+                && module.content.is_none()
+            {
+                let mut path: PathBuf = self.scope.iter().collect();
+                path.push(module.ident.to_string());
+                self.test_modules.push(path);
+            }
+            let span = item.span();
+            self.ranges.push((span.start().line, span.end().line));
+            return;
+        }
+        if let Item::Mod(module) = item {
+            self.scope.push(module.ident.to_string());
+            syn::visit::visit_item(self, item);
+            self.scope.pop();
+        } else {
+            syn::visit::visit_item(self, item);
+        }
+    }
+}
+
+/// Flattens a token stream into a [`SourceFile`].
+struct Lexer<'a> {
+    ignored: &'a [(usize, usize)],
+    interner: &'a mut Interner,
+    file: SourceFile,
+}
+
+impl Lexer<'_> {
+    /// Appends the tokens of `stream` in source order. Attributes, including
+    /// doc comments, are skipped, and a delimited group contributes its open
+    /// and close delimiters.
+    fn lex(&mut self, stream: TokenStream) {
+        let trees: Vec<TokenTree> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < trees.len() {
+            match &trees[i] {
+                TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                    let mut group = i + 1;
+                    if matches!(trees.get(group), Some(TokenTree::Punct(bang)) if bang.as_char() == '!')
+                    {
+                        group += 1;
+                    }
+                    if matches!(trees.get(group), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket)
+                    {
+                        i = group + 1;
+                        continue;
+                    }
+                    self.push("#", "#", punct.span().start().line);
+                }
+                TokenTree::Punct(punct) => {
+                    let text = punct.as_char().to_string();
+                    self.push(&text, &text, punct.span().start().line);
+                }
+                TokenTree::Ident(ident) => {
+                    let text = ident.to_string();
+                    let kind = match text.as_str() {
+                        "true" | "false" => LITERAL,
+                        keyword if KEYWORDS.contains(&keyword) => keyword,
+                        _ => IDENT,
+                    };
+                    self.push(kind, &text, ident.span().start().line);
+                }
+                TokenTree::Literal(literal) => {
+                    self.push(LITERAL, &literal.to_string(), literal.span().start().line);
+                }
+                TokenTree::Group(group) => {
+                    let delimiters = match group.delimiter() {
+                        Delimiter::Parenthesis => Some(("(", ")")),
+                        Delimiter::Brace => Some(("{", "}")),
+                        Delimiter::Bracket => Some(("[", "]")),
+                        Delimiter::None => None,
+                    };
+                    if let Some((open, _)) = delimiters {
+                        self.push(open, open, group.span_open().start().line);
+                    }
+                    self.lex(group.stream());
+                    if let Some((_, close)) = delimiters {
+                        self.push(close, close, group.span_close().start().line);
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    fn push(&mut self, kind: &str, text: &str, line: usize) {
+        if self
+            .ignored
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&line))
+        {
+            return;
+        }
+        self.file.kinds.push(self.interner.intern(kind));
+        self.file.texts.push(self.interner.intern(text));
+        self.file.lines.push(line);
+    }
+}
+
+/// A clone group before it is resolved to lines: the `(file, offset)` of
+/// each copy and the length they share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RawClone {
+    occurrences: Vec<(usize, usize)>,
+    length: usize,
+}
+
+/// Finds every maximal token run of at least `min_tokens` normalized tokens
+/// that occurs more than once.
+///
+/// Windows of `min_tokens` tokens are bucketed by a rolling hash and split
+/// by content. A set of equal windows is skipped when all its copies are
+/// preceded by the same token, since the set starting one token earlier
+/// covers it; otherwise the copies are extended while they keep agreeing.
+fn find_clones(files: &[SourceFile], min_tokens: usize) -> Vec<RawClone> {
+    let high = HASH_BASE.wrapping_pow(min_tokens.saturating_sub(1) as u32);
+    let mut windows: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    for (index, file) in files.iter().enumerate() {
+        let mut hash = 0u64;
+        for (offset, kind) in file.kinds.iter().enumerate() {
+            if offset >= min_tokens {
+                let oldest = u64::from(file.kinds[offset - min_tokens]) + 1;
+                hash = hash.wrapping_sub(oldest.wrapping_mul(high));
+            }
+            hash = hash
+                .wrapping_mul(HASH_BASE)
+                .wrapping_add(u64::from(*kind) + 1);
+            if offset + 1 >= min_tokens {
+                windows
+                    .entry(hash)
+                    .or_default()
+                    .push((index, offset + 1 - min_tokens));
+            }
+        }
+    }
+
+    let kinds =
+        |(file, offset): (usize, usize), length: usize| &files[file].kinds[offset..offset + length];
+    let mut clones = Vec::new();
+    for mut positions in windows.into_values().filter(|p| p.len() > 1) {
+        // Equal hashes may still be different windows
+        positions.sort_by(|a, b| {
+            kinds(*a, min_tokens)
+                .cmp(kinds(*b, min_tokens))
+                .then(a.cmp(b))
+        });
+        for set in positions.chunk_by(|a, b| kinds(*a, min_tokens) == kinds(*b, min_tokens)) {
+            if set.len() < 2 || extends_left(files, set) {
+                continue;
+            }
+            let length = extend_right(files, set, min_tokens);
+            let mut set = set.to_vec();
+            set.sort_unstable();
+            let occurrences = without_overlaps(&set, length);
+            if occurrences.len() > 1 {
+                clones.push(RawClone {
+                    occurrences,
+                    length,
+                });
+            }
+        }
+    }
+    without_contained(clones)
+}
+
+/// Returns `true` if every copy in `set` is preceded by the same token.
+fn extends_left(files: &[SourceFile], set: &[(usize, usize)]) -> bool {
+    let previous = |(file, offset): (usize, usize)| {
+        offset
+            .checked_sub(1)
+            .map(|offset| files[file].kinds[offset])
+    };
+    let first = previous(set[0]);
+    first.is_some() && set.iter().all(|position| previous(*position) == first)
+}
+
+/// Returns the length of the longest token run shared by every copy in
+/// `set`, which all agree on their first `min_tokens` tokens.
+fn extend_right(files: &[SourceFile], set: &[(usize, usize)], min_tokens: usize) -> usize {
+    let mut length = min_tokens;
+    loop {
+        let next = |(file, offset): (usize, usize)| files[file].kinds.get(offset + length);
+        let first = next(set[0]);
+        if first.is_none() || !set.iter().all(|position| next(*position) == first) {
+            return length;
+        }
+        length += 1;
+    }
+}
+
+/// Drops copies that overlap an earlier copy in the same file. `set` must be
+/// sorted.
+fn without_overlaps(set: &[(usize, usize)], length: usize) -> Vec<(usize, usize)> {
+    let mut kept: Vec<(usize, usize)> = Vec::new();
+    for &(file, offset) in set {
+        if kept.last().is_none_or(|&(last_file, last_offset)| {
+            last_file != file || offset >= last_offset + length
+        }) {
+            kept.push((file, offset));
+        }
+    }
+    kept
+}
+
+/// Drops clone groups whose copies all lie inside copies of a longer group,
+/// and sorts the rest longest first.
+fn without_contained(mut clones: Vec<RawClone>) -> Vec<RawClone> {
+    clones.sort_by(|a, b| {
+        b.length
+            .cmp(&a.length)
+            .then_with(|| a.occurrences.cmp(&b.occurrences))
+    });
+    let mut kept: Vec<RawClone> = Vec::new();
+    for clone in clones {
+        let contained = clone.occurrences.iter().all(|&(file, offset)| {
+            kept.iter().any(|longer| {
+                longer
+                    .occurrences
+                    .iter()
+                    .any(|&(longer_file, longer_offset)| {
+                        longer_file == file
+                            && longer_offset <= offset
+                            && offset + clone.length <= longer_offset + longer.length
+                    })
+            })
+        });
+        if !contained {
+            kept.push(clone);
+        }
+    }
+    kept
+}
+
+/// Line counts collected for one component.
+#[derive(Default)]
+struct ComponentLines {
+    files: usize,
+    code_lines: usize,
+    duplicated_lines: usize,
+    clone_groups: usize,
+}
+
+/// Resolves raw clones to lines, crates and components, and aggregates the
+/// duplicated lines per component and the clones shared between crates.
+fn summarize(
+    files: &[SourceFile],
+    clones: &[RawClone],
+    interner: &Interner,
+    components: &ComponentResolver,
+    crates: &ComponentResolver,
+    min_tokens: usize,
+    analysis_path: PathBuf,
+) -> DuplicationData {
+    let mut duplicated: Vec<Vec<bool>> = files
+        .iter()
+        .map(|file| vec![false; file.kinds.len()])
+        .collect();
+    let mut groups = Vec::new();
+    let mut component_groups: HashMap<String, usize> = HashMap::new();
+    let mut pairs: BTreeMap<(String, String), (usize, usize)> = BTreeMap::new();
+    for clone in clones {
+        let (first_file, first_offset) = clone.occurrences[0];
+        let first_texts = &files[first_file].texts[first_offset..first_offset + clone.length];
+        let mut kind = CloneKind::Exact;
+        let mut occurrences = Vec::new();
+        for &(index, offset) in &clone.occurrences {
+            let file = &files[index];
+            let end = offset + clone.length;
+            duplicated[index][offset..end].fill(true);
+            if file.texts[offset..end] != *first_texts {
+                kind = CloneKind::Renamed;
+            }
+            occurrences.push(CloneOccurrence {
+                path: file.path.clone(),
+                start_line: file.lines[offset],
+                end_line: file.lines[end - 1],
+                crate_name: crates.component_of(&file.path),
+                component: components.component_of(&file.path),
+            });
+        }
+
+        let mut crate_names: Vec<String> =
+            occurrences.iter().map(|o| o.crate_name.clone()).collect();
+        crate_names.sort();
+        crate_names.dedup();
+        for (i, first) in crate_names.iter().enumerate() {
+            for second in &crate_names[i + 1..] {
+                let entry = pairs.entry((first.clone(), second.clone())).or_default();
+                entry.0 += 1;
+                entry.1 += clone.length;
+            }
+        }
+        let mut component_names: Vec<&String> = occurrences.iter().map(|o| &o.component).collect();
+        component_names.sort();
+        component_names.dedup();
+        for name in component_names {
+            *component_groups.entry(name.clone()).or_default() += 1;
+        }
+
+        let normalized: Vec<&str> = files[first_file].kinds
+            [first_offset..first_offset + clone.length]
+            .iter()
+            .map(|kind| interner.text(*kind))
+            .collect();
+        groups.push(CloneGroup {
+            tokens: clone.length,
+            lines: occurrences
+                .iter()
+                .map(|o| o.end_line - o.start_line + 1)
+                .max()
+                .unwrap_or_default(),
+            kind,
+            crates: crate_names,
+            occurrences,
+            fingerprint: hash_bytes(normalized.join(" ").as_bytes())[..16].to_string(),
+        });
+    }
+
+    let mut by_component: BTreeMap<String, ComponentLines> = BTreeMap::new();
+    for (file, duplicated) in files.iter().zip(&duplicated) {
+        let mut duplicated_lines: Vec<usize> = file
+            .lines
+            .iter()
+            .zip(duplicated)
+            .filter(|(_, duplicated)| **duplicated)
+            .map(|(line, _)| *line)
+            .collect();
+        duplicated_lines.dedup();
+        let component = by_component
+            .entry(components.component_of(&file.path))
+            .or_default();
+        component.files += 1;
+        component.code_lines += file.code_lines();
+        component.duplicated_lines += duplicated_lines.len();
+    }
+    for (name, count) in component_groups {
+        by_component.entry(name).or_default().clone_groups = count;
+    }
+
+    let code_lines = by_component.values().map(|c| c.code_lines).sum();
+    let duplicated_lines = by_component.values().map(|c| c.duplicated_lines).sum();
+    let mut components: Vec<ComponentDuplication> = by_component
+        .into_iter()
+        .map(|(name, lines)| ComponentDuplication {
+            name,
+            files: lines.files,
+            code_lines: lines.code_lines,
+            duplicated_lines: lines.duplicated_lines,
+            duplicated_percentage: percentage(lines.duplicated_lines, lines.code_lines),
+            clone_groups: lines.clone_groups,
+        })
+        .collect();
+    components.sort_by(|a, b| {
+        b.duplicated_percentage
+            .total_cmp(&a.duplicated_percentage)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let mut cross_crate: Vec<CratePairDuplication> = pairs
+        .into_iter()
+        .map(
+            |((first, second), (clone_groups, duplicated_tokens))| CratePairDuplication {
+                first,
+                second,
+                clone_groups,
+                duplicated_tokens,
+            },
+        )
+        .collect();
+    cross_crate.sort_by(|a, b| {
+        b.clone_groups
+            .cmp(&a.clone_groups)
+            .then_with(|| b.duplicated_tokens.cmp(&a.duplicated_tokens))
+    });
+
+    DuplicationData {
+        groups,
+        components,
+        cross_crate,
+        files: files.len(),
+        code_lines,
+        duplicated_lines,
+        duplicated_percentage: percentage(duplicated_lines, code_lines),
+        min_tokens,
+        analysis_path,
+    }
+}
+
+/// Returns the repo-relative URI of an occurrence's file.
+fn occurrence_uri(occurrence: &CloneOccurrence) -> String {
+    occurrence.path.to_string_lossy().replace('\\', "/")
+}
+
+/// Formats each copy in `group` as `path:start-end`.
+fn occurrence_list(group: &CloneGroup) -> Vec<String> {
+    group
+        .occurrences
+        .iter()
+        .map(|o| format!("{}:{}-{}", occurrence_uri(o), o.start_line, o.end_line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// A function long enough to be reported with `min_tokens` of 30.
+    fn function(name: &str, factor: u32) -> String {
+        format!(
+            "pub fn {name}(values: &[u32]) -> u32 {{\n    let mut total = 0;\n    for value in values {{\n        if *value > {factor} {{\n            total += value * {factor};\n        }} else {{\n            total -= 1;\n        }}\n    }}\n    total\n}}\n"
+        )
+    }
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn args(path: &Path, components: ComponentMode) -> DuplicationArgs {
+        DuplicationArgs {
+            path: path.to_path_buf(),
+            min_tokens: 30,
+            include_tests: false,
+            components,
+            component_definitions: BTreeMap::new(),
+            output: DuplicationOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    fn lex(content: &str, include_tests: bool) -> (SourceFile, Interner) {
+        let mut interner = Interner::default();
+        let (file, _) = SourceFile::lex(
+            PathBuf::from("src/lib.rs"),
+            content,
+            include_tests,
+            &mut interner,
+        )
+        .unwrap();
+        (file, interner)
+    }
+
+    #[test]
+    fn test_lex_abstracts_identifiers_and_skips_attributes_uses_and_tests() {
+        let source = "use std::fmt;\n/// Docs\n#[inline]\nfn f(x: u32) -> bool { x > 1 && true }\n#[cfg(test)]\nmod tests { fn g() {} }\n";
+        let (file, interner) = lex(source, false);
+        let kinds: Vec<&str> = file.kinds.iter().map(|k| interner.text(*k)).collect();
+        assert_eq!(
+            kinds.join(" "),
+            "fn $id ( $id : $id ) - > $id { $id > $lit & & $lit }"
+        );
+        assert_eq!(file.code_lines(), 1);
+
+        let (file, _) = lex(source, true);
+        assert_eq!(file.code_lines(), 2);
+    }
+
+    #[test]
+    fn test_find_clones_reports_maximal_groups_once() {
+        let mut interner = Interner::default();
+        let files: Vec<SourceFile> = [
+            function("a", 1) + &function("b", 2),
+            function("c", 3),
+            "fn unrelated() { let x = 1; }\n".to_string(),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, source)| {
+            SourceFile::lex(
+                PathBuf::from(format!("{i}.rs")),
+                source,
+                false,
+                &mut interner,
+            )
+            .unwrap()
+            .0
+        })
+        .collect();
+
+        let clones = find_clones(&files, 30);
+        assert_eq!(clones.len(), 1);
+        assert_eq!(clones[0].occurrences.len(), 3);
+        assert_eq!(clones[0].length, files[1].kinds.len());
+        assert!(find_clones(&files, files[1].kinds.len() + 1).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_reports_components_and_cross_crate_clones() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(dir, "Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n");
+        for name in ["alpha", "beta"] {
+            write(
+                dir,
+                &format!("crates/{name}/Cargo.toml"),
+                &format!("[package]\nname = \"{name}\"\n"),
+            );
+        }
+        write(dir, "crates/alpha/src/lib.rs", &function("total", 1));
+        write(
+            dir,
+            "crates/beta/src/lib.rs",
+            &format!("{}\nfn other() {{}}\n", function("sum", 2)),
+        );
+        write(
+            dir,
+            "crates/beta/tests/it.rs",
+            &format!("{}{}", function("x", 1), function("y", 1)),
+        );
+
+        let data = DuplicationRule::new()
+            .analyze(&args(dir, ComponentMode::Crate))
+            .unwrap();
+
+        assert_eq!(data.files, 2);
+        assert_eq!(data.groups.len(), 1);
+        let group = &data.groups[0];
+        assert_eq!(group.kind, CloneKind::Renamed);
+        assert_eq!(group.crates, vec!["alpha", "beta"]);
+        assert_eq!(group.occurrences[0].start_line, 1);
+        assert_eq!(group.occurrences[0].end_line, 11);
+        assert!(group.is_cross_crate());
+
+        assert_eq!(data.cross_crate.len(), 1);
+        assert_eq!(data.cross_crate[0].first, "alpha");
+        assert_eq!(data.cross_crate[0].duplicated_tokens, group.tokens);
+
+        let alpha = data.components.iter().find(|c| c.name == "alpha").unwrap();
+        assert_eq!(alpha.duplicated_percentage, 100.0);
+        let beta = data.components.iter().find(|c| c.name == "beta").unwrap();
+        assert_eq!((beta.duplicated_lines, beta.code_lines), (11, 12));
+
+        let findings = data.to_findings();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[0].related_locations.len(), 1);
+        assert!(findings[0].message.contains("missing shared abstraction"));
+    }
+
+    #[test]
+    fn test_analyze_rejects_zero_min_tokens() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "src/lib.rs", "fn f() {}\n");
+        let mut args = args(temp_dir.path(), ComponentMode::Directory);
+        args.min_tokens = 0;
+        assert!(DuplicationRule::new().analyze(&args).is_err());
+    }
+}
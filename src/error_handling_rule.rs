@@ -0,0 +1,1048 @@
+//! Error Handling Rule
+//!
+//! This module provides the error handling rule, which counts the places where
+//! non-test code can panic and checks them against per-module budgets.
+//!
+//! # Overview
+//!
+//! Every `.rs` file is parsed with `syn`, and the following panic sites are
+//! recorded together with the module they live in:
+//!
+//! - **unwrap**: `.unwrap()` and `.unwrap_err()` calls
+//! - **expect**: `.expect(..)` and `.expect_err(..)` calls
+//! - **panic**, **todo**, **unimplemented**: the macros of the same name
+//! - **index**: indexing expressions such as `items[i]` or `text[1..]`, which
+//!   panic when out of bounds. Full-range slicing (`items[..]`) never panics
+//!   and is not counted.
+//!
+//! Arguments of other macros, such as `format!("{}", value.unwrap())`, are
+//! parsed as expressions where possible so that sites inside them are found.
+//!
+//! # Budgets
+//!
+//! A budget limits the number of sites in a module and its submodules, either
+//! for all kinds together (`total`) or per kind. `--ban crate::domain` sets a
+//! total budget of zero. Budgets are configured per module:
+//!
+//! ```toml
+//! [error_handling]
+//! banned_modules = ["crate::domain"]
+//!
+//! [error_handling.budgets."crate::app"]
+//! unwrap = 0
+//! total = 20
+//! ```
+//!
+//! `crate` matches every crate; a crate name such as `my_crate::app` matches
+//! only that crate. Every site counted against an exceeded budget is reported
+//! as an error.
+//!
+//! Test code (`#[cfg(test)]` modules, `#[test]` functions and the `tests`,
+//! `benches` and `examples` directories) is ignored unless `--include-tests`
+//! is set.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::error_handling_rule::ErrorHandlingRule;
+//! use raff_core::{ComponentMode, ErrorHandlingArgs, ErrorHandlingOutputFormat};
+//! use std::path::PathBuf;
+//!
+//! let rule = ErrorHandlingRule::new();
+//! let args = ErrorHandlingArgs {
+//!     path: PathBuf::from("."),
+//!     banned_modules: vec!["crate::domain".to_string()],
+//!     budgets: Default::default(),
+//!     include_tests: false,
+//!     components: ComponentMode::Crate,
+//!     component_definitions: Default::default(),
+//!     output: ErrorHandlingOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path does not exist or is not a directory
+//! - A file cannot be read or is not valid Rust
+//! - A module exceeds its budget
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
+use syn::visit::Visit;
+use syn::{Expr, Macro};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{
+    CiOutputFormat, ComponentMode, ErrorHandlingArgs, ErrorHandlingOutputFormat, PanicBudget,
+};
+use crate::components::ComponentResolver;
+use crate::error::{RaffError, Result};
+use crate::file_utils::relative_namespace;
+use crate::html_utils::{self, MetricRanges};
+use crate::output_utils::path_uri;
+use crate::rule::Rule;
+use crate::site_visitor::{SiteKind, SiteVisitor, density, kind_summary, site_location};
+use crate::statement_count_rule::{child_module_dir, is_test_target};
+use crate::unsafe_code_rule::module_matches;
+
+/// Kind of a panic site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanicKind {
+    /// `.unwrap()` or `.unwrap_err()`.
+    Unwrap,
+    /// `.expect(..)` or `.expect_err(..)`.
+    Expect,
+    /// `panic!(..)`.
+    Panic,
+    /// `todo!(..)`.
+    Todo,
+    /// `unimplemented!(..)`.
+    Unimplemented,
+    /// An indexing expression such as `items[i]`.
+    Index,
+}
+
+impl PanicKind {
+    /// Every kind, in report column order.
+    pub const ALL: [PanicKind; 6] = [
+        Self::Unwrap,
+        Self::Expect,
+        Self::Panic,
+        Self::Todo,
+        Self::Unimplemented,
+        Self::Index,
+    ];
+}
+
+impl std::fmt::Display for PanicKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Unwrap => "unwrap",
+            Self::Expect => "expect",
+            Self::Panic => "panic",
+            Self::Todo => "todo",
+            Self::Unimplemented => "unimplemented",
+            Self::Index => "index",
+        };
+        f.write_str(s)
+    }
+}
+
+/// One place where non-test code can panic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanicSite {
+    pub kind: PanicKind,
+    /// Path of the file relative to the analysis path.
+    pub path: PathBuf,
+    /// First line of the site (1-indexed).
+    pub start_line: usize,
+    /// Last line of the site (1-indexed).
+    pub end_line: usize,
+    /// Module containing the site, e.g. `my_crate::domain::order`.
+    pub module: String,
+    /// Enclosing function, impl block or trait, e.g. `Order::total`.
+    pub item: Option<String>,
+    /// Component containing the file.
+    pub component: String,
+    /// Labels of the exceeded budgets the site counts against.
+    pub exceeded_budgets: Vec<String>,
+}
+
+/// Panic sites within a single module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModulePanics {
+    /// Path of the module.
+    pub module: String,
+    /// Component containing the module.
+    pub component: String,
+    /// Number of sites in the module.
+    pub sites: usize,
+    /// Number of sites per kind.
+    pub by_kind: BTreeMap<PanicKind, usize>,
+}
+
+/// Panic sites of a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentPanics {
+    /// Name of the component.
+    pub name: String,
+    /// Number of analyzed files in the component.
+    pub files: usize,
+    /// Number of non-blank lines in the analyzed files.
+    pub code_lines: usize,
+    /// Number of panic sites.
+    pub sites: usize,
+    /// Number of sites per kind.
+    pub by_kind: BTreeMap<PanicKind, usize>,
+    /// Panic sites per 1,000 code lines.
+    pub density: f64,
+}
+
+/// Usage of a single budget limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    /// Module the budget applies to, including its submodules.
+    pub module: String,
+    /// Kind the limit applies to, or `None` for all kinds together.
+    pub kind: Option<PanicKind>,
+    /// Maximum number of sites.
+    pub limit: usize,
+    /// Number of sites counted against the limit.
+    pub used: usize,
+    /// Whether the budget comes from a banned module.
+    pub banned: bool,
+}
+
+impl BudgetUsage {
+    /// Returns `true` if more sites were found than the limit allows.
+    pub fn exceeded(&self) -> bool {
+        self.used > self.limit
+    }
+
+    /// Describes the budget, e.g. `crate::app unwrap <= 2` or
+    /// `crate::domain banned`.
+    pub fn label(&self) -> String {
+        if self.banned {
+            return format!("{} banned", self.module);
+        }
+        match self.kind {
+            Some(kind) => format!("{} {kind} <= {}", self.module, self.limit),
+            None => format!("{} total <= {}", self.module, self.limit),
+        }
+    }
+}
+
+/// Data type for error handling analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorHandlingData {
+    /// Every site, sorted by path and line.
+    pub sites: Vec<PanicSite>,
+    /// Modules containing panic sites, with the most sites first.
+    pub modules: Vec<ModulePanics>,
+    /// Per-component summary, with the highest density first.
+    pub components: Vec<ComponentPanics>,
+    /// Every budget limit with its usage, banned modules first.
+    pub budgets: Vec<BudgetUsage>,
+    /// Number of sites per kind across all analyzed files.
+    pub by_kind: BTreeMap<PanicKind, usize>,
+    /// Number of analyzed files.
+    pub files: usize,
+    /// Number of non-blank lines across all analyzed files.
+    pub code_lines: usize,
+    /// Panic sites per 1,000 code lines across all analyzed files.
+    pub density: f64,
+    /// The analyzed directory.
+    pub analysis_path: PathBuf,
+}
+
+impl ErrorHandlingData {
+    /// Returns the sites counted against an exceeded budget.
+    pub fn over_budget(&self) -> impl Iterator<Item = &PanicSite> {
+        self.sites
+            .iter()
+            .filter(|site| !site.exceeded_budgets.is_empty())
+    }
+
+    /// Returns the error to exit with when a budget is exceeded.
+    fn budget_error(&self) -> Option<RaffError> {
+        let exceeded: Vec<String> = self
+            .budgets
+            .iter()
+            .filter(|budget| budget.exceeded())
+            .map(BudgetUsage::label)
+            .collect();
+        (!exceeded.is_empty()).then(|| {
+            RaffError::analysis_error(
+                "error_handling",
+                format!(
+                    "{} budget(s) exceeded: {}.",
+                    exceeded.len(),
+                    exceeded.join(", ")
+                ),
+            )
+        })
+    }
+}
+
+impl ToFindings for ErrorHandlingData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.sites
+            .iter()
+            .map(|site| {
+                let context = site
+                    .item
+                    .as_ref()
+                    .map(|item| format!(" in '{item}'"))
+                    .unwrap_or_default();
+                let (severity, message) = if site.exceeded_budgets.is_empty() {
+                    (
+                        Severity::Note,
+                        format!("{} in module '{}'{context}", site.kind, site.module),
+                    )
+                } else {
+                    (
+                        Severity::Error,
+                        format!(
+                            "{} in module '{}'{context} exceeds the budget: {}",
+                            site.kind,
+                            site.module,
+                            site.exceeded_budgets.join(", ")
+                        ),
+                    )
+                };
+                Finding {
+                    rule_id: "error-handling".to_string(),
+                    rule_name: "Error Handling Rule".to_string(),
+                    severity,
+                    message,
+                    location: Some(Location::with_lines(
+                        path_uri(&site.path),
+                        site.start_line,
+                        site.end_line,
+                    )),
+                    related_locations: Vec::new(),
+                    help_uri: Some(
+                        "https://github.com/liamwh/raff/docs/error-handling".to_string(),
+                    ),
+                    fingerprint: Some(format!(
+                        "error-handling:{}:{}:{}:{}",
+                        path_uri(&site.path),
+                        site.module,
+                        site.item.as_deref().unwrap_or_default(),
+                        site.kind
+                    )),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rule to count panic sites and check them against module budgets.
+#[derive(Debug, Default)]
+pub struct ErrorHandlingRule;
+
+impl Rule for ErrorHandlingRule {
+    type Config = ErrorHandlingArgs;
+    type Data = ErrorHandlingData;
+
+    fn name() -> &'static str {
+        "error_handling"
+    }
+
+    fn description() -> &'static str {
+        "Counts unwrap, expect, panicking macros and indexing per module and checks module budgets"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+impl ErrorHandlingRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &ErrorHandlingArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &ErrorHandlingArgs) -> Result<ErrorHandlingData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &ErrorHandlingArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "error-handling")?,
+            };
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+            // Sites within budget are notes; exceeded budgets fail CI
+            return match data.budget_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            };
+        }
+
+        let output = match args.output {
+            ErrorHandlingOutputFormat::Table => {
+                self.print_table(&data);
+                None
+            }
+            ErrorHandlingOutputFormat::Json => Some(serde_json::to_string_pretty(&data)?),
+            ErrorHandlingOutputFormat::Yaml => Some(serde_yaml::to_string(&data)?),
+            ErrorHandlingOutputFormat::Html => Some(html_utils::render_html_doc(
+                &format!("Error Handling Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            )),
+        };
+        if let Some(output) = output {
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+        }
+        match data.budget_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &ErrorHandlingArgs) -> Result<ErrorHandlingData> {
+        let analysis_path = &args.path;
+        if !analysis_path.exists() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Path not found",
+                analysis_path.display().to_string(),
+            ));
+        }
+        if !analysis_path.is_dir() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Provided path is not a directory",
+                analysis_path.display().to_string(),
+            ));
+        }
+
+        let mut all_rs_files: Vec<PathBuf> = Vec::new();
+        crate::file_utils::collect_rs_files(analysis_path, false, &mut all_rs_files, None)?;
+        all_rs_files.sort();
+
+        let components =
+            ComponentResolver::new(analysis_path, args.components, &args.component_definitions)?;
+        let crates = ComponentResolver::new(analysis_path, ComponentMode::Crate, &BTreeMap::new())?;
+
+        let mut scanned = Vec::new();
+        let mut test_module_paths = Vec::new();
+        for path_buf in &all_rs_files {
+            let path = path_buf
+                .strip_prefix(analysis_path)
+                .unwrap_or(path_buf)
+                .to_path_buf();
+            if !args.include_tests && is_test_target(analysis_path, &path) {
+                continue;
+            }
+            let content = fs::read_to_string(path_buf).map_err(|e| {
+                RaffError::io_error_with_source("read source file", path_buf.clone(), e)
+            })?;
+            let ast = syn::parse_file(&content)
+                .map_err(|e| RaffError::parse_error_with_file(path.clone(), e.to_string()))?;
+            let mut visitor = SiteVisitor::<PanicKind>::new(args.include_tests);
+            visitor.visit_file(&ast);
+            let module_dir = child_module_dir(&path);
+            test_module_paths.extend(
+                visitor
+                    .test_modules
+                    .iter()
+                    .map(|module| module_dir.join(module)),
+            );
+            let code_lines = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count();
+            scanned.push((path, code_lines, visitor.sites));
+        }
+        // Files declared as `#[cfg(test)] mod name;` are test code in full
+        scanned.retain(|(path, _, _)| {
+            !test_module_paths
+                .iter()
+                .any(|module| path.starts_with(module) || *path == module.with_extension("rs"))
+        });
+
+        let mut component_summaries: BTreeMap<String, ComponentPanics> = BTreeMap::new();
+        let mut sites = Vec::new();
+        for (path, code_lines, raw_sites) in scanned {
+            let component = components.component_of(&path);
+            let summary = component_summaries
+                .entry(component.clone())
+                .or_insert_with(|| ComponentPanics {
+                    name: component.clone(),
+                    files: 0,
+                    code_lines: 0,
+                    sites: 0,
+                    by_kind: BTreeMap::new(),
+                    density: 0.0,
+                });
+            summary.files += 1;
+            summary.code_lines += code_lines;
+            let file_module = crates
+                .module_of(&path)
+                .unwrap_or_else(|| relative_namespace(&path, Path::new("")).replace('/', "::"));
+            for raw in raw_sites {
+                summary.sites += 1;
+                *summary.by_kind.entry(raw.kind).or_insert(0) += 1;
+                sites.push(PanicSite {
+                    kind: raw.kind,
+                    path: path.clone(),
+                    start_line: raw.start_line,
+                    end_line: raw.end_line,
+                    module: std::iter::once(file_module.clone())
+                        .chain(raw.modules)
+                        .collect::<Vec<_>>()
+                        .join("::"),
+                    item: raw.item,
+                    component: component.clone(),
+                    exceeded_budgets: Vec::new(),
+                });
+            }
+        }
+        sites.sort_by(|a, b| (&a.path, a.start_line).cmp(&(&b.path, b.start_line)));
+        tracing::info!(
+            files = all_rs_files.len(),
+            sites = sites.len(),
+            "Located panic sites"
+        );
+
+        let budgets = check_budgets(&mut sites, &args.banned_modules, &args.budgets);
+
+        let mut components: Vec<ComponentPanics> = component_summaries
+            .into_values()
+            .map(|mut component| {
+                component.density = density(component.sites, component.code_lines);
+                component
+            })
+            .collect();
+        components.sort_by(|a, b| {
+            b.density
+                .total_cmp(&a.density)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let files = components.iter().map(|c| c.files).sum();
+        let code_lines = components.iter().map(|c| c.code_lines).sum();
+        let mut by_kind = BTreeMap::new();
+        for site in &sites {
+            *by_kind.entry(site.kind).or_insert(0) += 1;
+        }
+        Ok(ErrorHandlingData {
+            modules: group_by_module(&sites),
+            components,
+            budgets,
+            by_kind,
+            files,
+            code_lines,
+            density: density(sites.len(), code_lines),
+            sites,
+            analysis_path: analysis_path.clone(),
+        })
+    }
+
+    fn print_table(&self, data: &ErrorHandlingData) {
+        println!("\nError Handling Report Interpretation:");
+        println!("-------------------------------------");
+        println!(
+            "- Sites: unwrap/expect calls, panic!/todo!/unimplemented! and indexing that can panic."
+        );
+        println!("- Per kLOC: Panic sites per 1,000 non-blank lines.");
+        println!("- Budgets limit the sites in a module and its submodules.");
+        println!("-------------------------------------");
+        println!(
+            "\n{} panic sites in {} files ({:.2} per kLOC): {}",
+            data.sites.len(),
+            data.files,
+            data.density,
+            kind_summary(&data.by_kind)
+        );
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Component",
+            "Files",
+            "Code Lines",
+            "Sites",
+            "Per kLOC",
+            "Kinds"
+        ]);
+        for component in &data.components {
+            table.add_row(row![
+                component.name,
+                r->component.files,
+                r->component.code_lines,
+                r->component.sites,
+                r->format!("{:.2}", component.density),
+                kind_summary(&component.by_kind)
+            ]);
+        }
+        println!("\nPanic Sites by Component:");
+        table.printstd();
+
+        if !data.modules.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row![
+                "Module",
+                "Component",
+                "Sites",
+                "Unwrap",
+                "Expect",
+                "Panic",
+                "Todo",
+                "Unimpl.",
+                "Index"
+            ]);
+            for module in &data.modules {
+                let count = |kind| module.by_kind.get(&kind).copied().unwrap_or(0);
+                table.add_row(row![
+                    module.module,
+                    module.component,
+                    r->module.sites,
+                    r->count(PanicKind::Unwrap),
+                    r->count(PanicKind::Expect),
+                    r->count(PanicKind::Panic),
+                    r->count(PanicKind::Todo),
+                    r->count(PanicKind::Unimplemented),
+                    r->count(PanicKind::Index)
+                ]);
+            }
+            println!("\nPanic Sites by Module:");
+            table.printstd();
+        }
+
+        if !data.budgets.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Budget", "Used", "Limit", "Status"]);
+            for budget in &data.budgets {
+                table.add_row(row![
+                    budget.label(),
+                    r->budget.used,
+                    r->budget.limit,
+                    if budget.exceeded() { "EXCEEDED" } else { "ok" }
+                ]);
+            }
+            println!("\nModule Budgets:");
+            table.printstd();
+        }
+
+        if data.over_budget().next().is_some() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Location", "Kind", "Module", "Item", "Budget"]);
+            for site in data.over_budget() {
+                table.add_row(row![
+                    site_location(&site.path, site.start_line, site.end_line),
+                    site.kind,
+                    site.module,
+                    site.item.as_deref().unwrap_or("-"),
+                    site.exceeded_budgets.join(", ")
+                ]);
+            }
+            println!("\nPanic Sites over Budget:");
+            table.printstd();
+        }
+    }
+
+    fn render_html_body(&self, data: &ErrorHandlingData) -> Markup {
+        let explanations = [
+            (
+                "Sites",
+                "unwrap/expect calls, panic!/todo!/unimplemented! and indexing that can panic.",
+            ),
+            ("Per kLOC", "Panic sites per 1,000 non-blank lines."),
+            (
+                "Budget",
+                "Maximum number of sites in a module and its submodules.",
+            ),
+        ];
+        let densities: Vec<f64> = data.components.iter().map(|c| c.density).collect();
+        let ranges = MetricRanges::from_values(&densities, false);
+        let over_budget: Vec<&PanicSite> = data.over_budget().collect();
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { (format!("{} panic sites in {} files ({:.2} per kLOC)", data.sites.len(), data.files, data.density)) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="number" { "Files" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Code Lines" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Sites" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Per kLOC" }
+                        th { "Kinds" }
+                    }
+                }
+                tbody {
+                    @for component in &data.components {
+                        tr {
+                            td { (component.name) }
+                            td { (component.files) }
+                            td { (component.code_lines) }
+                            td { (component.sites) }
+                            td style=({ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(component.density, r))}) { (format!("{:.2}", component.density)) }
+                            td { (kind_summary(&component.by_kind)) }
+                        }
+                    }
+                }
+            }
+            @if !data.modules.is_empty() {
+                h2 { "Panic Sites by Module" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Module" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Component" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Sites" }
+                            @for (index, kind) in PanicKind::ALL.iter().enumerate() {
+                                th class="sortable-header" data-column-index=(index + 3) data-sort-type="number" { (kind) }
+                            }
+                        }
+                    }
+                    tbody {
+                        @for module in &data.modules {
+                            tr {
+                                td { (module.module) }
+                                td { (module.component) }
+                                td { (module.sites) }
+                                @for kind in PanicKind::ALL {
+                                    td { (module.by_kind.get(&kind).copied().unwrap_or(0)) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !data.budgets.is_empty() {
+                h2 { "Module Budgets" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Budget" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="number" { "Used" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Limit" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="string" { "Status" }
+                        }
+                    }
+                    tbody {
+                        @for budget in &data.budgets {
+                            tr {
+                                td { (budget.label()) }
+                                td { (budget.used) }
+                                td { (budget.limit) }
+                                td { (if budget.exceeded() { "EXCEEDED" } else { "ok" }) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !over_budget.is_empty() {
+                h2 { "Panic Sites over Budget" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Location" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Kind" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Module" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="string" { "Item" }
+                            th class="sortable-header" data-column-index="4" data-sort-type="string" { "Budget" }
+                        }
+                    }
+                    tbody {
+                        @for site in &over_budget {
+                            tr {
+                                td { (site_location(&site.path, site.start_line, site.end_line)) }
+                                td { (site.kind) }
+                                td { (site.module) }
+                                td { (site.item.as_deref().unwrap_or("-")) }
+                                td { (site.exceeded_budgets.join(", ")) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Measures every limit of the banned modules and budgets, and marks the
+/// sites counted against an exceeded limit. Banned modules come first.
+fn check_budgets(
+    sites: &mut [PanicSite],
+    banned_modules: &[String],
+    budgets: &BTreeMap<String, PanicBudget>,
+) -> Vec<BudgetUsage> {
+    let limits = banned_modules
+        .iter()
+        .map(|module| (module, None, 0, true))
+        .chain(budgets.iter().flat_map(|(module, budget)| {
+            budget_limits(budget)
+                .into_iter()
+                .map(move |(kind, limit)| (module, kind, limit, false))
+        }));
+
+    let mut usages = Vec::new();
+    for (module, kind, limit, banned) in limits {
+        let counted = |site: &PanicSite| {
+            kind.is_none_or(|kind| site.kind == kind) && module_matches(&site.module, module)
+        };
+        let usage = BudgetUsage {
+            module: module.clone(),
+            kind,
+            limit,
+            used: sites.iter().filter(|site| counted(site)).count(),
+            banned,
+        };
+        if usage.exceeded() {
+            let label = usage.label();
+            for site in sites.iter_mut().filter(|site| counted(site)) {
+                site.exceeded_budgets.push(label.clone());
+            }
+        }
+        usages.push(usage);
+    }
+    usages
+}
+
+/// Returns the limits set in `budget`, the total first.
+fn budget_limits(budget: &PanicBudget) -> Vec<(Option<PanicKind>, usize)> {
+    [
+        (None, budget.total),
+        (Some(PanicKind::Unwrap), budget.unwrap),
+        (Some(PanicKind::Expect), budget.expect),
+        (Some(PanicKind::Panic), budget.panic),
+        (Some(PanicKind::Todo), budget.todo),
+        (Some(PanicKind::Unimplemented), budget.unimplemented),
+        (Some(PanicKind::Index), budget.index),
+    ]
+    .into_iter()
+    .filter_map(|(kind, limit)| limit.map(|limit| (kind, limit)))
+    .collect()
+}
+
+/// Groups the sites by module, with the most sites first.
+fn group_by_module(sites: &[PanicSite]) -> Vec<ModulePanics> {
+    let mut modules: BTreeMap<&str, ModulePanics> = BTreeMap::new();
+    for site in sites {
+        let module = modules
+            .entry(site.module.as_str())
+            .or_insert_with(|| ModulePanics {
+                module: site.module.clone(),
+                component: site.component.clone(),
+                sites: 0,
+                by_kind: BTreeMap::new(),
+            });
+        module.sites += 1;
+        *module.by_kind.entry(site.kind).or_insert(0) += 1;
+    }
+    let mut modules: Vec<ModulePanics> = modules.into_values().collect();
+    modules.sort_by(|a, b| b.sites.cmp(&a.sites).then_with(|| a.module.cmp(&b.module)));
+    modules
+}
+
+impl SiteKind for PanicKind {
+    /// Arguments of macros such as `format!` are visited as expressions.
+    const VISIT_MACRO_BODIES: bool = true;
+
+    /// Records `.unwrap()` and `.expect(..)` at the method name, so that a
+    /// call at the end of a long chain points at its own line, and indexing
+    /// other than full-range slicing at its brackets.
+    fn of_expr(expr: &Expr) -> Option<(Self, Span)> {
+        match expr {
+            Expr::MethodCall(node) => {
+                let kind = match (node.method.to_string().as_str(), node.args.len()) {
+                    ("unwrap" | "unwrap_err", 0) => PanicKind::Unwrap,
+                    ("expect" | "expect_err", 1) => PanicKind::Expect,
+                    _ => return None,
+                };
+                let span = node
+                    .method
+                    .span()
+                    .join(node.paren_token.span.close())
+                    .unwrap_or_else(|| node.method.span());
+                Some((kind, span))
+            }
+            Expr::Index(node) => {
+                let full_range = matches!(
+                    &*node.index,
+                    Expr::Range(range) if range.start.is_none() && range.end.is_none()
+                );
+                (!full_range).then(|| (PanicKind::Index, node.bracket_token.span.join()))
+            }
+            _ => None,
+        }
+    }
+
+    fn of_macro(node: &Macro) -> Option<Self> {
+        let name = node.path.segments.last()?.ident.to_string();
+        match name.as_str() {
+            "panic" => Some(PanicKind::Panic),
+            "todo" => Some(PanicKind::Todo),
+            "unimplemented" => Some(PanicKind::Unimplemented),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn args(path: &Path) -> ErrorHandlingArgs {
+        ErrorHandlingArgs {
+            path: path.to_path_buf(),
+            banned_modules: Vec::new(),
+            budgets: BTreeMap::new(),
+            include_tests: false,
+            components: ComponentMode::Crate,
+            component_definitions: BTreeMap::new(),
+            output: ErrorHandlingOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    fn write_crate(dir: &Path) {
+        write(dir, "Cargo.toml", "[package]\nname = \"shop\"\n");
+        write(dir, "src/lib.rs", "pub mod app;\npub mod domain;\n");
+        write(
+            dir,
+            "src/domain.rs",
+            "pub fn first(items: &[u32]) -> u32 {\n    items[0]\n}\n\npub fn parse(s: &str) -> u32 {\n    s.parse().unwrap()\n}\n",
+        );
+        write(
+            dir,
+            "src/app.rs",
+            "pub fn run(a: Option<u8>, b: Option<u8>) -> u8 {\n    a.expect(\"a\") + b.expect(\"b\")\n}\n\npub fn later() {\n    todo!()\n}\n\n#[cfg(test)]\nmod tests {\n    fn check() {\n        Some(1).unwrap();\n    }\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_visitor_records_every_kind_with_its_item() {
+        let source = "fn run(items: Vec<u8>, value: Option<u8>) {\n    let _ = &items[..];\n    let _ = items[1];\n    println!(\"{}\", value\n        .unwrap());\n    value.expect(\"set\");\n}\n\nmod inner {\n    impl Job {\n        fn go(&self) {\n            panic!(\"no\");\n            unimplemented!();\n        }\n    }\n}\n\n#[test]\nfn ignored() {\n    todo!()\n}\n\n#[cfg(test)]\nmod tests;\n";
+        let mut visitor = SiteVisitor::<PanicKind>::new(false);
+        visitor.visit_file(&syn::parse_file(source).unwrap());
+
+        let sites: Vec<(PanicKind, usize, Option<&str>)> = visitor
+            .sites
+            .iter()
+            .map(|site| (site.kind, site.start_line, site.item.as_deref()))
+            .collect();
+        assert_eq!(
+            sites,
+            vec![
+                (PanicKind::Index, 3, Some("run")),
+                (PanicKind::Unwrap, 5, Some("run")),
+                (PanicKind::Expect, 6, Some("run")),
+                (PanicKind::Panic, 12, Some("Job::go")),
+                (PanicKind::Unimplemented, 13, Some("Job::go")),
+            ]
+        );
+        assert_eq!(visitor.sites[3].modules, vec!["inner"]);
+        assert_eq!(visitor.test_modules, vec!["tests"]);
+    }
+
+    #[test]
+    fn test_analyze_checks_bans_and_budgets() {
+        let temp_dir = TempDir::new().unwrap();
+        write_crate(temp_dir.path());
+        let mut args = args(temp_dir.path());
+        args.banned_modules = vec!["crate::domain".to_string()];
+        args.budgets.insert(
+            "shop::app".to_string(),
+            PanicBudget {
+                total: Some(5),
+                expect: Some(1),
+                ..PanicBudget::default()
+            },
+        );
+
+        let data = ErrorHandlingRule::new().analyze(&args).unwrap();
+
+        assert_eq!(data.sites.len(), 5);
+        assert_eq!(data.by_kind[&PanicKind::Expect], 2);
+        assert_eq!(data.modules[0].module, "shop::app");
+        assert_eq!(data.modules[0].sites, 3);
+
+        let budgets: Vec<(String, usize, bool)> = data
+            .budgets
+            .iter()
+            .map(|budget| (budget.label(), budget.used, budget.exceeded()))
+            .collect();
+        assert_eq!(
+            budgets,
+            vec![
+                ("crate::domain banned".to_string(), 2, true),
+                ("shop::app total <= 5".to_string(), 3, false),
+                ("shop::app expect <= 1".to_string(), 2, true),
+            ]
+        );
+
+        let over_budget: Vec<(PanicKind, &str, usize)> = data
+            .over_budget()
+            .map(|site| (site.kind, site.module.as_str(), site.start_line))
+            .collect();
+        assert_eq!(
+            over_budget,
+            vec![
+                (PanicKind::Expect, "shop::app", 2),
+                (PanicKind::Expect, "shop::app", 2),
+                (PanicKind::Index, "shop::domain", 2),
+                (PanicKind::Unwrap, "shop::domain", 6),
+            ]
+        );
+
+        let findings = data.to_findings();
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        assert_eq!(errors, 4);
+        assert!(data.budget_error().is_some());
+
+        let data = ErrorHandlingRule::new()
+            .analyze(&self::args(temp_dir.path()))
+            .unwrap();
+        assert!(data.budgets.is_empty());
+        assert!(data.budget_error().is_none());
+    }
+
+    #[test]
+    fn test_include_tests_counts_test_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        write_crate(temp_dir.path());
+        let mut args = args(temp_dir.path());
+        args.include_tests = true;
+
+        let data = ErrorHandlingRule::new().analyze(&args).unwrap();
+
+        assert_eq!(data.sites.len(), 6);
+        assert!(
+            data.modules
+                .iter()
+                .any(|module| module.module == "shop::app::tests")
+        );
+    }
+}
@@ -0,0 +1,1498 @@
+//! Generics Rule
+//!
+//! This module provides the generics rule, which measures the complexity that
+//! lives in type signatures rather than in statements: generic parameters,
+//! where-clauses, trait bounds, associated types and trait impls.
+//!
+//! # Overview
+//!
+//! Every `.rs` file is parsed with `syn`, and the signature of each function,
+//! method, trait method, struct, enum, union, trait, impl block and type alias
+//! is measured:
+//!
+//! - **generic parameters**: type and const parameters, plus one for each
+//!   `impl Trait` argument, which is an anonymous type parameter
+//! - **lifetimes**: lifetime parameters
+//! - **where predicates**: predicates in the where-clause
+//! - **bounds**: trait bounds anywhere in the signature, including nested ones
+//! - **bound depth**: how deeply trait bounds nest. `T: Clone` has depth 1 and
+//!   `F: Fn() -> Box<dyn Display>` has depth 2.
+//! - **associated types**: projections such as `T::Item` or
+//!   `<T as Trait>::Output`, bindings such as `Iterator<Item = u8>`, and the
+//!   associated types a trait declares
+//!
+//! Methods of trait impls are not measured, as their signatures are dictated
+//! by the trait. Impl blocks are also counted per type, and blanket impls
+//! (`impl<T: Display> Describe for T`) per module.
+//!
+//! With `--public-only`, only items declared `pub` are measured; impl blocks
+//! have no visibility of their own and are always measured.
+//!
+//! Test code (`#[cfg(test)]` modules, `#[test]` functions and the `tests`,
+//! `benches` and `examples` directories) is ignored unless `--include-tests`
+//! is set.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::generics_rule::GenericsRule;
+//! use raff_core::{GenericsArgs, GenericsOutputFormat};
+//! use std::path::PathBuf;
+//!
+//! let rule = GenericsRule::new();
+//! let args = GenericsArgs {
+//!     path: PathBuf::from("."),
+//!     max_generic_params: Some(4),
+//!     max_where_predicates: None,
+//!     max_bound_depth: Some(2),
+//!     max_trait_impls: None,
+//!     max_blanket_impls: None,
+//!     public_only: true,
+//!     include_tests: false,
+//!     top: 20,
+//!     output: GenericsOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path does not exist or is not a directory
+//! - A file cannot be read or is not valid Rust
+//! - A threshold is breached
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    AssocType, Attribute, Constraint, FnArg, GenericParam, Generics, ImplItem, ImplItemFn, Item,
+    ItemEnum, ItemFn, ItemImpl, ItemMod, ItemStruct, ItemTrait, ItemType, ItemUnion, ReturnType,
+    Signature, TraitBound, TraitItem, TraitItemFn, TypeImplTrait, TypePath, Visibility,
+};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{CiOutputFormat, ComponentMode, GenericsArgs, GenericsOutputFormat};
+use crate::components::ComponentResolver;
+use crate::counter::{is_test_attr, item_attrs, type_name};
+use crate::error::{RaffError, Result};
+use crate::file_utils::relative_namespace;
+use crate::html_utils;
+use crate::output_utils::path_uri;
+use crate::rule::Rule;
+use crate::statement_count_rule::{child_module_dir, is_test_target};
+
+/// Kind of a measured signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureKind {
+    Function,
+    Method,
+    TraitMethod,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Impl,
+    TypeAlias,
+}
+
+impl std::fmt::Display for SignatureKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Function => "fn",
+            Self::Method => "method",
+            Self::TraitMethod => "trait method",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Union => "union",
+            Self::Trait => "trait",
+            Self::Impl => "impl",
+            Self::TypeAlias => "type alias",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Signature metrics of a single generic item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericItem {
+    /// Name of the item, e.g. `Cache::get` or `impl Display for Cache`.
+    pub name: String,
+    pub kind: SignatureKind,
+    /// Path of the file relative to the analysis path.
+    pub path: PathBuf,
+    /// First line of the item (1-indexed).
+    pub start_line: usize,
+    /// Last line of the signature or item (1-indexed).
+    pub end_line: usize,
+    /// Module containing the item, e.g. `my_crate::cache`.
+    pub module: String,
+    /// Whether the item is declared `pub`.
+    pub public: bool,
+    /// Type and const parameters, plus `impl Trait` arguments.
+    pub generic_params: usize,
+    /// Lifetime parameters.
+    pub lifetimes: usize,
+    /// Where-clause predicates.
+    pub where_predicates: usize,
+    /// Trait bounds anywhere in the signature.
+    pub bounds: usize,
+    /// Deepest nesting of trait bounds.
+    pub bound_depth: usize,
+    /// Associated type projections, bindings and declarations.
+    pub assoc_types: usize,
+}
+
+/// Impl blocks of a single type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeImpls {
+    /// Crate and name of the type, e.g. `my_crate::Cache`.
+    pub name: String,
+    /// Number of trait impls.
+    pub trait_impls: usize,
+    /// Number of inherent impl blocks.
+    pub inherent_impls: usize,
+    /// Implemented traits, in order of appearance.
+    pub traits: Vec<String>,
+    /// File of the first impl block.
+    pub path: PathBuf,
+    /// Line of the first impl block (1-indexed).
+    pub line: usize,
+}
+
+/// Generics metrics of a single module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleGenerics {
+    /// Path of the module.
+    pub module: String,
+    /// Number of generic items in the module.
+    pub generic_items: usize,
+    /// Sum of the generic parameters of its items.
+    pub generic_params: usize,
+    /// Sum of the where-clause predicates of its items.
+    pub where_predicates: usize,
+    /// Sum of the associated types of its items.
+    pub assoc_types: usize,
+    /// Deepest nesting of trait bounds in its items.
+    pub max_bound_depth: usize,
+    /// Blanket impls in the module, e.g. `Describe for T`.
+    pub blanket_impls: Vec<String>,
+    /// File of the first blanket impl, if any.
+    pub path: Option<PathBuf>,
+    /// Line of the first blanket impl (1-indexed), if any.
+    pub line: Option<usize>,
+}
+
+/// A metric with a configurable threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GenericsMetric {
+    GenericParams,
+    WherePredicates,
+    BoundDepth,
+    TraitImpls,
+    BlanketImpls,
+}
+
+impl std::fmt::Display for GenericsMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::GenericParams => "generic parameters",
+            Self::WherePredicates => "where-clause predicates",
+            Self::BoundDepth => "trait bound depth",
+            Self::TraitImpls => "trait impls",
+            Self::BlanketImpls => "blanket impls",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A breached generics threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericsViolation {
+    pub metric: GenericsMetric,
+    /// The item, type or module over the limit.
+    pub subject: String,
+    pub value: usize,
+    pub limit: usize,
+    /// Path of the file relative to the analysis path.
+    pub path: PathBuf,
+    /// First line of the subject (1-indexed).
+    pub start_line: usize,
+    /// Last line of the subject (1-indexed).
+    pub end_line: usize,
+}
+
+impl GenericsViolation {
+    /// Describes the violation, e.g. `'parse' has 5 generic parameters (limit 4)`.
+    pub fn message(&self) -> String {
+        format!(
+            "'{}' has {} {} (limit {})",
+            self.subject, self.value, self.metric, self.limit
+        )
+    }
+}
+
+/// Data type for generics analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericsData {
+    /// Items with generic parameters, lifetimes, bounds or associated types,
+    /// the most generic first.
+    pub items: Vec<GenericItem>,
+    /// Types with impl blocks, with the most trait impls first.
+    pub types: Vec<TypeImpls>,
+    /// Modules with generic items or blanket impls, sorted by name.
+    pub modules: Vec<ModuleGenerics>,
+    /// Breached thresholds.
+    pub violations: Vec<GenericsViolation>,
+    /// Number of analyzed files.
+    pub files: usize,
+    /// Number of measured signatures, generic or not.
+    pub signatures: usize,
+    /// The analyzed directory.
+    pub analysis_path: PathBuf,
+}
+
+impl GenericsData {
+    /// Returns the error to exit with when a threshold is breached.
+    fn thresholds_error(&self) -> Option<RaffError> {
+        let violations = self.violations.len();
+        (violations > 0).then(|| {
+            RaffError::analysis_error(
+                "generics",
+                format!("{violations} generics threshold(s) breached."),
+            )
+        })
+    }
+}
+
+impl ToFindings for GenericsData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.violations
+            .iter()
+            .map(|violation| Finding {
+                rule_id: "generics".to_string(),
+                rule_name: "Generics Rule".to_string(),
+                severity: Severity::Error,
+                message: violation.message(),
+                location: Some(Location::with_lines(
+                    path_uri(&violation.path),
+                    violation.start_line,
+                    violation.end_line,
+                )),
+                related_locations: Vec::new(),
+                help_uri: Some("https://github.com/liamwh/raff/docs/generics".to_string()),
+                fingerprint: Some(format!(
+                    "generics:{}:{}:{:?}",
+                    path_uri(&violation.path),
+                    violation.subject,
+                    violation.metric
+                )),
+            })
+            .collect()
+    }
+}
+
+/// Rule to measure the complexity of type signatures.
+#[derive(Debug, Default)]
+pub struct GenericsRule;
+
+impl Rule for GenericsRule {
+    type Config = GenericsArgs;
+    type Data = GenericsData;
+
+    fn name() -> &'static str {
+        "generics"
+    }
+
+    fn description() -> &'static str {
+        "Measures generic parameters, trait bounds, associated types and impls in type signatures"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+impl GenericsRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &GenericsArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &GenericsArgs) -> Result<GenericsData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &GenericsArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "generics")?,
+            };
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+            return match data.thresholds_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            };
+        }
+
+        let output = match args.output {
+            GenericsOutputFormat::Table => {
+                self.print_table(&data, args.top);
+                None
+            }
+            GenericsOutputFormat::Json => Some(serde_json::to_string_pretty(&data)?),
+            GenericsOutputFormat::Yaml => Some(serde_yaml::to_string(&data)?),
+            GenericsOutputFormat::Html => Some(html_utils::render_html_doc(
+                &format!("Generics Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            )),
+        };
+        if let Some(output) = output {
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+        }
+        match data.thresholds_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &GenericsArgs) -> Result<GenericsData> {
+        let analysis_path = &args.path;
+        if !analysis_path.exists() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Path not found",
+                analysis_path.display().to_string(),
+            ));
+        }
+        if !analysis_path.is_dir() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Provided path is not a directory",
+                analysis_path.display().to_string(),
+            ));
+        }
+
+        let mut all_rs_files: Vec<PathBuf> = Vec::new();
+        crate::file_utils::collect_rs_files(analysis_path, false, &mut all_rs_files, None)?;
+        all_rs_files.sort();
+
+        let crates = ComponentResolver::new(analysis_path, ComponentMode::Crate, &BTreeMap::new())?;
+
+        let mut scanned = Vec::new();
+        let mut test_module_paths = Vec::new();
+        for path_buf in &all_rs_files {
+            let path = path_buf
+                .strip_prefix(analysis_path)
+                .unwrap_or(path_buf)
+                .to_path_buf();
+            if !args.include_tests && is_test_target(analysis_path, &path) {
+                continue;
+            }
+            let content = fs::read_to_string(path_buf).map_err(|e| {
+                RaffError::io_error_with_source("read source file", path_buf.clone(), e)
+            })?;
+            let ast = syn::parse_file(&content)
+                .map_err(|e| RaffError::parse_error_with_file(path.clone(), e.to_string()))?;
+            let mut visitor = GenericsVisitor::new(args.include_tests, args.public_only);
+            visitor.visit_file(&ast);
+            let module_dir = child_module_dir(&path);
+            test_module_paths.extend(
+                visitor
+                    .test_modules
+                    .iter()
+                    .map(|module| module_dir.join(module)),
+            );
+            scanned.push((path, visitor));
+        }
+        // Files declared as `#[cfg(test)] mod name;` are test code in full
+        scanned.retain(|(path, _)| {
+            !test_module_paths
+                .iter()
+                .any(|module| path.starts_with(module) || *path == module.with_extension("rs"))
+        });
+
+        let files = scanned.len();
+        let mut signatures = 0;
+        let mut items = Vec::new();
+        let mut types: BTreeMap<String, TypeImpls> = BTreeMap::new();
+        let mut modules: BTreeMap<String, ModuleGenerics> = BTreeMap::new();
+        for (path, visitor) in scanned {
+            let file_module = crates
+                .module_of(&path)
+                .unwrap_or_else(|| relative_namespace(&path, Path::new("")).replace('/', "::"));
+            let module_path = |inline: &[String]| {
+                std::iter::once(file_module.clone())
+                    .chain(inline.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join("::")
+            };
+
+            signatures += visitor.signatures.len();
+            for raw in visitor.signatures {
+                let metrics = raw.metrics;
+                if !metrics.is_generic() {
+                    continue;
+                }
+                let module = module_path(&raw.modules);
+                let summary = modules
+                    .entry(module.clone())
+                    .or_insert_with(|| ModuleGenerics::new(&module));
+                summary.generic_items += 1;
+                summary.generic_params += metrics.generic_params;
+                summary.where_predicates += metrics.where_predicates;
+                summary.assoc_types += metrics.assoc_types;
+                summary.max_bound_depth = summary.max_bound_depth.max(metrics.bound_depth);
+                items.push(GenericItem {
+                    name: raw.name,
+                    kind: raw.kind,
+                    path: path.clone(),
+                    start_line: raw.start_line,
+                    end_line: raw.end_line,
+                    module,
+                    public: raw.public,
+                    generic_params: metrics.generic_params,
+                    lifetimes: metrics.lifetimes,
+                    where_predicates: metrics.where_predicates,
+                    bounds: metrics.bounds,
+                    bound_depth: metrics.bound_depth,
+                    assoc_types: metrics.assoc_types,
+                });
+            }
+
+            for raw in visitor.impls {
+                let module = module_path(&raw.modules);
+                if raw.blanket {
+                    let summary = modules
+                        .entry(module.clone())
+                        .or_insert_with(|| ModuleGenerics::new(&module));
+                    summary.blanket_impls.push(format!(
+                        "{} for {}",
+                        raw.trait_name.as_deref().unwrap_or_default(),
+                        raw.self_type
+                    ));
+                    if summary.path.is_none() {
+                        summary.path = Some(path.clone());
+                        summary.line = Some(raw.line);
+                    }
+                    continue;
+                }
+                let krate = module.split("::").next().unwrap_or_default();
+                let name = format!("{krate}::{}", raw.self_type);
+                let impls = types.entry(name.clone()).or_insert_with(|| TypeImpls {
+                    name,
+                    trait_impls: 0,
+                    inherent_impls: 0,
+                    traits: Vec::new(),
+                    path: path.clone(),
+                    line: raw.line,
+                });
+                match raw.trait_name {
+                    Some(trait_name) => {
+                        impls.trait_impls += 1;
+                        impls.traits.push(trait_name);
+                    }
+                    None => impls.inherent_impls += 1,
+                }
+            }
+        }
+
+        items.sort_by(|a, b| {
+            (
+                b.generic_params,
+                b.where_predicates,
+                b.bound_depth,
+                b.bounds,
+            )
+                .cmp(&(
+                    a.generic_params,
+                    a.where_predicates,
+                    a.bound_depth,
+                    a.bounds,
+                ))
+                .then_with(|| (&a.path, a.start_line).cmp(&(&b.path, b.start_line)))
+        });
+        let mut types: Vec<TypeImpls> = types.into_values().collect();
+        types.sort_by(|a, b| {
+            b.trait_impls
+                .cmp(&a.trait_impls)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let modules: Vec<ModuleGenerics> = modules.into_values().collect();
+        tracing::info!(
+            files,
+            signatures,
+            generic_items = items.len(),
+            "Measured type signatures"
+        );
+
+        let violations = threshold_violations(args, &items, &types, &modules);
+        Ok(GenericsData {
+            items,
+            types,
+            modules,
+            violations,
+            files,
+            signatures,
+            analysis_path: analysis_path.clone(),
+        })
+    }
+
+    fn print_table(&self, data: &GenericsData, top: usize) {
+        println!("\nGenerics Report Interpretation:");
+        println!("-------------------------------");
+        println!("- Params: Type and const parameters, plus `impl Trait` arguments.");
+        println!("- Where: Where-clause predicates.");
+        println!("- Bounds: Trait bounds anywhere in the signature.");
+        println!("- Depth: Deepest nesting of trait bounds.");
+        println!("- Assoc: Associated type projections, bindings and declarations.");
+        println!("- Blanket impls implement a trait for every type meeting its bounds.");
+        println!("-------------------------------");
+        println!(
+            "\n{} of {} signatures in {} files are generic, {} threshold(s) breached.",
+            data.items.len(),
+            data.signatures,
+            data.files,
+            data.violations.len()
+        );
+
+        if !data.items.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row![
+                "Item",
+                "Kind",
+                "Location",
+                "Params",
+                "Lifetimes",
+                "Where",
+                "Bounds",
+                "Depth",
+                "Assoc"
+            ]);
+            for item in data.items.iter().take(top) {
+                table.add_row(row![
+                    item.name,
+                    item.kind,
+                    format!("{}:{}", path_uri(&item.path), item.start_line),
+                    r->item.generic_params,
+                    r->item.lifetimes,
+                    r->item.where_predicates,
+                    r->item.bounds,
+                    r->item.bound_depth,
+                    r->item.assoc_types
+                ]);
+            }
+            println!("\nMost Generic Items:");
+            table.printstd();
+        }
+
+        if data.types.iter().any(|t| t.trait_impls > 0) {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Type", "Trait Impls", "Inherent Impls", "Traits"]);
+            for impls in data.types.iter().filter(|t| t.trait_impls > 0).take(top) {
+                table.add_row(row![
+                    impls.name,
+                    r->impls.trait_impls,
+                    r->impls.inherent_impls,
+                    impls.traits.join(", ")
+                ]);
+            }
+            println!("\nTrait Impls per Type:");
+            table.printstd();
+        }
+
+        if !data.modules.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row![
+                "Module",
+                "Generic Items",
+                "Params",
+                "Where",
+                "Assoc",
+                "Max Depth",
+                "Blanket Impls"
+            ]);
+            for module in &data.modules {
+                table.add_row(row![
+                    module.module,
+                    r->module.generic_items,
+                    r->module.generic_params,
+                    r->module.where_predicates,
+                    r->module.assoc_types,
+                    r->module.max_bound_depth,
+                    module.blanket_impls.join(", ")
+                ]);
+            }
+            println!("\nGenerics by Module:");
+            table.printstd();
+        }
+
+        if !data.violations.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Location", "Subject", "Metric", "Value", "Limit"]);
+            for violation in &data.violations {
+                table.add_row(row![
+                    format!("{}:{}", path_uri(&violation.path), violation.start_line),
+                    violation.subject,
+                    violation.metric,
+                    r->violation.value,
+                    r->violation.limit
+                ]);
+            }
+            println!("\nThreshold Violations:");
+            table.printstd();
+        }
+    }
+
+    fn render_html_body(&self, data: &GenericsData) -> Markup {
+        let explanations = [
+            (
+                "Params",
+                "Type and const parameters, plus impl Trait arguments.",
+            ),
+            ("Where", "Where-clause predicates."),
+            ("Bounds", "Trait bounds anywhere in the signature."),
+            ("Depth", "Deepest nesting of trait bounds."),
+            (
+                "Assoc",
+                "Associated type projections, bindings and declarations.",
+            ),
+        ];
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { (format!("{} of {} signatures in {} files are generic", data.items.len(), data.signatures, data.files)) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Item" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="string" { "Kind" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="string" { "Location" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Params" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Lifetimes" }
+                        th class="sortable-header" data-column-index="5" data-sort-type="number" { "Where" }
+                        th class="sortable-header" data-column-index="6" data-sort-type="number" { "Bounds" }
+                        th class="sortable-header" data-column-index="7" data-sort-type="number" { "Depth" }
+                        th class="sortable-header" data-column-index="8" data-sort-type="number" { "Assoc" }
+                    }
+                }
+                tbody {
+                    @for item in &data.items {
+                        tr {
+                            td { (item.name) }
+                            td { (item.kind) }
+                            td { (format!("{}:{}", path_uri(&item.path), item.start_line)) }
+                            td { (item.generic_params) }
+                            td { (item.lifetimes) }
+                            td { (item.where_predicates) }
+                            td { (item.bounds) }
+                            td { (item.bound_depth) }
+                            td { (item.assoc_types) }
+                        }
+                    }
+                }
+            }
+            @if data.types.iter().any(|t| t.trait_impls > 0) {
+                h2 { "Trait Impls per Type" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Type" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="number" { "Trait Impls" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Inherent Impls" }
+                            th { "Traits" }
+                        }
+                    }
+                    tbody {
+                        @for impls in data.types.iter().filter(|t| t.trait_impls > 0) {
+                            tr {
+                                td { (impls.name) }
+                                td { (impls.trait_impls) }
+                                td { (impls.inherent_impls) }
+                                td { (impls.traits.join(", ")) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !data.modules.is_empty() {
+                h2 { "Generics by Module" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Module" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="number" { "Generic Items" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Params" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Where" }
+                            th class="sortable-header" data-column-index="4" data-sort-type="number" { "Assoc" }
+                            th class="sortable-header" data-column-index="5" data-sort-type="number" { "Max Depth" }
+                            th { "Blanket Impls" }
+                        }
+                    }
+                    tbody {
+                        @for module in &data.modules {
+                            tr {
+                                td { (module.module) }
+                                td { (module.generic_items) }
+                                td { (module.generic_params) }
+                                td { (module.where_predicates) }
+                                td { (module.assoc_types) }
+                                td { (module.max_bound_depth) }
+                                td { (module.blanket_impls.join(", ")) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !data.violations.is_empty() {
+                h2 { "Threshold Violations" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Location" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Subject" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Metric" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Value" }
+                            th class="sortable-header" data-column-index="4" data-sort-type="number" { "Limit" }
+                        }
+                    }
+                    tbody {
+                        @for violation in &data.violations {
+                            tr {
+                                td { (format!("{}:{}", path_uri(&violation.path), violation.start_line)) }
+                                td { (violation.subject) }
+                                td { (violation.metric) }
+                                td { (violation.value) }
+                                td { (violation.limit) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ModuleGenerics {
+    fn new(module: &str) -> Self {
+        Self {
+            module: module.to_string(),
+            generic_items: 0,
+            generic_params: 0,
+            where_predicates: 0,
+            assoc_types: 0,
+            max_bound_depth: 0,
+            blanket_impls: Vec::new(),
+            path: None,
+            line: None,
+        }
+    }
+}
+
+/// Checks the items, types and modules against the configured thresholds.
+fn threshold_violations(
+    args: &GenericsArgs,
+    items: &[GenericItem],
+    types: &[TypeImpls],
+    modules: &[ModuleGenerics],
+) -> Vec<GenericsViolation> {
+    let mut violations = Vec::new();
+    let item_limits = [
+        (GenericsMetric::GenericParams, args.max_generic_params),
+        (GenericsMetric::WherePredicates, args.max_where_predicates),
+        (GenericsMetric::BoundDepth, args.max_bound_depth),
+    ];
+    for item in items {
+        for (metric, limit) in item_limits {
+            let value = match metric {
+                GenericsMetric::GenericParams => item.generic_params,
+                GenericsMetric::WherePredicates => item.where_predicates,
+                _ => item.bound_depth,
+            };
+            if let Some(limit) = limit
+                && value > limit
+            {
+                violations.push(GenericsViolation {
+                    metric,
+                    subject: item.name.clone(),
+                    value,
+                    limit,
+                    path: item.path.clone(),
+                    start_line: item.start_line,
+                    end_line: item.end_line,
+                });
+            }
+        }
+    }
+    if let Some(limit) = args.max_trait_impls {
+        for impls in types.iter().filter(|t| t.trait_impls > limit) {
+            violations.push(GenericsViolation {
+                metric: GenericsMetric::TraitImpls,
+                subject: impls.name.clone(),
+                value: impls.trait_impls,
+                limit,
+                path: impls.path.clone(),
+                start_line: impls.line,
+                end_line: impls.line,
+            });
+        }
+    }
+    if let Some(limit) = args.max_blanket_impls {
+        for module in modules.iter().filter(|m| m.blanket_impls.len() > limit) {
+            let line = module.line.unwrap_or(1);
+            violations.push(GenericsViolation {
+                metric: GenericsMetric::BlanketImpls,
+                subject: module.module.clone(),
+                value: module.blanket_impls.len(),
+                limit,
+                path: module.path.clone().unwrap_or_default(),
+                start_line: line,
+                end_line: line,
+            });
+        }
+    }
+    violations
+}
+
+/// Signature metrics collected by [`BoundVisitor`].
+#[derive(Debug, Default, Clone, Copy)]
+struct SignatureMetrics {
+    generic_params: usize,
+    lifetimes: usize,
+    where_predicates: usize,
+    bounds: usize,
+    bound_depth: usize,
+    assoc_types: usize,
+}
+
+impl SignatureMetrics {
+    fn is_generic(&self) -> bool {
+        self.generic_params
+            + self.lifetimes
+            + self.where_predicates
+            + self.bounds
+            + self.assoc_types
+            > 0
+    }
+}
+
+/// Measures trait bounds, associated types and `impl Trait` arguments in the
+/// parts of a signature it is shown.
+struct BoundVisitor<'a> {
+    /// Type parameters in scope, including `Self`.
+    params: &'a [String],
+    /// Whether `impl Trait` types count as generic parameters.
+    in_arguments: bool,
+    depth: usize,
+    metrics: SignatureMetrics,
+}
+
+impl<'a> BoundVisitor<'a> {
+    fn new(params: &'a [String]) -> Self {
+        Self {
+            params,
+            in_arguments: false,
+            depth: 0,
+            metrics: SignatureMetrics::default(),
+        }
+    }
+
+    /// Counts the parameters and where-clause of `generics` and visits their
+    /// bounds.
+    fn visit_declared_generics(&mut self, generics: &Generics) {
+        for param in &generics.params {
+            match param {
+                GenericParam::Lifetime(_) => self.metrics.lifetimes += 1,
+                GenericParam::Type(_) | GenericParam::Const(_) => {
+                    self.metrics.generic_params += 1;
+                }
+            }
+        }
+        self.metrics.where_predicates += generics
+            .where_clause
+            .as_ref()
+            .map_or(0, |clause| clause.predicates.len());
+        self.visit_generics(generics);
+    }
+
+    /// Measures the generics, arguments and return type of a function.
+    fn visit_signature(&mut self, sig: &Signature) {
+        self.visit_declared_generics(&sig.generics);
+        self.in_arguments = true;
+        for input in &sig.inputs {
+            if let FnArg::Typed(arg) = input {
+                self.visit_type(&arg.ty);
+            }
+        }
+        self.in_arguments = false;
+        if let ReturnType::Type(_, ty) = &sig.output {
+            self.visit_type(ty);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for BoundVisitor<'_> {
+    fn visit_trait_bound(&mut self, node: &'ast TraitBound) {
+        self.metrics.bounds += 1;
+        self.depth += 1;
+        self.metrics.bound_depth = self.metrics.bound_depth.max(self.depth);
+        syn::visit::visit_trait_bound(self, node);
+        self.depth -= 1;
+    }
+
+    fn visit_type_impl_trait(&mut self, node: &'ast TypeImplTrait) {
+        if self.in_arguments {
+            self.metrics.generic_params += 1;
+        }
+        syn::visit::visit_type_impl_trait(self, node);
+    }
+
+    /// Counts `T::Item` and `<T as Trait>::Output` projections.
+    fn visit_type_path(&mut self, node: &'ast TypePath) {
+        let projection = node.qself.is_some()
+            || (node.path.segments.len() > 1
+                && self
+                    .params
+                    .iter()
+                    .any(|param| node.path.segments[0].ident == param));
+        if projection {
+            self.metrics.assoc_types += 1;
+        }
+        syn::visit::visit_type_path(self, node);
+    }
+
+    /// Counts bindings such as `Iterator<Item = u8>`.
+    fn visit_assoc_type(&mut self, node: &'ast AssocType) {
+        self.metrics.assoc_types += 1;
+        syn::visit::visit_assoc_type(self, node);
+    }
+
+    /// Counts constraints such as `Iterator<Item: Display>`.
+    fn visit_constraint(&mut self, node: &'ast Constraint) {
+        self.metrics.assoc_types += 1;
+        syn::visit::visit_constraint(self, node);
+    }
+}
+
+/// A signature found by [`GenericsVisitor`], before it is placed in a module.
+#[derive(Debug)]
+struct RawSignature {
+    name: String,
+    kind: SignatureKind,
+    start_line: usize,
+    end_line: usize,
+    /// Inline modules enclosing the item, outermost first.
+    modules: Vec<String>,
+    public: bool,
+    metrics: SignatureMetrics,
+}
+
+/// An impl block found by [`GenericsVisitor`].
+#[derive(Debug)]
+struct RawImpl {
+    self_type: String,
+    trait_name: Option<String>,
+    /// Whether the impl is for a bare type parameter of the impl.
+    blanket: bool,
+    line: usize,
+    /// Inline modules enclosing the impl, outermost first.
+    modules: Vec<String>,
+}
+
+/// Collects the signatures and impl blocks of a single file.
+struct GenericsVisitor {
+    include_tests: bool,
+    public_only: bool,
+    /// Names of the enclosing inline modules.
+    modules: Vec<String>,
+    /// Name of the enclosing impl block's type or trait.
+    owner: Option<String>,
+    /// Type parameters of the enclosing impl block or trait, and `Self`.
+    outer_params: Vec<String>,
+    signatures: Vec<RawSignature>,
+    impls: Vec<RawImpl>,
+    /// Out-of-line `#[cfg(test)]` modules declared in the file, as
+    /// `/`-separated paths relative to the file's module.
+    test_modules: Vec<String>,
+}
+
+impl GenericsVisitor {
+    fn new(include_tests: bool, public_only: bool) -> Self {
+        Self {
+            include_tests,
+            public_only,
+            modules: Vec::new(),
+            owner: None,
+            outer_params: Vec::new(),
+            signatures: Vec::new(),
+            impls: Vec::new(),
+            test_modules: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if `attrs` mark test code that should be skipped.
+    fn skips(&self, attrs: &[Attribute]) -> bool {
+        !self.include_tests && attrs.iter().any(is_test_attr)
+    }
+
+    /// Returns the type parameters in scope for `generics`.
+    fn params_with(&self, generics: &Generics) -> Vec<String> {
+        let mut params = self.outer_params.clone();
+        params.extend(generics.type_params().map(|param| param.ident.to_string()));
+        params
+    }
+
+    /// Measures a signature with `measure` and records it, unless only
+    /// public items are measured and it is not public.
+    fn record(
+        &mut self,
+        name: String,
+        kind: SignatureKind,
+        span: proc_macro2::Span,
+        public: bool,
+        generics: &Generics,
+        measure: impl FnOnce(&mut BoundVisitor<'_>),
+    ) {
+        if self.public_only && !public && kind != SignatureKind::Impl {
+            return;
+        }
+        let params = self.params_with(generics);
+        let mut visitor = BoundVisitor::new(&params);
+        measure(&mut visitor);
+        self.signatures.push(RawSignature {
+            name,
+            kind,
+            start_line: span.start().line,
+            end_line: span.end().line,
+            modules: self.modules.clone(),
+            public,
+            metrics: visitor.metrics,
+        });
+    }
+
+    /// Names `name` after the enclosing impl block or trait, if any.
+    fn qualified(&self, name: &str) -> String {
+        match &self.owner {
+            Some(owner) => format!("{owner}::{name}"),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GenericsVisitor {
+    fn visit_item(&mut self, item: &'ast Item) {
+        if self.skips(item_attrs(item)) {
+            if let Item::Mod(item_mod) = item
+                && item_mod.content.is_none()
+            {
+                let mut path = self.modules.clone();
+                path.push(item_mod.ident.to_string());
+                self.test_modules.push(path.join("/"));
+            }
+            return;
+        }
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        self.modules.push(node.ident.to_string());
+        syn::visit::visit_item_mod(self, node);
+        self.modules.pop();
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.record(
+            node.sig.ident.to_string(),
+            SignatureKind::Function,
+            node.sig.span(),
+            is_pub(&node.vis),
+            &node.sig.generics,
+            |visitor| visitor.visit_signature(&node.sig),
+        );
+        // Nested items are measured on their own; the body is not a signature
+        syn::visit::visit_block(self, &node.block);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        self.record(
+            node.ident.to_string(),
+            SignatureKind::Struct,
+            node.span(),
+            is_pub(&node.vis),
+            &node.generics,
+            |visitor| visitor.visit_declared_generics(&node.generics),
+        );
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast ItemEnum) {
+        self.record(
+            node.ident.to_string(),
+            SignatureKind::Enum,
+            node.span(),
+            is_pub(&node.vis),
+            &node.generics,
+            |visitor| visitor.visit_declared_generics(&node.generics),
+        );
+    }
+
+    fn visit_item_union(&mut self, node: &'ast ItemUnion) {
+        self.record(
+            node.ident.to_string(),
+            SignatureKind::Union,
+            node.span(),
+            is_pub(&node.vis),
+            &node.generics,
+            |visitor| visitor.visit_declared_generics(&node.generics),
+        );
+    }
+
+    fn visit_item_type(&mut self, node: &'ast ItemType) {
+        self.record(
+            node.ident.to_string(),
+            SignatureKind::TypeAlias,
+            node.span(),
+            is_pub(&node.vis),
+            &node.generics,
+            |visitor| {
+                visitor.visit_declared_generics(&node.generics);
+                visitor.visit_type(&node.ty);
+            },
+        );
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        let public = is_pub(&node.vis);
+        self.record(
+            node.ident.to_string(),
+            SignatureKind::Trait,
+            node.span(),
+            public,
+            &node.generics,
+            |visitor| {
+                visitor.visit_declared_generics(&node.generics);
+                for supertrait in &node.supertraits {
+                    visitor.visit_type_param_bound(supertrait);
+                }
+                for item in &node.items {
+                    if let TraitItem::Type(assoc) = item {
+                        visitor.metrics.assoc_types += 1;
+                        for bound in &assoc.bounds {
+                            visitor.visit_type_param_bound(bound);
+                        }
+                    }
+                }
+            },
+        );
+
+        let owner = self.owner.replace(node.ident.to_string());
+        let mut outer_params = self.params_with(&node.generics);
+        outer_params.push("Self".to_string());
+        let outer_params = std::mem::replace(&mut self.outer_params, outer_params);
+        for item in &node.items {
+            if let TraitItem::Fn(method) = item {
+                self.visit_trait_method(method, public);
+            }
+        }
+        self.outer_params = outer_params;
+        self.owner = owner;
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        let self_type = type_name(&node.self_ty);
+        let trait_name = node.trait_.as_ref().map(|(_, path, _)| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default()
+        });
+        let blanket = trait_name.is_some()
+            && node
+                .generics
+                .type_params()
+                .any(|param| param.ident == self_type);
+        let name = match &trait_name {
+            Some(trait_name) => format!("impl {trait_name} for {self_type}"),
+            None => format!("impl {self_type}"),
+        };
+        let span = node.span();
+        self.record(
+            name,
+            SignatureKind::Impl,
+            span,
+            false,
+            &node.generics,
+            |visitor| {
+                visitor.visit_declared_generics(&node.generics);
+                if let Some((_, path, _)) = &node.trait_ {
+                    visitor.visit_path(path);
+                }
+                visitor.visit_type(&node.self_ty);
+            },
+        );
+        self.impls.push(RawImpl {
+            self_type: self_type.clone(),
+            trait_name: trait_name.clone(),
+            blanket,
+            line: span.start().line,
+            modules: self.modules.clone(),
+        });
+
+        // Methods of trait impls follow the trait's signatures
+        if trait_name.is_some() {
+            return;
+        }
+        let owner = self.owner.replace(self_type);
+        let mut outer_params = self.params_with(&node.generics);
+        outer_params.push("Self".to_string());
+        let outer_params = std::mem::replace(&mut self.outer_params, outer_params);
+        for item in &node.items {
+            if let ImplItem::Fn(method) = item {
+                self.visit_impl_method(method);
+            }
+        }
+        self.outer_params = outer_params;
+        self.owner = owner;
+    }
+}
+
+impl GenericsVisitor {
+    fn visit_impl_method(&mut self, node: &ImplItemFn) {
+        if self.skips(&node.attrs) {
+            return;
+        }
+        self.record(
+            self.qualified(&node.sig.ident.to_string()),
+            SignatureKind::Method,
+            node.sig.span(),
+            is_pub(&node.vis),
+            &node.sig.generics,
+            |visitor| visitor.visit_signature(&node.sig),
+        );
+    }
+
+    fn visit_trait_method(&mut self, node: &TraitItemFn, public: bool) {
+        self.record(
+            self.qualified(&node.sig.ident.to_string()),
+            SignatureKind::TraitMethod,
+            node.sig.span(),
+            public,
+            &node.sig.generics,
+            |visitor| visitor.visit_signature(&node.sig),
+        );
+    }
+}
+
+fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = r#"pub fn plain(x: u32) -> u32 {
+    x
+}
+
+pub fn apply<F, T: Clone>(f: F, value: impl Into<T>) -> T
+where
+    F: Fn(T) -> Box<dyn Iterator<Item = T>>,
+{
+    todo!()
+}
+
+pub trait Store {
+    type Key: Ord;
+    type Value;
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value>;
+}
+
+pub trait Describe {}
+
+pub struct Wrapper<'a, T>(&'a T);
+
+impl<T: std::fmt::Display> Describe for T {}
+
+impl<'a, T> Clone for Wrapper<'a, T> {
+    fn clone<U: Copy>(&self) -> Self {
+        Wrapper(self.0)
+    }
+}
+
+impl<'a, T> PartialEq for Wrapper<'a, T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<'a, T> Wrapper<'a, T> {
+    pub fn get<U: From<&'a T>>(&self) -> U {
+        U::from(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    fn generic<T: Clone>(_: T) {}
+}
+"#;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn args(path: &Path) -> GenericsArgs {
+        GenericsArgs {
+            path: path.to_path_buf(),
+            max_generic_params: None,
+            max_where_predicates: None,
+            max_bound_depth: None,
+            max_trait_impls: None,
+            max_blanket_impls: None,
+            public_only: false,
+            include_tests: false,
+            top: 20,
+            output: GenericsOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    fn metrics(
+        visitor: &GenericsVisitor,
+        name: &str,
+    ) -> (usize, usize, usize, usize, usize, usize) {
+        let signature = visitor
+            .signatures
+            .iter()
+            .find(|signature| signature.name == name)
+            .unwrap_or_else(|| panic!("no signature named {name}"));
+        let m = signature.metrics;
+        (
+            m.generic_params,
+            m.lifetimes,
+            m.where_predicates,
+            m.bounds,
+            m.bound_depth,
+            m.assoc_types,
+        )
+    }
+
+    #[test]
+    fn test_visitor_measures_signatures() {
+        let mut visitor = GenericsVisitor::new(false, false);
+        visitor.visit_file(&syn::parse_file(SOURCE).unwrap());
+
+        assert_eq!(metrics(&visitor, "plain"), (0, 0, 0, 0, 0, 0));
+        // F, T and `impl Into<T>`; Fn -> dyn Iterator nests two bounds deep
+        assert_eq!(metrics(&visitor, "apply"), (3, 0, 1, 4, 2, 1));
+        assert_eq!(metrics(&visitor, "Store"), (0, 0, 0, 1, 1, 2));
+        assert_eq!(metrics(&visitor, "Store::get"), (0, 0, 0, 0, 0, 2));
+        assert_eq!(metrics(&visitor, "Wrapper"), (1, 1, 0, 0, 0, 0));
+        assert_eq!(metrics(&visitor, "Wrapper::get"), (1, 0, 0, 1, 1, 0));
+        assert!(
+            visitor
+                .signatures
+                .iter()
+                .all(|signature| signature.name != "Wrapper::clone" && signature.name != "generic")
+        );
+        assert_eq!(visitor.test_modules, Vec::<String>::new());
+
+        let impls: Vec<(&str, Option<&str>, bool)> = visitor
+            .impls
+            .iter()
+            .map(|i| (i.self_type.as_str(), i.trait_name.as_deref(), i.blanket))
+            .collect();
+        assert_eq!(
+            impls,
+            vec![
+                ("T", Some("Describe"), true),
+                ("Wrapper", Some("Clone"), false),
+                ("Wrapper", Some("PartialEq"), false),
+                ("Wrapper", None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analyze_reports_threshold_violations() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "Cargo.toml", "[package]\nname = \"kit\"\n");
+        write(temp_dir.path(), "src/lib.rs", SOURCE);
+        let mut args = args(temp_dir.path());
+        args.max_generic_params = Some(2);
+        args.max_bound_depth = Some(1);
+        args.max_trait_impls = Some(1);
+        args.max_blanket_impls = Some(0);
+
+        let data = GenericsRule::new().analyze(&args).unwrap();
+
+        assert_eq!(data.items[0].name, "apply");
+        assert_eq!(data.types[0].name, "kit::Wrapper");
+        assert_eq!(data.types[0].traits, vec!["Clone", "PartialEq"]);
+        assert_eq!(data.modules.len(), 1);
+        assert_eq!(data.modules[0].blanket_impls, vec!["Describe for T"]);
+
+        let violations: Vec<(GenericsMetric, &str, usize)> = data
+            .violations
+            .iter()
+            .map(|v| (v.metric, v.subject.as_str(), v.value))
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                (GenericsMetric::GenericParams, "apply", 3),
+                (GenericsMetric::BoundDepth, "apply", 2),
+                (GenericsMetric::TraitImpls, "kit::Wrapper", 2),
+                (GenericsMetric::BlanketImpls, "kit", 1),
+            ]
+        );
+        assert_eq!(data.violations[3].start_line, 22);
+        assert_eq!(data.to_findings().len(), 4);
+        assert!(data.thresholds_error().is_some());
+
+        args.public_only = true;
+        let data = GenericsRule::new().analyze(&args).unwrap();
+        assert!(
+            data.items
+                .iter()
+                .all(|item| item.public || item.kind == SignatureKind::Impl)
+        );
+    }
+}
@@ -4,7 +4,8 @@
 //! particularly for discovering staged files for pre-commit hook analysis.
 
 use crate::error::{RaffError, Result};
-use std::path::PathBuf;
+use git2::Repository;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use tracing::instrument;
@@ -138,6 +139,68 @@ pub fn filter_rust_files(files: &[PathBuf]) -> Vec<PathBuf> {
         .collect()
 }
 
+/// Applies `f` to every item across `jobs` worker threads, returning the
+/// results in input order.
+///
+/// `git2::Repository` handles must not be shared between threads, so each
+/// worker opens its own handle on the repository at `repo_path`. With
+/// `jobs <= 1` the work runs on the calling thread.
+///
+/// # Errors
+///
+/// Returns the first error produced by `f`, an error if a worker cannot open
+/// the repository, or an error if a worker thread panics.
+pub fn map_with_repository<T, R, F>(
+    repo_path: &Path,
+    items: &[T],
+    jobs: usize,
+    f: F,
+) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&Repository, &T) -> Result<R> + Sync,
+{
+    let open = || {
+        Repository::open(repo_path).map_err(|e| {
+            RaffError::git_error_with_repo(
+                format!("open Git repository: {}", e),
+                repo_path.to_path_buf(),
+            )
+        })
+    };
+    if items.is_empty() {
+        return Ok(Vec::new());
+    }
+    let jobs = jobs.clamp(1, items.len());
+    if jobs == 1 {
+        let repo = open()?;
+        return items.iter().map(|item| f(&repo, item)).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(jobs);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let (open, f) = (&open, &f);
+                scope.spawn(move || -> Result<Vec<R>> {
+                    let repo = open()?;
+                    chunk.iter().map(|item| f(&repo, item)).collect()
+                })
+            })
+            .collect();
+        let mut results = Vec::with_capacity(items.len());
+        for worker in workers {
+            let chunk = worker
+                .join()
+                .map_err(|_| RaffError::git_error("Git worker thread panicked"))??;
+            results.extend(chunk);
+        }
+        Ok(results)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
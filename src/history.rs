@@ -0,0 +1,795 @@
+//! Git history walking shared by history-based rules.
+//!
+//! Volatility, contributor reporting and any other rule that mines Git history
+//! select their commits through this module, so that `--since`, `--until`,
+//! `--from`, `--to`, `--range` and `--branch` behave identically everywhere.
+//!
+//! # Overview
+//!
+//! A [`HistoryArgs`] (as parsed from the command line and merged with the
+//! configuration file) is resolved against a repository into a
+//! [`HistoryWindow`]. The window knows which commit the walk starts from, which
+//! commits are excluded as ancestors of the range start, and which date bounds
+//! apply.
+//!
+//! The window also carries a [`HistoryFilter`], which drops noise from the
+//! analysis: changes to generated or lock files, commits by bots, mass
+//! formatting commits matched by message, and commits listed in a
+//! `.git-blame-ignore-revs`-style file.
+//!
+//! ```no_run
+//! # fn main() -> raff_core::error::Result<()> {
+//! use git2::Repository;
+//! use raff_core::cli::HistoryArgs;
+//! use raff_core::history::HistoryWindow;
+//!
+//! let repo = Repository::open(".")?;
+//! let args = HistoryArgs {
+//!     range: Some("v0.1.0..HEAD".to_string()),
+//!     ..HistoryArgs::default()
+//! };
+//! let window = HistoryWindow::resolve(&repo, &args, false)?;
+//! for oid in window.commits(&repo)? {
+//!     println!("{oid}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use git2::{Commit, Oid, Repository, RevparseMode, Revwalk, Sort};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::HistoryArgs;
+use crate::error::{RaffError, Result};
+
+/// A resolved selection of commits to analyze.
+#[derive(Debug, Clone)]
+pub struct HistoryWindow {
+    /// The commit the walk starts from (the newest commit considered).
+    pub tip: Oid,
+    /// Commit whose ancestors (including itself) are excluded from the walk.
+    pub boundary: Option<Oid>,
+    /// Inclusive lower bound on commit time, in seconds since the epoch.
+    pub since: Option<i64>,
+    /// Exclusive upper bound on commit time, in seconds since the epoch.
+    pub until: Option<i64>,
+    /// Whether commits with more than one parent are skipped.
+    pub skip_merges: bool,
+    /// Path, author, message and revision filters.
+    pub filter: HistoryFilter,
+}
+
+impl HistoryWindow {
+    /// Resolves the given history arguments against `repo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RaffError::InvalidInput`] when a revision cannot be resolved,
+    /// a date is malformed, or mutually exclusive options are combined.
+    pub fn resolve(repo: &Repository, args: &HistoryArgs, skip_merges: bool) -> Result<Self> {
+        if args.range.is_some() && (args.from.is_some() || args.to.is_some()) {
+            return Err(RaffError::invalid_input_with_arg(
+                "--range cannot be combined with --from or --to",
+                "--range",
+            ));
+        }
+        if args.branch.is_some() && (args.to.is_some() || args.range.is_some()) {
+            return Err(RaffError::invalid_input_with_arg(
+                "--branch cannot be combined with --to or --range",
+                "--branch",
+            ));
+        }
+
+        let (from, to) = match &args.range {
+            Some(range) => resolve_range(repo, range)?,
+            None => {
+                let from = args
+                    .from
+                    .as_deref()
+                    .map(|rev| resolve_commit(repo, rev, "--from"))
+                    .transpose()?;
+                let to = match (&args.to, &args.branch) {
+                    (Some(rev), _) => Some(resolve_commit(repo, rev, "--to")?),
+                    (None, Some(branch)) => Some(resolve_commit(repo, branch, "--branch")?),
+                    (None, None) => None,
+                };
+                (from, to)
+            }
+        };
+
+        let tip = match to {
+            Some(oid) => oid,
+            None => repo.head()?.peel_to_commit()?.id(),
+        };
+
+        let since = args
+            .since
+            .as_deref()
+            .map(|date| parse_date(date, "--since"))
+            .transpose()?;
+        let until = args
+            .until
+            .as_deref()
+            .map(|date| parse_date(date, "--until").map(|ts| ts + SECONDS_PER_DAY))
+            .transpose()?;
+
+        if let (Some(since), Some(until)) = (since, until)
+            && since >= until
+        {
+            return Err(RaffError::invalid_input_with_arg(
+                "--since must be earlier than or equal to --until",
+                "--since",
+            ));
+        }
+
+        let filter = HistoryFilter::new(repo, args)?;
+
+        Ok(Self {
+            tip,
+            boundary: from,
+            since,
+            until,
+            skip_merges,
+            filter,
+        })
+    }
+
+    /// Returns a revision walk over the window, oldest commit first.
+    ///
+    /// The walk honours the tip and boundary but not the date bounds or merge
+    /// skipping; use [`HistoryWindow::includes`] or [`HistoryWindow::commits`]
+    /// for those.
+    pub fn revwalk<'repo>(&self, repo: &'repo Repository) -> Result<Revwalk<'repo>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(self.tip)?;
+        if let Some(boundary) = self.boundary {
+            revwalk.hide(boundary)?;
+        }
+        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+        Ok(revwalk)
+    }
+
+    /// Returns a revision walk over every ancestor of the tip, oldest first,
+    /// ignoring the boundary and the date bounds.
+    ///
+    /// Useful for questions about the full history leading up to the window,
+    /// such as when a crate first appeared.
+    pub fn full_revwalk<'repo>(&self, repo: &'repo Repository) -> Result<Revwalk<'repo>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(self.tip)?;
+        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+        Ok(revwalk)
+    }
+
+    /// Returns `true` if `commit` falls inside the date bounds, is not a
+    /// skipped merge commit and is not rejected by the [`HistoryFilter`].
+    #[must_use]
+    pub fn includes(&self, commit: &Commit<'_>) -> bool {
+        let time = commit.time().seconds();
+        if self.since.is_some_and(|since| time < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| time >= until) {
+            return false;
+        }
+        if self.skip_merges && commit.parent_count() > 1 {
+            return false;
+        }
+        self.filter.includes_commit(commit)
+    }
+
+    /// Collects the ids of all commits in the window, oldest first.
+    pub fn commits(&self, repo: &Repository) -> Result<Vec<Oid>> {
+        let mut commits = Vec::new();
+        for oid in self.revwalk(repo)? {
+            let oid = oid?;
+            let commit = repo.find_commit(oid)?;
+            if self.includes(&commit) {
+                commits.push(oid);
+            } else {
+                tracing::trace!(commit_id = %oid, "Commit is outside the history window, skipping.");
+            }
+        }
+        tracing::debug!(count = commits.len(), "Collected commits in history window");
+        Ok(commits)
+    }
+
+    /// Returns key/value pairs identifying this window, for use in cache keys.
+    #[must_use]
+    pub fn cache_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![("history_tip".to_string(), self.tip.to_string())];
+        if let Some(boundary) = self.boundary {
+            params.push(("history_boundary".to_string(), boundary.to_string()));
+        }
+        if let Some(since) = self.since {
+            params.push(("history_since".to_string(), since.to_string()));
+        }
+        if let Some(until) = self.until {
+            params.push(("history_until".to_string(), until.to_string()));
+        }
+        if self.skip_merges {
+            params.push(("skip_merges".to_string(), "true".to_string()));
+        }
+        params.extend(self.filter.cache_params());
+        params
+    }
+}
+
+/// Filters that exclude noise from history analysis.
+///
+/// Path patterns are globs matched against repository-relative paths; a
+/// pattern without a `/` also matches the file name at any depth, so
+/// `Cargo.lock` excludes every lock file in a workspace. Author patterns are
+/// matched case-insensitively against both the author name and email, with
+/// `*` as the only wildcard. Message patterns are regular expressions.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    include_paths: Option<PathPatterns>,
+    exclude_paths: Option<PathPatterns>,
+    exclude_authors: Vec<String>,
+    exclude_messages: Option<RegexSet>,
+    ignored_revs: HashSet<Oid>,
+    ignore_revs_file: Option<PathBuf>,
+}
+
+/// A compiled set of path globs, kept alongside its source patterns.
+#[derive(Debug, Clone)]
+struct PathPatterns {
+    patterns: Vec<String>,
+    full_path: GlobSet,
+    file_name: GlobSet,
+}
+
+impl PathPatterns {
+    fn new(patterns: &[String], flag: &str) -> Result<Option<Self>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut full_path = GlobSetBuilder::new();
+        let mut file_name = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).map_err(|e| {
+                RaffError::invalid_input_with_arg(
+                    format!("Invalid {} glob '{}': {}", flag, pattern, e),
+                    pattern.clone(),
+                )
+            })?;
+            if pattern.contains('/') {
+                full_path.add(glob);
+            } else {
+                file_name.add(glob);
+            }
+        }
+        let build = |builder: GlobSetBuilder| {
+            builder.build().map_err(|e| {
+                RaffError::invalid_input_with_arg(format!("Invalid {} globs: {}", flag, e), flag)
+            })
+        };
+        Ok(Some(Self {
+            patterns: patterns.to_vec(),
+            full_path: build(full_path)?,
+            file_name: build(file_name)?,
+        }))
+    }
+
+    fn is_match(&self, path: &Path) -> bool {
+        self.full_path.is_match(path)
+            || path
+                .file_name()
+                .is_some_and(|name| self.file_name.is_match(name))
+    }
+}
+
+impl HistoryFilter {
+    /// Compiles the filters described by `args`.
+    ///
+    /// A relative `ignore_revs_file` is resolved against the repository root.
+    /// When no file is given, `.git-blame-ignore-revs` in the repository root
+    /// is used if present.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RaffError::InvalidInput`] for malformed globs or regexes, and
+    /// an I/O error if an explicitly given ignore-revs file cannot be read.
+    pub fn new(repo: &Repository, args: &HistoryArgs) -> Result<Self> {
+        let include_paths = PathPatterns::new(&args.include_paths, "--include-path")?;
+        let exclude_paths = PathPatterns::new(&args.exclude_paths, "--exclude-path")?;
+        let exclude_messages = if args.exclude_messages.is_empty() {
+            None
+        } else {
+            Some(RegexSet::new(&args.exclude_messages).map_err(|e| {
+                RaffError::invalid_input_with_arg(
+                    format!("Invalid --exclude-message regex: {}", e),
+                    "--exclude-message",
+                )
+            })?)
+        };
+
+        let workdir = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+        let ignore_revs_file = match &args.ignore_revs_file {
+            Some(path) if path.is_absolute() => Some(path.clone()),
+            Some(path) => Some(workdir.join(path)),
+            None => Some(workdir.join(DEFAULT_IGNORE_REVS_FILE)).filter(|path| path.is_file()),
+        };
+        let ignored_revs = match &ignore_revs_file {
+            Some(path) => read_ignore_revs(repo, path)?,
+            None => HashSet::new(),
+        };
+
+        Ok(Self {
+            include_paths,
+            exclude_paths,
+            exclude_authors: args
+                .exclude_authors
+                .iter()
+                .map(|pattern| pattern.to_lowercase())
+                .collect(),
+            exclude_messages,
+            ignored_revs,
+            ignore_revs_file,
+        })
+    }
+
+    /// Returns `true` if changes to `path` should be counted.
+    #[must_use]
+    pub fn includes_path(&self, path: &Path) -> bool {
+        if self
+            .include_paths
+            .as_ref()
+            .is_some_and(|patterns| !patterns.is_match(path))
+        {
+            return false;
+        }
+        !self
+            .exclude_paths
+            .as_ref()
+            .is_some_and(|patterns| patterns.is_match(path))
+    }
+
+    /// Returns `true` if any include or exclude path patterns are configured.
+    #[must_use]
+    pub fn filters_paths(&self) -> bool {
+        self.include_paths.is_some() || self.exclude_paths.is_some()
+    }
+
+    /// Returns `true` if `commit` passes the revision, author and message filters.
+    #[must_use]
+    pub fn includes_commit(&self, commit: &Commit<'_>) -> bool {
+        if self.ignored_revs.contains(&commit.id()) {
+            tracing::trace!(commit_id = %commit.id(), "Commit is listed in ignore-revs file, skipping.");
+            return false;
+        }
+        if !self.exclude_authors.is_empty() {
+            let author = commit.author();
+            let identities = [author.name(), author.email()];
+            let excluded = identities.into_iter().flatten().any(|identity| {
+                let identity = identity.to_lowercase();
+                self.exclude_authors
+                    .iter()
+                    .any(|pattern| wildcard_match(pattern, &identity))
+            });
+            if excluded {
+                tracing::trace!(commit_id = %commit.id(), "Commit author is excluded, skipping.");
+                return false;
+            }
+        }
+        if let Some(messages) = &self.exclude_messages
+            && messages.is_match(String::from_utf8_lossy(commit.message_bytes()).trim_end())
+        {
+            tracing::trace!(commit_id = %commit.id(), "Commit message is excluded, skipping.");
+            return false;
+        }
+        true
+    }
+
+    /// Returns key/value pairs identifying these filters, for use in cache keys.
+    #[must_use]
+    pub fn cache_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(patterns) = &self.include_paths {
+            params.push(("include_paths".to_string(), patterns.patterns.join(",")));
+        }
+        if let Some(patterns) = &self.exclude_paths {
+            params.push(("exclude_paths".to_string(), patterns.patterns.join(",")));
+        }
+        if !self.exclude_authors.is_empty() {
+            params.push((
+                "exclude_authors".to_string(),
+                self.exclude_authors.join(","),
+            ));
+        }
+        if let Some(messages) = &self.exclude_messages {
+            params.push((
+                "exclude_messages".to_string(),
+                messages.patterns().join("\u{1f}"),
+            ));
+        }
+        if !self.ignored_revs.is_empty() {
+            let mut revs: Vec<String> = self.ignored_revs.iter().map(Oid::to_string).collect();
+            revs.sort();
+            let digest = Sha256::digest(revs.join("\n").as_bytes());
+            params.push(("ignored_revs".to_string(), format!("{:x}", digest)));
+        }
+        params
+    }
+
+    /// Returns the ignore-revs file in use, if any.
+    #[must_use]
+    pub fn ignore_revs_file(&self) -> Option<&Path> {
+        self.ignore_revs_file.as_deref()
+    }
+}
+
+/// File consulted for ignored revisions when none is configured.
+const DEFAULT_IGNORE_REVS_FILE: &str = ".git-blame-ignore-revs";
+
+/// Reads a `.git-blame-ignore-revs`-style file into a set of commit ids.
+///
+/// Blank lines and `#` comments are skipped. Entries that do not resolve to a
+/// commit are reported and ignored, matching how a stale entry should not
+/// abort an analysis.
+fn read_ignore_revs(repo: &Repository, path: &Path) -> Result<HashSet<Oid>> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        RaffError::io_error_with_source("read ignore-revs file", path.to_path_buf(), e)
+    })?;
+    let mut revs = HashSet::new();
+    for line in content.lines() {
+        let entry = line.split('#').next().unwrap_or_default().trim();
+        if entry.is_empty() {
+            continue;
+        }
+        match repo
+            .revparse_single(entry)
+            .and_then(|object| object.peel_to_commit())
+        {
+            Ok(commit) => {
+                revs.insert(commit.id());
+            }
+            Err(e) => {
+                tracing::warn!(revision = entry, file = %path.display(), error = %e, "Could not resolve ignored revision, skipping.");
+            }
+        }
+    }
+    tracing::debug!(count = revs.len(), file = %path.display(), "Loaded ignored revisions");
+    Ok(revs)
+}
+
+/// Matches `text` against `pattern`, where `*` matches any sequence of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Parses a `YYYY-MM-DD` date into a UTC timestamp at midnight.
+///
+/// `flag` names the option the value came from and is used in error messages.
+pub fn parse_date(date: &str, flag: &str) -> Result<i64> {
+    let naive_date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| {
+        RaffError::invalid_input_with_arg(
+            format!(
+                "Invalid {} date format '{}': {}. Please use YYYY-MM-DD.",
+                flag, date, e
+            ),
+            date.to_string(),
+        )
+    })?;
+    let midnight = naive_date.and_hms_opt(0, 0, 0).ok_or_else(|| {
+        RaffError::invalid_input_with_arg(
+            format!("Invalid {} date '{}'", flag, date),
+            date.to_string(),
+        )
+    })?;
+    Ok(Utc.from_utc_datetime(&midnight).timestamp())
+}
+
+/// Resolves a single revision (commit id, tag, branch, `HEAD~3`, ...) to a commit id.
+fn resolve_commit(repo: &Repository, rev: &str, flag: &str) -> Result<Oid> {
+    repo.revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map(|commit| commit.id())
+        .map_err(|e| {
+            RaffError::invalid_input_with_arg(
+                format!("Could not resolve {} revision '{}': {}", flag, rev, e),
+                rev.to_string(),
+            )
+        })
+}
+
+/// Resolves an `A..B` range into its (exclusive) start and end commits.
+///
+/// A bare revision is treated as the end of the range. Either side of `A..B`
+/// may be omitted, in which case it defaults to `HEAD`, as in `git log`.
+fn resolve_range(repo: &Repository, range: &str) -> Result<(Option<Oid>, Option<Oid>)> {
+    if range.contains("...") {
+        return Err(RaffError::invalid_input_with_arg(
+            format!(
+                "Symmetric difference ranges are not supported: '{}'. Use A..B instead.",
+                range
+            ),
+            range.to_string(),
+        ));
+    }
+    let Some((start, end)) = range.split_once("..") else {
+        return Ok((None, Some(resolve_commit(repo, range, "--range")?)));
+    };
+    let start = if start.is_empty() { "HEAD" } else { start };
+    let end = if end.is_empty() { "HEAD" } else { end };
+    let spec = repo.revparse(&format!("{start}..{end}")).map_err(|e| {
+        RaffError::invalid_input_with_arg(
+            format!("Could not resolve --range '{}': {}", range, e),
+            range.to_string(),
+        )
+    })?;
+    debug_assert!(spec.mode().contains(RevparseMode::RANGE));
+    let peel = |object: Option<&git2::Object<'_>>| -> Result<Option<Oid>> {
+        object
+            .map(|o| o.peel_to_commit().map(|c| c.id()))
+            .transpose()
+            .map_err(RaffError::from)
+    };
+    Ok((peel(spec.from())?, peel(spec.to())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit_on(dir: &Path, file: &str, date: &str) {
+        fs::write(dir.join(file), date).expect("Failed to write file");
+        git(dir, &["add", "."]);
+        let output = Command::new("git")
+            .args(["commit", "-m", file])
+            .env("GIT_AUTHOR_DATE", format!("{date}T12:00:00Z"))
+            .env("GIT_COMMITTER_DATE", format!("{date}T12:00:00Z"))
+            .current_dir(dir)
+            .output()
+            .expect("Failed to run git commit");
+        assert!(output.status.success(), "git commit failed");
+    }
+
+    /// Creates a repository with three commits on `main` and one on `release`.
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir = temp_dir.path();
+        git(dir, &["init", "-b", "main"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        commit_on(dir, "a.rs", "2024-01-01");
+        git(dir, &["tag", "v1"]);
+        commit_on(dir, "b.rs", "2024-02-01");
+        git(dir, &["branch", "release"]);
+        commit_on(dir, "c.rs", "2024-03-01");
+        git(dir, &["checkout", "-q", "release"]);
+        commit_on(dir, "d.rs", "2024-04-01");
+        git(dir, &["checkout", "-q", "main"]);
+        temp_dir
+    }
+
+    fn messages(repo: &Repository, window: &HistoryWindow) -> Vec<String> {
+        window
+            .commits(repo)
+            .unwrap()
+            .into_iter()
+            .map(|oid| {
+                repo.find_commit(oid)
+                    .unwrap()
+                    .summary()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_window_walks_head_oldest_first() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let window = HistoryWindow::resolve(&repo, &HistoryArgs::default(), false).unwrap();
+
+        assert_eq!(messages(&repo, &window), vec!["a.rs", "b.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn test_branch_selects_other_tip() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            branch: Some("release".to_string()),
+            ..HistoryArgs::default()
+        };
+        let window = HistoryWindow::resolve(&repo, &args, false).unwrap();
+
+        assert_eq!(messages(&repo, &window), vec!["a.rs", "b.rs", "d.rs"]);
+    }
+
+    #[test]
+    fn test_from_excludes_ancestors() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            from: Some("v1".to_string()),
+            ..HistoryArgs::default()
+        };
+        let window = HistoryWindow::resolve(&repo, &args, false).unwrap();
+
+        assert_eq!(messages(&repo, &window), vec!["b.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn test_range_matches_from_and_to() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let range = HistoryArgs {
+            range: Some("v1..release".to_string()),
+            ..HistoryArgs::default()
+        };
+        let from_to = HistoryArgs {
+            from: Some("v1".to_string()),
+            to: Some("release".to_string()),
+            ..HistoryArgs::default()
+        };
+
+        let range_window = HistoryWindow::resolve(&repo, &range, false).unwrap();
+        let from_to_window = HistoryWindow::resolve(&repo, &from_to, false).unwrap();
+
+        assert_eq!(range_window.tip, from_to_window.tip);
+        assert_eq!(range_window.boundary, from_to_window.boundary);
+        assert_eq!(messages(&repo, &range_window), vec!["b.rs", "d.rs"]);
+    }
+
+    #[test]
+    fn test_since_and_until_are_inclusive_dates() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            since: Some("2024-02-01".to_string()),
+            until: Some("2024-02-01".to_string()),
+            ..HistoryArgs::default()
+        };
+        let window = HistoryWindow::resolve(&repo, &args, false).unwrap();
+
+        assert_eq!(messages(&repo, &window), vec!["b.rs"]);
+    }
+
+    #[test]
+    fn test_range_with_from_is_rejected() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            range: Some("v1..HEAD".to_string()),
+            from: Some("v1".to_string()),
+            ..HistoryArgs::default()
+        };
+
+        let result = HistoryWindow::resolve(&repo, &args, false);
+        assert!(matches!(result, Err(RaffError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_unknown_revision_is_invalid_input() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            branch: Some("does-not-exist".to_string()),
+            ..HistoryArgs::default()
+        };
+
+        let result = HistoryWindow::resolve(&repo, &args, false);
+        assert!(matches!(result, Err(RaffError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_filter_excludes_authors_messages_and_ignored_revs() {
+        let temp_dir = create_repo();
+        let dir = temp_dir.path();
+        let repo = Repository::open(dir).unwrap();
+        let c = repo.revparse_single("main").unwrap().id();
+        fs::write(dir.join("ignore-revs"), format!("# formatting\n{c}\n")).unwrap();
+
+        let args = HistoryArgs {
+            exclude_authors: vec!["*[bot]*".to_string()],
+            exclude_messages: vec!["^b\\.rs$".to_string()],
+            ignore_revs_file: Some(PathBuf::from("ignore-revs")),
+            ..HistoryArgs::default()
+        };
+        let window = HistoryWindow::resolve(&repo, &args, false).unwrap();
+
+        assert_eq!(messages(&repo, &window), vec!["a.rs"]);
+        assert_eq!(
+            window.filter.ignore_revs_file(),
+            Some(dir.join("ignore-revs").as_path())
+        );
+
+        let bot = git2::Signature::now("dependabot[bot]", "bot@example.com").unwrap();
+        let head = repo.find_commit(c).unwrap();
+        let bot_commit = repo
+            .commit(None, &bot, &bot, "bump", &head.tree().unwrap(), &[&head])
+            .unwrap();
+        assert!(
+            !window
+                .filter
+                .includes_commit(&repo.find_commit(bot_commit).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_filter_path_patterns() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            include_paths: vec!["crates/**".to_string()],
+            exclude_paths: vec!["Cargo.lock".to_string(), "crates/gen/**".to_string()],
+            ..HistoryArgs::default()
+        };
+        let filter = HistoryFilter::new(&repo, &args).unwrap();
+
+        assert!(filter.filters_paths());
+        assert!(filter.includes_path(Path::new("crates/core/src/lib.rs")));
+        assert!(!filter.includes_path(Path::new("crates/core/Cargo.lock")));
+        assert!(!filter.includes_path(Path::new("crates/gen/src/lib.rs")));
+        assert!(!filter.includes_path(Path::new("src/main.rs")));
+        assert!(!HistoryFilter::default().filters_paths());
+    }
+
+    #[test]
+    fn test_filter_rejects_invalid_regex() {
+        let temp_dir = create_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let args = HistoryArgs {
+            exclude_messages: vec!["(".to_string()],
+            ..HistoryArgs::default()
+        };
+
+        let result = HistoryFilter::new(&repo, &args);
+        assert!(matches!(result, Err(RaffError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn test_parse_date_rejects_bad_format() {
+        assert!(parse_date("01/02/2024", "--since").is_err());
+        assert_eq!(
+            parse_date("1970-01-02", "--since").unwrap(),
+            SECONDS_PER_DAY
+        );
+    }
+}
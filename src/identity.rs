@@ -0,0 +1,200 @@
+//! Author identity resolution for history-based rules.
+//!
+//! Git records an author as a free-form name and email, so the same person
+//! often appears under several identities. [`IdentityResolver`] maps every
+//! commit signature to a canonical identity by applying, in order:
+//!
+//! 1. The repository's `.mailmap` (and `mailmap.file`/`mailmap.blob` config)
+//! 2. Configured alias groups, which map extra emails or names to a canonical email
+//!
+//! Canonical identities are keyed on their lowercased email address, so two
+//! people who share a name are kept apart.
+
+use std::collections::{BTreeMap, HashMap};
+
+use git2::{Mailmap, Repository, Signature};
+
+use crate::error::{RaffError, Result};
+
+/// A resolved author identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    /// Display name after mailmap resolution.
+    pub name: String,
+    /// Canonical lowercased email, used as the identity key.
+    pub email: String,
+    /// The identity as recorded in the commit, formatted as `Name <email>`.
+    pub raw: String,
+}
+
+/// Resolves commit signatures to canonical identities.
+pub struct IdentityResolver {
+    mailmap: Mailmap,
+    /// Lowercased alias email or name, mapped to its canonical email.
+    aliases: HashMap<String, String>,
+}
+
+impl IdentityResolver {
+    /// Loads the repository's mailmap and indexes `aliases`.
+    ///
+    /// `aliases` maps a canonical email to the other emails or names that
+    /// belong to the same person.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mailmap cannot be loaded, or if an alias is
+    /// claimed by two different canonical emails.
+    pub fn new(repo: &Repository, aliases: &BTreeMap<String, Vec<String>>) -> Result<Self> {
+        let mailmap = repo
+            .mailmap()
+            .map_err(|e| RaffError::git_error(format!("load mailmap: {e}")))?;
+
+        let mut index = HashMap::new();
+        for (canonical, identities) in aliases {
+            let canonical = canonical.to_lowercase();
+            for alias in identities {
+                let alias = alias.to_lowercase();
+                if let Some(existing) = index.insert(alias.clone(), canonical.clone())
+                    && existing != canonical
+                {
+                    return Err(RaffError::invalid_input_with_arg(
+                        format!("alias '{alias}' belongs to both '{existing}' and '{canonical}'"),
+                        "aliases",
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            mailmap,
+            aliases: index,
+        })
+    }
+
+    /// Resolves a commit signature to its canonical identity.
+    #[must_use]
+    pub fn resolve(&self, signature: &Signature<'_>) -> Identity {
+        let raw = format!(
+            "{} <{}>",
+            signature.name().unwrap_or("Unknown"),
+            signature.email().unwrap_or_default()
+        );
+        let (name, email) = match self.mailmap.resolve_signature(signature) {
+            Ok(resolved) => identity_parts(&resolved),
+            Err(_) => identity_parts(signature),
+        };
+        let email = self
+            .aliases
+            .get(&email)
+            .or_else(|| self.aliases.get(&name.to_lowercase()))
+            .cloned()
+            .unwrap_or(email);
+        Identity { name, email, raw }
+    }
+
+    /// Resolves a name and email that did not come from a commit signature,
+    /// such as a `Co-authored-by:` trailer.
+    #[must_use]
+    pub fn resolve_name_email(&self, name: &str, email: &str) -> Identity {
+        match Signature::now(name, email) {
+            Ok(signature) => self.resolve(&signature),
+            Err(_) => Identity {
+                name: name.to_string(),
+                email: email.to_lowercase(),
+                raw: format!("{name} <{email}>"),
+            },
+        }
+    }
+
+    /// Returns the canonical email for a bare email address, such as one
+    /// listed in a CODEOWNERS file.
+    #[must_use]
+    pub fn canonical_email(&self, email: &str) -> String {
+        // libgit2 matches mailmap emails case-sensitively, and commit emails
+        // are usually lowercase
+        let email = email.to_lowercase();
+        match Signature::now(&email, &email) {
+            Ok(signature) => self.resolve(&signature).email,
+            Err(_) => email,
+        }
+    }
+}
+
+/// Returns the name and lowercased email of a signature. Signatures without
+/// an email are keyed on their lowercased name instead.
+fn identity_parts(signature: &Signature<'_>) -> (String, String) {
+    let name = signature.name().unwrap_or("Unknown").to_string();
+    let email = match signature.email() {
+        Some(email) if !email.is_empty() => email.to_lowercase(),
+        _ => name.to_lowercase(),
+    };
+    (name, email)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn resolver(mailmap: &str, aliases: &[(&str, &[&str])]) -> (TempDir, IdentityResolver) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join(".mailmap"), mailmap).unwrap();
+        let aliases = aliases
+            .iter()
+            .map(|(canonical, identities)| {
+                (
+                    canonical.to_string(),
+                    identities.iter().map(|s| s.to_string()).collect(),
+                )
+            })
+            .collect();
+        let resolver = IdentityResolver::new(&repo, &aliases).unwrap();
+        (temp_dir, resolver)
+    }
+
+    #[test]
+    fn test_resolve_applies_mailmap_then_aliases() {
+        let (_dir, resolver) = resolver(
+            "Alice Smith <alice@example.com> <alice@old.example.com>\n",
+            &[("alice@example.com", &["Ally", "a.smith@Corp.example.com"])],
+        );
+
+        let mailmapped =
+            resolver.resolve(&Signature::now("alice", "alice@old.example.com").unwrap());
+        assert_eq!(mailmapped.name, "Alice Smith");
+        assert_eq!(mailmapped.email, "alice@example.com");
+        assert_eq!(mailmapped.raw, "alice <alice@old.example.com>");
+
+        let by_email =
+            resolver.resolve(&Signature::now("A S", "a.smith@corp.example.com").unwrap());
+        assert_eq!(by_email.email, "alice@example.com");
+
+        let by_name = resolver.resolve(&Signature::now("Ally", "ally@home.example.com").unwrap());
+        assert_eq!(by_name.email, "alice@example.com");
+
+        assert_eq!(
+            resolver.canonical_email("Alice@Old.example.com"),
+            "alice@example.com"
+        );
+    }
+
+    #[test]
+    fn test_resolve_keeps_same_name_different_email_apart() {
+        let (_dir, resolver) = resolver("", &[]);
+        let first = resolver.resolve(&Signature::now("Sam", "sam@one.example.com").unwrap());
+        let second = resolver.resolve(&Signature::now("Sam", "Sam@Two.example.com").unwrap());
+        assert_ne!(first.email, second.email);
+        assert_eq!(second.email, "sam@two.example.com");
+    }
+
+    #[test]
+    fn test_new_rejects_alias_with_two_owners() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let aliases = BTreeMap::from([
+            ("a@example.com".to_string(), vec!["shared".to_string()]),
+            ("b@example.com".to_string(), vec!["Shared".to_string()]),
+        ]);
+        assert!(IdentityResolver::new(&repo, &aliases).is_err());
+    }
+}
@@ -19,20 +19,37 @@
 //! - [`file_utils`] - File system operations and path handling
 //! - [`statement_count_rule`] - Statement count analysis rule
 //! - [`volatility_rule`] - Code volatility analysis based on git history
+//! - [`history`] - Revision and date selection shared by history-based rules
+//! - [`commit_stats`] - Cached per-commit diff statistics for history-based rules
+//! - [`identity`] - Author identity resolution via `.mailmap` and alias groups
 //! - [`coupling_rule`] - Dependency coupling analysis
 //! - [`rust_code_analysis_rule`] - Wrapper for rust-code-analysis
+//! - [`complexity`] - Built-in complexity metrics engine for rust-code-analysis
 //! - [`contributor_report`] - Contributor activity reporting
+//! - [`code_age_rule`] - Blame-based code age and stale code analysis
+//! - [`ownership_rule`] - Ownership and bus factor analysis
+//! - [`codeowners_rule`] - CODEOWNERS suggestion and drift checking
+//! - [`duplication_rule`] - Token-based duplicated code detection
+//! - [`api_surface_rule`] - Public API surface and encapsulation metrics
+//! - [`unsafe_code_rule`] - Unsafe code and FFI boundary locations and allow-lists
+//! - [`site_visitor`] - Shared `syn` visitor that records code sites with their enclosing items
+//! - [`error_handling_rule`] - Unwrap, expect, panic and indexing budgets per module
+//! - [`generics_rule`] - Generic parameter, trait bound and impl metrics
 //! - [`all_rules`] - Orchestration for running all rules
 //! - [`cache`] - Result caching for improved performance
 //! - [`ci_report`] - CI/CD platform report generation (SARIF, JUnit)
 //! - [`cli_report`] - CLI-friendly table output for terminal consumption
+//! - [`output_utils`] - Report writing and formatting helpers shared by rules
 //!
 //! ## Usage as a Library
 //!
 //! Raff can be used as a library to programmatically analyze Rust code:
 //!
 //! ```rust,no_run
-//! use raff_core::{StatementCountRule, Cli, StatementCountArgs, StatementCountOutputFormat};
+//! use raff_core::{
+//!     StatementCountRule, Cli, ComponentMode, StatementCountArgs, StatementCountGranularity,
+//!     StatementCountOutputFormat,
+//! };
 //! use std::path::PathBuf;
 //!
 //! # fn main() -> raff_core::error::Result<()> {
@@ -43,10 +60,22 @@
 //! let args = StatementCountArgs {
 //!     path: PathBuf::from("./src"),
 //!     threshold: 1000,
+//!     components: ComponentMode::Directory,
+//!     granularity: StatementCountGranularity::Component,
+//!     top: 20,
+//!     max_function_statements: None,
+//!     include_tests: false,
+//!     parse_macros: false,
+//!     max_component_statements: None,
+//!     min_component_statements: None,
+//!     max_file_statements: None,
+//!     max_module_statements: None,
+//!     max_gini: None,
 //!     output: StatementCountOutputFormat::Table,
 //!     ci_output: None,
 //!     output_file: None,
 //!     staged: false,
+//!     component_definitions: Default::default(),
 //! };
 //!
 //! // Run the analysis
@@ -68,47 +97,80 @@
 
 // Module declarations
 pub mod all_rules;
+pub mod api_surface_rule;
 pub mod cache;
 pub mod ci_report;
 pub mod cli;
 pub mod cli_report;
+pub mod code_age_rule;
+pub mod codeowners_rule;
+pub mod commit_stats;
+pub mod complexity;
+pub mod components;
 pub mod config;
 pub mod config_hierarchy;
 pub mod contributor_report;
 pub mod counter;
 pub mod coupling_rule;
+pub mod duplication_rule;
 pub mod error;
+pub mod error_handling_rule;
 pub mod file_utils;
+pub mod generics_rule;
 pub mod git_utils;
+pub mod history;
 pub mod html_utils;
+pub mod identity;
+pub mod output_utils;
+pub mod ownership_rule;
 pub mod reporting;
 pub mod rule;
 pub mod rust_code_analysis_rule;
+pub mod site_visitor;
 pub mod statement_count_rule;
 pub mod table_utils;
+pub mod unsafe_code_rule;
 pub mod volatility_rule;
 
 // Public API exports
 pub use crate::all_rules::run_all;
+pub use crate::api_surface_rule::ApiSurfaceRule;
 pub use crate::cli::{
-    AllArgs, AllOutputFormat, CiOutputFormat, Cli, Commands, ContributorReportArgs,
-    ContributorReportOutputFormat, CouplingArgs, CouplingGranularity, CouplingOutputFormat,
-    RustCodeAnalysisArgs, RustCodeAnalysisOutputFormat, StatementCountArgs,
-    StatementCountOutputFormat, VolatilityArgs, VolatilityOutputFormat,
+    AllArgs, AllOutputFormat, ApiSurfaceArgs, ApiSurfaceOutputFormat, CiOutputFormat, Cli,
+    CodeAgeArgs, CodeAgeGranularity, CodeAgeOutputFormat, CodeownersAction, CodeownersArgs,
+    CodeownersOutputFormat, Commands, ComponentMode, ContributorReportArgs,
+    ContributorReportOutputFormat, ContributorReportPeriod, CouplingArgs, CouplingGranularity,
+    CouplingOutputFormat, DuplicationArgs, DuplicationOutputFormat, ErrorHandlingArgs,
+    ErrorHandlingOutputFormat, GenericsArgs, GenericsOutputFormat, HistoryArgs, OwnershipArgs,
+    OwnershipGranularity, OwnershipOutputFormat, OwnershipSource, PanicBudget,
+    RustCodeAnalysisArgs, RustCodeAnalysisBackend, RustCodeAnalysisOutputFormat,
+    StatementCountArgs, StatementCountGranularity, StatementCountOutputFormat, UnsafeCodeArgs,
+    UnsafeCodeOutputFormat, VolatilityArgs, VolatilityOutputFormat,
 };
+pub use crate::code_age_rule::CodeAgeRule;
+pub use crate::codeowners_rule::CodeownersRule;
 pub use crate::contributor_report::ContributorReportRule;
 pub use crate::coupling_rule::CouplingRule;
+pub use crate::duplication_rule::DuplicationRule;
+pub use crate::error_handling_rule::ErrorHandlingRule;
+pub use crate::generics_rule::GenericsRule;
+pub use crate::ownership_rule::OwnershipRule;
 pub use crate::rust_code_analysis_rule::RustCodeAnalysisRule;
 pub use crate::statement_count_rule::StatementCountRule;
+pub use crate::unsafe_code_rule::UnsafeCodeRule;
 pub use crate::volatility_rule::VolatilityRule;
 
 // Config exports
 pub use crate::config::{
-    ContributorReportConfig, CouplingConfig, GeneralConfig, PreCommitProfile, PreCommitSettings,
-    ProfileConfig, RaffConfig, RustCodeAnalysisConfig, StatementCountConfig, VolatilityConfig,
-    apply_pre_commit_profile, load_config, load_config_from_path, merge_all_args,
-    merge_contributor_report_args, merge_coupling_args, merge_rust_code_analysis_args,
-    merge_statement_count_args, merge_volatility_args,
+    ApiSurfaceConfig, CodeAgeConfig, CodeownersConfig, ContributorReportConfig, CouplingConfig,
+    DuplicationConfig, ErrorHandlingConfig, GeneralConfig, GenericsConfig, OwnershipConfig,
+    PreCommitProfile, PreCommitSettings, ProfileConfig, RaffConfig, RustCodeAnalysisConfig,
+    StatementCountConfig, UnsafeCodeConfig, VolatilityConfig, apply_pre_commit_profile,
+    load_config, load_config_from_path, merge_all_args, merge_api_surface_args,
+    merge_code_age_args, merge_codeowners_args, merge_contributor_report_args, merge_coupling_args,
+    merge_duplication_args, merge_error_handling_args, merge_generics_args, merge_ownership_args,
+    merge_rust_code_analysis_args, merge_statement_count_args, merge_unsafe_code_args,
+    merge_volatility_args,
 };
 
 // Config hierarchy exports
@@ -2,11 +2,15 @@
 
 use clap::Parser;
 use raff_core::{
-    AllOutputFormat, CacheManager, Cli, Commands, ConfigSourceType, ContributorReportRule,
-    CouplingGranularity, CouplingRule, RustCodeAnalysisRule, StatementCountRule, VolatilityRule,
-    all_rules, apply_pre_commit_profile, error::RaffError, error::Result, load_hierarchical_config,
-    merge_all_args, merge_contributor_report_args, merge_coupling_args,
-    merge_rust_code_analysis_args, merge_statement_count_args, merge_volatility_args,
+    AllOutputFormat, ApiSurfaceRule, CacheManager, Cli, CodeAgeRule, CodeownersRule, Commands,
+    ConfigSourceType, ContributorReportRule, CouplingGranularity, CouplingRule, DuplicationRule,
+    ErrorHandlingRule, GenericsRule, OwnershipRule, RustCodeAnalysisRule, StatementCountRule,
+    UnsafeCodeRule, VolatilityRule, all_rules, apply_pre_commit_profile, error::RaffError,
+    error::Result, load_hierarchical_config, merge_all_args, merge_api_surface_args,
+    merge_code_age_args, merge_codeowners_args, merge_contributor_report_args, merge_coupling_args,
+    merge_duplication_args, merge_error_handling_args, merge_generics_args, merge_ownership_args,
+    merge_rust_code_analysis_args, merge_statement_count_args, merge_unsafe_code_args,
+    merge_volatility_args,
 };
 use std::process::exit;
 
@@ -110,7 +114,8 @@ fn main() -> Result<()> {
             rule.run(&merged_args)
         }
         Commands::RustCodeAnalysis(args) => {
-            let merged_args = merge_rust_code_analysis_args(&args, &config);
+            let mut merged_args = merge_rust_code_analysis_args(&args, &config);
+            merged_args.no_cache = cli_args.no_cache;
             let rule = RustCodeAnalysisRule::new();
             tracing::info!("Running RustCodeAnalysis rule with args: {:?}", merged_args);
             rule.run(&merged_args)
@@ -129,6 +134,7 @@ fn main() -> Result<()> {
             let mut merged_args = merge_all_args(&args, &config);
             // Propagate global staged flag and profile staged setting
             merged_args.staged = cli_args.staged || profile_staged || args.staged;
+            merged_args.no_cache = cli_args.no_cache;
             if pre_commit_profile_active {
                 merged_args.output = AllOutputFormat::Cli;
                 merged_args.fail_on_warnings = true;
@@ -148,6 +154,54 @@ fn main() -> Result<()> {
             );
             rule.run(&merged_args)
         }
+        Commands::CodeAge(args) => {
+            let merged_args = merge_code_age_args(&args, &config);
+            let rule = CodeAgeRule::new();
+            tracing::info!("Running CodeAge rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::Ownership(args) => {
+            let merged_args = merge_ownership_args(&args, &config);
+            let rule = OwnershipRule::new();
+            tracing::info!("Running Ownership rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::Codeowners(args) => {
+            let merged_args = merge_codeowners_args(&args, &config);
+            let rule = CodeownersRule::new();
+            tracing::info!("Running Codeowners rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::Duplication(args) => {
+            let merged_args = merge_duplication_args(&args, &config);
+            let rule = DuplicationRule::new();
+            tracing::info!("Running Duplication rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::ApiSurface(args) => {
+            let merged_args = merge_api_surface_args(&args, &config);
+            let rule = ApiSurfaceRule::new();
+            tracing::info!("Running ApiSurface rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::UnsafeCode(args) => {
+            let merged_args = merge_unsafe_code_args(&args, &config);
+            let rule = UnsafeCodeRule::new();
+            tracing::info!("Running UnsafeCode rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::ErrorHandling(args) => {
+            let merged_args = merge_error_handling_args(&args, &config);
+            let rule = ErrorHandlingRule::new();
+            tracing::info!("Running ErrorHandling rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
+        Commands::Generics(args) => {
+            let merged_args = merge_generics_args(&args, &config);
+            let rule = GenericsRule::new();
+            tracing::info!("Running Generics rule with args: {:?}", merged_args);
+            rule.run(&merged_args)
+        }
     };
 
     if let Err(e) = run_result {
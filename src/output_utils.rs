@@ -0,0 +1,59 @@
+//! Helpers shared by the rules for writing their reports.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::error::{RaffError, Result};
+
+/// Writes a rendered report to `output_file`, or to stdout when it is `None`.
+pub fn write_output(output: &str, output_file: Option<&Path>) -> Result<()> {
+    let Some(output_file) = output_file else {
+        println!("{output}");
+        return Ok(());
+    };
+    let mut file = File::create(output_file).map_err(|e| {
+        RaffError::io_error_with_source("create output file", output_file.to_path_buf(), e)
+    })?;
+    file.write_all(output.as_bytes()).map_err(|e| {
+        RaffError::io_error_with_source("write output file", output_file.to_path_buf(), e)
+    })
+}
+
+/// Returns `path` as a `/`-separated URI.
+pub fn path_uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Returns `part` as a percentage of `total`, or 0 when `total` is 0.
+pub fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_output_writes_the_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("report.json");
+        write_output("{}", Some(&file)).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "{}");
+
+        let missing = temp_dir.path().join("missing/report.json");
+        assert!(write_output("{}", Some(&missing)).is_err());
+    }
+
+    #[test]
+    fn test_path_uri_and_percentage() {
+        assert_eq!(path_uri(Path::new("src\\lib.rs")), "src/lib.rs");
+        assert_eq!(percentage(1, 4), 25.0);
+        assert_eq!(percentage(1, 0), 0.0);
+    }
+}
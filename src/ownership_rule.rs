@@ -0,0 +1,852 @@
+//! Ownership Rule
+//!
+//! This module provides the ownership rule, which reports how knowledge of
+//! each crate or module is distributed across authors.
+//!
+//! # Overview
+//!
+//! Lines are attributed to authors in one of two ways:
+//! - **Churn** (default): lines added and deleted by each author within the
+//!   history window, using the shared [`commit_stats`](crate::commit_stats) cache
+//! - **Blame**: lines of the current `.rs` code each author last changed
+//!
+//! For every component the report gives:
+//! - **Primary Owner**: The author with the largest share of lines
+//! - **Ownership %**: The primary owner's share of the component's lines
+//! - **Bus Factor**: The minimum number of authors who together account for
+//!   more than 50% of the lines
+//! - **Owner Active**: Whether the primary owner has committed within the last
+//!   `--inactive-months` months (a month is counted as 30 days)
+//!
+//! Components whose primary owner is inactive are reported as warnings.
+//! Authors are identified by email address after `.mailmap` resolution and
+//! the alias groups in `[contributor_report.aliases]`.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::ownership_rule::OwnershipRule;
+//! use raff_core::{
+//!     HistoryArgs, OwnershipArgs, OwnershipGranularity, OwnershipOutputFormat, OwnershipSource,
+//! };
+//! use std::path::PathBuf;
+//!
+//! let rule = OwnershipRule::new();
+//! let args = OwnershipArgs {
+//!     path: PathBuf::from("."),
+//!     history: HistoryArgs::default(),
+//!     granularity: OwnershipGranularity::Crate,
+//!     source: OwnershipSource::Churn,
+//!     inactive_months: 6,
+//!     aliases: Default::default(),
+//!     output: OwnershipOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path is not a valid Git repository
+//! - No crates (Cargo.toml files) are found
+//! - Git operations fail (e.g., a revision cannot be resolved)
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use git2::Repository;
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use serde::{Deserialize, Serialize};
+
+use crate::ci_report::{Finding, Severity, ToFindings};
+use crate::cli::{
+    CiOutputFormat, ComponentMode, OwnershipArgs, OwnershipGranularity, OwnershipOutputFormat,
+    OwnershipSource,
+};
+use crate::code_age_rule::{blame_runs, last_commit_by_author, rust_files_by_crate};
+use crate::commit_stats::CommitStatsStore;
+use crate::components::ComponentResolver;
+use crate::error::{RaffError, Result};
+use crate::git_utils::map_with_repository;
+use crate::history::HistoryWindow;
+use crate::html_utils::{self, MetricRanges};
+use crate::identity::{Identity, IdentityResolver};
+use crate::rule::Rule;
+use crate::volatility_rule::{CrateStatsMap, VolatilityRule};
+
+/// Seconds in the 30-day month used for the inactive threshold.
+const SECONDS_PER_MONTH: i64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of author columns shown in the HTML heat map.
+const MAX_HEATMAP_AUTHORS: usize = 20;
+
+/// An author's share of a component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorShare {
+    /// Author name.
+    pub author: String,
+    /// Author email, used as the identity.
+    pub email: String,
+    /// Lines attributed to the author.
+    pub lines: u64,
+    /// Percentage of the component's lines attributed to the author.
+    pub percentage: f64,
+}
+
+/// Ownership statistics for a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentOwnership {
+    /// Name of the crate or directory.
+    pub name: String,
+    /// Lines attributed to any author.
+    pub total_lines: u64,
+    /// Author shares, largest first.
+    pub authors: Vec<AuthorShare>,
+    /// Minimum number of authors covering more than 50% of the lines.
+    pub bus_factor: usize,
+    /// Time of the primary owner's most recent commit.
+    pub primary_owner_last_commit: Option<i64>,
+    /// Whether the primary owner has committed within the inactive threshold.
+    pub primary_owner_active: bool,
+}
+
+impl ComponentOwnership {
+    /// Returns the author with the largest share, if any.
+    #[must_use]
+    pub fn primary_owner(&self) -> Option<&AuthorShare> {
+        self.authors.first()
+    }
+}
+
+/// Data type for ownership analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipData {
+    /// Per-component ownership, lowest bus factor first.
+    pub components: Vec<ComponentOwnership>,
+    /// How lines were attributed to authors.
+    pub source: OwnershipSource,
+    /// Months without a commit after which an owner counts as inactive.
+    pub inactive_months: u32,
+    /// The analyzed repository.
+    pub analysis_path: PathBuf,
+}
+
+impl ToFindings for OwnershipData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.components
+            .iter()
+            .filter(|component| !component.primary_owner_active)
+            .filter_map(|component| {
+                let owner = component.primary_owner()?;
+                Some(Finding {
+                    rule_id: "ownership".to_string(),
+                    rule_name: "Ownership Rule".to_string(),
+                    severity: Severity::Warning,
+                    message: format!(
+                        "Component '{}' is mostly owned by '{}' ({:.0}% of lines, bus factor {}), who has not committed in over {} months",
+                        component.name,
+                        owner.author,
+                        owner.percentage,
+                        component.bus_factor,
+                        self.inactive_months
+                    ),
+                    location: None, // Ownership is component-level, no specific file location
+                    related_locations: Vec::new(),
+                    help_uri: Some("https://github.com/liamwh/raff/docs/ownership".to_string()),
+                    fingerprint: Some(format!("ownership:{}:{}", component.name, owner.email)),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Rule to report knowledge distribution and bus factor per component.
+#[derive(Debug, Default)]
+pub struct OwnershipRule;
+
+impl Rule for OwnershipRule {
+    type Config = OwnershipArgs;
+    type Data = OwnershipData;
+
+    fn name() -> &'static str {
+        "ownership"
+    }
+
+    fn description() -> &'static str {
+        "Reports the primary owner, ownership share and bus factor of each crate or module"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+/// Lines attributed per component and author email, plus the display name
+/// seen for each email.
+#[derive(Default)]
+struct Attribution {
+    lines: HashMap<String, HashMap<String, u64>>,
+    names: HashMap<String, String>,
+}
+
+impl Attribution {
+    fn add(&mut self, component: String, author: &str, email: &str, lines: u64) {
+        if lines == 0 {
+            return;
+        }
+        self.names
+            .entry(email.to_string())
+            .or_insert_with(|| author.to_string());
+        *self
+            .lines
+            .entry(component)
+            .or_default()
+            .entry(email.to_string())
+            .or_default() += lines;
+    }
+}
+
+impl OwnershipRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &OwnershipArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &OwnershipArgs) -> Result<OwnershipData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &OwnershipArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "ownership")?,
+            };
+            // Ownership findings are warnings, which don't fail CI
+            return crate::output_utils::write_output(&output, args.output_file.as_deref());
+        }
+
+        let output = match args.output {
+            OwnershipOutputFormat::Table => {
+                self.print_table(&data);
+                return Ok(());
+            }
+            OwnershipOutputFormat::Json => serde_json::to_string_pretty(&data)?,
+            OwnershipOutputFormat::Yaml => serde_yaml::to_string(&data)?,
+            OwnershipOutputFormat::Csv => self.render_csv(&data)?,
+            OwnershipOutputFormat::Html => html_utils::render_html_doc(
+                &format!("Ownership Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            ),
+        };
+        crate::output_utils::write_output(&output, args.output_file.as_deref())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &OwnershipArgs) -> Result<OwnershipData> {
+        let analysis_path = args.path.canonicalize()?;
+        let repo = Repository::open(&analysis_path).map_err(|e| {
+            RaffError::git_error_with_repo(
+                format!("open Git repository: {}", e),
+                analysis_path.clone(),
+            )
+        })?;
+        let window = HistoryWindow::resolve(&repo, &args.history, false)?;
+        let crates = VolatilityRule::new().discover_crates_and_init_stats(&analysis_path)?;
+        let jobs = args.history.jobs.unwrap_or_else(num_cpus::get);
+        let identities = IdentityResolver::new(&repo, &args.aliases)?;
+        // Modules are named by their Rust module path, as in the other reports
+        let modules = match args.granularity {
+            OwnershipGranularity::Crate => None,
+            OwnershipGranularity::Module => Some(ComponentResolver::new(
+                &analysis_path,
+                ComponentMode::Crate,
+                &BTreeMap::new(),
+            )?),
+        };
+
+        let attribution = match args.source {
+            OwnershipSource::Churn => {
+                self.attribute_churn(&repo, &window, &crates, modules.as_ref(), jobs, &identities)?
+            }
+            OwnershipSource::Blame => {
+                self.attribute_blame(&repo, &window, &crates, modules.as_ref(), jobs, &identities)?
+            }
+        };
+
+        let last_seen = last_commit_by_author(&repo, window.tip, &identities)?;
+        let active_since =
+            Utc::now().timestamp() - i64::from(args.inactive_months) * SECONDS_PER_MONTH;
+        let mut components: Vec<ComponentOwnership> = attribution
+            .lines
+            .into_iter()
+            .map(|(name, by_author)| {
+                build_component(
+                    name,
+                    by_author,
+                    &attribution.names,
+                    &last_seen,
+                    active_since,
+                )
+            })
+            .collect();
+        components.sort_by(|a, b| {
+            a.bus_factor.cmp(&b.bus_factor).then_with(|| {
+                let share =
+                    |c: &ComponentOwnership| c.primary_owner().map_or(0.0, |o| o.percentage);
+                share(b)
+                    .partial_cmp(&share(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a.name.cmp(&b.name))
+            })
+        });
+
+        Ok(OwnershipData {
+            components,
+            source: args.source,
+            inactive_months: args.inactive_months,
+            analysis_path,
+        })
+    }
+
+    /// Attributes the lines added and deleted by each commit in the window.
+    fn attribute_churn(
+        &self,
+        repo: &Repository,
+        window: &HistoryWindow,
+        crates: &CrateStatsMap,
+        modules: Option<&ComponentResolver>,
+        jobs: usize,
+        identities: &IdentityResolver,
+    ) -> Result<Attribution> {
+        let mut attribution = Attribution::default();
+        for churn in churn_by_file(repo, window, jobs, identities)? {
+            if let Some(component) = component_of(&churn.path, crates, modules) {
+                attribution.add(
+                    component,
+                    &churn.identity.name,
+                    &churn.identity.email,
+                    churn.lines,
+                );
+            }
+        }
+        Ok(attribution)
+    }
+
+    /// Attributes the current lines of each `.rs` file to their last author.
+    fn attribute_blame(
+        &self,
+        repo: &Repository,
+        window: &HistoryWindow,
+        crates: &CrateStatsMap,
+        modules: Option<&ComponentResolver>,
+        jobs: usize,
+        identities: &IdentityResolver,
+    ) -> Result<Attribution> {
+        let files: Vec<PathBuf> = rust_files_by_crate(repo, window.tip, crates)?
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| window.filter.includes_path(path))
+            .collect();
+        let tip = window.tip;
+        let blamed = map_with_repository(repo.path(), &files, jobs, |repo, path| {
+            blame_runs(repo, tip, path)
+        })?;
+
+        let mut attribution = Attribution::default();
+        for (path, runs) in files.iter().zip(blamed) {
+            let Some(component) = component_of(path, crates, modules) else {
+                continue;
+            };
+            for run in runs {
+                let identity = identities.resolve_name_email(&run.author, &run.email);
+                attribution.add(
+                    component.clone(),
+                    &identity.name,
+                    &identity.email,
+                    run.lines as u64,
+                );
+            }
+        }
+        Ok(attribution)
+    }
+
+    fn print_table(&self, data: &OwnershipData) {
+        println!("\nOwnership Report Interpretation:");
+        println!("-----------------------------------");
+        println!(
+            "- Ownership %: Share of the component's lines attributed to the primary owner ({}).",
+            match data.source {
+                OwnershipSource::Churn => "by lines added and deleted",
+                OwnershipSource::Blame => "by git blame of the current code",
+            }
+        );
+        println!(
+            "- Bus Factor: Fewest authors who together account for more than 50% of the lines."
+        );
+        println!(
+            "- Owner Active: Whether the primary owner has committed in the last {} months.",
+            data.inactive_months
+        );
+        println!("-----------------------------------");
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Component",
+            "Primary Owner",
+            "Ownership %",
+            "Bus Factor",
+            "Authors",
+            "Owner Active"
+        ]);
+        for component in &data.components {
+            let (owner, share) = component
+                .primary_owner()
+                .map_or(("N/A", 0.0), |o| (o.author.as_str(), o.percentage));
+            table.add_row(row![
+                component.name,
+                owner,
+                r->format!("{:.1}", share),
+                r->component.bus_factor,
+                r->component.authors.len(),
+                if component.primary_owner_active { "yes" } else { "no" }
+            ]);
+        }
+        println!("\nOwnership Report:");
+        table.printstd();
+    }
+
+    fn render_csv(&self, data: &OwnershipData) -> Result<String> {
+        let mut wtr = csv::WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(vec![]);
+        wtr.write_record([
+            "component",
+            "primary_owner",
+            "primary_owner_email",
+            "ownership_percentage",
+            "bus_factor",
+            "author_count",
+            "total_lines",
+            "primary_owner_active",
+        ])?;
+        for component in &data.components {
+            let owner = component.primary_owner();
+            wtr.write_record([
+                component.name.clone(),
+                owner.map_or_else(String::new, |o| o.author.clone()),
+                owner.map_or_else(String::new, |o| o.email.clone()),
+                format!("{:.1}", owner.map_or(0.0, |o| o.percentage)),
+                component.bus_factor.to_string(),
+                component.authors.len().to_string(),
+                component.total_lines.to_string(),
+                component.primary_owner_active.to_string(),
+            ])?;
+        }
+        let bytes = wtr
+            .into_inner()
+            .map_err(|e| RaffError::parse_error(format!("Failed to get CSV bytes: {}", e)))?;
+        String::from_utf8(bytes)
+            .map_err(|e| RaffError::parse_error(format!("Failed to convert CSV to UTF-8: {}", e)))
+    }
+
+    fn render_html_body(&self, data: &OwnershipData) -> Markup {
+        let explanations = [
+            (
+                "Ownership %",
+                "Share of the component's lines attributed to the primary owner.",
+            ),
+            (
+                "Bus Factor",
+                "Fewest authors who together account for more than 50% of the component's lines.",
+            ),
+            (
+                "Heat Map",
+                "Each cell is an author's share of a component; darker red means more concentrated knowledge.",
+            ),
+        ];
+
+        // Heat map columns: authors with the most lines across all components
+        let mut totals: HashMap<&str, (u64, &str)> = HashMap::new();
+        for share in data.components.iter().flat_map(|c| &c.authors) {
+            let entry = totals
+                .entry(share.email.as_str())
+                .or_insert((0, share.author.as_str()));
+            entry.0 += share.lines;
+        }
+        let mut columns: Vec<(&str, u64, &str)> = totals
+            .into_iter()
+            .map(|(email, (lines, author))| (email, lines, author))
+            .collect();
+        columns.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        columns.truncate(MAX_HEATMAP_AUTHORS);
+
+        let heat = MetricRanges {
+            min: 0.0,
+            max: 100.0,
+            higher_is_better: false,
+        };
+        let share_of = |component: &ComponentOwnership, email: &str| {
+            component
+                .authors
+                .iter()
+                .find(|share| share.email == email)
+                .map_or(0.0, |share| share.percentage)
+        };
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            table class="sortable-table" {
+                caption { (format!("Primary owners inactive after {} months", data.inactive_months)) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="string" { "Primary Owner" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Ownership %" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Bus Factor" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="string" { "Owner Active" }
+                    }
+                }
+                tbody {
+                    @for component in &data.components {
+                        @let owner = component.primary_owner();
+                        tr {
+                            td { (component.name) }
+                            td { (owner.map_or("N/A", |o| o.author.as_str())) }
+                            td style=(html_utils::get_metric_cell_style(owner.map_or(0.0, |o| o.percentage), &heat)) { (format!("{:.1}", owner.map_or(0.0, |o| o.percentage))) }
+                            td { (component.bus_factor) }
+                            td { (if component.primary_owner_active { "yes" } else { "no" }) }
+                        }
+                    }
+                }
+            }
+            h2 { "Ownership Heat Map" }
+            table class="sortable-table" {
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                        @for (idx, (email, _, author)) in columns.iter().enumerate() {
+                            th class="sortable-header" data-column-index=(idx + 1) data-sort-type="number" title=(email) { (author) }
+                        }
+                    }
+                }
+                tbody {
+                    @for component in &data.components {
+                        tr {
+                            td { (component.name) }
+                            @for (email, _, _) in &columns {
+                                @let share = share_of(component, email);
+                                td style=(html_utils::get_metric_cell_style(share, &heat)) { (format!("{:.0}", share)) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Lines added plus deleted in one file by one commit.
+pub(crate) struct FileChurn {
+    pub(crate) path: PathBuf,
+    pub(crate) identity: Identity,
+    pub(crate) lines: u64,
+}
+
+/// Returns the churn of every file changed in the window that passes the
+/// window's path filter, attributed to the resolved commit author.
+pub(crate) fn churn_by_file(
+    repo: &Repository,
+    window: &HistoryWindow,
+    jobs: usize,
+    identities: &IdentityResolver,
+) -> Result<Vec<FileChurn>> {
+    let commits = window.commits(repo)?;
+    let mut store = CommitStatsStore::open(repo)?;
+    store.prefetch(repo, &commits, jobs)?;
+
+    let mut churn = Vec::new();
+    for oid in commits {
+        let commit = repo.find_commit(oid)?;
+        let identity = identities.resolve(&commit.author());
+        let diff = store.get_or_compute(repo, &commit)?;
+        churn.extend(
+            diff.files
+                .iter()
+                .filter(|change| window.filter.includes_path(&change.path))
+                .map(|change| FileChurn {
+                    path: change.path.clone(),
+                    identity: identity.clone(),
+                    lines: u64::from(change.lines_added) + u64::from(change.lines_deleted),
+                }),
+        );
+    }
+    store.save()?;
+    Ok(churn)
+}
+
+/// Returns the component `path` belongs to, or `None` if it is outside every
+/// crate. Components are crates, or the modules resolved by `modules` when
+/// given; files other than `.rs` files belong to the module of their directory.
+fn component_of(
+    path: &Path,
+    crates: &CrateStatsMap,
+    modules: Option<&ComponentResolver>,
+) -> Option<String> {
+    let (crate_name, _) = VolatilityRule::new().find_owning_crate(path, crates)?;
+    match modules {
+        None => Some(crate_name),
+        Some(modules) if path.extension().is_some_and(|ext| ext == "rs") => modules.module_of(path),
+        Some(modules) => modules.module_of(path.parent().unwrap_or(Path::new(""))),
+    }
+}
+
+/// Builds a component's ownership summary from its per-author line counts.
+fn build_component(
+    name: String,
+    by_author: HashMap<String, u64>,
+    names: &HashMap<String, String>,
+    last_seen: &HashMap<String, i64>,
+    active_since: i64,
+) -> ComponentOwnership {
+    let total_lines: u64 = by_author.values().sum();
+    let mut authors: Vec<AuthorShare> = by_author
+        .into_iter()
+        .map(|(email, lines)| AuthorShare {
+            author: names.get(&email).cloned().unwrap_or_else(|| email.clone()),
+            percentage: lines as f64 * 100.0 / total_lines.max(1) as f64,
+            email,
+            lines,
+        })
+        .collect();
+    authors.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.email.cmp(&b.email)));
+
+    let primary_owner_last_commit = authors
+        .first()
+        .and_then(|owner| last_seen.get(&owner.email).copied());
+    ComponentOwnership {
+        name,
+        total_lines,
+        bus_factor: bus_factor(&authors, total_lines),
+        primary_owner_active: primary_owner_last_commit.is_some_and(|time| time >= active_since),
+        primary_owner_last_commit,
+        authors,
+    }
+}
+
+/// Returns the fewest authors, taken largest first, whose lines exceed half of `total`.
+fn bus_factor(authors: &[AuthorShare], total: u64) -> usize {
+    let mut covered = 0;
+    for (idx, share) in authors.iter().enumerate() {
+        covered += share.lines;
+        if covered * 2 > total {
+            return idx + 1;
+        }
+    }
+    authors.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::HistoryArgs;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn commit(dir: &Path, file: &str, content: &str, author: (&str, &str), date: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        for args in [vec!["add", "-A"], vec!["commit", "-q", "-m", file]] {
+            let output = Command::new("git")
+                .args(&args)
+                .env("GIT_AUTHOR_NAME", author.0)
+                .env("GIT_AUTHOR_EMAIL", author.1)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_NAME", author.0)
+                .env("GIT_COMMITTER_EMAIL", author.1)
+                .env("GIT_COMMITTER_DATE", date)
+                .current_dir(dir)
+                .output()
+                .expect("Failed to run git");
+            assert!(output.status.success(), "git {:?} failed", args);
+        }
+    }
+
+    /// Creates a workspace where `core` is written by a departed author and
+    /// `app` is shared between two active authors.
+    fn create_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let old = ("Old Author", "old@example.com");
+        let alice = ("Alice", "alice@example.com");
+        let bob = ("Bob", "bob@example.com");
+        let recent = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let output = Command::new("git")
+            .args(["init", "-q", "-b", "main"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        commit(
+            dir,
+            "core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
+            old,
+            "2020-01-01T00:00:00Z",
+        );
+        commit(
+            dir,
+            "core/src/lib.rs",
+            "a\nb\nc\nd\n",
+            old,
+            "2020-01-01T00:00:00Z",
+        );
+        commit(dir, "core/src/extra.rs", "e\n", alice, &recent);
+        commit(
+            dir,
+            "app/Cargo.toml",
+            "[package]\nname = \"app\"\n",
+            alice,
+            &recent,
+        );
+        commit(dir, "app/src/main.rs", "a\nb\nc\n", alice, &recent);
+        commit(dir, "app/src/cli.rs", "a\nb\nc\nd\n", bob, &recent);
+        temp_dir
+    }
+
+    fn args(path: &Path, source: OwnershipSource) -> OwnershipArgs {
+        OwnershipArgs {
+            path: path.to_path_buf(),
+            history: HistoryArgs {
+                jobs: Some(2),
+                ..HistoryArgs::default()
+            },
+            granularity: OwnershipGranularity::Crate,
+            source,
+            inactive_months: 6,
+            aliases: BTreeMap::new(),
+            output: OwnershipOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    fn component<'a>(data: &'a OwnershipData, name: &str) -> &'a ComponentOwnership {
+        data.components
+            .iter()
+            .find(|c| c.name == name)
+            .expect("component should be reported")
+    }
+
+    #[test]
+    fn test_blame_ownership_and_bus_factor() {
+        let temp_dir = create_repo();
+        let data = OwnershipRule::new()
+            .analyze(&args(temp_dir.path(), OwnershipSource::Blame))
+            .unwrap();
+
+        let core = component(&data, "core");
+        assert_eq!(core.primary_owner().unwrap().email, "old@example.com");
+        assert_eq!(core.primary_owner().unwrap().percentage, 80.0);
+        assert_eq!(core.bus_factor, 1);
+        assert!(!core.primary_owner_active);
+
+        let app = component(&data, "app");
+        // Bob blamed for 4 of 7 lines holds a strict majority on his own
+        assert_eq!(app.primary_owner().unwrap().author, "Bob");
+        assert_eq!(app.bus_factor, 1);
+        assert!(app.primary_owner_active);
+
+        let findings = data.to_findings();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("'core'"));
+    }
+
+    #[test]
+    fn test_churn_ownership_counts_manifest_changes() {
+        let temp_dir = create_repo();
+        let data = OwnershipRule::new()
+            .analyze(&args(temp_dir.path(), OwnershipSource::Churn))
+            .unwrap();
+
+        // Alice wrote app's manifest (2 lines) and main.rs (3 lines); Bob wrote cli.rs (4 lines)
+        let app = component(&data, "app");
+        assert_eq!(app.total_lines, 9);
+        assert_eq!(app.primary_owner().unwrap().author, "Alice");
+        assert_eq!(app.bus_factor, 1);
+    }
+
+    #[test]
+    fn test_module_granularity_names_rust_modules() {
+        let temp_dir = create_repo();
+        let mut args = args(temp_dir.path(), OwnershipSource::Blame);
+        args.granularity = OwnershipGranularity::Module;
+        let data = OwnershipRule::new().analyze(&args).unwrap();
+
+        let mut names: Vec<&str> = data.components.iter().map(|c| c.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["app", "app::cli", "core", "core::extra"]);
+        let cli = component(&data, "app::cli");
+        assert_eq!(cli.primary_owner().unwrap().email, "bob@example.com");
+        assert_eq!(cli.total_lines, 4);
+    }
+
+    #[test]
+    fn test_aliases_unify_authors() {
+        let temp_dir = create_repo();
+        let mut args = args(temp_dir.path(), OwnershipSource::Blame);
+        args.aliases = BTreeMap::from([(
+            "bob@example.com".to_string(),
+            vec!["old@example.com".to_string()],
+        )]);
+        let data = OwnershipRule::new().analyze(&args).unwrap();
+
+        // The departed author's lines now belong to Bob, who is active
+        let core = component(&data, "core");
+        assert_eq!(core.primary_owner().unwrap().email, "bob@example.com");
+        assert_eq!(core.primary_owner().unwrap().percentage, 80.0);
+        assert!(core.primary_owner_active);
+        assert!(data.to_findings().is_empty());
+    }
+
+    #[test]
+    fn test_bus_factor_requires_strict_majority() {
+        let share = |lines| AuthorShare {
+            author: String::new(),
+            email: String::new(),
+            lines,
+            percentage: 0.0,
+        };
+        assert_eq!(bus_factor(&[share(5), share(5)], 10), 2);
+        assert_eq!(bus_factor(&[share(6), share(4)], 10), 1);
+        assert_eq!(bus_factor(&[], 0), 0);
+    }
+}
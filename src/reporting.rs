@@ -1,10 +1,16 @@
 use prettytable::{Cell, Row, Table, format};
 use std::collections::HashMap;
 
+/// Prints the statement count table. `component_stats` holds the file count
+/// and production statement count of each component and `test_stats` its
+/// test statement count. Percentages are of `grand_total`, and include test
+/// statements when `include_tests` is set.
 pub fn print_report(
     component_stats: &HashMap<String, (usize, usize)>,
+    test_stats: &HashMap<String, usize>,
     grand_total: usize,
     threshold: usize,
+    include_tests: bool,
 ) -> bool {
     let mut table = Table::new();
     let format = format::FormatBuilder::new()
@@ -30,6 +36,7 @@ pub fn print_report(
         Cell::new("Component"),
         Cell::new("Percent"),
         Cell::new("Statements"),
+        Cell::new("Test Statements"),
         Cell::new("Files"),
     ]));
 
@@ -38,7 +45,13 @@ pub fn print_report(
 
     let mut any_over_threshold = false;
     for &(component, &(files, stmts)) in &sorted {
-        let percent = ((stmts as f64 / grand_total as f64) * 100.0).round() as usize;
+        let test_stmts = test_stats.get(component).copied().unwrap_or(0);
+        let measured = if include_tests {
+            stmts + test_stmts
+        } else {
+            stmts
+        };
+        let percent = ((measured as f64 / grand_total as f64) * 100.0).round() as usize;
         if percent > threshold {
             any_over_threshold = true;
         }
@@ -46,6 +59,7 @@ pub fn print_report(
             Cell::new(component),
             Cell::new(&format!("{percent} %")),
             Cell::new(&stmts.to_string()),
+            Cell::new(&test_stmts.to_string()),
             Cell::new(&files.to_string()),
         ]));
     }
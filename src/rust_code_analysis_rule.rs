@@ -1,41 +1,63 @@
 //! Rust Code Analysis Rule
 //!
-//! This module provides a wrapper around the `rust-code-analysis-cli` tool to perform
-//! extended code metrics analysis. It aggregates complex metrics like cyclomatic complexity,
-//! Halstead metrics, and lines of code counts for Rust files.
+//! This module performs extended code metrics analysis. It aggregates complex metrics like
+//! cyclomatic complexity, Halstead metrics, and lines of code counts for Rust files.
 //!
 //! # Overview
 //!
-//! The rule acts as a convenient wrapper around the `rust-code-analysis-cli` external tool,
-//! which provides detailed code metrics including:
+//! Metrics are computed by one of two backends, selected with `--backend`:
+//!
+//! - `builtin` (default): the in-process [`crate::complexity`] engine, which works on the
+//!   `syn` AST and supports Rust only
+//! - `external`: the `rust-code-analysis-cli` tool, which supports every language the tool does
+//!
+//! Both produce the same [`AnalysisUnit`]/[`CodeSpace`] trees, with metrics including:
 //!
 //! - **Lines of Code**: SLOC, PLOC, LLOC, CLOC, and blank lines
 //! - **Cyclomatic Complexity**: Sum and average complexity measures
+//! - **Cognitive Complexity**: Sum and average, weighted by nesting depth
 //! - **Halstead Metrics**: Length, vocabulary, volume, effort, time, and bug estimates
+//! - **NOM/NARGS/NEXITS**: Number of methods, arguments, and exit points
+//! - **Maintainability Index**: Original, SEI, and Visual Studio variants
+//! - **ABC**: Assignments, branches, and conditions, and their magnitude
 //!
 //! # Prerequisites
 //!
-//! This rule requires `rust-code-analysis-cli` to be installed and available in your PATH.
-//! If the tool is not found, the analysis will fail with a clear error message.
+//! The external backend requires `rust-code-analysis-cli` to be installed and available in
+//! your PATH. If the tool is not found, the analysis will fail with a clear error message.
+//! The builtin backend has no prerequisites.
 //!
 //! # Usage
 //!
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use raff_core::rust_code_analysis_rule::RustCodeAnalysisRule;
-//! use raff_core::{RustCodeAnalysisArgs, RustCodeAnalysisOutputFormat};
+//! use raff_core::{
+//!     ComponentMode, RustCodeAnalysisArgs, RustCodeAnalysisBackend, RustCodeAnalysisOutputFormat,
+//! };
 //! use std::path::PathBuf;
 //!
 //! let rule = RustCodeAnalysisRule::new();
 //! let args = RustCodeAnalysisArgs {
 //!     path: PathBuf::from("."),
 //!     language: "rust".to_string(),
+//!     backend: RustCodeAnalysisBackend::Builtin,
+//!     components: ComponentMode::Directory,
+//!     component_definitions: Default::default(),
+//!     max_file_cognitive: None,
+//!     min_file_mi: None,
+//!     max_file_abc: None,
+//!     max_function_cyclomatic: None,
+//!     max_function_cognitive: None,
+//!     max_function_args: None,
+//!     max_function_length: None,
 //!     metrics: true,
 //!     jobs: 4,
 //!     extra_flags: vec![],
 //!     output: RustCodeAnalysisOutputFormat::Table,
 //!     ci_output: None,
 //!     output_file: None,
+//!     no_cache: false,
 //! };
 //!
 //! if let Err(e) = rule.run(&args) {
@@ -70,11 +92,43 @@
 //! - **Time**: Estimated time to develop/understand (seconds)
 //! - **Bugs**: Estimated number of delivered bugs
 //!
+//! ## Cognitive Complexity, Maintainability Index and ABC
+//!
+//! - **Cog Sum / Cog Max**: Cognitive complexity of the file and of its most complex function.
+//!   Unlike cyclomatic complexity, nested control flow costs more than flat control flow
+//! - **MI Avg / MI Min**: Mean and lowest maintainability index of the file's functions, on
+//!   the 0-100 Visual Studio scale (higher is better)
+//! - **ABC**: Magnitude of the file's assignment, branch (call) and condition counts
+//!
+//! # Thresholds
+//!
+//! `--max-file-cognitive`, `--min-file-mi` and `--max-file-abc` fail the run for files that
+//! breach them. `--max-function-cyclomatic`, `--max-function-cognitive`,
+//! `--max-function-args` and `--max-function-length` do the same for single functions,
+//! methods and closures. Breaches are reported after the table and as error findings in
+//! CI output; function findings carry the function's line span, so SARIF annotations
+//! point at the function itself.
+//!
+//! # Roll-ups
+//!
+//! File metrics are also rolled up per top-level module, per crate and per component
+//! (grouped by `--components` and `[components.define]`, like the other rules). Each
+//! [`ComplexityRollup`] carries the sum, mean, 90th percentile and maximum of cyclomatic and
+//! cognitive complexity over the group's functions, its total Halstead effort and its mean
+//! maintainability index. The `all` command joins the component roll-ups with size, coupling and
+//! volatility in its HTML dashboard.
+//!
+//! # Caching
+//!
+//! Results are cached per file, keyed on a hash of its content, so later runs only
+//! re-analyze files that changed. The cache is discarded when the backend, its version, the
+//! language, `--metrics` or the extra flags change, and it is skipped with `--no-cache`.
+//!
 //! # Output Formats
 //!
 //! The rule supports multiple output formats:
 //! - `Table`: Human-readable table with metric explanations
-//! - `Json`: Machine-readable JSON (passthrough from tool)
+//! - `Json`: Machine-readable JSON of every analyzed file and its spaces
 //! - `Yaml`: Machine-readable YAML
 //! - `Html`: Interactive HTML report with color-coded metrics
 //!
@@ -82,31 +136,38 @@
 //!
 //! The rule automatically discovers relevant source files based on the specified language:
 //! - For Rust (`.rs` files), it walks the directory tree while ignoring `target/`, `node_modules/`, etc.
-//! - For other languages, it passes the path directly to the underlying tool
+//! - For other languages, it passes the path directly to the external tool
 //!
 //! # Errors
 //!
 //! This module returns [`RaffError`] in the following cases:
+//! - The builtin backend is asked to analyze a language other than Rust
 //! - `rust-code-analysis-cli` is not found in PATH
 //! - The tool exits with a non-zero status
 //! - The tool produces invalid JSON output
 
+use crate::cache::{CacheEntry, CacheKey, CacheManager, hash_bytes, hash_file};
 use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::components::ComponentResolver;
 use crate::error::{RaffError, Result};
 use crate::rule::Rule;
 use prettytable::{Attr, Cell, Row, Table, format as pt_format};
 use serde::{Deserialize, Serialize};
 // use std::fmt::Write; // No longer needed for HTML buffer
 use maud::html;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use tracing::instrument;
 
-use crate::cli::{CiOutputFormat, RustCodeAnalysisArgs, RustCodeAnalysisOutputFormat};
+use crate::cli::{
+    CiOutputFormat, ComponentMode, RustCodeAnalysisArgs, RustCodeAnalysisBackend,
+    RustCodeAnalysisOutputFormat,
+};
 use crate::html_utils;
 
-// --- Structs for deserializing rust-code-analysis-cli JSON output ---
+// --- Structs for rust-code-analysis metrics, matching the rust-code-analysis-cli JSON output ---
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct LocMetrics {
     pub sloc: f64,
     pub ploc: f64,
@@ -115,13 +176,13 @@ pub struct LocMetrics {
     pub blank: f64,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CyclomaticMetrics {
     pub sum: f64,
     pub average: f64,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct HalsteadMetrics {
     pub n1: f64,
     pub n2: f64,
@@ -134,16 +195,71 @@ pub struct HalsteadMetrics {
     pub bugs: f64,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CognitiveMetrics {
+    pub sum: f64,
+    pub average: f64,
+}
+
+/// Number of methods: functions and closures declared in a space.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NomMetrics {
+    pub functions: f64,
+    pub closures: f64,
+    pub total: f64,
+}
+
+/// Number of arguments of the functions and closures in a space.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NargsMetrics {
+    pub total: f64,
+    pub average: f64,
+}
+
+/// Number of exit points (`return` and `?`) of the functions in a space.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NexitsMetrics {
+    pub sum: f64,
+    pub average: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MaintainabilityMetrics {
+    pub mi_original: f64,
+    pub mi_sei: f64,
+    pub mi_visual_studio: f64,
+}
+
+/// Assignments, branches (calls) and conditions, and the magnitude of the
+/// resulting vector.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AbcMetrics {
+    pub assignments: f64,
+    pub branches: f64,
+    pub conditions: f64,
+    pub magnitude: f64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
 pub struct ItemMetrics {
-    // For simplicity, focusing on loc and cyclomatic for now. Add others as needed.
     pub loc: Option<LocMetrics>,
     pub cyclomatic: Option<CyclomaticMetrics>,
     pub halstead: Option<HalsteadMetrics>,
-    // Other metrics like 'nom', 'mi', 'abc', 'cognitive' can be added here
+    #[serde(default)]
+    pub cognitive: Option<CognitiveMetrics>,
+    #[serde(default)]
+    pub nom: Option<NomMetrics>,
+    #[serde(default)]
+    pub nargs: Option<NargsMetrics>,
+    #[serde(default)]
+    pub nexits: Option<NexitsMetrics>,
+    #[serde(default)]
+    pub mi: Option<MaintainabilityMetrics>,
+    #[serde(default)]
+    pub abc: Option<AbcMetrics>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct CodeSpace {
     pub name: String,
     pub kind: String,
@@ -153,7 +269,7 @@ pub struct CodeSpace {
     pub spaces: Vec<CodeSpace>, // For nested items
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AnalysisUnit {
     pub name: String, // Full path to the file
     pub kind: String, // "unit" for files
@@ -177,6 +293,136 @@ struct FileAggregatedMetrics {
     halstead_time: f64,
     halstead_bugs: f64,
     items_with_metrics: usize, // Count of spaces (e.g. functions) that contributed metrics
+    // Space metrics include their nested spaces, so file totals of the metrics below
+    // come from the top-level spaces only, and per-function values from function-like spaces
+    cognitive_sum: f64,
+    cognitive_max: f64,
+    abc_assignments: f64,
+    abc_branches: f64,
+    abc_conditions: f64,
+    mi_sum: f64,
+    mi_min: Option<f64>,
+    mi_count: usize,
+}
+
+impl FileAggregatedMetrics {
+    /// Mean maintainability index (Visual Studio variant) of the file's functions.
+    fn mi_average(&self) -> Option<f64> {
+        (self.mi_count > 0).then(|| self.mi_sum / self.mi_count as f64)
+    }
+
+    /// Magnitude of the file's ABC vector.
+    fn abc_magnitude(&self) -> f64 {
+        (self.abc_assignments.powi(2) + self.abc_branches.powi(2) + self.abc_conditions.powi(2))
+            .sqrt()
+    }
+}
+
+/// Checks every function-like space under `spaces` against the function
+/// limits. `scope` is the `::`-joined name of the enclosing spaces.
+fn function_violations(
+    thresholds: &ComplexityThresholds,
+    file: &str,
+    spaces: &[CodeSpace],
+    scope: &str,
+    violations: &mut Vec<ComplexityViolation>,
+) {
+    for space in spaces {
+        let name = if scope.is_empty() {
+            space.name.clone()
+        } else {
+            format!("{scope}::{}", space.name)
+        };
+        if is_function_like(space) {
+            let mut violation = |metric: &str, value: f64, limit: String, check: &str| {
+                violations.push(ComplexityViolation {
+                    message: format!(
+                        "Function '{name}' in '{file}' has {metric} {value}, exceeding the limit of {limit}"
+                    ),
+                    location: Some(Location::with_lines(
+                        file.to_string(),
+                        space.start_line,
+                        space.end_line,
+                    )),
+                    fingerprint: format!("rust-code-analysis:{check}:{file}:{name}:{limit}"),
+                });
+            };
+
+            if let Some(max) = thresholds.max_function_cyclomatic
+                && let Some(cyclomatic) = &space.metrics.cyclomatic
+                && cyclomatic.sum > max
+            {
+                violation(
+                    "cyclomatic complexity",
+                    cyclomatic.sum,
+                    max.to_string(),
+                    "function-cyclomatic",
+                );
+            }
+            if let Some(max) = thresholds.max_function_cognitive
+                && let Some(cognitive) = &space.metrics.cognitive
+                && cognitive.sum > max
+            {
+                violation(
+                    "cognitive complexity",
+                    cognitive.sum,
+                    max.to_string(),
+                    "function-cognitive",
+                );
+            }
+            // Argument counts include nested closures; subtract them to get the function's own
+            if let Some(max) = thresholds.max_function_args
+                && let Some(nargs) = &space.metrics.nargs
+            {
+                let nested: f64 = space
+                    .spaces
+                    .iter()
+                    .filter_map(|child| child.metrics.nargs.as_ref())
+                    .map(|child| child.total)
+                    .sum();
+                let args = nargs.total - nested;
+                if args > max as f64 {
+                    violation("arguments", args, max.to_string(), "function-args");
+                }
+            }
+            if let Some(max) = thresholds.max_function_length {
+                let length = space.end_line.saturating_sub(space.start_line) + 1;
+                if length > max {
+                    violation(
+                        "length",
+                        length as f64,
+                        format!("{max} lines"),
+                        "function-length",
+                    );
+                }
+            }
+        }
+        function_violations(thresholds, file, &space.spaces, &name, violations);
+    }
+}
+
+fn is_function_like(space: &CodeSpace) -> bool {
+    matches!(
+        space.kind.as_str(),
+        "function" | "method" | "closure" | "associated_function"
+    )
+}
+
+/// Aggregates the metrics of every space in a file.
+fn aggregate_file_metrics(unit: &AnalysisUnit) -> FileAggregatedMetrics {
+    let mut acc = FileAggregatedMetrics::default();
+    aggregate_metrics_recursive(&unit.spaces, &mut acc);
+    for space in &unit.spaces {
+        if let Some(cognitive) = &space.metrics.cognitive {
+            acc.cognitive_sum += cognitive.sum;
+        }
+        if let Some(abc) = &space.metrics.abc {
+            acc.abc_assignments += abc.assignments;
+            acc.abc_branches += abc.branches;
+            acc.abc_conditions += abc.conditions;
+        }
+    }
+    acc
 }
 
 // --- Recursive Aggregation Logic ---
@@ -215,14 +461,22 @@ fn aggregate_metrics_recursive(spaces: &[CodeSpace], acc: &mut FileAggregatedMet
         // Heuristic: if a space of kind function/method/closure provided any non-zero primary metric, count it.
         // Or, more simply, if it had a metrics block that wasn't entirely default/empty.
         // For now, `has_metrics_for_this_space` based on positive primary metric values.
-        if has_metrics_for_this_space
-            && (space.kind == "function"
-                || space.kind == "method"
-                || space.kind == "closure"
-                || space.kind == "associated_function")
-        {
+        if has_metrics_for_this_space && is_function_like(space) {
             acc.items_with_metrics += 1;
         }
+        if is_function_like(space) {
+            if let Some(cognitive) = &space.metrics.cognitive {
+                acc.cognitive_max = acc.cognitive_max.max(cognitive.sum);
+            }
+            if let Some(mi) = &space.metrics.mi {
+                acc.mi_sum += mi.mi_visual_studio;
+                acc.mi_count += 1;
+                acc.mi_min = Some(
+                    acc.mi_min
+                        .map_or(mi.mi_visual_studio, |min| min.min(mi.mi_visual_studio)),
+                );
+            }
+        }
 
         // Recursive call for nested spaces
         if !space.spaces.is_empty() {
@@ -231,15 +485,311 @@ fn aggregate_metrics_recursive(spaces: &[CodeSpace], acc: &mut FileAggregatedMet
     }
 }
 
+/// What a single file contributes to the complexity roll-ups.
+#[derive(Default, Debug)]
+struct FileRollupInputs {
+    sloc: f64,
+    lloc: f64,
+    halstead_effort: f64,
+    functions: usize,
+    cyclomatic: Vec<f64>,
+    cognitive: Vec<f64>,
+    mi: Vec<f64>,
+}
+
+fn rollup_inputs(unit: &AnalysisUnit) -> FileRollupInputs {
+    let mut inputs = FileRollupInputs::default();
+    // File-level metrics already cover every space; without them, the
+    // top-level spaces do, since nested metrics are inclusive
+    let file_metrics: Vec<&ItemMetrics> = match &unit.metrics {
+        Some(metrics) => vec![metrics],
+        None => unit.spaces.iter().map(|space| &space.metrics).collect(),
+    };
+    for metrics in file_metrics {
+        if let Some(loc) = &metrics.loc {
+            inputs.sloc += loc.sloc;
+            inputs.lloc += loc.lloc;
+        }
+        if let Some(halstead) = &metrics.halstead {
+            inputs.halstead_effort += halstead.effort;
+        }
+    }
+    collect_function_metrics(&unit.spaces, &mut inputs);
+    inputs
+}
+
+/// Records the outermost function-like spaces; their metrics include any
+/// closures nested in them.
+fn collect_function_metrics(spaces: &[CodeSpace], inputs: &mut FileRollupInputs) {
+    for space in spaces {
+        if !is_function_like(space) {
+            collect_function_metrics(&space.spaces, inputs);
+            continue;
+        }
+        inputs.functions += 1;
+        if let Some(cyclomatic) = &space.metrics.cyclomatic {
+            inputs.cyclomatic.push(cyclomatic.sum);
+        }
+        if let Some(cognitive) = &space.metrics.cognitive {
+            inputs.cognitive.push(cognitive.sum);
+        }
+        if let Some(mi) = &space.metrics.mi {
+            inputs.mi.push(mi.mi_visual_studio);
+        }
+    }
+}
+
+/// Rolls up files by group name, sorted by name.
+fn build_rollups<'a>(
+    files: impl IntoIterator<Item = (String, &'a FileRollupInputs)>,
+) -> Vec<ComplexityRollup> {
+    let mut groups: BTreeMap<String, Vec<&FileRollupInputs>> = BTreeMap::new();
+    for (name, inputs) in files {
+        groups.entry(name).or_default().push(inputs);
+    }
+    groups
+        .into_iter()
+        .map(|(name, files)| {
+            let values = |select: fn(&FileRollupInputs) -> &[f64]| -> Vec<f64> {
+                files
+                    .iter()
+                    .flat_map(|f| select(f).iter().copied())
+                    .collect()
+            };
+            let mi = values(|f| &f.mi);
+            ComplexityRollup {
+                name,
+                files: files.len(),
+                functions: files.iter().map(|f| f.functions).sum(),
+                sloc: files.iter().map(|f| f.sloc).sum(),
+                lloc: files.iter().map(|f| f.lloc).sum(),
+                cyclomatic: Distribution::from_values(&values(|f| &f.cyclomatic)),
+                cognitive: Distribution::from_values(&values(|f| &f.cognitive)),
+                halstead_effort: files.iter().map(|f| f.halstead_effort).sum(),
+                mi_mean: (!mi.is_empty()).then(|| mi.iter().sum::<f64>() / mi.len() as f64),
+            }
+        })
+        .collect()
+}
+
+/// Rolls up the analyzed files per module, crate and component. Paths are
+/// resolved relative to `analysis_path`; files outside any crate are grouped
+/// by top-level directory.
+fn rollup_complexity(
+    analysis_path: &Path,
+    analysis_results: &[AnalysisUnit],
+    components: ComponentMode,
+    component_definitions: &BTreeMap<String, Vec<String>>,
+) -> Result<ComplexityRollups> {
+    if analysis_results.is_empty() {
+        return Ok(ComplexityRollups::default());
+    }
+    let modules = ComponentResolver::new(analysis_path, ComponentMode::Module, &BTreeMap::new())?;
+    let components = ComponentResolver::new(analysis_path, components, component_definitions)?;
+
+    let files: Vec<(PathBuf, FileRollupInputs)> = analysis_results
+        .iter()
+        .map(|unit| {
+            let path = Path::new(&unit.name);
+            let relative = path.strip_prefix(analysis_path).unwrap_or(path);
+            (relative.to_path_buf(), rollup_inputs(unit))
+        })
+        .collect();
+
+    Ok(ComplexityRollups {
+        modules: build_rollups(
+            files
+                .iter()
+                .map(|(path, f)| (modules.component_of(path), f)),
+        ),
+        crates: build_rollups(files.iter().map(|(path, f)| {
+            let name = modules
+                .owning_crate(path)
+                .map_or_else(|| modules.component_of(path), |c| c.name.clone());
+            (name, f)
+        })),
+        components: build_rollups(
+            files
+                .iter()
+                .map(|(path, f)| (components.component_of(path), f)),
+        ),
+    })
+}
+
 // --- Rule implementation ---
 
 #[derive(Debug)]
 pub struct RustCodeAnalysisRule;
 
+/// Per-file complexity thresholds; `None` disables a check.
+#[derive(Serialize, Debug, Default, Clone, Copy)]
+pub struct ComplexityThresholds {
+    pub max_file_cognitive: Option<f64>,
+    /// Minimum mean maintainability index (Visual Studio variant, 0-100).
+    pub min_file_mi: Option<f64>,
+    pub max_file_abc: Option<f64>,
+    pub max_function_cyclomatic: Option<f64>,
+    pub max_function_cognitive: Option<f64>,
+    pub max_function_args: Option<usize>,
+    /// Maximum function length in lines.
+    pub max_function_length: Option<usize>,
+}
+
+impl ComplexityThresholds {
+    fn has_function_limits(&self) -> bool {
+        self.max_function_cyclomatic.is_some()
+            || self.max_function_cognitive.is_some()
+            || self.max_function_args.is_some()
+            || self.max_function_length.is_some()
+    }
+}
+
+/// A breached complexity threshold.
+#[derive(Debug, Clone)]
+pub struct ComplexityViolation {
+    pub message: String,
+    pub location: Option<Location>,
+    pub fingerprint: String,
+}
+
+/// Sum, mean, 90th percentile and maximum of a per-function metric.
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    pub sum: f64,
+    pub mean: f64,
+    /// Nearest-rank 90th percentile.
+    pub p90: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    /// Summarizes `values`; an empty slice yields all zeros.
+    pub fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let sum: f64 = sorted.iter().sum();
+        let rank = (sorted.len() as f64 * 0.9).ceil() as usize;
+        Self {
+            sum,
+            mean: sum / sorted.len() as f64,
+            p90: sorted[rank.clamp(1, sorted.len()) - 1],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Complexity metrics rolled up over the files of a module, crate or
+/// component. Per-function distributions count each top-level function or
+/// method once, with the closures nested in it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ComplexityRollup {
+    pub name: String,
+    pub files: usize,
+    pub functions: usize,
+    pub sloc: f64,
+    pub lloc: f64,
+    pub cyclomatic: Distribution,
+    pub cognitive: Distribution,
+    pub halstead_effort: f64,
+    /// Mean maintainability index (Visual Studio variant, 0-100) of the
+    /// functions, if any reported one.
+    pub mi_mean: Option<f64>,
+}
+
+/// Complexity roll-ups at every grouping level, each sorted by name.
+#[derive(Serialize, Debug, Default)]
+pub struct ComplexityRollups {
+    /// Grouped by top-level module (`crate::module`).
+    pub modules: Vec<ComplexityRollup>,
+    /// Grouped by owning crate.
+    pub crates: Vec<ComplexityRollup>,
+    /// Grouped by `--components` and `[components.define]`.
+    pub components: Vec<ComplexityRollup>,
+}
+
 #[derive(Serialize, Debug)]
 pub struct RustCodeAnalysisData {
     pub analysis_results: Vec<AnalysisUnit>,
     pub analysis_path: PathBuf,
+    pub thresholds: ComplexityThresholds,
+    pub rollups: ComplexityRollups,
+}
+
+impl RustCodeAnalysisData {
+    /// Returns every file that breaches a complexity threshold.
+    pub fn threshold_violations(&self) -> Vec<ComplexityViolation> {
+        let thresholds = &self.thresholds;
+        let mut violations = Vec::new();
+        for unit in &self.analysis_results {
+            let aggregated = aggregate_file_metrics(unit);
+            let file = crate::ci_report::normalize_repo_relative(
+                Path::new(&unit.name),
+                &self.analysis_path,
+            );
+            let mut violation = |message: String, check: &str, limit: f64| {
+                violations.push(ComplexityViolation {
+                    message,
+                    location: Some(Location::new(file.clone())),
+                    fingerprint: format!("rust-code-analysis:{check}:{file}:{limit}"),
+                });
+            };
+
+            if let Some(max) = thresholds.max_file_cognitive
+                && aggregated.cognitive_sum > max
+            {
+                violation(
+                    format!(
+                        "File '{file}' has cognitive complexity {:.0}, exceeding the limit of {max}",
+                        aggregated.cognitive_sum
+                    ),
+                    "file-cognitive",
+                    max,
+                );
+            }
+            if let Some(min) = thresholds.min_file_mi
+                && let Some(mi) = aggregated.mi_average()
+                && mi < min
+            {
+                violation(
+                    format!(
+                        "File '{file}' has a mean maintainability index of {mi:.1}, below the minimum of {min}"
+                    ),
+                    "file-mi",
+                    min,
+                );
+            }
+            if let Some(max) = thresholds.max_file_abc
+                && aggregated.abc_magnitude() > max
+            {
+                violation(
+                    format!(
+                        "File '{file}' has ABC magnitude {:.1}, exceeding the limit of {max}",
+                        aggregated.abc_magnitude()
+                    ),
+                    "file-abc",
+                    max,
+                );
+            }
+            if thresholds.has_function_limits() {
+                function_violations(thresholds, &file, &unit.spaces, "", &mut violations);
+            }
+        }
+        violations
+    }
+
+    /// Returns the error to exit with when a complexity threshold is breached.
+    fn thresholds_error(&self) -> Option<RaffError> {
+        let violations = self.threshold_violations().len();
+        (violations > 0).then(|| {
+            RaffError::analysis_error(
+                "rust_code_analysis",
+                format!("{violations} complexity threshold(s) breached."),
+            )
+        })
+    }
 }
 
 impl ToFindings for RustCodeAnalysisData {
@@ -251,8 +801,7 @@ impl ToFindings for RustCodeAnalysisData {
         // RustCodeAnalysis provides informational metrics, not errors or warnings
         for unit in &self.analysis_results {
             // Aggregate metrics for this file
-            let mut aggregated = FileAggregatedMetrics::default();
-            aggregate_metrics_recursive(&unit.spaces, &mut aggregated);
+            let aggregated = aggregate_file_metrics(unit);
 
             // Only create findings for files with actual metrics
             if aggregated.sloc > 0.0 || aggregated.cyclomatic_sum > 0.0 {
@@ -275,6 +824,7 @@ impl ToFindings for RustCodeAnalysisData {
                         aggregated.sloc, cyclomatic_avg, aggregated.halstead_volume
                     ),
                     location: Some(Location::new(relative_path)),
+                    related_locations: Vec::new(),
                     help_uri: Some(
                         "https://github.com/liamwh/raff/docs/rust-code-analysis".to_string(),
                     ),
@@ -286,6 +836,22 @@ impl ToFindings for RustCodeAnalysisData {
             }
         }
 
+        findings.extend(
+            self.threshold_violations()
+                .into_iter()
+                .map(|violation| Finding {
+                    rule_id: "rust-code-analysis".to_string(),
+                    rule_name: "Rust Code Analysis Rule".to_string(),
+                    severity: Severity::Error,
+                    message: violation.message,
+                    location: violation.location,
+                    related_locations: Vec::new(),
+                    help_uri: Some(
+                        "https://github.com/liamwh/raff/docs/rust-code-analysis".to_string(),
+                    ),
+                    fingerprint: Some(violation.fingerprint),
+                }),
+        );
         findings
     }
 }
@@ -299,7 +865,7 @@ impl Rule for RustCodeAnalysisRule {
     }
 
     fn description() -> &'static str {
-        "Performs extended code analysis using rust-code-analysis-cli tool"
+        "Performs extended code analysis using the builtin metrics engine or rust-code-analysis-cli"
     }
 
     fn run(&self, config: &Self::Config) -> Result<()> {
@@ -353,13 +919,28 @@ impl RustCodeAnalysisRule {
                 println!("{output}");
             }
 
-            // Note severity doesn't fail CI - always return Ok
-            return Ok(());
+            // Metric notes don't fail CI; breached thresholds do
+            return match data.thresholds_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            };
         }
 
         match args.output {
             RustCodeAnalysisOutputFormat::Table => {
                 print_analysis_table(&data.analysis_results, &data.analysis_path)?;
+                print_rollup_table("Complexity by Module", &data.rollups.modules);
+                print_rollup_table("Complexity by Crate", &data.rollups.crates);
+                if data.rollups.components != data.rollups.crates {
+                    print_rollup_table("Complexity by Component", &data.rollups.components);
+                }
+                let violations = data.threshold_violations();
+                if !violations.is_empty() {
+                    println!("\nComplexity thresholds breached:");
+                    for violation in &violations {
+                        println!("  - {}", violation.message);
+                    }
+                }
             }
             RustCodeAnalysisOutputFormat::Json => {
                 let json_output = serde_json::to_string_pretty(&data.analysis_results)?;
@@ -370,10 +951,7 @@ impl RustCodeAnalysisRule {
                 println!("{yaml_output}");
             }
             RustCodeAnalysisOutputFormat::Html => {
-                let body = self.render_rust_code_analysis_html_body(
-                    &data.analysis_results,
-                    &data.analysis_path,
-                )?;
+                let body = self.render_rust_code_analysis_html_body(&data)?;
                 let title = format!(
                     "Rust Code Analysis Report: {}",
                     data.analysis_path.display()
@@ -383,10 +961,13 @@ impl RustCodeAnalysisRule {
             }
         }
 
-        Ok(())
+        match data.thresholds_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
-    #[instrument(skip(self, args))]
+    #[instrument(skip(self, args), fields(backend = ?args.backend))]
     fn analyze_impl(&self, args: &RustCodeAnalysisArgs) -> Result<RustCodeAnalysisData> {
         let analysis_path = PathBuf::from(&args.path);
 
@@ -396,106 +977,40 @@ impl RustCodeAnalysisRule {
             args.language
         );
 
-        let file_path_args = discover_and_filter_files(&analysis_path, &args.language)?;
-
-        if file_path_args.is_empty() {
-            println!(
-                "No files matching language '{:?}' found in path '{:?}'.",
-                args.language, args.path
-            );
-            return Ok(RustCodeAnalysisData {
-                analysis_results: vec![],
-                analysis_path,
-            });
-        }
-
-        tracing::debug!("Discovered files for CLI: {:?}", file_path_args);
-
-        let mut cmd_args = Vec::new();
-        cmd_args.extend(file_path_args);
-
-        cmd_args.push("-l".to_string());
-        cmd_args.push(args.language.clone());
-
-        if args.metrics {
-            cmd_args.push("-m".to_string());
-        }
-
-        cmd_args.push("-O".to_string());
-        cmd_args.push("json".to_string());
-
-        cmd_args.push("-j".to_string());
-        cmd_args.push(args.jobs.to_string());
-
-        cmd_args.extend(args.extra_flags.clone());
-
-        tracing::info!(
-            "Assembled arguments for rust-code-analysis-cli: {:?}",
-            cmd_args
-        );
-
-        let mut command = std::process::Command::new("rust-code-analysis-cli");
-        command.args(&cmd_args);
-
-        tracing::info!("Executing command: {:?}", command);
-
-        let output = command.output().map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                RaffError::analysis_error(
-                    "rust_code_analysis",
-                    "rust-code-analysis-cli not found. Please ensure it is installed and in your PATH."
-                )
-            } else {
-                RaffError::analysis_error(
-                    "rust_code_analysis",
-                    format!("Failed to execute rust-code-analysis-cli: {}", e)
-                )
-            }
-        })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            tracing::error!("rust-code-analysis-cli failed. Stderr:\n{}", stderr);
-            return Err(RaffError::analysis_error(
-                "rust_code_analysis",
-                format!(
-                    "rust-code-analysis-cli exited with error code {}:\n{}",
-                    output.status, stderr
-                ),
-            ));
-        }
-
-        let stdout_str = String::from_utf8_lossy(&output.stdout);
-        tracing::debug!("rust-code-analysis-cli stdout length: {}", stdout_str.len());
-
-        if stdout_str.trim().is_empty() {
-            tracing::info!("rust-code-analysis-cli produced no output.");
-            return Ok(RustCodeAnalysisData {
-                analysis_results: vec![],
-                analysis_path,
-            });
-        }
-
-        let mut analysis_results: Vec<AnalysisUnit> = Vec::new();
-        for line in stdout_str.lines() {
-            if line.trim().is_empty() {
-                continue;
+        let analysis_results = match args.backend {
+            RustCodeAnalysisBackend::Builtin => {
+                let mut cache =
+                    ResultCache::for_args(&analysis_path, args, &builtin_tool_version());
+                analyze_builtin(&analysis_path, args, &mut cache)?
             }
-            match serde_json::from_str::<AnalysisUnit>(line) {
-                Ok(unit) => analysis_results.push(unit),
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to parse a line of JSON output: {}. Line: '{}'",
-                        e,
-                        line
-                    );
-                }
+            RustCodeAnalysisBackend::External => {
+                let mut cache = match external_tool_version() {
+                    Some(version) => ResultCache::for_args(&analysis_path, args, &version),
+                    None => ResultCache::disabled(),
+                };
+                analyze_external(&analysis_path, args, &mut cache)?
             }
-        }
+        };
+        let rollups = rollup_complexity(
+            &analysis_path,
+            &analysis_results,
+            args.components,
+            &args.component_definitions,
+        )?;
 
         Ok(RustCodeAnalysisData {
             analysis_results,
             analysis_path,
+            thresholds: ComplexityThresholds {
+                max_file_cognitive: args.max_file_cognitive,
+                min_file_mi: args.min_file_mi,
+                max_file_abc: args.max_file_abc,
+                max_function_cyclomatic: args.max_function_cyclomatic,
+                max_function_cognitive: args.max_function_cognitive,
+                max_function_args: args.max_function_args,
+                max_function_length: args.max_function_length,
+            },
+            rollups,
         })
     }
 
@@ -511,9 +1026,10 @@ impl RustCodeAnalysisRule {
 
     pub fn render_rust_code_analysis_html_body(
         &self,
-        analysis_results: &[AnalysisUnit],
-        project_root: &Path,
+        data: &RustCodeAnalysisData,
     ) -> Result<maud::Markup> {
+        let analysis_results = &data.analysis_results;
+        let project_root = data.analysis_path.as_path();
         let _title = format!("Rust Code Analysis Report: {}", project_root.display());
 
         let explanations_data = [
@@ -567,6 +1083,30 @@ impl RustCodeAnalysisRule {
                 "H Bugs",
                 "Halstead Bugs. Higher indicates more potential bugs (worse).",
             ),
+            (
+                "Cog Sum",
+                "Cognitive Complexity Sum. Weights control flow by nesting depth; higher is harder to understand (worse).",
+            ),
+            (
+                "Cog Max",
+                "Highest Cognitive Complexity of a single function (worse when higher).",
+            ),
+            (
+                "MI Avg",
+                "Mean Maintainability Index of the functions (0-100). Higher is more maintainable (better).",
+            ),
+            (
+                "MI Min",
+                "Lowest Maintainability Index of a single function (better when higher).",
+            ),
+            (
+                "ABC",
+                "ABC magnitude: size from assignments, branches (calls) and conditions (worse when higher).",
+            ),
+            (
+                "Mean / P90 / Max",
+                "In the module, crate and component roll-ups: mean, 90th percentile and highest value over the functions.",
+            ),
         ];
         let explanations_markup = html_utils::render_metric_explanation_list(&explanations_data);
 
@@ -577,8 +1117,7 @@ impl RustCodeAnalysisRule {
                 .strip_prefix(project_root)
                 .map_or_else(|_| full_path.clone(), |p| p.to_path_buf());
             let relative_path_str = path_for_display.display().to_string();
-            let mut aggregated_metrics = FileAggregatedMetrics::default();
-            aggregate_metrics_recursive(&unit.spaces, &mut aggregated_metrics);
+            let aggregated_metrics = aggregate_file_metrics(unit);
             aggregated_metrics_list.push((relative_path_str, aggregated_metrics));
         }
 
@@ -656,6 +1195,41 @@ impl RustCodeAnalysisRule {
                 .collect::<Vec<f64>>(),
             false,
         );
+        let cog_sum_ranges = html_utils::MetricRanges::from_values(
+            &aggregated_metrics_list
+                .iter()
+                .map(|(_, m)| m.cognitive_sum)
+                .collect::<Vec<f64>>(),
+            false,
+        );
+        let cog_max_ranges = html_utils::MetricRanges::from_values(
+            &aggregated_metrics_list
+                .iter()
+                .map(|(_, m)| m.cognitive_max)
+                .collect::<Vec<f64>>(),
+            false,
+        );
+        let mi_avg_ranges = html_utils::MetricRanges::from_values(
+            &aggregated_metrics_list
+                .iter()
+                .filter_map(|(_, m)| m.mi_average())
+                .collect::<Vec<f64>>(),
+            true,
+        );
+        let mi_min_ranges = html_utils::MetricRanges::from_values(
+            &aggregated_metrics_list
+                .iter()
+                .filter_map(|(_, m)| m.mi_min)
+                .collect::<Vec<f64>>(),
+            true,
+        );
+        let abc_ranges = html_utils::MetricRanges::from_values(
+            &aggregated_metrics_list
+                .iter()
+                .map(|(_, m)| m.abc_magnitude())
+                .collect::<Vec<f64>>(),
+            false,
+        );
         let h_bugs_ranges = html_utils::MetricRanges::from_values(
             &aggregated_metrics_list
                 .iter()
@@ -683,6 +1257,11 @@ impl RustCodeAnalysisRule {
                         th class="sortable-header" data-column-index="11" data-sort-type="number" { "H Effort" }
                         th class="sortable-header" data-column-index="12" data-sort-type="number" { "H Time" }
                         th class="sortable-header" data-column-index="13" data-sort-type="number" { "H Bugs" }
+                        th class="sortable-header" data-column-index="14" data-sort-type="number" { "Cog Sum" }
+                        th class="sortable-header" data-column-index="15" data-sort-type="number" { "Cog Max" }
+                        th class="sortable-header" data-column-index="16" data-sort-type="number" { "MI Avg" }
+                        th class="sortable-header" data-column-index="17" data-sort-type="number" { "MI Min" }
+                        th class="sortable-header" data-column-index="18" data-sort-type="number" { "ABC" }
                     }
                 }
                 tbody {
@@ -703,15 +1282,36 @@ impl RustCodeAnalysisRule {
                             td style=({h_effort_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.halstead_effort, r))}) { (format!("{:.0}", metrics.halstead_effort)) }
                             td style=({h_time_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.halstead_time, r))}) { (format!("{:.1}", metrics.halstead_time)) }
                             td style=({h_bugs_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.halstead_bugs, r))}) { (format!("{:.2}", metrics.halstead_bugs)) }
+                            td style=({cog_sum_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.cognitive_sum, r))}) { (format!("{:.0}", metrics.cognitive_sum)) }
+                            td style=({cog_max_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.cognitive_max, r))}) { (format!("{:.0}", metrics.cognitive_max)) }
+                            td style=({metrics.mi_average().zip(mi_avg_ranges.as_ref()).map_or_else(String::new, |(mi, r)| html_utils::get_metric_cell_style(mi, r))}) { (format_optional(metrics.mi_average())) }
+                            td style=({metrics.mi_min.zip(mi_min_ranges.as_ref()).map_or_else(String::new, |(mi, r)| html_utils::get_metric_cell_style(mi, r))}) { (format_optional(metrics.mi_min)) }
+                            td style=({abc_ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(metrics.abc_magnitude(), r))}) { (format!("{:.1}", metrics.abc_magnitude())) }
                         }
                     }
                 }
             }
         };
 
+        let violations = data.threshold_violations();
         let body_content = html! {
             (explanations_markup)
             (table_markup)
+            (render_rollup_table("Complexity by Module", &data.rollups.modules))
+            (render_rollup_table("Complexity by Crate", &data.rollups.crates))
+            @if data.rollups.components != data.rollups.crates {
+                (render_rollup_table("Complexity by Component", &data.rollups.components))
+            }
+            @if !violations.is_empty() {
+                p style="color: red;" {
+                    b { "Complexity thresholds breached:" }
+                }
+                ul {
+                    @for violation in &violations {
+                        li { (violation.message) }
+                    }
+                }
+            }
         };
 
         Ok(body_content)
@@ -758,6 +1358,16 @@ fn print_analysis_table(analysis_results: &[AnalysisUnit], project_root: &Path)
     );
     println!("H Time     : Halstead Time (sec) - Estimated time to develop/understand (summed).");
     println!("H Bugs     : Halstead Bugs - Estimated number of delivered bugs (summed).");
+    println!("Cog Sum    : Cognitive Complexity Sum - Control flow weighted by nesting depth.");
+    println!("Cog Max    : Cognitive Complexity Max - Highest complexity of a single function.");
+    println!(
+        "MI Avg     : Maintainability Index Average - Mean over functions, 0-100 (higher is better)."
+    );
+    println!("MI Min     : Maintainability Index Min - Lowest of a single function.");
+    println!(
+        "ABC        : ABC Magnitude - Size from assignments, branches (calls) and conditions."
+    );
+    println!("Mean/P90/Max: In the module, crate and component roll-ups, taken over functions.");
     println!("\n"); // Add a newline before the table
 
     let mut table = Table::new();
@@ -777,6 +1387,11 @@ fn print_analysis_table(analysis_results: &[AnalysisUnit], project_root: &Path)
         Cell::new("H Effort").with_style(Attr::Bold),
         Cell::new("H Time").with_style(Attr::Bold),
         Cell::new("H Bugs").with_style(Attr::Bold),
+        Cell::new("Cog Sum").with_style(Attr::Bold),
+        Cell::new("Cog Max").with_style(Attr::Bold),
+        Cell::new("MI Avg").with_style(Attr::Bold),
+        Cell::new("MI Min").with_style(Attr::Bold),
+        Cell::new("ABC").with_style(Attr::Bold),
     ]));
 
     for unit in analysis_results {
@@ -787,8 +1402,7 @@ fn print_analysis_table(analysis_results: &[AnalysisUnit], project_root: &Path)
             .unwrap_or_else(|_err| full_path.clone());
         let relative_path_str = path_for_display.display().to_string();
 
-        let mut aggregated_metrics = FileAggregatedMetrics::default();
-        aggregate_metrics_recursive(&unit.spaces, &mut aggregated_metrics);
+        let aggregated_metrics = aggregate_file_metrics(unit);
 
         let cyclomatic_avg = if aggregated_metrics.items_with_metrics > 0 {
             aggregated_metrics.cyclomatic_sum / aggregated_metrics.items_with_metrics as f64
@@ -811,6 +1425,11 @@ fn print_analysis_table(analysis_results: &[AnalysisUnit], project_root: &Path)
             Cell::new(&format!("{:.0}", aggregated_metrics.halstead_effort)),
             Cell::new(&format!("{:.1}", aggregated_metrics.halstead_time)),
             Cell::new(&format!("{:.2}", aggregated_metrics.halstead_bugs)),
+            Cell::new(&format!("{:.0}", aggregated_metrics.cognitive_sum)),
+            Cell::new(&format!("{:.0}", aggregated_metrics.cognitive_max)),
+            Cell::new(&format_optional(aggregated_metrics.mi_average())),
+            Cell::new(&format_optional(aggregated_metrics.mi_min)),
+            Cell::new(&format!("{:.1}", aggregated_metrics.abc_magnitude())),
         ]));
     }
 
@@ -818,64 +1437,579 @@ fn print_analysis_table(analysis_results: &[AnalysisUnit], project_root: &Path)
     Ok(())
 }
 
-fn get_extension_for_language(language: &str) -> Option<&str> {
-    match language {
-        "rust" => Some(".rs"),
-        // Future extensions for other languages can be added here.
-        _ => None,
-    }
+const ROLLUP_HEADERS: [&str; 15] = [
+    "Name", "Files", "Fns", "SLOC", "LLOC", "Cyc Sum", "Cyc Mean", "Cyc P90", "Cyc Max", "Cog Sum",
+    "Cog Mean", "Cog P90", "Cog Max", "H Effort", "MI Mean",
+];
+
+/// Formats a roll-up as table cells, in [`ROLLUP_HEADERS`] order.
+fn rollup_cells(rollup: &ComplexityRollup) -> [String; 15] {
+    [
+        rollup.name.clone(),
+        rollup.files.to_string(),
+        rollup.functions.to_string(),
+        format!("{:.0}", rollup.sloc),
+        format!("{:.0}", rollup.lloc),
+        format!("{:.0}", rollup.cyclomatic.sum),
+        format!("{:.1}", rollup.cyclomatic.mean),
+        format!("{:.0}", rollup.cyclomatic.p90),
+        format!("{:.0}", rollup.cyclomatic.max),
+        format!("{:.0}", rollup.cognitive.sum),
+        format!("{:.1}", rollup.cognitive.mean),
+        format!("{:.0}", rollup.cognitive.p90),
+        format!("{:.0}", rollup.cognitive.max),
+        format!("{:.0}", rollup.halstead_effort),
+        format_optional(rollup.mi_mean),
+    ]
 }
 
-#[instrument]
-fn discover_and_filter_files(root_dir: &PathBuf, language: &str) -> Result<Vec<String>> {
-    if !root_dir.exists() {
-        return Err(RaffError::invalid_input(format!(
-            "Root path not found: {}",
-            root_dir.display()
-        )));
+fn print_rollup_table(title: &str, rollups: &[ComplexityRollup]) {
+    if rollups.is_empty() {
+        return;
     }
-
-    // If a specific file extension is known for the language, walk the directory and find all matching files.
-    if let Some(extension) = get_extension_for_language(language) {
-        let mut discovered_files = Vec::new();
-        let walker = walkdir::WalkDir::new(root_dir).into_iter();
-
-        for entry_result in walker.filter_entry(|e| {
-            if e.file_type().is_dir() {
-                let file_name = e.file_name().to_string_lossy();
-                if file_name == "target"
-                    || file_name == "frontend"
-                    || file_name == "node_modules"
-                    || file_name == ".git"
-                {
-                    return false; // Skip these directories
+    println!("\n{title}:");
+    let mut table = Table::new();
+    table.set_format(*pt_format::consts::FORMAT_BOX_CHARS);
+    table.add_row(Row::new(
+        ROLLUP_HEADERS
+            .iter()
+            .map(|header| Cell::new(header).with_style(Attr::Bold))
+            .collect(),
+    ));
+    for rollup in rollups {
+        table.add_row(Row::new(
+            rollup_cells(rollup)
+                .iter()
+                .map(|cell| Cell::new(cell))
+                .collect(),
+        ));
+    }
+    table.printstd();
+}
+
+fn render_rollup_table(caption: &str, rollups: &[ComplexityRollup]) -> maud::Markup {
+    html! {
+        table class="sortable-table" {
+            caption { (caption) }
+            thead {
+                tr {
+                    @for (index, header) in ROLLUP_HEADERS.iter().enumerate() {
+                        th class="sortable-header" data-column-index=(index) data-sort-type=(if index == 0 { "string" } else { "number" }) { (header) }
+                    }
                 }
             }
-            true
-        }) {
-            match entry_result {
-                Ok(entry) => {
-                    if entry.file_type().is_file()
-                        && let Some(path_str) = entry.path().to_str()
-                        && path_str.ends_with(extension)
-                    {
-                        discovered_files.push(path_str.to_string());
+            tbody {
+                @for rollup in rollups {
+                    tr {
+                        @for cell in rollup_cells(rollup) {
+                            td { (cell) }
+                        }
                     }
                 }
+            }
+        }
+    }
+}
+
+/// Cache version for stored analysis results.
+/// Increment this when [`AnalysisUnit`] or the builtin engine's output
+/// changes, to invalidate old results.
+const RESULT_CACHE_VERSION: &str = "1";
+
+/// Rewrite an unchanged result store once it is this old, so that the
+/// cache's expiry does not discard results that are still in use.
+const RESULT_CACHE_REFRESH_AFTER_SECONDS: u64 = 24 * 60 * 60;
+
+/// Analysis results of earlier runs, keyed by the SHA-256 hash of each
+/// file's content.
+///
+/// One store is kept per analysis path, backend, tool version and set of
+/// flags that affect the metrics, so changing any of them starts afresh.
+/// Saving keeps only the files seen in the current run.
+struct ResultCache {
+    cache: Option<(CacheManager, CacheKey)>,
+    stored: HashMap<String, AnalysisUnit>,
+    current: HashMap<String, AnalysisUnit>,
+    misses: usize,
+    stored_age_seconds: u64,
+}
+
+impl ResultCache {
+    /// A cache that never hits and never saves.
+    fn disabled() -> Self {
+        Self {
+            cache: None,
+            stored: HashMap::new(),
+            current: HashMap::new(),
+            misses: 0,
+            stored_age_seconds: 0,
+        }
+    }
+
+    /// Opens the store for `args` in `cache_manager`. A missing, expired or
+    /// unreadable store starts out empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache entry cannot be read.
+    fn open(
+        cache_manager: CacheManager,
+        analysis_path: &Path,
+        args: &RustCodeAnalysisArgs,
+        tool_version: &str,
+    ) -> Result<Self> {
+        let cache_key = CacheKey::new(
+            format!(
+                "rust_code_analysis:{}",
+                analysis_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| analysis_path.to_path_buf())
+                    .display()
+            ),
+            None,
+            vec![
+                (
+                    "cache_version".to_string(),
+                    RESULT_CACHE_VERSION.to_string(),
+                ),
+                ("backend".to_string(), format!("{:?}", args.backend)),
+                ("tool_version".to_string(), tool_version.to_string()),
+                ("language".to_string(), args.language.clone()),
+                ("metrics".to_string(), args.metrics.to_string()),
+                ("extra_flags".to_string(), args.extra_flags.join("\u{1f}")),
+            ],
+        );
+        let (stored, stored_age_seconds) = match cache_manager.get(&cache_key)? {
+            Some(entry) => match serde_json::from_slice(&entry.data) {
+                Ok(stored) => (stored, entry.age_seconds()),
                 Err(e) => {
-                    tracing::warn!(
-                        "Error accessing entry in {}: {}. Skipping.",
-                        root_dir.display(),
-                        e
-                    );
+                    tracing::debug!(error = %e, "Discarding unreadable analysis result store");
+                    (HashMap::new(), 0)
+                }
+            },
+            None => (HashMap::new(), 0),
+        };
+        tracing::debug!(files = stored.len(), "Loaded analysis result store");
+        Ok(Self {
+            cache: Some((cache_manager, cache_key)),
+            stored,
+            current: HashMap::new(),
+            misses: 0,
+            stored_age_seconds,
+        })
+    }
+
+    /// Opens the store in the default cache directory, or a disabled cache
+    /// with `--no-cache`. Cache failures only cost speed, so they are logged
+    /// rather than returned.
+    fn for_args(analysis_path: &Path, args: &RustCodeAnalysisArgs, tool_version: &str) -> Self {
+        if args.no_cache {
+            return Self::disabled();
+        }
+        match CacheManager::new()
+            .and_then(|manager| Self::open(manager, analysis_path, args, tool_version))
+        {
+            Ok(cache) => cache,
+            Err(e) => {
+                tracing::warn!("Analysis result cache unavailable: {}", e);
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Returns the stored result for a file with content hash `hash`,
+    /// renamed to `path`, since identical files share an entry.
+    fn get(&mut self, hash: &str, path: &Path) -> Option<AnalysisUnit> {
+        let mut unit = self.stored.get(hash)?.clone();
+        unit.name = path.display().to_string();
+        self.current.insert(hash.to_string(), unit.clone());
+        Some(unit)
+    }
+
+    /// Records the fresh result for a file with content hash `hash`.
+    fn insert(&mut self, hash: String, unit: &AnalysisUnit) {
+        self.misses += 1;
+        self.current.insert(hash, unit.clone());
+    }
+
+    /// Writes the results of this run back, if they changed or the store is
+    /// close to expiring.
+    fn save(&self) {
+        let Some((cache_manager, cache_key)) = &self.cache else {
+            return;
+        };
+        let unchanged = self.misses == 0 && self.current.len() == self.stored.len();
+        if unchanged && self.stored_age_seconds < RESULT_CACHE_REFRESH_AFTER_SECONDS {
+            return;
+        }
+        let saved = serde_json::to_vec(&self.current)
+            .map_err(RaffError::from)
+            .and_then(|data| cache_manager.put(cache_key, CacheEntry::new(data)));
+        match saved {
+            Ok(()) => tracing::debug!(
+                files = self.current.len(),
+                reanalyzed = self.misses,
+                "Saved analysis result store"
+            ),
+            Err(e) => tracing::warn!("Failed to save analysis result cache: {}", e),
+        }
+    }
+}
+
+/// Identifies the builtin engine in cache keys.
+fn builtin_tool_version() -> String {
+    format!("raff {}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Returns the output of `rust-code-analysis-cli --version`, or `None` if the
+/// tool cannot be run.
+fn external_tool_version() -> Option<String> {
+    let output = std::process::Command::new("rust-code-analysis-cli")
+        .arg("--version")
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Analyzes files with the built-in [`crate::complexity`] engine, spreading
+/// them over `args.jobs` threads. Files whose content is in `cache` are not
+/// re-analyzed.
+fn analyze_builtin(
+    analysis_path: &Path,
+    args: &RustCodeAnalysisArgs,
+    cache: &mut ResultCache,
+) -> Result<Vec<AnalysisUnit>> {
+    if args.language != "rust" {
+        return Err(RaffError::invalid_input_with_arg(
+            format!(
+                "The builtin backend only supports Rust, not '{}'. Use --backend external for other languages.",
+                args.language
+            ),
+            "backend",
+        ));
+    }
+    if !args.extra_flags.is_empty() {
+        tracing::warn!(
+            "Ignoring extra flags {:?}: they only apply to the external backend",
+            args.extra_flags
+        );
+    }
+
+    let files = discover_source_files(analysis_path, ".rs")?;
+    if files.is_empty() {
+        println!(
+            "No files matching language '{:?}' found in path '{:?}'.",
+            args.language, args.path
+        );
+        return Ok(vec![]);
+    }
+
+    let mut results: Vec<Option<AnalysisUnit>> = vec![None; files.len()];
+    let mut pending = Vec::new();
+    for (index, path) in files.iter().enumerate() {
+        let content = match std::fs::read(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let hash = hash_bytes(&content);
+        match cache.get(&hash, path) {
+            Some(unit) => results[index] = Some(unit),
+            None => pending.push((index, hash, content)),
+        }
+    }
+    tracing::debug!(
+        cached = files.len() - pending.len(),
+        pending = pending.len(),
+        "Analyzing files with the builtin engine"
+    );
+
+    let analyze = |(index, hash, content): &(usize, String, Vec<u8>)| {
+        let path = &files[*index];
+        let analyzed = String::from_utf8(content.clone())
+            .map_err(|e| RaffError::parse_error_with_file(path.clone(), e.to_string()))
+            .and_then(|source| {
+                crate::complexity::analyze_source(&path.display().to_string(), &source)
+            });
+        match analyzed {
+            Ok(unit) => Some((*index, hash.clone(), unit)),
+            Err(e) => {
+                tracing::warn!("Skipping {}: {}", path.display(), e);
+                None
+            }
+        }
+    };
+    if !pending.is_empty() {
+        let jobs = args.jobs.clamp(1, pending.len());
+        let chunk_size = pending.len().div_ceil(jobs);
+        let analyzed = std::thread::scope(|scope| {
+            let workers: Vec<_> = pending
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let analyze = &analyze;
+                    scope.spawn(move || chunk.iter().filter_map(analyze).collect::<Vec<_>>())
+                })
+                .collect();
+            let mut analyzed = Vec::with_capacity(pending.len());
+            for worker in workers {
+                analyzed.extend(worker.join().map_err(|_| {
+                    RaffError::analysis_error(
+                        "rust_code_analysis",
+                        "Analysis worker thread panicked",
+                    )
+                })?);
+            }
+            Ok::<_, RaffError>(analyzed)
+        })?;
+        for (index, hash, unit) in analyzed {
+            cache.insert(hash, &unit);
+            results[index] = Some(unit);
+        }
+    }
+    cache.save();
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Analyzes files by running `rust-code-analysis-cli` and parsing its JSON
+/// output. For languages with a known extension, files whose content is in
+/// `cache` are not passed to the tool again.
+fn analyze_external(
+    analysis_path: &Path,
+    args: &RustCodeAnalysisArgs,
+    cache: &mut ResultCache,
+) -> Result<Vec<AnalysisUnit>> {
+    let Some(extension) = get_extension_for_language(&args.language) else {
+        // The tool discovers the files itself, so there is nothing to cache
+        let file_path_args =
+            discover_and_filter_files(&analysis_path.to_path_buf(), &args.language)?;
+        return run_external_tool(file_path_args, args);
+    };
+
+    let files = discover_source_files(analysis_path, extension)?;
+    if files.is_empty() {
+        println!(
+            "No files matching language '{:?}' found in path '{:?}'.",
+            args.language, args.path
+        );
+        return Ok(vec![]);
+    }
+
+    let mut analysis_results = Vec::new();
+    let mut pending: HashMap<String, String> = HashMap::new();
+    for path in &files {
+        let hash = hash_file(path)?;
+        match cache.get(&hash, path) {
+            Some(unit) => analysis_results.push(unit),
+            None => {
+                pending.insert(path.display().to_string(), hash);
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        let mut file_path_args = Vec::with_capacity(pending.len() * 2);
+        for path in &files {
+            let path = path.display().to_string();
+            if pending.contains_key(&path) {
+                file_path_args.push("-p".to_string());
+                file_path_args.push(path);
+            }
+        }
+        for unit in run_external_tool(file_path_args, args)? {
+            if let Some(hash) = pending.remove(&unit.name) {
+                cache.insert(hash, &unit);
+            }
+            analysis_results.push(unit);
+        }
+    }
+    cache.save();
+
+    analysis_results.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(analysis_results)
+}
+
+/// Runs `rust-code-analysis-cli` on `file_path_args` (`-p <path>` pairs) and
+/// parses its JSON output.
+fn run_external_tool(
+    file_path_args: Vec<String>,
+    args: &RustCodeAnalysisArgs,
+) -> Result<Vec<AnalysisUnit>> {
+    if file_path_args.is_empty() {
+        println!(
+            "No files matching language '{:?}' found in path '{:?}'.",
+            args.language, args.path
+        );
+        return Ok(vec![]);
+    }
+
+    tracing::debug!("Discovered files for CLI: {:?}", file_path_args);
+
+    let mut cmd_args = Vec::new();
+    cmd_args.extend(file_path_args);
+
+    cmd_args.push("-l".to_string());
+    cmd_args.push(args.language.clone());
+
+    if args.metrics {
+        cmd_args.push("-m".to_string());
+    }
+
+    cmd_args.push("-O".to_string());
+    cmd_args.push("json".to_string());
+
+    cmd_args.push("-j".to_string());
+    cmd_args.push(args.jobs.to_string());
+
+    cmd_args.extend(args.extra_flags.clone());
+
+    tracing::info!(
+        "Assembled arguments for rust-code-analysis-cli: {:?}",
+        cmd_args
+    );
+
+    let mut command = std::process::Command::new("rust-code-analysis-cli");
+    command.args(&cmd_args);
+
+    tracing::info!("Executing command: {:?}", command);
+
+    let output = command.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            RaffError::analysis_error(
+                "rust_code_analysis",
+                "rust-code-analysis-cli not found. Please ensure it is installed and in your PATH.",
+            )
+        } else {
+            RaffError::analysis_error(
+                "rust_code_analysis",
+                format!("Failed to execute rust-code-analysis-cli: {}", e),
+            )
+        }
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        tracing::error!("rust-code-analysis-cli failed. Stderr:\n{}", stderr);
+        return Err(RaffError::analysis_error(
+            "rust_code_analysis",
+            format!(
+                "rust-code-analysis-cli exited with error code {}:\n{}",
+                output.status, stderr
+            ),
+        ));
+    }
+
+    let stdout_str = String::from_utf8_lossy(&output.stdout);
+    tracing::debug!("rust-code-analysis-cli stdout length: {}", stdout_str.len());
+
+    if stdout_str.trim().is_empty() {
+        tracing::info!("rust-code-analysis-cli produced no output.");
+        return Ok(vec![]);
+    }
+
+    let mut analysis_results: Vec<AnalysisUnit> = Vec::new();
+    for line in stdout_str.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AnalysisUnit>(line) {
+            Ok(unit) => analysis_results.push(unit),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to parse a line of JSON output: {}. Line: '{}'",
+                    e,
+                    line
+                );
+            }
+        }
+    }
+
+    Ok(analysis_results)
+}
+
+/// Formats an optional metric to one decimal place, or `-` when absent.
+fn format_optional(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{value:.1}"))
+}
+
+fn get_extension_for_language(language: &str) -> Option<&str> {
+    match language {
+        "rust" => Some(".rs"),
+        // Future extensions for other languages can be added here.
+        _ => None,
+    }
+}
+
+/// Walks `root_dir` for files ending in `extension`, skipping build output,
+/// frontend and VCS directories. The result is sorted.
+fn discover_source_files(root_dir: &Path, extension: &str) -> Result<Vec<PathBuf>> {
+    if !root_dir.exists() {
+        return Err(RaffError::invalid_input(format!(
+            "Root path not found: {}",
+            root_dir.display()
+        )));
+    }
+
+    let mut discovered_files = Vec::new();
+    let walker = walkdir::WalkDir::new(root_dir).into_iter();
+
+    for entry_result in walker.filter_entry(|e| {
+        if e.file_type().is_dir() {
+            let file_name = e.file_name().to_string_lossy();
+            if file_name == "target"
+                || file_name == "frontend"
+                || file_name == "node_modules"
+                || file_name == ".git"
+            {
+                return false; // Skip these directories
+            }
+        }
+        true
+    }) {
+        match entry_result {
+            Ok(entry) => {
+                if entry.file_type().is_file()
+                    && let Some(path_str) = entry.path().to_str()
+                    && path_str.ends_with(extension)
+                {
+                    discovered_files.push(entry.into_path());
                 }
             }
+            Err(e) => {
+                tracing::warn!(
+                    "Error accessing entry in {}: {}. Skipping.",
+                    root_dir.display(),
+                    e
+                );
+            }
         }
+    }
+    discovered_files.sort();
+    Ok(discovered_files)
+}
+
+#[instrument]
+fn discover_and_filter_files(root_dir: &PathBuf, language: &str) -> Result<Vec<String>> {
+    if !root_dir.exists() {
+        return Err(RaffError::invalid_input(format!(
+            "Root path not found: {}",
+            root_dir.display()
+        )));
+    }
+
+    // If a specific file extension is known for the language, walk the directory and find all matching files.
+    if let Some(extension) = get_extension_for_language(language) {
+        let discovered_files = discover_source_files(root_dir, extension)?;
 
         let mut file_path_args = Vec::new();
         for file in discovered_files {
             file_path_args.push("-p".to_string());
-            file_path_args.push(file);
+            file_path_args.push(file.display().to_string());
         }
         Ok(file_path_args)
     } else {
@@ -1026,33 +2160,113 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: temp_dir.path().to_path_buf(),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: true,
         };
 
-        // Note: This test requires rust-code-analysis-cli to be installed
-        // If it's not available, the analyze will fail, which is expected behavior
-        let result = <RustCodeAnalysisRule as Rule>::analyze(&rule, &args);
+        // The builtin backend needs no external tool, so analysis succeeds
+        let data = <RustCodeAnalysisRule as Rule>::analyze(&rule, &args)
+            .expect("builtin analysis should succeed");
+        assert_eq!(
+            data.analysis_path,
+            temp_dir.path(),
+            "Analyzed data should have the correct analysis path"
+        );
+        assert_eq!(data.analysis_results.len(), 1);
+        assert_eq!(data.analysis_results[0].spaces[0].name, "main");
+    }
 
-        // We just check that it returns the right type (Result<RustCodeAnalysisData>)
-        // If rust-code-analysis-cli is not installed, it will return an error
-        match result {
-            Ok(data) => {
-                assert_eq!(
-                    data.analysis_path,
-                    temp_dir.path(),
-                    "Analyzed data should have the correct analysis path"
-                );
-            }
-            Err(_) => {
-                // This is expected if rust-code-analysis-cli is not installed
-                // We just want to verify the method signature is correct
-            }
+    #[test]
+    fn test_builtin_backend_analyzes_files_across_jobs() {
+        let temp_dir = tempdir().expect("Failed to create temp directory");
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(
+                src_dir.join(format!("{name}.rs")),
+                format!("fn {name}(x: u8) -> u8 {{\n    if x > 1 {{ x }} else {{ 0 }}\n}}\n"),
+            )
+            .unwrap();
         }
+        fs::write(src_dir.join("broken.rs"), "fn (").unwrap();
+
+        let args = RustCodeAnalysisArgs {
+            path: temp_dir.path().to_path_buf(),
+            language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
+            metrics: true,
+            jobs: 2,
+            extra_flags: vec![],
+            output: RustCodeAnalysisOutputFormat::Json,
+            ci_output: None,
+            output_file: None,
+            no_cache: true,
+        };
+        let data = RustCodeAnalysisRule::new().analyze(&args).unwrap();
+
+        // Files that fail to parse are skipped; the rest keep path order
+        let names: Vec<_> = data
+            .analysis_results
+            .iter()
+            .map(|unit| unit.spaces[0].name.as_str())
+            .collect();
+        assert_eq!(names, ["a", "b", "c"]);
+        let metrics = &data.analysis_results[0].spaces[0].metrics;
+        assert_eq!(metrics.cyclomatic.as_ref().unwrap().sum, 2.0);
+        assert_eq!(metrics.cognitive.as_ref().unwrap().sum, 2.0);
+        assert_eq!(data.to_findings().len(), 3);
+    }
+
+    #[test]
+    fn test_builtin_backend_rejects_other_languages() {
+        let temp_dir = tempdir().unwrap();
+        let args = RustCodeAnalysisArgs {
+            path: temp_dir.path().to_path_buf(),
+            language: "python".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
+            metrics: true,
+            jobs: 1,
+            extra_flags: vec![],
+            output: RustCodeAnalysisOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+            no_cache: true,
+        };
+        let err = RustCodeAnalysisRule::new().analyze(&args).unwrap_err();
+        assert!(err.to_string().contains("--backend external"));
     }
 
     #[test]
@@ -1062,12 +2276,23 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: fake_path,
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: true,
         };
 
         // Call the Rule trait's analyze method
@@ -1089,12 +2314,23 @@ fn main() {
         let config = RustCodeAnalysisArgs {
             path: PathBuf::from("."),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: None,
             output_file: None,
+            no_cache: true,
         };
 
         // Verify Data type is RustCodeAnalysisData
@@ -1111,6 +2347,8 @@ fn main() {
         let data = RustCodeAnalysisData {
             analysis_results: vec![],
             analysis_path: PathBuf::from("/test"),
+            thresholds: ComplexityThresholds::default(),
+            rollups: ComplexityRollups::default(),
         };
 
         let findings = data.to_findings();
@@ -1155,6 +2393,7 @@ fn main() {
                         time: 125.0,
                         bugs: 0.05,
                     }),
+                    ..Default::default()
                 },
                 spaces: vec![],
             }],
@@ -1164,6 +2403,8 @@ fn main() {
         let data = RustCodeAnalysisData {
             analysis_results: vec![unit],
             analysis_path: PathBuf::from("/test/project"),
+            thresholds: ComplexityThresholds::default(),
+            rollups: ComplexityRollups::default(),
         };
 
         let findings = data.to_findings();
@@ -1220,6 +2461,7 @@ fn main() {
                         time: 20.0,
                         bugs: 0.01,
                     }),
+                    ..Default::default()
                 },
                 spaces: vec![],
             }],
@@ -1229,6 +2471,8 @@ fn main() {
         let data = RustCodeAnalysisData {
             analysis_results: vec![unit],
             analysis_path: PathBuf::from("/test"),
+            thresholds: ComplexityThresholds::default(),
+            rollups: ComplexityRollups::default(),
         };
 
         let findings = data.to_findings();
@@ -1274,6 +2518,7 @@ fn main() {
                         time: 61.33,
                         bugs: 0.02,
                     }),
+                    ..Default::default()
                 },
                 spaces: vec![],
             }],
@@ -1283,6 +2528,8 @@ fn main() {
         let data = RustCodeAnalysisData {
             analysis_results: vec![unit],
             analysis_path: PathBuf::from("/test/project"),
+            thresholds: ComplexityThresholds::default(),
+            rollups: ComplexityRollups::default(),
         };
 
         let findings = data.to_findings();
@@ -1312,12 +2559,23 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: temp_dir.path().to_path_buf(),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
+            no_cache: true,
         };
 
         // Note: This test requires rust-code-analysis-cli to be installed
@@ -1353,12 +2611,23 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: temp_dir.path().to_path_buf(),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: Some(CiOutputFormat::JUnit),
             output_file: None,
+            no_cache: true,
         };
 
         // Note: This test requires rust-code-analysis-cli to be installed
@@ -1391,12 +2660,23 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: temp_dir.path().to_path_buf(),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: None,
+            no_cache: true,
         };
 
         // Note severity should not cause CI failure
@@ -1431,12 +2711,23 @@ fn main() {
         let args = RustCodeAnalysisArgs {
             path: temp_dir.path().to_path_buf(),
             language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
             metrics: true,
             jobs: 1,
             extra_flags: vec![],
             output: RustCodeAnalysisOutputFormat::Table,
             ci_output: Some(CiOutputFormat::Sarif),
             output_file: Some(output_file.clone()),
+            no_cache: true,
         };
 
         let result = rule.run(&args);
@@ -1454,4 +2745,308 @@ fn main() {
             }
         }
     }
+
+    fn builtin_data(
+        sources: &[(&str, &str)],
+        thresholds: ComplexityThresholds,
+    ) -> RustCodeAnalysisData {
+        RustCodeAnalysisData {
+            analysis_results: sources
+                .iter()
+                .map(|(name, source)| {
+                    crate::complexity::analyze_source(&format!("/project/{name}"), source).unwrap()
+                })
+                .collect(),
+            analysis_path: PathBuf::from("/project"),
+            thresholds,
+            rollups: ComplexityRollups::default(),
+        }
+    }
+
+    const NESTED: &str = "
+impl Parser {
+    fn parse(&self, input: &str) -> usize {
+        let mut count = 0;
+        for c in input.chars() {
+            if c == 'a' {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    fn simple(&self) -> usize {
+        self.len()
+    }
+}
+";
+
+    #[test]
+    fn test_aggregate_file_metrics_counts_nested_spaces_once() {
+        let data = builtin_data(&[("lib.rs", NESTED)], ComplexityThresholds::default());
+        let aggregated = aggregate_file_metrics(&data.analysis_results[0]);
+
+        // for(1) + nested if(2); the impl space already includes both methods
+        assert_eq!(aggregated.cognitive_sum, 3.0);
+        assert_eq!(aggregated.cognitive_max, 3.0);
+        // let, +=; chars(), len(); ==
+        assert_eq!(
+            (
+                aggregated.abc_assignments,
+                aggregated.abc_branches,
+                aggregated.abc_conditions
+            ),
+            (2.0, 2.0, 1.0)
+        );
+        assert_eq!(aggregated.mi_count, 2);
+        let mi_min = aggregated.mi_min.unwrap();
+        assert!(mi_min <= aggregated.mi_average().unwrap());
+    }
+
+    #[test]
+    fn test_threshold_violations_and_findings() {
+        let thresholds = ComplexityThresholds {
+            max_file_cognitive: Some(2.0),
+            min_file_mi: Some(99.0),
+            max_file_abc: Some(100.0),
+            ..Default::default()
+        };
+        let data = builtin_data(&[("lib.rs", NESTED), ("empty.rs", "")], thresholds);
+
+        let violations = data.threshold_violations();
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert!(messages[0].starts_with("File 'lib.rs' has cognitive complexity 3"));
+        assert!(messages[1].contains("maintainability index"));
+        assert_eq!(
+            violations[0].fingerprint,
+            "rust-code-analysis:file-cognitive:lib.rs:2"
+        );
+        assert!(data.thresholds_error().is_some());
+
+        let errors: Vec<_> = data
+            .to_findings()
+            .into_iter()
+            .filter(|f| f.severity == Severity::Error)
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].location.as_ref().map(|l| l.uri.as_str()),
+            Some("lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_item_metrics_parses_extended_cli_metrics() {
+        let json = r#"{
+            "loc": {"sloc": 10.0, "ploc": 8.0, "lloc": 4.0, "cloc": 1.0, "blank": 1.0},
+            "cyclomatic": {"sum": 3.0, "average": 1.5, "min": 1.0, "max": 2.0},
+            "halstead": null,
+            "cognitive": {"sum": 4.0, "average": 2.0, "min": 0.0, "max": 4.0},
+            "mi": {"mi_original": 110.0, "mi_sei": 95.0, "mi_visual_studio": 64.3},
+            "abc": {"assignments": 3.0, "branches": 4.0, "conditions": 0.0, "magnitude": 5.0,
+                    "assignments_average": 1.5},
+            "nom": {"functions": 2.0, "closures": 0.0, "functions_average": 1.0, "total": 2.0, "average": 1.0}
+        }"#;
+        let metrics: ItemMetrics = serde_json::from_str(json).unwrap();
+        assert_eq!(metrics.cognitive.unwrap().sum, 4.0);
+        assert_eq!(metrics.mi.unwrap().mi_visual_studio, 64.3);
+        assert_eq!(metrics.abc.unwrap().magnitude, 5.0);
+        assert_eq!(metrics.nom.unwrap().total, 2.0);
+        assert!(metrics.nargs.is_none(), "missing metrics default to None");
+    }
+
+    #[test]
+    fn test_function_violations_use_line_spans() {
+        let source = "
+fn wide(a: u8, b: u8, c: u8) -> u8 {
+    let f = |x: u8, y: u8| x + y;
+    if a > b && b > c { f(a, b) } else { c }
+}
+
+struct S;
+
+impl S {
+    fn method(&self, v: Vec<u8>) -> usize {
+        v.len()
+    }
+}
+";
+        let thresholds = ComplexityThresholds {
+            max_function_cyclomatic: Some(2.0),
+            max_function_args: Some(2),
+            max_function_length: Some(3),
+            ..Default::default()
+        };
+        let data = builtin_data(&[("src/lib.rs", source)], thresholds);
+        let violations = data.threshold_violations();
+        let messages: Vec<_> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Function 'wide' in 'src/lib.rs' has cyclomatic complexity 4, exceeding the limit of 2",
+                "Function 'wide' in 'src/lib.rs' has arguments 3, exceeding the limit of 2",
+                "Function 'wide' in 'src/lib.rs' has length 4, exceeding the limit of 3 lines",
+            ]
+        );
+
+        let location = violations[0].location.as_ref().unwrap();
+        assert_eq!(location.uri, "src/lib.rs");
+        assert_eq!((location.start_line, location.end_line), (Some(2), Some(5)));
+        assert_eq!(
+            violations[1].fingerprint,
+            "rust-code-analysis:function-args:src/lib.rs:wide:2"
+        );
+
+        let thresholds = ComplexityThresholds {
+            max_function_args: Some(1),
+            ..Default::default()
+        };
+        let data = builtin_data(&[("src/lib.rs", source)], thresholds);
+        let names: Vec<_> = data
+            .threshold_violations()
+            .into_iter()
+            .map(|v| v.fingerprint)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "rust-code-analysis:function-args:src/lib.rs:wide:1",
+                "rust-code-analysis:function-args:src/lib.rs:wide::{closure}:1",
+                "rust-code-analysis:function-args:src/lib.rs:S::method:1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribution_from_values() {
+        let values: Vec<f64> = (1..=10).rev().map(f64::from).collect();
+        let distribution = Distribution::from_values(&values);
+        assert_eq!(
+            distribution,
+            Distribution {
+                sum: 55.0,
+                mean: 5.5,
+                p90: 9.0,
+                max: 10.0,
+            }
+        );
+        assert_eq!(Distribution::from_values(&[]), Distribution::default());
+        assert_eq!(Distribution::from_values(&[3.0]).p90, 3.0);
+    }
+
+    #[test]
+    fn test_rollups_group_by_module_crate_and_component() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let crate_dir = root.join("crates/demo");
+        fs::create_dir_all(crate_dir.join("src/parser")).unwrap();
+        fs::create_dir_all(root.join("scripts")).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_dir.join("src/lib.rs"),
+            "pub mod parser;\nfn one() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            crate_dir.join("src/parser/mod.rs"),
+            "fn parse(x: u8) -> u8 {\n    if x > 1 { x } else { 0 }\n}\nfn id(x: u8) -> u8 {\n    x\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("scripts/gen.rs"),
+            "fn main() {\n    let f = |x: u8| if x > 0 { 1 } else { 0 };\n    f(1);\n}\n",
+        )
+        .unwrap();
+
+        let results: Vec<AnalysisUnit> = discover_source_files(root, ".rs")
+            .unwrap()
+            .iter()
+            .map(|path| crate::complexity::analyze_file(path).unwrap())
+            .collect();
+        let definitions = BTreeMap::from([(
+            "parsing".to_string(),
+            vec!["crates/demo/src/parser/**".to_string()],
+        )]);
+        let rollups =
+            rollup_complexity(root, &results, ComponentMode::Directory, &definitions).unwrap();
+
+        let names = |rollups: &[ComplexityRollup]| -> Vec<String> {
+            rollups.iter().map(|r| r.name.clone()).collect()
+        };
+        assert_eq!(names(&rollups.modules), ["demo", "demo::parser", "scripts"]);
+        assert_eq!(names(&rollups.crates), ["demo", "scripts"]);
+        assert_eq!(names(&rollups.components), ["crates", "parsing", "scripts"]);
+
+        let demo = &rollups.crates[0];
+        assert_eq!((demo.files, demo.functions), (2, 3));
+        assert_eq!(demo.cyclomatic.sum, 4.0);
+        assert_eq!(demo.cyclomatic.max, 2.0);
+        assert_eq!(demo.cyclomatic.p90, 2.0);
+        assert!(demo.sloc > 0.0 && demo.halstead_effort > 0.0);
+        assert!(demo.mi_mean.is_some());
+
+        // The closure is counted as part of `main`, not as a function of its own:
+        // main(1) + closure(1) + if(1)
+        let scripts = &rollups.crates[1];
+        assert_eq!(scripts.functions, 1);
+        assert_eq!(scripts.cyclomatic.sum, 3.0);
+    }
+
+    #[test]
+    fn test_result_cache_reanalyzes_only_changed_files() {
+        let temp_dir = tempdir().unwrap();
+        let cache_dir = tempdir().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("a.rs"), "fn a() {}\n").unwrap();
+        fs::write(src_dir.join("b.rs"), "fn b() {}\n").unwrap();
+
+        let args = RustCodeAnalysisArgs {
+            path: temp_dir.path().to_path_buf(),
+            language: "rust".to_string(),
+            backend: RustCodeAnalysisBackend::Builtin,
+            components: ComponentMode::Directory,
+            component_definitions: Default::default(),
+            max_file_cognitive: None,
+            min_file_mi: None,
+            max_file_abc: None,
+            max_function_cyclomatic: None,
+            max_function_cognitive: None,
+            max_function_args: None,
+            max_function_length: None,
+            metrics: true,
+            jobs: 2,
+            extra_flags: vec![],
+            output: RustCodeAnalysisOutputFormat::Json,
+            ci_output: None,
+            output_file: None,
+            no_cache: false,
+        };
+        let run = |tool_version: &str| {
+            let cache_manager =
+                CacheManager::with_dir(Some(cache_dir.path().to_path_buf())).unwrap();
+            let mut cache =
+                ResultCache::open(cache_manager, temp_dir.path(), &args, tool_version).unwrap();
+            let results = analyze_builtin(temp_dir.path(), &args, &mut cache).unwrap();
+            let names: Vec<String> = results
+                .iter()
+                .map(|unit| unit.spaces[0].name.clone())
+                .collect();
+            (cache.misses, names)
+        };
+
+        assert_eq!(run("v1"), (2, vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(run("v1"), (0, vec!["a".to_string(), "b".to_string()]));
+
+        fs::write(src_dir.join("b.rs"), "fn renamed() {}\n").unwrap();
+        assert_eq!(run("v1"), (1, vec!["a".to_string(), "renamed".to_string()]));
+
+        // A different tool version must not reuse the results
+        assert_eq!(run("v2").0, 2);
+    }
 }
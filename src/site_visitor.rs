@@ -0,0 +1,253 @@
+//! Site Visitor
+//!
+//! This module provides the `syn` visitor shared by the rules that locate
+//! sites of interest in source files, such as unsafe blocks.
+//!
+//! A rule describes what it looks for by implementing [`SiteKind`] for its
+//! kind enum. [`SiteVisitor`] walks a file, asks the kind for sites at each
+//! item, function, impl block, expression and macro, and records every site
+//! together with the inline modules and items enclosing it.
+//!
+//! Test code (`#[cfg(test)]` items and `#[test]` functions) is skipped unless
+//! tests are included. Out-of-line `#[cfg(test)] mod name;` declarations are
+//! collected so that the caller can skip the files they point to.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use proc_macro2::Span;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{
+    Attribute, Block, Expr, ImplItemFn, Item, ItemFn, ItemImpl, ItemMod, ItemTrait, Macro,
+    Signature, Token, TraitItemFn,
+};
+
+use crate::counter::{is_test_attr, item_attrs, type_name};
+use crate::output_utils::path_uri;
+
+/// A kind of site recorded by [`SiteVisitor`].
+///
+/// Every method has a default that finds nothing, so a kind only implements
+/// the syntax it looks for.
+pub(crate) trait SiteKind: Copy {
+    /// Whether the bodies of macros are parsed as statements or
+    /// comma-separated expressions and visited for sites.
+    const VISIT_MACRO_BODIES: bool = false;
+
+    /// Sites at `item` itself, recorded at the item's span. Functions are
+    /// reported through [`SiteKind::of_fn`] instead.
+    fn of_item(_item: &Item) -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// Sites at a free function, method or trait method, recorded at the
+    /// function's span within the function.
+    fn of_fn(_attrs: &[Attribute], _sig: &Signature) -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// The site at an impl block, recorded at its span within the block.
+    fn of_impl(_node: &ItemImpl) -> Option<Self> {
+        None
+    }
+
+    /// The site at `expr` and the span to record it at.
+    fn of_expr(_expr: &Expr) -> Option<(Self, Span)> {
+        None
+    }
+
+    /// The site at a macro invocation, recorded at the macro's span.
+    fn of_macro(_node: &Macro) -> Option<Self> {
+        None
+    }
+}
+
+/// A site found by [`SiteVisitor`], before it is placed in a module.
+#[derive(Debug)]
+pub(crate) struct RawSite<K> {
+    pub(crate) kind: K,
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    /// Inline modules enclosing the site, outermost first.
+    pub(crate) modules: Vec<String>,
+    pub(crate) item: Option<String>,
+}
+
+/// Collects the sites of a single file.
+pub(crate) struct SiteVisitor<K> {
+    include_tests: bool,
+    /// Names of the enclosing inline modules.
+    modules: Vec<String>,
+    /// Names of the enclosing functions, impl blocks and traits.
+    items: Vec<String>,
+    pub(crate) sites: Vec<RawSite<K>>,
+    /// Out-of-line `#[cfg(test)]` modules declared in the file, as
+    /// `/`-separated paths relative to the file's module.
+    pub(crate) test_modules: Vec<String>,
+}
+
+impl<K: SiteKind> SiteVisitor<K> {
+    pub(crate) fn new(include_tests: bool) -> Self {
+        Self {
+            include_tests,
+            modules: Vec::new(),
+            items: Vec::new(),
+            sites: Vec::new(),
+            test_modules: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, kind: K, span: Span) {
+        self.sites.push(RawSite {
+            kind,
+            start_line: span.start().line,
+            end_line: span.end().line,
+            modules: self.modules.clone(),
+            item: (!self.items.is_empty()).then(|| self.items.join("::")),
+        });
+    }
+
+    /// Returns `true` if `attrs` mark test code that should be skipped.
+    fn skips(&self, attrs: &[Attribute]) -> bool {
+        !self.include_tests && attrs.iter().any(is_test_attr)
+    }
+
+    /// Visits an item named `name`, making it the innermost enclosing item.
+    fn within(&mut self, name: String, visit: impl FnOnce(&mut Self)) {
+        self.items.push(name);
+        visit(self);
+        self.items.pop();
+    }
+
+    /// Visits a function named by `sig`, recording its own sites first.
+    fn within_fn(
+        &mut self,
+        attrs: &[Attribute],
+        sig: &Signature,
+        span: Span,
+        visit: impl FnOnce(&mut Self),
+    ) {
+        self.within(sig.ident.to_string(), |this| {
+            for kind in K::of_fn(attrs, sig) {
+                this.record(kind, span);
+            }
+            visit(this);
+        });
+    }
+}
+
+impl<'ast, K: SiteKind> Visit<'ast> for SiteVisitor<K> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        if self.skips(item_attrs(item)) {
+            if let Item::Mod(item_mod) = item
+                && item_mod.content.is_none()
+            {
+                let mut path = self.modules.clone();
+                path.push(item_mod.ident.to_string());
+                self.test_modules.push(path.join("/"));
+            }
+            return;
+        }
+        for kind in K::of_item(item) {
+            self.record(kind, item.span());
+        }
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        self.modules.push(node.ident.to_string());
+        let items = std::mem::take(&mut self.items);
+        syn::visit::visit_item_mod(self, node);
+        self.items = items;
+        self.modules.pop();
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.within_fn(&node.attrs, &node.sig, node.span(), |this| {
+            syn::visit::visit_item_fn(this, node);
+        });
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        self.within(type_name(&node.self_ty), |this| {
+            if let Some(kind) = K::of_impl(node) {
+                this.record(kind, node.span());
+            }
+            syn::visit::visit_item_impl(this, node);
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        if self.skips(&node.attrs) {
+            return;
+        }
+        self.within_fn(&node.attrs, &node.sig, node.span(), |this| {
+            syn::visit::visit_impl_item_fn(this, node);
+        });
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast ItemTrait) {
+        self.within(node.ident.to_string(), |this| {
+            syn::visit::visit_item_trait(this, node);
+        });
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        self.within_fn(&node.attrs, &node.sig, node.span(), |this| {
+            syn::visit::visit_trait_item_fn(this, node);
+        });
+    }
+
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if let Some((kind, span)) = K::of_expr(node) {
+            self.record(kind, span);
+        }
+        syn::visit::visit_expr(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        if let Some(kind) = K::of_macro(node) {
+            self.record(kind, node.span());
+        }
+        if !K::VISIT_MACRO_BODIES {
+            return;
+        }
+        if let Ok(stmts) = node.parse_body_with(Block::parse_within) {
+            for stmt in &stmts {
+                self.visit_stmt(stmt);
+            }
+        } else if let Ok(exprs) =
+            node.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        {
+            for expr in &exprs {
+                self.visit_expr(expr);
+            }
+        }
+    }
+}
+
+/// Sites per 1,000 code lines.
+pub(crate) fn density(sites: usize, code_lines: usize) -> f64 {
+    if code_lines == 0 {
+        0.0
+    } else {
+        sites as f64 * 1000.0 / code_lines as f64
+    }
+}
+
+/// Formats site counts per kind, e.g. `unwrap 3, index 1`.
+pub(crate) fn kind_summary<K: Display>(by_kind: &BTreeMap<K, usize>) -> String {
+    by_kind
+        .iter()
+        .map(|(kind, count)| format!("{kind} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats the location of a site as `path:start-end`.
+pub(crate) fn site_location(path: &Path, start_line: usize, end_line: usize) -> String {
+    format!("{}:{}-{}", path_uri(path), start_line, end_line)
+}
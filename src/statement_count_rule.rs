@@ -1,9 +1,26 @@
 //! Statement Count Rule
 //!
 //! This module provides the statement count analysis rule, which counts the number of
-//! statements in each Rust component (top-level directory under the source path) and
+//! statements in each Rust component (see [`crate::components`]; by default the
+//! top-level directory under the source path) and
 //! checks whether any component exceeds a specified percentage threshold of the total
-//! statements.
+//! statements. It can also list the largest functions, methods and closures
+//! (`--granularity item`) and flag functions over a per-function statement limit
+//! (`--max-function-statements`).
+//!
+//! Test code — `#[cfg(test)]` and `#[test]` items, out-of-line `#[cfg(test)]`
+//! modules and files under `tests/`, `benches/` and `examples/` — is reported
+//! in a separate column. Thresholds apply to production code only unless
+//! `--include-tests` is set.
+//!
+//! Besides the percentage threshold, absolute limits can be set per component
+//! (`--max-component-statements`, `--min-component-statements`), per file
+//! (`--max-file-statements`) and per module (`--max-module-statements`),
+//! and `--max-gini` caps the imbalance of component sizes.
+//!
+//! With `--parse-macros`, statements inside macro invocations and
+//! `macro_rules!` definitions are counted too, and files where most
+//! statements come from macros are listed as macro-heavy.
 //!
 //! # Overview
 //!
@@ -16,17 +33,31 @@
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use raff_core::statement_count_rule::StatementCountRule;
-//! use raff_core::{StatementCountArgs, StatementCountOutputFormat};
+//! use raff_core::{
+//!     ComponentMode, StatementCountArgs, StatementCountGranularity, StatementCountOutputFormat,
+//! };
 //! use std::path::PathBuf;
 //!
 //! let rule = StatementCountRule::new();
 //! let args = StatementCountArgs {
 //!     path: PathBuf::from("."),
 //!     threshold: 10,
+//!     components: ComponentMode::Directory,
+//!     granularity: StatementCountGranularity::Component,
+//!     top: 20,
+//!     max_function_statements: None,
+//!     include_tests: false,
+//!     parse_macros: false,
+//!     max_component_statements: None,
+//!     min_component_statements: None,
+//!     max_file_statements: None,
+//!     max_module_statements: None,
+//!     max_gini: None,
 //!     output: StatementCountOutputFormat::Table,
 //!     ci_output: None,
 //!     output_file: None,
 //!     staged: false,
+//!     component_definitions: Default::default(),
 //! };
 //!
 //! if let Err(e) = rule.run(&args) {
@@ -40,6 +71,7 @@
 //!
 //! - [`StatementCountRule`]: The main rule implementation
 //! - [`StatementCountData`]: Contains the analysis results including component stats and thresholds
+//! - [`ItemStatementCount`]: The statement count of a single item and where it is defined
 //!
 //! # Errors
 //!
@@ -52,18 +84,26 @@
 use bincode;
 use maud::Markup;
 use maud::html;
+use prettytable::{Table, row};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, io::Write, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
 use syn::File as SynFile;
 use syn::visit::Visit;
 use tracing::instrument;
 
 use crate::cache::{CacheEntry, CacheKey, CacheManager};
-use crate::ci_report::{Finding, Severity, ToFindings};
-use crate::cli::{CiOutputFormat, StatementCountArgs, StatementCountOutputFormat}; // Import the specific args struct
-use crate::counter::StmtCounter; // Assuming counter.rs is at crate::counter
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{
+    CiOutputFormat, StatementCountArgs, StatementCountGranularity, StatementCountOutputFormat,
+}; // Import the specific args struct
+use crate::components::ComponentResolver;
+use crate::counter::{ItemKind, ItemStmtCount, StmtCounter}; // Assuming counter.rs is at crate::counter
 use crate::error::{RaffError, Result};
-use crate::file_utils::{relative_namespace, top_level_component}; // Assuming file_utils.rs is at crate::file_utils
 use crate::html_utils; // Now using Maud-based html_utils
 use crate::reporting::print_report; // Assuming reporting.rs is at crate::reporting // Import the new HTML utilities
 use crate::rule::Rule;
@@ -72,24 +112,337 @@ use crate::rule::Rule;
 #[derive(Debug, Default)]
 pub struct StatementCountRule;
 
+/// The statement count of a single item and where it is defined.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemStatementCount {
+    /// Source file, relative to the analysis path.
+    pub file: String,
+    /// Top-level component the file belongs to.
+    pub component: String,
+    pub item: ItemStmtCount,
+}
+
+/// A file in which most statements come from macro bodies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroHeavyFile {
+    /// Source file, relative to the analysis path.
+    pub file: String,
+    pub component: String,
+    pub statements: usize,
+    /// Statements found inside macro bodies, included in `statements`.
+    pub macro_statements: usize,
+}
+
+/// The statement count of a single file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStatementCount {
+    /// Source file, relative to the analysis path.
+    pub file: String,
+    pub component: String,
+    /// Production statements.
+    pub statements: usize,
+    pub test_statements: usize,
+}
+
+/// Absolute size limits, which unlike the percentage threshold do not depend
+/// on the size of the rest of the codebase.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SizeLimits {
+    /// Maximum statements in a single component.
+    pub max_component_statements: Option<usize>,
+    /// Minimum statements in a single component.
+    pub min_component_statements: Option<usize>,
+    /// Maximum statements in a single file.
+    pub max_file_statements: Option<usize>,
+    /// Maximum statements in a single module, including its submodules.
+    pub max_module_statements: Option<usize>,
+    /// Maximum Gini coefficient of component sizes.
+    pub max_gini: Option<f64>,
+}
+
+/// A breached size limit.
+#[derive(Debug, Clone)]
+pub struct SizeViolation {
+    pub message: String,
+    pub location: Option<Location>,
+    pub fingerprint: String,
+}
+
+/// Share of a file's statements that must come from macros for the file to
+/// be reported as macro-heavy.
+const MACRO_HEAVY_SHARE: f64 = 0.5;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatementCountData {
+    /// File count and production statement count per component.
     pub component_stats: HashMap<String, (usize, usize)>,
+    /// Total production statements.
     pub grand_total: usize,
+    /// Test statements per component.
+    pub test_stats: HashMap<String, usize>,
+    /// Total test statements.
+    pub test_total: usize,
+    /// Whether thresholds apply to test code as well as production code.
+    pub include_tests: bool,
     pub threshold: usize,
+    /// Every function, method, closure and inline module, largest first.
+    pub items: Vec<ItemStatementCount>,
+    /// Maximum number of statements allowed in a single function or method.
+    pub max_function_statements: Option<usize>,
+    /// Every file, in path order.
+    pub files: Vec<FileStatementCount>,
+    pub limits: SizeLimits,
+    /// Files where most statements come from macros, by macro statements
+    /// descending. Only populated when macro bodies are parsed.
+    pub macro_heavy_files: Vec<MacroHeavyFile>,
     pub analysis_path: PathBuf,
 }
 
+impl StatementCountData {
+    /// Statements of `component` that thresholds apply to, given its
+    /// production statement count.
+    pub fn measured_statements(&self, component: &str, statements: usize) -> usize {
+        if self.include_tests {
+            statements + self.test_stats.get(component).copied().unwrap_or(0)
+        } else {
+            statements
+        }
+    }
+
+    /// Total statements that thresholds apply to.
+    pub fn measured_total(&self) -> usize {
+        if self.include_tests {
+            self.grand_total + self.test_total
+        } else {
+            self.grand_total
+        }
+    }
+
+    /// Items that thresholds and item listings apply to.
+    fn measured_items(&self) -> impl Iterator<Item = &ItemStatementCount> {
+        self.items
+            .iter()
+            .filter(|entry| self.include_tests || !entry.item.is_test)
+    }
+
+    /// Returns the functions and methods over `max_function_statements`,
+    /// largest first.
+    pub fn oversized_functions(&self) -> Vec<&ItemStatementCount> {
+        let Some(max) = self.max_function_statements else {
+            return Vec::new();
+        };
+        self.measured_items()
+            .filter(|entry| entry.item.kind.is_function() && entry.item.statements > max)
+            .collect()
+    }
+
+    /// Returns the `top` largest functions, methods and closures.
+    pub fn largest_functions(&self, top: usize) -> impl Iterator<Item = &ItemStatementCount> {
+        self.measured_items()
+            .filter(|entry| entry.item.kind != ItemKind::Module)
+            .take(top)
+    }
+
+    fn any_component_over_threshold(&self) -> bool {
+        let total = self.measured_total();
+        self.component_stats
+            .iter()
+            .any(|(component, &(_file_count, st_count))| {
+                if total == 0 {
+                    return false;
+                }
+                let percentage = (self.measured_statements(component, st_count) * 100) / total;
+                percentage > self.threshold
+            })
+    }
+
+    /// Gini coefficient of the component sizes that thresholds apply to: 0
+    /// when all components are the same size, approaching 1 when a single
+    /// component holds all statements.
+    pub fn gini(&self) -> f64 {
+        let mut sizes: Vec<usize> = self
+            .component_stats
+            .iter()
+            .map(|(component, &(_file_count, st_count))| {
+                self.measured_statements(component, st_count)
+            })
+            .collect();
+        let total: usize = sizes.iter().sum();
+        if sizes.len() < 2 || total == 0 {
+            return 0.0;
+        }
+        sizes.sort_unstable();
+        let n = sizes.len() as f64;
+        let weighted: f64 = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| (i + 1) as f64 * size as f64)
+            .sum();
+        (2.0 * weighted) / (n * total as f64) - (n + 1.0) / n
+    }
+
+    /// Returns every breached absolute size limit and the Gini ceiling.
+    pub fn size_violations(&self) -> Vec<SizeViolation> {
+        let limits = &self.limits;
+        let mut violations = Vec::new();
+
+        let mut components: Vec<_> = self.component_stats.iter().collect();
+        components.sort_by_key(|&(name, _)| name);
+        for (component, &(_file_count, st_count)) in components {
+            let statements = self.measured_statements(component, st_count);
+            if let Some(max) = limits.max_component_statements
+                && statements > max
+            {
+                violations.push(SizeViolation {
+                    message: format!(
+                        "Component '{component}' has {statements} statements, exceeding the limit of {max}"
+                    ),
+                    location: None,
+                    fingerprint: format!("statement-count:component-size:{component}:{max}"),
+                });
+            }
+            if let Some(min) = limits.min_component_statements
+                && statements < min
+            {
+                violations.push(SizeViolation {
+                    message: format!(
+                        "Component '{component}' has {statements} statements, below the minimum of {min}"
+                    ),
+                    location: None,
+                    fingerprint: format!("statement-count:anemic:{component}:{min}"),
+                });
+            }
+        }
+
+        let measured = |file: &FileStatementCount| {
+            if self.include_tests {
+                file.statements + file.test_statements
+            } else {
+                file.statements
+            }
+        };
+        if let Some(max) = limits.max_file_statements {
+            for file in &self.files {
+                let statements = measured(file);
+                if statements > max {
+                    violations.push(SizeViolation {
+                        message: format!(
+                            "File '{}' has {statements} statements, exceeding the limit of {max}",
+                            file.file
+                        ),
+                        location: Some(Location::new(file.file.clone())),
+                        fingerprint: format!("statement-count:file-size:{}:{max}", file.file),
+                    });
+                }
+            }
+        }
+
+        if let Some(max) = limits.max_module_statements {
+            // A file-backed module owns its file, including inline modules,
+            // and the files of its out-of-line submodules. Crate roots are
+            // left to the component limits.
+            for file in &self.files {
+                let path = Path::new(&file.file);
+                if matches!(
+                    path.file_stem().and_then(|stem| stem.to_str()),
+                    Some("lib" | "main")
+                ) {
+                    continue;
+                }
+                let module_dir = child_module_dir(path);
+                let statements: usize = self
+                    .files
+                    .iter()
+                    .filter(|other| {
+                        let other = Path::new(&other.file);
+                        other == path || other.starts_with(&module_dir)
+                    })
+                    .map(measured)
+                    .sum();
+                if statements > max {
+                    violations.push(SizeViolation {
+                        message: format!(
+                            "Module '{}' has {statements} statements, exceeding the limit of {max}",
+                            file.file
+                        ),
+                        location: Some(Location::new(file.file.clone())),
+                        fingerprint: format!("statement-count:module-size:{}:{max}", file.file),
+                    });
+                }
+            }
+            for entry in self.measured_items() {
+                let item = &entry.item;
+                if item.kind == ItemKind::Module && item.statements > max {
+                    violations.push(SizeViolation {
+                        message: format!(
+                            "Module '{}' has {} statements, exceeding the limit of {max}",
+                            item.name, item.statements
+                        ),
+                        location: Some(Location::with_lines(
+                            entry.file.clone(),
+                            item.start_line,
+                            item.end_line,
+                        )),
+                        fingerprint: format!(
+                            "statement-count:module-size:{}:{}:{max}",
+                            entry.file, item.name
+                        ),
+                    });
+                }
+            }
+        }
+
+        if let Some(max) = limits.max_gini {
+            let gini = self.gini();
+            if gini > max {
+                violations.push(SizeViolation {
+                    message: format!(
+                        "Component sizes are unbalanced: Gini coefficient {gini:.2} exceeds {max:.2}"
+                    ),
+                    location: None,
+                    fingerprint: format!("statement-count:gini:{max}"),
+                });
+            }
+        }
+        violations
+    }
+
+    /// Returns the error to exit with when a size limit is breached.
+    fn size_limits_error(&self) -> Option<RaffError> {
+        let violations = self.size_violations().len();
+        (violations > 0).then(|| {
+            RaffError::analysis_error(
+                "statement_count",
+                format!("{violations} size limit(s) breached."),
+            )
+        })
+    }
+
+    /// Returns the error to exit with when a function exceeds the limit.
+    fn oversized_functions_error(&self) -> Option<RaffError> {
+        let oversized = self.oversized_functions().len();
+        let max = self.max_function_statements?;
+        (oversized > 0).then(|| {
+            RaffError::analysis_error(
+                "statement_count",
+                format!("{oversized} function(s) exceed {max} statements."),
+            )
+        })
+    }
+}
+
 impl ToFindings for StatementCountData {
     #[instrument(skip(self), fields(rule_id = "statement-count", threshold = self.threshold))]
     fn to_findings(&self) -> Vec<Finding> {
         let mut findings = Vec::new();
 
-        for (component, (_file_count, stmt_count)) in &self.component_stats {
-            if self.grand_total == 0 {
+        let total = self.measured_total();
+        for (component, &(_file_count, stmt_count)) in &self.component_stats {
+            if total == 0 {
                 continue;
             }
-            let percentage = (*stmt_count * 100) / self.grand_total;
+            let stmt_count = self.measured_statements(component, stmt_count);
+            let percentage = (stmt_count * 100) / total;
 
             if percentage > self.threshold {
                 findings.push(Finding {
@@ -101,6 +454,7 @@ impl ToFindings for StatementCountData {
                         component, stmt_count, percentage, self.threshold
                     ),
                     location: None, // We don't track individual files in StatementCountData
+                    related_locations: Vec::new(),
                     help_uri: Some(
                         "https://github.com/liamwh/raff/docs/statement-count".to_string(),
                     ),
@@ -111,6 +465,52 @@ impl ToFindings for StatementCountData {
                 });
             }
         }
+
+        if let Some(max) = self.max_function_statements {
+            for entry in self.oversized_functions() {
+                let item = &entry.item;
+                findings.push(Finding {
+                    rule_id: "statement-count".to_string(),
+                    rule_name: "Statement Count Rule".to_string(),
+                    severity: Severity::Error,
+                    message: format!(
+                        "{} '{}' has {} statements, exceeding the limit of {}",
+                        if item.kind == ItemKind::Method {
+                            "Method"
+                        } else {
+                            "Function"
+                        },
+                        item.name,
+                        item.statements,
+                        max
+                    ),
+                    location: Some(Location::with_lines(
+                        entry.file.clone(),
+                        item.start_line,
+                        item.end_line,
+                    )),
+                    related_locations: Vec::new(),
+                    help_uri: Some(
+                        "https://github.com/liamwh/raff/docs/statement-count".to_string(),
+                    ),
+                    fingerprint: Some(format!(
+                        "statement-count:{}:{}:{}",
+                        entry.file, item.name, max
+                    )),
+                });
+            }
+        }
+
+        findings.extend(self.size_violations().into_iter().map(|violation| Finding {
+            rule_id: "statement-count".to_string(),
+            rule_name: "Statement Count Rule".to_string(),
+            severity: Severity::Error,
+            message: violation.message,
+            location: violation.location,
+            related_locations: Vec::new(),
+            help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
+            fingerprint: Some(violation.fingerprint),
+        }));
         findings
     }
 }
@@ -178,8 +578,7 @@ impl StatementCountRule {
             }
 
             // Apply exit code policy
-            let has_errors = findings.iter().any(|f| f.severity == Severity::Error);
-            if has_errors {
+            if data.any_component_over_threshold() {
                 return Err(RaffError::analysis_error(
                     "statement_count",
                     format!(
@@ -188,6 +587,12 @@ impl StatementCountRule {
                     ),
                 ));
             }
+            if let Some(error) = data.oversized_functions_error() {
+                return Err(error);
+            }
+            if let Some(error) = data.size_limits_error() {
+                return Err(error);
+            }
             return Ok(());
         }
 
@@ -197,8 +602,36 @@ impl StatementCountRule {
                     "\nStatement Count Report (analyzing path: {}):",
                     data.analysis_path.display()
                 );
-                let any_over_threshold =
-                    print_report(&data.component_stats, data.grand_total, data.threshold);
+                let any_over_threshold = print_report(
+                    &data.component_stats,
+                    &data.test_stats,
+                    data.measured_total(),
+                    data.threshold,
+                    data.include_tests,
+                );
+                if args.granularity == StatementCountGranularity::Item {
+                    println!("\nLargest functions (top {}):", args.top);
+                    print_item_table(data.largest_functions(args.top));
+                }
+                let oversized = data.oversized_functions();
+                if let Some(max) = data.max_function_statements
+                    && !oversized.is_empty()
+                {
+                    println!("\nFunctions over {max} statements:");
+                    print_item_table(oversized.into_iter());
+                }
+                if !data.macro_heavy_files.is_empty() {
+                    println!("\nMacro-heavy files:");
+                    print_macro_heavy_table(&data.macro_heavy_files);
+                }
+                println!("\nBalance (Gini coefficient): {:.2}", data.gini());
+                let violations = data.size_violations();
+                if !violations.is_empty() {
+                    println!("\nSize limits breached:");
+                    for violation in &violations {
+                        println!("  - {}", violation.message);
+                    }
+                }
                 if any_over_threshold {
                     return Err(RaffError::analysis_error(
                         "statement_count",
@@ -208,29 +641,31 @@ impl StatementCountRule {
                         ),
                     ));
                 }
+                if let Some(error) = data.oversized_functions_error() {
+                    return Err(error);
+                }
+                if let Some(error) = data.size_limits_error() {
+                    return Err(error);
+                }
                 println!(
-                    "\nAll components are within {}% threshold. (Total statements = {})",
-                    data.threshold, data.grand_total
+                    "\nAll components are within {}% threshold. (Total statements = {}, test statements = {})",
+                    data.threshold, data.grand_total, data.test_total
                 );
             }
             StatementCountOutputFormat::Html => {
-                let html_body = self.render_statement_count_html_body(&data)?;
+                let mut html_body = self.render_statement_count_html_body(&data)?;
+                if args.granularity == StatementCountGranularity::Item {
+                    html_body = html! {
+                        (html_body)
+                        (self.render_item_html_body(&data, args.top))
+                    };
+                }
                 let full_html = html_utils::render_html_doc(
                     &format!("Statement Count Report: {}", data.analysis_path.display()),
                     html_body,
                 );
                 println!("{full_html}");
-                let any_over_threshold =
-                    data.component_stats
-                        .values()
-                        .any(|&(_file_count, st_count)| {
-                            if data.grand_total == 0 {
-                                return false;
-                            }
-                            let percentage = (st_count * 100) / data.grand_total;
-                            percentage > data.threshold
-                        });
-                if any_over_threshold {
+                if data.any_component_over_threshold() {
                     return Err(RaffError::analysis_error(
                         "statement_count",
                         format!(
@@ -239,6 +674,12 @@ impl StatementCountRule {
                         ),
                     ));
                 }
+                if let Some(error) = data.oversized_functions_error() {
+                    return Err(error);
+                }
+                if let Some(error) = data.size_limits_error() {
+                    return Err(error);
+                }
             }
         }
         Ok(())
@@ -258,6 +699,28 @@ impl StatementCountRule {
             vec![
                 ("threshold".to_string(), threshold.to_string()),
                 ("staged".to_string(), args.staged.to_string()),
+                ("components".to_string(), format!("{:?}", args.components)),
+                (
+                    "component_definitions".to_string(),
+                    format!("{:?}", args.component_definitions),
+                ),
+                ("include_tests".to_string(), args.include_tests.to_string()),
+                ("parse_macros".to_string(), args.parse_macros.to_string()),
+                (
+                    "size_limits".to_string(),
+                    format!(
+                        "{:?}:{:?}:{:?}:{:?}:{:?}",
+                        args.max_component_statements,
+                        args.min_component_statements,
+                        args.max_file_statements,
+                        args.max_module_statements,
+                        args.max_gini
+                    ),
+                ),
+                (
+                    "max_function_statements".to_string(),
+                    format!("{:?}", args.max_function_statements),
+                ),
             ],
         );
         let cache_manager = if args.staged {
@@ -304,17 +767,96 @@ impl StatementCountRule {
             ));
         }
 
-        let mut file_to_stmt: HashMap<String, usize> = HashMap::new();
+        let components =
+            ComponentResolver::new(analysis_path, args.components, &args.component_definitions)?;
+        let relative = |path: &'_ PathBuf| {
+            path.strip_prefix(analysis_path)
+                .unwrap_or(path)
+                .to_path_buf()
+        };
+
+        let mut counters: Vec<(PathBuf, StmtCounter)> = Vec::new();
         for path_buf in &all_rs_files {
             let content = fs::read_to_string(path_buf)?;
             let ast: SynFile = syn::parse_file(&content)?;
-            let mut counter = StmtCounter::new();
+            let mut counter = StmtCounter::new().parse_macros(args.parse_macros);
             counter.visit_file(&ast);
-            let key = path_buf.to_string_lossy().into_owned();
-            file_to_stmt.insert(key, counter.count);
+            counters.push((relative(path_buf), counter));
         }
+        // Files declared as `#[cfg(test)] mod name;` are test code in full
+        let test_module_paths: Vec<PathBuf> = counters
+            .iter()
+            .flat_map(|(path, counter)| {
+                counter
+                    .test_modules
+                    .iter()
+                    .map(|module| child_module_dir(path).join(module))
+            })
+            .collect();
 
-        if file_to_stmt.is_empty() {
+        let mut component_stats: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut test_stats: HashMap<String, usize> = HashMap::new();
+        let mut items: Vec<ItemStatementCount> = Vec::new();
+        let mut macro_heavy_files: Vec<MacroHeavyFile> = Vec::new();
+        let mut files: Vec<FileStatementCount> = Vec::new();
+        for (path, counter) in counters {
+            let is_test_file = is_test_target(analysis_path, &path)
+                || test_module_paths
+                    .iter()
+                    .any(|module| path.starts_with(module) || path == module.with_extension("rs"));
+            let test_statements = if is_test_file {
+                counter.count
+            } else {
+                counter.test_count
+            };
+            let component = components.component_of(&path);
+            let entry = component_stats.entry(component.clone()).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += counter.count - test_statements;
+            *test_stats.entry(component.clone()).or_insert(0) += test_statements;
+
+            let file = path.to_string_lossy().replace('\\', "/");
+            files.push(FileStatementCount {
+                file: file.clone(),
+                component: component.clone(),
+                statements: counter.count - test_statements,
+                test_statements,
+            });
+            if counter.macro_count > 0
+                && counter.macro_count as f64 >= counter.count as f64 * MACRO_HEAVY_SHARE
+            {
+                macro_heavy_files.push(MacroHeavyFile {
+                    file: file.clone(),
+                    component: component.clone(),
+                    statements: counter.count,
+                    macro_statements: counter.macro_count,
+                });
+            }
+            items.extend(counter.items.into_iter().map(|mut item| {
+                item.is_test |= is_test_file;
+                ItemStatementCount {
+                    file: file.clone(),
+                    component: component.clone(),
+                    item,
+                }
+            }));
+        }
+        items.sort_by(|a, b| {
+            b.item
+                .statements
+                .cmp(&a.item.statements)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.item.start_line.cmp(&b.item.start_line))
+        });
+
+        files.sort_by(|a, b| a.file.cmp(&b.file));
+        macro_heavy_files.sort_by(|a, b| {
+            b.macro_statements
+                .cmp(&a.macro_statements)
+                .then_with(|| a.file.cmp(&b.file))
+        });
+
+        if component_stats.is_empty() {
             return Err(RaffError::analysis_error(
                 "statement_count",
                 format!(
@@ -324,19 +866,9 @@ impl StatementCountRule {
             ));
         }
 
-        let mut component_stats: HashMap<String, (usize, usize)> = HashMap::new();
-        for path_buf in &all_rs_files {
-            let namespace = relative_namespace(path_buf, analysis_path);
-            let top = top_level_component(&namespace);
-            let path_str = path_buf.to_string_lossy();
-            let stmt_count = *file_to_stmt.get(&path_str.into_owned()).unwrap_or(&0);
-            let entry = component_stats.entry(top).or_insert((0, 0));
-            entry.0 += 1;
-            entry.1 += stmt_count;
-        }
-
         let grand_total: usize = component_stats.values().map(|&(_f, st)| st).sum();
-        if grand_total == 0 {
+        let test_total: usize = test_stats.values().sum();
+        if grand_total + test_total == 0 {
             return Err(RaffError::analysis_error(
                 "statement_count",
                 format!(
@@ -349,7 +881,21 @@ impl StatementCountRule {
         let result = StatementCountData {
             component_stats,
             grand_total,
+            test_stats,
+            test_total,
+            include_tests: args.include_tests,
             threshold,
+            items,
+            max_function_statements: args.max_function_statements,
+            files,
+            limits: SizeLimits {
+                max_component_statements: args.max_component_statements,
+                min_component_statements: args.min_component_statements,
+                max_file_statements: args.max_file_statements,
+                max_module_statements: args.max_module_statements,
+                max_gini: args.max_gini,
+            },
+            macro_heavy_files,
             analysis_path: analysis_path.to_path_buf(),
         };
 
@@ -377,11 +923,15 @@ impl StatementCountRule {
             ("File Count", "Number of .rs files within this component."),
             (
                 "Statement Count",
-                "Total number of Rust statements counted in this component.",
+                "Number of Rust statements in this component's production code.",
+            ),
+            (
+                "Test Statements",
+                "Number of Rust statements in this component's test code: #[cfg(test)] and #[test] items and files under tests/, benches/ and examples/.",
             ),
             (
                 "Percentage",
-                "This component's statement count as a percentage of the grand total. Cells are colored red if this exceeds the threshold.",
+                "This component's statement count as a percentage of the grand total. Test statements are only included with --include-tests. Cells are colored red if this exceeds the threshold.",
             ),
         ];
         let explanations_markup = html_utils::render_metric_explanation_list(&explanations_data);
@@ -397,20 +947,23 @@ impl StatementCountRule {
                         th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
                         th class="sortable-header" data-column-index="1" data-sort-type="number" { "File Count" }
                         th class="sortable-header" data-column-index="2" data-sort-type="number" { "Statement Count" }
-                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Percentage" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Test Statements" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Percentage" }
                     }
                 }
                 tbody {
                     @for (name, (file_count, st_count)) in sorted_components {
-                        @let percentage = st_count
+                        @let percentage = data
+                            .measured_statements(name, *st_count)
                             .checked_mul(100)
-                            .and_then(|count| count.checked_div(data.grand_total))
+                            .and_then(|count| count.checked_div(data.measured_total()))
                             .unwrap_or(0);
                         @let percentage_style = html_utils::get_cell_style(percentage as f64, data.threshold as f64, data.threshold as f64, false);
                         tr {
                             td { (name) }
                             td { (file_count) }
                             td { (st_count) }
+                            td { (data.test_stats.get(name).copied().unwrap_or(0)) }
                             td style=(percentage_style) { (format!("{}%", percentage)) }
                         }
                     }
@@ -422,12 +975,30 @@ impl StatementCountRule {
             p {
                 b { "Grand Total Statements: " (data.grand_total) }
             }
-            @let any_over_threshold = data.component_stats.values().any(|&(_file_count, st_count)| {
-                if data.grand_total == 0 { return false; }
-                let percentage = (st_count * 100) / data.grand_total;
-                percentage > data.threshold
-            });
-            @if any_over_threshold {
+            p {
+                "Test Statements: " (data.test_total)
+                @if !data.include_tests {
+                    " (not included in thresholds)"
+                }
+            }
+            p {
+                "Balance (Gini coefficient): " (format!("{:.2}", data.gini()))
+                @if let Some(max) = data.limits.max_gini {
+                    (format!(" (limit {:.2})", max))
+                }
+            }
+            @let violations = data.size_violations();
+            @if !violations.is_empty() {
+                p style="color: red;" {
+                    b { "Size limits breached:" }
+                }
+                ul {
+                    @for violation in &violations {
+                        li { (violation.message) }
+                    }
+                }
+            }
+            @if data.any_component_over_threshold() {
                 p style="color: red;" {
                     b { "Warning: At least one component exceeds the " (data.threshold) "% threshold." }
                 }
@@ -438,18 +1009,154 @@ impl StatementCountRule {
             }
         };
 
+        let macro_markup = html! {
+            @if !data.macro_heavy_files.is_empty() {
+                h2 { "Macro-heavy Files" }
+                table class="sortable-table" {
+                    caption { "Files where most statements come from macro bodies." }
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "File" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Component" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Statements" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "From Macros" }
+                        }
+                    }
+                    tbody {
+                        @for file in &data.macro_heavy_files {
+                            tr {
+                                td { (file.file) }
+                                td { (file.component) }
+                                td { (file.statements) }
+                                td { (file.macro_statements) }
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
         Ok(html! {
             (explanations_markup)
             (table_markup)
             (summary_markup)
+            (macro_markup)
         })
     }
+
+    /// Renders the `top` largest functions as a sortable table. Functions over
+    /// the per-function limit are highlighted.
+    pub fn render_item_html_body(&self, data: &StatementCountData, top: usize) -> Markup {
+        let limit = data.max_function_statements.unwrap_or(usize::MAX);
+        html! {
+            h2 { "Largest Functions" }
+            table class="sortable-table" {
+                caption {
+                    (format!("Top {} functions, methods and closures by statement count.", top))
+                    @if let Some(max) = data.max_function_statements {
+                        (format!(" Function limit: {} statements.", max))
+                    }
+                }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Item" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="string" { "Kind" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="string" { "Component" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="string" { "Location" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Statements" }
+                    }
+                }
+                tbody {
+                    @for entry in data.largest_functions(top) {
+                        @let item = &entry.item;
+                        @let style = if item.kind.is_function() {
+                            html_utils::get_cell_style(item.statements as f64, limit as f64, limit as f64, false)
+                        } else {
+                            String::new()
+                        };
+                        tr {
+                            td { (item.name) }
+                            td { (item.kind) }
+                            td { (entry.component) }
+                            td { (format!("{}:{}-{}", entry.file, item.start_line, item.end_line)) }
+                            td style=(style) { (item.statements) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` for files in Cargo's test, bench and example target
+/// directories, that is a `tests`, `benches` or `examples` directory directly
+/// under a crate root. `path` is relative to `analysis_path`.
+pub(crate) fn is_test_target(analysis_path: &Path, path: &Path) -> bool {
+    path.ancestors().skip(1).any(|dir| {
+        matches!(
+            dir.file_name().and_then(|name| name.to_str()),
+            Some("tests" | "benches" | "examples")
+        ) && analysis_path
+            .join(dir.parent().unwrap_or(Path::new("")))
+            .join("Cargo.toml")
+            .is_file()
+    })
+}
+
+/// Returns the directory holding the out-of-line child modules of the module
+/// defined in `file`: its own directory for `lib.rs`, `main.rs` and `mod.rs`,
+/// and a directory named after the file otherwise.
+pub(crate) fn child_module_dir(file: &Path) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    match file.file_stem().and_then(|stem| stem.to_str()) {
+        Some("lib" | "main" | "mod") | None => parent.to_path_buf(),
+        Some(stem) => parent.join(stem),
+    }
+}
+
+/// Prints macro-heavy files with their total and macro statement counts.
+fn print_macro_heavy_table(files: &[MacroHeavyFile]) {
+    let mut table = Table::new();
+    table.set_format(crate::table_utils::get_default_table_format());
+    table.set_titles(row!["File", "Component", "Statements", "From Macros"]);
+    for file in files {
+        table.add_row(row![
+            file.file,
+            file.component,
+            r -> file.statements,
+            r -> format!(
+                "{} ({}%)",
+                file.macro_statements,
+                file.macro_statements * 100 / file.statements.max(1)
+            )
+        ]);
+    }
+    table.printstd();
+}
+
+/// Prints items as a table of name, kind, location and statement count.
+fn print_item_table<'a>(items: impl Iterator<Item = &'a ItemStatementCount>) {
+    let mut table = Table::new();
+    table.set_format(crate::table_utils::get_default_table_format());
+    table.set_titles(row!["Item", "Kind", "Location", "Statements"]);
+    for entry in items {
+        let item = &entry.item;
+        table.add_row(row![
+            item.name,
+            item.kind,
+            format!("{}:{}-{}", entry.file, item.start_line, item.end_line),
+            r -> item.statements
+        ]);
+    }
+    table.printstd();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{StatementCountArgs, StatementCountOutputFormat};
+    use crate::cli::{
+        ComponentMode, StatementCountArgs, StatementCountGranularity, StatementCountOutputFormat,
+    };
     use std::fs;
     use std::path::PathBuf;
     use tempfile::TempDir;
@@ -523,13 +1230,41 @@ pub fn func_b() {
         StatementCountArgs {
             path,
             threshold: 10,
+            components: ComponentMode::Directory,
+            granularity: StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         }
     }
 
+    #[test]
+    fn test_is_test_target_requires_a_crate_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("Cargo.toml"), "").unwrap();
+        fs::create_dir_all(root.join("crates/beta")).unwrap();
+        fs::write(root.join("crates/beta/Cargo.toml"), "").unwrap();
+
+        assert!(is_test_target(root, Path::new("tests/it.rs")));
+        assert!(is_test_target(root, Path::new("examples/demo/main.rs")));
+        assert!(is_test_target(root, Path::new("crates/beta/benches/b.rs")));
+        assert!(!is_test_target(root, Path::new("src/examples/mod.rs")));
+        assert!(!is_test_target(root, Path::new("src/tests.rs")));
+        assert!(!is_test_target(root, Path::new("crates/tests/lib.rs")));
+    }
+
     #[test]
     fn test_statement_count_rule_new_creates_instance() {
         let rule = StatementCountRule::new();
@@ -945,10 +1680,22 @@ pub fn func_b() {
         let config = StatementCountArgs {
             path: PathBuf::from("."),
             threshold: 10,
+            components: ComponentMode::Directory,
+            granularity: StatementCountGranularity::Component,
+            top: 20,
+            max_function_statements: None,
+            include_tests: false,
+            parse_macros: false,
+            max_component_statements: None,
+            min_component_statements: None,
+            max_file_statements: None,
+            max_module_statements: None,
+            max_gini: None,
             output: StatementCountOutputFormat::Table,
             ci_output: None,
             output_file: None,
             staged: false,
+            component_definitions: Default::default(),
         };
 
         // Verify Data type is StatementCountData
@@ -1090,4 +1837,274 @@ pub fn func_b() {
             "error message should mention exceeding threshold"
         );
     }
+
+    #[test]
+    fn test_analyze_records_items_largest_first() {
+        let temp_dir = create_test_directory();
+        let rule = StatementCountRule::new();
+        let args = create_test_args(temp_dir.path().to_path_buf());
+
+        let data = rule.analyze(&args).expect("analyze should succeed");
+
+        let largest = &data.items[0];
+        assert_eq!(largest.item.name, "main");
+        assert_eq!(largest.item.statements, 3);
+        assert_eq!(largest.file, "src/main.rs");
+        assert_eq!(largest.component, "src");
+        assert_eq!((largest.item.start_line, largest.item.end_line), (2, 6));
+        assert_eq!(data.items.len(), 3);
+    }
+
+    #[test]
+    fn test_to_findings_reports_functions_over_limit_with_location() {
+        let temp_dir = create_test_directory();
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(temp_dir.path().to_path_buf());
+        args.threshold = 100;
+        args.max_function_statements = Some(2);
+
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        let findings = data.to_findings();
+
+        assert_eq!(findings.len(), 1, "only main exceeds 2 statements");
+        assert!(findings[0].message.contains("'main' has 3 statements"));
+        let location = findings[0].location.as_ref().expect("should be located");
+        assert_eq!(location.uri, "src/main.rs");
+        assert_eq!(location.start_line, Some(2));
+        assert_eq!(location.end_line, Some(6));
+
+        let error = rule.run(&args).expect_err("run should fail over the limit");
+        assert!(error.to_string().contains("exceed 2 statements"));
+
+        args.max_function_statements = Some(3);
+        assert!(
+            rule.run(&args).is_ok(),
+            "a function at the limit is allowed"
+        );
+    }
+
+    #[test]
+    fn test_render_item_html_body_lists_items() {
+        let temp_dir = create_test_directory();
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(temp_dir.path().to_path_buf());
+        args.granularity = StatementCountGranularity::Item;
+        args.threshold = 100;
+
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        let html_string = rule.render_item_html_body(&data, 2).into_string();
+
+        assert!(html_string.contains("Largest Functions"));
+        assert!(html_string.contains("src/main.rs:2-6"));
+        assert_eq!(
+            html_string.matches("<tr>").count(),
+            3,
+            "header and two items"
+        );
+        assert!(rule.run(&args).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_separates_test_code_from_production() {
+        let temp_dir = create_test_directory();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("src/lib.rs"),
+            r#"
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+mod inline_tests {
+    #[test]
+    fn adds() {
+        let sum = super::add(1, 2);
+        assert_eq!(sum, 3);
+    }
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/tests.rs"),
+            "fn helper() {\n    let a = 1;\n    let b = 2;\n}\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("tests")).unwrap();
+        fs::write(
+            root.join("tests/integration.rs"),
+            "#[test]\nfn it_works() {\n    let x = 1;\n}\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(
+            root.join("other/mod.rs"),
+            "fn f() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n    let d = 4;\n}\n",
+        )
+        .unwrap();
+
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(root.to_path_buf());
+        args.threshold = 55;
+
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        // main.rs has 3 statements and lib.rs has 1 outside its test module
+        assert_eq!(data.component_stats["src"], (3, 4));
+        assert_eq!(data.test_stats["src"], 4);
+        assert_eq!(data.component_stats["tests"], (1, 0));
+        assert_eq!(data.test_stats["tests"], 1);
+        assert_eq!(data.grand_total, 8);
+        assert!(
+            data.largest_functions(10).all(|entry| !entry.item.is_test),
+            "test functions are not listed by default"
+        );
+        assert!(rule.run(&args).is_ok(), "src holds 4 of 8 statements");
+
+        args.include_tests = true;
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        assert_eq!(data.measured_total(), 13);
+        assert!(data.largest_functions(10).any(|entry| entry.item.is_test));
+        assert!(rule.run(&args).is_err(), "src holds 8 of 13 statements");
+    }
+
+    #[test]
+    fn test_analyze_counts_macro_bodies_and_lists_macro_heavy_files() {
+        let temp_dir = create_test_directory();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("views")).unwrap();
+        fs::write(
+            root.join("views/page.rs"),
+            r#"
+pub fn page() -> Markup {
+    html! {
+        div {
+            h1 { "Title" }
+            p { "Body" }
+        }
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(root.to_path_buf());
+        args.threshold = 100;
+
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        assert_eq!(data.component_stats["views"], (1, 1));
+        assert!(data.macro_heavy_files.is_empty());
+
+        args.parse_macros = true;
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        assert_eq!(data.component_stats["views"], (1, 4));
+        assert_eq!(data.macro_heavy_files.len(), 1);
+        let heavy = &data.macro_heavy_files[0];
+        assert_eq!(heavy.file, "views/page.rs");
+        assert_eq!((heavy.statements, heavy.macro_statements), (4, 3));
+
+        let html_string = rule
+            .render_statement_count_html_body(&data)
+            .unwrap()
+            .into_string();
+        assert!(html_string.contains("Macro-heavy Files"));
+    }
+
+    #[test]
+    fn test_size_violations_apply_absolute_limits_and_gini_ceiling() {
+        let temp_dir = create_multi_component_test_directory();
+        fs::write(
+            temp_dir.path().join("component_a/extra.rs"),
+            "mod inner {\n    fn f() {\n        let a = 1;\n        let b = 2;\n        let c = 3;\n    }\n}\n",
+        )
+        .unwrap();
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(temp_dir.path().to_path_buf());
+        args.threshold = 100;
+
+        // component_a has 5 statements and component_b has 4
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        assert!(data.size_violations().is_empty());
+        assert!((data.gini() - 1.0 / 18.0).abs() < 1e-9);
+        assert!(rule.run(&args).is_ok());
+
+        args.max_component_statements = Some(4);
+        args.min_component_statements = Some(5);
+        args.max_file_statements = Some(3);
+        args.max_module_statements = Some(2);
+        args.max_gini = Some(0.05);
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        let fingerprints: Vec<_> = data
+            .size_violations()
+            .into_iter()
+            .map(|violation| violation.fingerprint)
+            .collect();
+        assert_eq!(
+            fingerprints,
+            vec![
+                "statement-count:component-size:component_a:4",
+                "statement-count:anemic:component_b:5",
+                "statement-count:file-size:component_b/mod.rs:3",
+                "statement-count:module-size:component_a/extra.rs:2",
+                "statement-count:module-size:component_a/mod.rs:2",
+                "statement-count:module-size:component_b/mod.rs:2",
+                "statement-count:module-size:component_a/extra.rs:inner:2",
+                "statement-count:gini:0.05",
+            ]
+        );
+        assert_eq!(data.to_findings().len(), 8);
+        let error = rule
+            .run(&args)
+            .expect_err("run should fail on breached limits");
+        assert!(error.to_string().contains("8 size limit(s) breached"));
+    }
+
+    #[test]
+    fn test_module_limit_counts_file_modules_and_their_submodules() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir_all(src.join("parser")).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "mod parser;\nfn f() {\n    let a = 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("parser.rs"),
+            "mod lexer;\nfn parse() {\n    let a = 1;\n    let b = 2;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("parser/lexer.rs"),
+            "fn lex() {\n    let a = 1;\n    let b = 2;\n}\n",
+        )
+        .unwrap();
+        let rule = StatementCountRule::new();
+        let mut args = create_test_args(temp_dir.path().to_path_buf());
+        args.threshold = 100;
+        args.max_module_statements = Some(3);
+
+        // `parser` holds 2 statements itself and 2 in `parser::lexer`
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        let violations = data.size_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].fingerprint,
+            "statement-count:module-size:src/parser.rs:3"
+        );
+        assert!(violations[0].message.contains("has 4 statements"));
+    }
+
+    #[test]
+    fn test_gini_is_zero_for_single_or_equal_components() {
+        let temp_dir = create_test_directory();
+        let rule = StatementCountRule::new();
+        let args = create_test_args(temp_dir.path().to_path_buf());
+        let data = rule.analyze(&args).expect("analyze should succeed");
+        assert_eq!(data.gini(), 0.0);
+    }
 }
@@ -0,0 +1,1197 @@
+//! Unsafe Code Rule
+//!
+//! This module provides the unsafe code rule, which locates unsafe code and
+//! FFI boundaries and checks that they stay inside the modules allowed to
+//! contain them.
+//!
+//! # Overview
+//!
+//! Every `.rs` file is parsed with `syn`, and the following sites are recorded
+//! together with the module they live in:
+//!
+//! - **unsafe block**: `unsafe { .. }` expressions
+//! - **unsafe fn**: functions, methods and trait methods declared `unsafe`
+//! - **unsafe impl**: `unsafe impl` blocks
+//! - **extern block**: `extern "C" { .. }` blocks declaring foreign items
+//! - **no_mangle**: items marked `#[no_mangle]` or `#[unsafe(no_mangle)]`
+//!
+//! With `--allow-module crate::ffi --allow-module crate::sys`, every site
+//! outside those modules (and their submodules) is reported as an error.
+//! `crate` matches every crate; a crate name such as `my_crate::ffi` matches
+//! only that crate.
+//!
+//! Sites are also summarized per component as a density: sites per 1,000
+//! non-blank lines. With `--trend`, the density is measured again at the last
+//! commit of each `--period` in the history window, using today's component
+//! layout, to show whether unsafe code is spreading or being contained.
+//!
+//! Test code (`#[cfg(test)]` modules, `#[test]` functions and the `tests`,
+//! `benches` and `examples` directories) is ignored unless `--include-tests`
+//! is set.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use raff_core::unsafe_code_rule::UnsafeCodeRule;
+//! use raff_core::{
+//!     ComponentMode, ContributorReportPeriod, HistoryArgs, UnsafeCodeArgs,
+//!     UnsafeCodeOutputFormat,
+//! };
+//! use std::path::PathBuf;
+//!
+//! let rule = UnsafeCodeRule::new();
+//! let args = UnsafeCodeArgs {
+//!     path: PathBuf::from("."),
+//!     allowed_modules: vec!["crate::ffi".to_string()],
+//!     include_tests: false,
+//!     components: ComponentMode::Crate,
+//!     component_definitions: Default::default(),
+//!     trend: false,
+//!     period: ContributorReportPeriod::Month,
+//!     history: HistoryArgs::default(),
+//!     output: UnsafeCodeOutputFormat::Table,
+//!     ci_output: None,
+//!     output_file: None,
+//! };
+//!
+//! if let Err(e) = rule.run(&args) {
+//!     eprintln!("Error: {}", e);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Errors
+//!
+//! This module returns [`RaffError`] in the following cases:
+//! - The provided path does not exist or is not a directory
+//! - A file cannot be read or is not valid Rust
+//! - `--trend` is set and the path is not a Git repository, or the history
+//!   window cannot be resolved
+//! - Unsafe code is found outside the allowed modules
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::DateTime;
+use git2::{ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult};
+use maud::{Markup, html};
+use prettytable::{Table, row};
+use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Attribute, Expr, Item, ItemImpl, Signature};
+
+use crate::ci_report::{Finding, Location, Severity, ToFindings};
+use crate::cli::{CiOutputFormat, ComponentMode, UnsafeCodeArgs, UnsafeCodeOutputFormat};
+use crate::components::ComponentResolver;
+use crate::contributor_report::period_label;
+use crate::counter::item_attrs;
+use crate::error::{RaffError, Result};
+use crate::file_utils::relative_namespace;
+use crate::history::{HistoryFilter, HistoryWindow};
+use crate::html_utils::{self, MetricRanges};
+use crate::output_utils::path_uri;
+use crate::rule::Rule;
+use crate::site_visitor::{SiteKind, SiteVisitor, density, kind_summary, site_location};
+use crate::statement_count_rule::{child_module_dir, is_test_target};
+
+/// Kind of an unsafe code or FFI boundary site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsafeKind {
+    /// An `unsafe { .. }` block.
+    UnsafeBlock,
+    /// A function, method or trait method declared `unsafe`.
+    UnsafeFn,
+    /// An `unsafe impl` block.
+    UnsafeImpl,
+    /// An `extern` block declaring foreign functions or statics.
+    ExternBlock,
+    /// An item exported under its own symbol name with `#[no_mangle]`.
+    NoMangle,
+}
+
+impl std::fmt::Display for UnsafeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::UnsafeBlock => "unsafe block",
+            Self::UnsafeFn => "unsafe fn",
+            Self::UnsafeImpl => "unsafe impl",
+            Self::ExternBlock => "extern block",
+            Self::NoMangle => "no_mangle",
+        };
+        f.write_str(s)
+    }
+}
+
+/// One unsafe code or FFI boundary site.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsafeSite {
+    pub kind: UnsafeKind,
+    /// Path of the file relative to the analysis path.
+    pub path: PathBuf,
+    /// First line of the site (1-indexed).
+    pub start_line: usize,
+    /// Last line of the site (1-indexed).
+    pub end_line: usize,
+    /// Module containing the site, e.g. `my_crate::ffi::bindings`.
+    pub module: String,
+    /// Enclosing function, impl block or trait, e.g. `Buffer::as_ptr`.
+    pub item: Option<String>,
+    /// Component containing the file.
+    pub component: String,
+    /// Whether unsafe code is allowed in the module.
+    pub allowed: bool,
+}
+
+/// Unsafe sites within a single module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleUnsafe {
+    /// Path of the module.
+    pub module: String,
+    /// Component containing the module.
+    pub component: String,
+    /// Number of sites in the module.
+    pub sites: usize,
+    /// Number of sites per kind.
+    pub by_kind: BTreeMap<UnsafeKind, usize>,
+    /// Whether unsafe code is allowed in the module.
+    pub allowed: bool,
+}
+
+/// Unsafe density of a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentUnsafe {
+    /// Name of the component.
+    pub name: String,
+    /// Number of analyzed files in the component.
+    pub files: usize,
+    /// Number of non-blank lines in the analyzed files.
+    pub code_lines: usize,
+    /// Number of unsafe sites.
+    pub sites: usize,
+    /// Number of sites per kind.
+    pub by_kind: BTreeMap<UnsafeKind, usize>,
+    /// Unsafe sites per 1,000 code lines.
+    pub density: f64,
+    /// Number of sites outside the allowed modules.
+    pub violations: usize,
+}
+
+/// Unsafe density at the last commit of one period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsafeTrendPoint {
+    /// Period label, `YYYY-MM` for months or `YYYY-Qn` for quarters.
+    pub period: String,
+    /// The measured commit.
+    pub commit: String,
+    /// Commit date, `YYYY-MM-DD`.
+    pub date: String,
+    /// Number of unsafe sites at the commit.
+    pub sites: usize,
+    /// Number of non-blank lines at the commit.
+    pub code_lines: usize,
+    /// Unsafe sites per 1,000 code lines.
+    pub density: f64,
+    /// Per-component density, with the highest density first.
+    pub components: Vec<ComponentUnsafe>,
+}
+
+/// Data type for unsafe code analysis results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsafeCodeData {
+    /// Every site, sorted by path and line.
+    pub sites: Vec<UnsafeSite>,
+    /// Modules containing unsafe code, with the most sites first.
+    pub modules: Vec<ModuleUnsafe>,
+    /// Per-component density, with the highest density first.
+    pub components: Vec<ComponentUnsafe>,
+    /// Density over time, oldest period first. Empty unless `--trend` is set.
+    pub trend: Vec<UnsafeTrendPoint>,
+    /// Modules in which unsafe code is allowed; empty allows it everywhere.
+    pub allowed_modules: Vec<String>,
+    /// Number of analyzed files.
+    pub files: usize,
+    /// Number of non-blank lines across all analyzed files.
+    pub code_lines: usize,
+    /// Unsafe sites per 1,000 code lines across all analyzed files.
+    pub density: f64,
+    /// The analyzed directory.
+    pub analysis_path: PathBuf,
+}
+
+impl UnsafeCodeData {
+    /// Returns the sites outside the allowed modules.
+    pub fn violations(&self) -> impl Iterator<Item = &UnsafeSite> {
+        self.sites.iter().filter(|site| !site.allowed)
+    }
+
+    /// Returns the error to exit with when unsafe code is found outside the
+    /// allowed modules.
+    fn violations_error(&self) -> Option<RaffError> {
+        let violations = self.violations().count();
+        (violations > 0).then(|| {
+            RaffError::analysis_error(
+                "unsafe_code",
+                format!("{violations} unsafe site(s) outside the allowed modules."),
+            )
+        })
+    }
+}
+
+impl ToFindings for UnsafeCodeData {
+    fn to_findings(&self) -> Vec<Finding> {
+        self.sites
+            .iter()
+            .map(|site| {
+                let context = site
+                    .item
+                    .as_ref()
+                    .map(|item| format!(" in '{item}'"))
+                    .unwrap_or_default();
+                let (severity, message) = if site.allowed {
+                    (
+                        Severity::Note,
+                        format!("{} in module '{}'{context}", site.kind, site.module),
+                    )
+                } else {
+                    (
+                        Severity::Error,
+                        format!(
+                            "{} in module '{}'{context} is outside the allowed modules: {}",
+                            site.kind,
+                            site.module,
+                            self.allowed_modules.join(", ")
+                        ),
+                    )
+                };
+                Finding {
+                    rule_id: "unsafe-code".to_string(),
+                    rule_name: "Unsafe Code Rule".to_string(),
+                    severity,
+                    message,
+                    location: Some(Location::with_lines(
+                        path_uri(&site.path),
+                        site.start_line,
+                        site.end_line,
+                    )),
+                    related_locations: Vec::new(),
+                    help_uri: Some("https://github.com/liamwh/raff/docs/unsafe-code".to_string()),
+                    fingerprint: Some(format!(
+                        "unsafe-code:{}:{}:{}:{}",
+                        path_uri(&site.path),
+                        site.module,
+                        site.item.as_deref().unwrap_or_default(),
+                        site.kind
+                    )),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rule to locate unsafe code and FFI boundaries.
+#[derive(Debug, Default)]
+pub struct UnsafeCodeRule;
+
+impl Rule for UnsafeCodeRule {
+    type Config = UnsafeCodeArgs;
+    type Data = UnsafeCodeData;
+
+    fn name() -> &'static str {
+        "unsafe_code"
+    }
+
+    fn description() -> &'static str {
+        "Locates unsafe code and FFI boundaries per module and checks them against allowed modules"
+    }
+
+    fn run(&self, config: &Self::Config) -> Result<()> {
+        self.run_impl(config)
+    }
+
+    fn analyze(&self, config: &Self::Config) -> Result<Self::Data> {
+        self.analyze_impl(config)
+    }
+}
+
+impl UnsafeCodeRule {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Public wrapper that delegates to the Rule trait's run method
+    pub fn run(&self, args: &UnsafeCodeArgs) -> Result<()> {
+        self.run_impl(args)
+    }
+
+    /// Public wrapper that delegates to the Rule trait's analyze method
+    pub fn analyze(&self, args: &UnsafeCodeArgs) -> Result<UnsafeCodeData> {
+        self.analyze_impl(args)
+    }
+
+    fn run_impl(&self, args: &UnsafeCodeArgs) -> Result<()> {
+        let data = self.analyze(args)?;
+
+        // Check for CI output first (takes precedence)
+        if let Some(ci_format) = &args.ci_output {
+            let findings = data.to_findings();
+            let output = match ci_format {
+                CiOutputFormat::Sarif => crate::ci_report::to_sarif(&findings)?,
+                CiOutputFormat::JUnit => crate::ci_report::to_junit(&findings, "unsafe-code")?,
+            };
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+            // Allowed sites are notes; sites outside the allowed modules fail CI
+            return match data.violations_error() {
+                Some(error) => Err(error),
+                None => Ok(()),
+            };
+        }
+
+        let output = match args.output {
+            UnsafeCodeOutputFormat::Table => {
+                self.print_table(&data);
+                None
+            }
+            UnsafeCodeOutputFormat::Json => Some(serde_json::to_string_pretty(&data)?),
+            UnsafeCodeOutputFormat::Yaml => Some(serde_yaml::to_string(&data)?),
+            UnsafeCodeOutputFormat::Html => Some(html_utils::render_html_doc(
+                &format!("Unsafe Code Report: {}", data.analysis_path.display()),
+                self.render_html_body(&data),
+            )),
+        };
+        if let Some(output) = output {
+            crate::output_utils::write_output(&output, args.output_file.as_deref())?;
+        }
+        match data.violations_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, err)]
+    fn analyze_impl(&self, args: &UnsafeCodeArgs) -> Result<UnsafeCodeData> {
+        let analysis_path = &args.path;
+        if !analysis_path.exists() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Path not found",
+                analysis_path.display().to_string(),
+            ));
+        }
+        if !analysis_path.is_dir() {
+            return Err(RaffError::invalid_input_with_arg(
+                "Provided path is not a directory",
+                analysis_path.display().to_string(),
+            ));
+        }
+
+        let mut all_rs_files: Vec<PathBuf> = Vec::new();
+        crate::file_utils::collect_rs_files(analysis_path, false, &mut all_rs_files, None)?;
+        all_rs_files.sort();
+
+        let scanner = Scanner {
+            analysis_path,
+            include_tests: args.include_tests,
+            allowed_modules: &args.allowed_modules,
+            components: ComponentResolver::new(
+                analysis_path,
+                args.components,
+                &args.component_definitions,
+            )?,
+            crates: ComponentResolver::new(analysis_path, ComponentMode::Crate, &BTreeMap::new())?,
+        };
+
+        let mut sources = Vec::new();
+        for path_buf in &all_rs_files {
+            let path = path_buf
+                .strip_prefix(analysis_path)
+                .unwrap_or(path_buf)
+                .to_path_buf();
+            let content = fs::read_to_string(path_buf).map_err(|e| {
+                RaffError::io_error_with_source("read source file", path_buf.clone(), e)
+            })?;
+            sources.push((path, content));
+        }
+        let scan = scanner.scan(sources, true)?;
+        tracing::info!(
+            files = scan.files.len(),
+            sites = scan.sites.len(),
+            "Located unsafe code"
+        );
+
+        let trend = if args.trend {
+            self.trend(args, &scanner)?
+        } else {
+            Vec::new()
+        };
+
+        let code_lines = scan.files.iter().map(|file| file.code_lines).sum();
+        Ok(UnsafeCodeData {
+            modules: scan.modules(),
+            components: scan.components(),
+            trend,
+            allowed_modules: args.allowed_modules.clone(),
+            files: scan.files.len(),
+            code_lines,
+            density: density(scan.sites.len(), code_lines),
+            sites: scan.sites,
+            analysis_path: analysis_path.clone(),
+        })
+    }
+
+    /// Measures the unsafe density at the last commit of each period in the
+    /// history window, oldest period first.
+    fn trend(&self, args: &UnsafeCodeArgs, scanner: &Scanner<'_>) -> Result<Vec<UnsafeTrendPoint>> {
+        let analysis_path = args.path.canonicalize()?;
+        let repo = Repository::open(&analysis_path).map_err(|e| {
+            RaffError::git_error_with_repo(
+                format!("open Git repository: {}", e),
+                analysis_path.clone(),
+            )
+        })?;
+        let window = HistoryWindow::resolve(&repo, &args.history, false)?;
+
+        // Commits come oldest first, so the last one seen in a period wins
+        let mut samples: BTreeMap<String, (Oid, String)> = BTreeMap::new();
+        for oid in window.commits(&repo)? {
+            let commit = repo.find_commit(oid)?;
+            let Some(time) = DateTime::from_timestamp(commit.time().seconds(), 0) else {
+                continue;
+            };
+            let day = time.date_naive();
+            samples.insert(
+                period_label(day, args.period),
+                (oid, day.format("%Y-%m-%d").to_string()),
+            );
+        }
+        tracing::info!(
+            periods = samples.len(),
+            "Measuring unsafe density over time"
+        );
+
+        let mut trend = Vec::new();
+        for (period, (oid, date)) in samples {
+            let sources = rust_sources_at(&repo, oid, &window.filter)?;
+            let scan = scanner.scan(sources, false)?;
+            let code_lines = scan.files.iter().map(|file| file.code_lines).sum();
+            trend.push(UnsafeTrendPoint {
+                period,
+                commit: oid.to_string()[..8].to_string(),
+                date,
+                sites: scan.sites.len(),
+                code_lines,
+                density: density(scan.sites.len(), code_lines),
+                components: scan.components(),
+            });
+        }
+        Ok(trend)
+    }
+
+    fn print_table(&self, data: &UnsafeCodeData) {
+        println!("\nUnsafe Code Report Interpretation:");
+        println!("-----------------------------------");
+        println!(
+            "- Sites: unsafe blocks, unsafe fns, unsafe impls, extern blocks and #[no_mangle] items."
+        );
+        println!("- Per kLOC: Unsafe sites per 1,000 non-blank lines.");
+        if data.allowed_modules.is_empty() {
+            println!("- No allowed modules are configured, so unsafe code is allowed everywhere.");
+        } else {
+            println!(
+                "- Unsafe code is only allowed in: {}",
+                data.allowed_modules.join(", ")
+            );
+        }
+        println!("-----------------------------------");
+        println!(
+            "\n{} unsafe sites in {} files ({:.2} per kLOC), {} outside the allowed modules.",
+            data.sites.len(),
+            data.files,
+            data.density,
+            data.violations().count()
+        );
+
+        let mut table = Table::new();
+        table.set_format(crate::table_utils::get_default_table_format());
+        table.set_titles(row![
+            "Component",
+            "Files",
+            "Code Lines",
+            "Sites",
+            "Per kLOC",
+            "Violations",
+            "Kinds"
+        ]);
+        for component in &data.components {
+            table.add_row(row![
+                component.name,
+                r->component.files,
+                r->component.code_lines,
+                r->component.sites,
+                r->format!("{:.2}", component.density),
+                r->component.violations,
+                kind_summary(&component.by_kind)
+            ]);
+        }
+        println!("\nUnsafe Density by Component:");
+        table.printstd();
+
+        if !data.modules.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Module", "Component", "Sites", "Allowed", "Kinds"]);
+            for module in &data.modules {
+                table.add_row(row![
+                    module.module,
+                    module.component,
+                    r->module.sites,
+                    if module.allowed { "yes" } else { "no" },
+                    kind_summary(&module.by_kind)
+                ]);
+            }
+            println!("\nUnsafe Code by Module:");
+            table.printstd();
+        }
+
+        if data.violations().next().is_some() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row!["Location", "Kind", "Module", "Item"]);
+            for site in data.violations() {
+                table.add_row(row![
+                    site_location(&site.path, site.start_line, site.end_line),
+                    site.kind,
+                    site.module,
+                    site.item.as_deref().unwrap_or("-")
+                ]);
+            }
+            println!("\nUnsafe Code Outside the Allowed Modules:");
+            table.printstd();
+        }
+
+        if !data.trend.is_empty() {
+            let mut table = Table::new();
+            table.set_format(crate::table_utils::get_default_table_format());
+            table.set_titles(row![
+                "Period",
+                "Commit",
+                "Component",
+                "Sites",
+                "Code Lines",
+                "Per kLOC"
+            ]);
+            for point in &data.trend {
+                table.add_row(row![
+                    point.period,
+                    point.commit,
+                    "(all)",
+                    r->point.sites,
+                    r->point.code_lines,
+                    r->format!("{:.2}", point.density)
+                ]);
+                for component in &point.components {
+                    table.add_row(row![
+                        "",
+                        "",
+                        component.name,
+                        r->component.sites,
+                        r->component.code_lines,
+                        r->format!("{:.2}", component.density)
+                    ]);
+                }
+            }
+            println!("\nUnsafe Density over Time:");
+            table.printstd();
+        }
+    }
+
+    fn render_html_body(&self, data: &UnsafeCodeData) -> Markup {
+        let explanations = [
+            (
+                "Sites",
+                "unsafe blocks, unsafe fns, unsafe impls, extern blocks and #[no_mangle] items.",
+            ),
+            ("Per kLOC", "Unsafe sites per 1,000 non-blank lines."),
+            (
+                "Violations",
+                "Sites outside the allowed modules; none when no modules are configured.",
+            ),
+        ];
+        let densities: Vec<f64> = data.components.iter().map(|c| c.density).collect();
+        let ranges = MetricRanges::from_values(&densities, false);
+        let violations: Vec<&UnsafeSite> = data.violations().collect();
+
+        html! {
+            (html_utils::render_metric_explanation_list(&explanations))
+            @if !data.allowed_modules.is_empty() {
+                p { "Unsafe code is only allowed in: " (data.allowed_modules.join(", ")) }
+            }
+            table class="sortable-table" {
+                caption { (format!("{} unsafe sites in {} files ({:.2} per kLOC), {} outside the allowed modules", data.sites.len(), data.files, data.density, violations.len())) }
+                thead {
+                    tr {
+                        th class="sortable-header" data-column-index="0" data-sort-type="string" { "Component" }
+                        th class="sortable-header" data-column-index="1" data-sort-type="number" { "Files" }
+                        th class="sortable-header" data-column-index="2" data-sort-type="number" { "Code Lines" }
+                        th class="sortable-header" data-column-index="3" data-sort-type="number" { "Sites" }
+                        th class="sortable-header" data-column-index="4" data-sort-type="number" { "Per kLOC" }
+                        th class="sortable-header" data-column-index="5" data-sort-type="number" { "Violations" }
+                        th { "Kinds" }
+                    }
+                }
+                tbody {
+                    @for component in &data.components {
+                        tr {
+                            td { (component.name) }
+                            td { (component.files) }
+                            td { (component.code_lines) }
+                            td { (component.sites) }
+                            td style=({ranges.as_ref().map_or_else(String::new, |r| html_utils::get_metric_cell_style(component.density, r))}) { (format!("{:.2}", component.density)) }
+                            td { (component.violations) }
+                            td { (kind_summary(&component.by_kind)) }
+                        }
+                    }
+                }
+            }
+            @if !data.modules.is_empty() {
+                h2 { "Unsafe Code by Module" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Module" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Component" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="number" { "Sites" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="string" { "Allowed" }
+                            th { "Kinds" }
+                        }
+                    }
+                    tbody {
+                        @for module in &data.modules {
+                            tr {
+                                td { (module.module) }
+                                td { (module.component) }
+                                td { (module.sites) }
+                                td { (if module.allowed { "yes" } else { "no" }) }
+                                td { (kind_summary(&module.by_kind)) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !violations.is_empty() {
+                h2 { "Unsafe Code Outside the Allowed Modules" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Location" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Kind" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Module" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="string" { "Item" }
+                        }
+                    }
+                    tbody {
+                        @for site in &violations {
+                            tr {
+                                td { (site_location(&site.path, site.start_line, site.end_line)) }
+                                td { (site.kind) }
+                                td { (site.module) }
+                                td { (site.item.as_deref().unwrap_or("-")) }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !data.trend.is_empty() {
+                h2 { "Unsafe Density over Time" }
+                table class="sortable-table" {
+                    thead {
+                        tr {
+                            th class="sortable-header" data-column-index="0" data-sort-type="string" { "Period" }
+                            th class="sortable-header" data-column-index="1" data-sort-type="string" { "Commit" }
+                            th class="sortable-header" data-column-index="2" data-sort-type="string" { "Component" }
+                            th class="sortable-header" data-column-index="3" data-sort-type="number" { "Sites" }
+                            th class="sortable-header" data-column-index="4" data-sort-type="number" { "Code Lines" }
+                            th class="sortable-header" data-column-index="5" data-sort-type="number" { "Per kLOC" }
+                        }
+                    }
+                    tbody {
+                        @for point in &data.trend {
+                            @for component in &point.components {
+                                tr {
+                                    td { (point.period) }
+                                    td { (point.commit) }
+                                    td { (component.name) }
+                                    td { (component.sites) }
+                                    td { (component.code_lines) }
+                                    td { (format!("{:.2}", component.density)) }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Locates the unsafe sites in a set of source files.
+struct Scanner<'a> {
+    analysis_path: &'a Path,
+    include_tests: bool,
+    allowed_modules: &'a [String],
+    components: ComponentResolver,
+    crates: ComponentResolver,
+}
+
+/// A file that was scanned for unsafe code.
+struct ScannedFile {
+    component: String,
+    code_lines: usize,
+}
+
+/// The files and sites found by one [`Scanner::scan`].
+struct Scan {
+    files: Vec<ScannedFile>,
+    sites: Vec<UnsafeSite>,
+}
+
+impl Scanner<'_> {
+    /// Scans `sources`, given as paths relative to the analysis path and
+    /// their contents. A file that fails to parse is an error when `strict`
+    /// is set, and skipped otherwise.
+    fn scan(&self, sources: Vec<(PathBuf, String)>, strict: bool) -> Result<Scan> {
+        let mut scanned = Vec::new();
+        let mut test_module_paths = Vec::new();
+        for (path, content) in sources {
+            if !self.include_tests && is_test_target(self.analysis_path, &path) {
+                continue;
+            }
+            let ast = match syn::parse_file(&content) {
+                Ok(ast) => ast,
+                Err(e) if strict => {
+                    return Err(RaffError::parse_error_with_file(path, e.to_string()));
+                }
+                Err(e) => {
+                    tracing::debug!(path = %path.display(), error = %e, "Skipping unparsable file");
+                    continue;
+                }
+            };
+            let mut visitor = SiteVisitor::<UnsafeKind>::new(self.include_tests);
+            visitor.visit_file(&ast);
+            let module_dir = child_module_dir(&path);
+            test_module_paths.extend(
+                visitor
+                    .test_modules
+                    .iter()
+                    .map(|module| module_dir.join(module)),
+            );
+            let code_lines = content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count();
+            scanned.push((path, code_lines, visitor.sites));
+        }
+        // Files declared as `#[cfg(test)] mod name;` are test code in full
+        scanned.retain(|(path, _, _)| {
+            !test_module_paths
+                .iter()
+                .any(|module| path.starts_with(module) || *path == module.with_extension("rs"))
+        });
+
+        let mut files = Vec::new();
+        let mut sites = Vec::new();
+        for (path, code_lines, raw_sites) in scanned {
+            let component = self.components.component_of(&path);
+            let file_module = self
+                .crates
+                .module_of(&path)
+                .unwrap_or_else(|| relative_namespace(&path, Path::new("")).replace('/', "::"));
+            for raw in raw_sites {
+                let module = std::iter::once(file_module.clone())
+                    .chain(raw.modules)
+                    .collect::<Vec<_>>()
+                    .join("::");
+                sites.push(UnsafeSite {
+                    kind: raw.kind,
+                    path: path.clone(),
+                    start_line: raw.start_line,
+                    end_line: raw.end_line,
+                    allowed: is_allowed(&module, self.allowed_modules),
+                    module,
+                    item: raw.item,
+                    component: component.clone(),
+                });
+            }
+            files.push(ScannedFile {
+                component,
+                code_lines,
+            });
+        }
+        sites.sort_by(|a, b| (&a.path, a.start_line).cmp(&(&b.path, b.start_line)));
+        Ok(Scan { files, sites })
+    }
+}
+
+impl Scan {
+    /// Groups the sites by module, with the most sites first.
+    fn modules(&self) -> Vec<ModuleUnsafe> {
+        let mut modules: BTreeMap<&str, ModuleUnsafe> = BTreeMap::new();
+        for site in &self.sites {
+            let module = modules
+                .entry(site.module.as_str())
+                .or_insert_with(|| ModuleUnsafe {
+                    module: site.module.clone(),
+                    component: site.component.clone(),
+                    sites: 0,
+                    by_kind: BTreeMap::new(),
+                    allowed: site.allowed,
+                });
+            module.sites += 1;
+            *module.by_kind.entry(site.kind).or_insert(0) += 1;
+        }
+        let mut modules: Vec<ModuleUnsafe> = modules.into_values().collect();
+        modules.sort_by(|a, b| b.sites.cmp(&a.sites).then_with(|| a.module.cmp(&b.module)));
+        modules
+    }
+
+    /// Summarizes the files and sites per component, with the highest
+    /// density first.
+    fn components(&self) -> Vec<ComponentUnsafe> {
+        let mut components: BTreeMap<&str, ComponentUnsafe> = BTreeMap::new();
+        let entry = |name: &str| ComponentUnsafe {
+            name: name.to_string(),
+            files: 0,
+            code_lines: 0,
+            sites: 0,
+            by_kind: BTreeMap::new(),
+            density: 0.0,
+            violations: 0,
+        };
+        for file in &self.files {
+            let component = components
+                .entry(file.component.as_str())
+                .or_insert_with(|| entry(&file.component));
+            component.files += 1;
+            component.code_lines += file.code_lines;
+        }
+        for site in &self.sites {
+            let component = components
+                .entry(site.component.as_str())
+                .or_insert_with(|| entry(&site.component));
+            component.sites += 1;
+            *component.by_kind.entry(site.kind).or_insert(0) += 1;
+            component.violations += usize::from(!site.allowed);
+        }
+        let mut components: Vec<ComponentUnsafe> = components
+            .into_values()
+            .map(|mut component| {
+                component.density = density(component.sites, component.code_lines);
+                component
+            })
+            .collect();
+        components.sort_by(|a, b| {
+            b.density
+                .total_cmp(&a.density)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        components
+    }
+}
+
+impl SiteKind for UnsafeKind {
+    fn of_item(item: &Item) -> Vec<Self> {
+        match item {
+            Item::ForeignMod(_) => vec![UnsafeKind::ExternBlock],
+            Item::Fn(_) => Vec::new(),
+            _ if item_attrs(item).iter().any(is_no_mangle) => vec![UnsafeKind::NoMangle],
+            _ => Vec::new(),
+        }
+    }
+
+    fn of_fn(attrs: &[Attribute], sig: &Signature) -> Vec<Self> {
+        let mut kinds = Vec::new();
+        if attrs.iter().any(is_no_mangle) {
+            kinds.push(UnsafeKind::NoMangle);
+        }
+        if sig.unsafety.is_some() {
+            kinds.push(UnsafeKind::UnsafeFn);
+        }
+        kinds
+    }
+
+    fn of_impl(node: &ItemImpl) -> Option<Self> {
+        node.unsafety.map(|_| UnsafeKind::UnsafeImpl)
+    }
+
+    fn of_expr(expr: &Expr) -> Option<(Self, Span)> {
+        match expr {
+            Expr::Unsafe(node) => Some((UnsafeKind::UnsafeBlock, node.span())),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` for `#[no_mangle]` and the Rust 2024 `#[unsafe(no_mangle)]`.
+fn is_no_mangle(attr: &Attribute) -> bool {
+    let path = attr.path();
+    path.is_ident("no_mangle")
+        || (path.is_ident("unsafe")
+            && attr
+                .parse_args::<syn::Path>()
+                .is_ok_and(|inner| inner.is_ident("no_mangle")))
+}
+
+/// Returns `true` if `module` is one of `allowed_modules` or nested in one.
+/// An empty list allows every module.
+fn is_allowed(module: &str, allowed_modules: &[String]) -> bool {
+    if allowed_modules.is_empty() {
+        return true;
+    }
+    allowed_modules
+        .iter()
+        .any(|pattern| module_matches(module, pattern))
+}
+
+/// Returns `true` if `module` is `pattern` or nested in it. A pattern
+/// starting with `crate` matches that module in every crate.
+pub(crate) fn module_matches(module: &str, pattern: &str) -> bool {
+    let (krate, in_crate) = module.split_once("::").unwrap_or((module, ""));
+    let pattern = pattern.replace('-', "_");
+    let (pattern_crate, pattern_module) =
+        pattern.split_once("::").unwrap_or((pattern.as_str(), ""));
+    (pattern_crate == "crate" || pattern_crate == krate)
+        && (pattern_module.is_empty()
+            || in_crate == pattern_module
+            || in_crate.starts_with(&format!("{pattern_module}::")))
+}
+
+/// Reads the `.rs` files at `commit_id` that pass `filter`, as paths
+/// relative to the repository root and their contents.
+fn rust_sources_at(
+    repo: &Repository,
+    commit_id: Oid,
+    filter: &HistoryFilter,
+) -> Result<Vec<(PathBuf, String)>> {
+    let tree = repo.find_commit(commit_id)?.tree()?;
+    let mut blobs = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        let name = entry.name().unwrap_or_default();
+        if entry.kind() == Some(ObjectType::Blob) && name.ends_with(".rs") {
+            let path = Path::new(dir).join(name);
+            if filter.includes_path(&path) {
+                blobs.push((path, entry.id()));
+            }
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(|e| RaffError::git_error(format!("walk tree of commit {}: {}", commit_id, e)))?;
+
+    let mut sources = Vec::new();
+    for (path, id) in blobs {
+        let blob = repo.find_blob(id)?;
+        match std::str::from_utf8(blob.content()) {
+            Ok(content) => sources.push((path, content.to_string())),
+            Err(_) => tracing::debug!(path = %path.display(), "Skipping non-UTF-8 file"),
+        }
+    }
+    sources.sort();
+    Ok(sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{ContributorReportPeriod, HistoryArgs};
+    use git2::{Signature, Time};
+    use tempfile::TempDir;
+
+    const FFI: &str = "extern \"C\" {\n    fn abs(x: i32) -> i32;\n}\n\n#[unsafe(no_mangle)]\npub extern \"C\" fn exported() {}\n\npub fn call(x: i32) -> i32 {\n    unsafe { abs(x) }\n}\n";
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn args(path: &Path, allowed_modules: &[&str]) -> UnsafeCodeArgs {
+        UnsafeCodeArgs {
+            path: path.to_path_buf(),
+            allowed_modules: allowed_modules.iter().map(|m| m.to_string()).collect(),
+            include_tests: false,
+            components: ComponentMode::Crate,
+            component_definitions: BTreeMap::new(),
+            trend: false,
+            period: ContributorReportPeriod::Month,
+            history: HistoryArgs::default(),
+            output: UnsafeCodeOutputFormat::Table,
+            ci_output: None,
+            output_file: None,
+        }
+    }
+
+    fn write_crate(dir: &Path) {
+        write(dir, "Cargo.toml", "[package]\nname = \"native-io\"\n");
+        write(dir, "src/lib.rs", "pub mod ffi;\npub mod buffer;\n");
+        write(dir, "src/ffi.rs", FFI);
+        write(
+            dir,
+            "src/buffer.rs",
+            "pub struct Buffer(*mut u8);\n\nunsafe impl Send for Buffer {}\n\nimpl Buffer {\n    pub unsafe fn get(&self) -> u8 {\n        unsafe { *self.0 }\n    }\n}\n\n#[cfg(test)]\nmod tests {\n    fn peek() {\n        unsafe {}\n    }\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_visitor_records_every_kind_with_its_item() {
+        let source = format!(
+            "{FFI}\nmod inner {{\n    pub unsafe trait Raw {{\n        unsafe fn raw(&self);\n    }}\n}}\n#[test]\nfn ignored() {{ unsafe {{}} }}\n#[cfg(test)]\nmod tests;\n"
+        );
+        let mut visitor = SiteVisitor::<UnsafeKind>::new(false);
+        visitor.visit_file(&syn::parse_file(&source).unwrap());
+
+        let sites: Vec<(UnsafeKind, usize, Option<&str>)> = visitor
+            .sites
+            .iter()
+            .map(|site| (site.kind, site.start_line, site.item.as_deref()))
+            .collect();
+        assert_eq!(
+            sites,
+            vec![
+                (UnsafeKind::ExternBlock, 1, None),
+                (UnsafeKind::NoMangle, 5, Some("exported")),
+                (UnsafeKind::UnsafeBlock, 9, Some("call")),
+                (UnsafeKind::UnsafeFn, 14, Some("Raw::raw")),
+            ]
+        );
+        assert_eq!(visitor.sites[3].modules, vec!["inner"]);
+        assert_eq!(visitor.test_modules, vec!["tests"]);
+    }
+
+    #[test]
+    fn test_is_allowed_matches_module_prefixes() {
+        let allowed = vec!["crate::ffi".to_string(), "native-io::sys".to_string()];
+        assert!(is_allowed("app::ffi", &allowed));
+        assert!(is_allowed("app::ffi::bindings", &allowed));
+        assert!(!is_allowed("app::ffinance", &allowed));
+        assert!(is_allowed("native_io::sys", &allowed));
+        assert!(!is_allowed("app::sys", &allowed));
+        assert!(!is_allowed("app", &allowed));
+        assert!(is_allowed("app", &[]));
+    }
+
+    #[test]
+    fn test_analyze_reports_sites_outside_allowed_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        write_crate(temp_dir.path());
+
+        let data = UnsafeCodeRule::new()
+            .analyze(&args(temp_dir.path(), &["crate::ffi"]))
+            .unwrap();
+
+        assert_eq!(data.sites.len(), 6);
+        let violations: Vec<(UnsafeKind, &str, Option<&str>)> = data
+            .violations()
+            .map(|site| (site.kind, site.module.as_str(), site.item.as_deref()))
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                (UnsafeKind::UnsafeImpl, "native_io::buffer", Some("Buffer")),
+                (
+                    UnsafeKind::UnsafeFn,
+                    "native_io::buffer",
+                    Some("Buffer::get")
+                ),
+                (
+                    UnsafeKind::UnsafeBlock,
+                    "native_io::buffer",
+                    Some("Buffer::get")
+                ),
+            ]
+        );
+        assert_eq!(data.modules[0].module, "native_io::buffer");
+        assert!(!data.modules[0].allowed);
+        assert!(data.modules[1].allowed);
+
+        assert_eq!(data.components.len(), 1);
+        let component = &data.components[0];
+        assert_eq!((component.files, component.sites), (3, 6));
+        assert_eq!(component.violations, 3);
+        assert_eq!(component.density, density(6, component.code_lines));
+
+        let findings = data.to_findings();
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        assert_eq!(errors, 3);
+        assert!(data.violations_error().is_some());
+
+        let data = UnsafeCodeRule::new()
+            .analyze(&args(temp_dir.path(), &[]))
+            .unwrap();
+        assert!(data.violations_error().is_none());
+    }
+
+    #[test]
+    fn test_trend_measures_the_last_commit_of_each_period() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let repo = Repository::init(dir).unwrap();
+        let commit = |message: &str, time: i64| {
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let signature = Signature::new("Dev", "dev@example.com", &Time::new(time, 0)).unwrap();
+            let parents: Vec<git2::Commit<'_>> = repo
+                .head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap();
+        };
+
+        write(dir, "Cargo.toml", "[package]\nname = \"native-io\"\n");
+        write(dir, "src/lib.rs", "pub mod ffi;\n");
+        write(dir, "src/ffi.rs", FFI);
+        // 2024-01-15 and 2024-01-20, then 2024-03-10
+        commit("Add FFI", 1_705_312_800);
+        write(dir, "src/lib.rs", "pub mod ffi;\npub fn safe() {}\n");
+        commit("Add safe fn", 1_705_744_800);
+        write(
+            dir,
+            "src/lib.rs",
+            "pub mod ffi;\npub fn safe() {\n    unsafe {}\n}\n",
+        );
+        commit("Add unsafe block", 1_710_064_800);
+
+        let mut args = args(dir, &[]);
+        args.trend = true;
+        let data = UnsafeCodeRule::new().analyze(&args).unwrap();
+
+        let trend: Vec<(&str, usize)> = data
+            .trend
+            .iter()
+            .map(|point| (point.period.as_str(), point.sites))
+            .collect();
+        assert_eq!(trend, vec![("2024-01", 3), ("2024-03", 4)]);
+        assert_eq!(data.trend[0].date, "2024-01-20");
+        assert_eq!(data.trend[1].components[0].name, "native-io");
+    }
+}
@@ -31,19 +31,23 @@
 //! ```no_run
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! use raff_core::volatility_rule::VolatilityRule;
-//! use raff_core::{VolatilityArgs, VolatilityOutputFormat};
+//! use raff_core::{HistoryArgs, VolatilityArgs, VolatilityOutputFormat};
 //! use std::path::PathBuf;
 //!
 //! let rule = VolatilityRule::new();
 //! let args = VolatilityArgs {
 //!     path: PathBuf::from("."),
 //!     alpha: 0.5,
-//!     since: Some("2023-01-01".to_string()),
+//!     history: HistoryArgs {
+//!         since: Some("2023-01-01".to_string()),
+//!         ..HistoryArgs::default()
+//!     },
 //!     normalize: true,
 //!     output: VolatilityOutputFormat::Table,
 //!     skip_merges: false,
 //!     ci_output: None,
 //!     output_file: None,
+//!     component_definitions: Default::default(),
 //! };
 //!
 //! if let Err(e) = rule.run(&args) {
@@ -76,8 +80,8 @@
 //! - Git operations fail (e.g., corrupted repository)
 
 use bincode;
-use chrono::{DateTime, NaiveDate, TimeZone, Utc}; // For parsing --since date
-use git2::{DiffOptions, Repository, Sort, TreeWalkMode, TreeWalkResult};
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{Repository, TreeWalkMode, TreeWalkResult};
 use maud::{Markup, html};
 use prettytable::{Cell, Row, Table, format}; // Added for table output
 use serde::{Deserialize, Serialize}; // Added for custom output struct
@@ -86,14 +90,16 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{BufRead, BufReader, Write}; // For reading files line by line in LoC calculation and for writing output files
 use std::path::{Path, PathBuf};
-use toml::Value as TomlValue;
 use tracing::instrument; // Added import for tracing
 use walkdir::WalkDir; // For recursively finding Cargo.toml files // For parsing Cargo.toml
 
 use crate::cache::{CacheEntry, CacheKey, CacheManager};
 use crate::ci_report::{Finding, Severity, ToFindings};
-use crate::cli::{CiOutputFormat, VolatilityArgs, VolatilityOutputFormat}; // Ensure VolatilityOutputFormat is imported
+use crate::cli::{CiOutputFormat, ComponentMode, VolatilityArgs, VolatilityOutputFormat}; // Ensure VolatilityOutputFormat is imported
+use crate::commit_stats::CommitStatsStore;
+use crate::components::{ComponentResolver, discover_crates};
 use crate::error::{RaffError, Result};
+use crate::history::HistoryWindow;
 use crate::html_utils; // Import the new HTML utilities
 use crate::rule::Rule;
 
@@ -133,7 +139,7 @@ pub type CrateStatsMap = HashMap<String, CrateStats>;
 
 /// Cache version for volatility data.
 /// Increment this when the serialization format changes to invalidate old cache entries.
-const VOLATILITY_CACHE_VERSION: &str = "2";
+const VOLATILITY_CACHE_VERSION: &str = "3";
 
 /// Rule to calculate code volatility for each crate in a Git repository.
 #[derive(Debug, Default)]
@@ -213,6 +219,7 @@ impl ToFindings for VolatilityData {
                         }
                     ),
                     location: None, // Volatility is crate-level, no specific file location
+                    related_locations: Vec::new(),
                     help_uri: Some(
                         "https://github.com/liamwh/raff/docs/volatility".to_string(),
                     ),
@@ -262,8 +269,8 @@ impl VolatilityRule {
     }
 
     /// Step 2 & 3: Identify crates and initialize their statistics.
-    /// Scans the given repository path for `Cargo.toml` files, extracts crate names,
-    /// and initializes their statistics.
+    /// Discovers the crates under the given repository path (see
+    /// [`crate::components::discover_crates`]) and initializes their statistics.
     ///
     /// # Arguments
     /// * `analysis_path_canonical` - The root path of the repository to scan.
@@ -271,85 +278,35 @@ impl VolatilityRule {
     /// # Returns
     /// A `Result` containing a map from crate name to its initialized `CrateStats`,
     /// or an error if discovery or parsing fails.
-    fn discover_crates_and_init_stats(
+    pub(crate) fn discover_crates_and_init_stats(
         &self,
         analysis_path_canonical: &Path,
     ) -> Result<CrateStatsMap> {
-        let mut crate_stats_map = CrateStatsMap::new();
         tracing::debug!(
-            "Discovering crates by finding Cargo.toml files in {}",
+            "Discovering crates in {}",
             analysis_path_canonical.display()
         );
 
-        for entry in WalkDir::new(analysis_path_canonical)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_name().to_string_lossy() == "Cargo.toml")
-        {
-            let cargo_toml_path_abs = entry.path();
-            let crate_root_abs = match cargo_toml_path_abs.parent() {
-                Some(p) => p.to_path_buf(),
-                None => {
-                    tracing::warn!(
-                        path = %cargo_toml_path_abs.display(),
-                        "Cargo.toml found with no parent directory, skipping."
-                    );
-                    continue;
-                }
-            };
-
-            let crate_root_relative = crate_root_abs
-                .strip_prefix(analysis_path_canonical)
-                .map_err(|e| {
-                    RaffError::parse_error_with_file(
-                        crate_root_abs.clone(),
-                        format!("Failed to make crate root path relative: {}", e),
-                    )
-                })?
-                .to_path_buf();
-
-            let content = fs::read_to_string(cargo_toml_path_abs)?;
-
-            let toml_value = content.parse::<TomlValue>()?;
-
-            let crate_name_opt = toml_value
-                .get("package")
-                .and_then(|p| p.get("name"))
-                .and_then(|n| n.as_str())
-                .map(String::from);
-
-            if let Some(name) = crate_name_opt {
-                if crate_stats_map.contains_key(&name) {
-                    tracing::warn!(
-                        crate_name = name,
-                        new_path_relative = %crate_root_relative.display(),
-                        "Duplicate crate name found. Overwriting with new path."
-                    );
-                }
-                tracing::debug!(
-                    "  Found crate: '{}' at (relative) {}",
-                    name,
-                    crate_root_relative.display()
-                );
-                crate_stats_map.insert(
-                    name.clone(),
-                    CrateStats {
-                        root_path: crate_root_relative,
-                        commit_touch_count: 0,
-                        lines_added: 0,
-                        lines_deleted: 0,
-                        raw_score: 0.0,
-                        total_loc: None,
-                        normalized_score: None,
-                        birth_commit_time: None,
-                    },
-                );
-            } else {
-                tracing::warn!(
-                    path = %cargo_toml_path_abs.display(),
-                    "Could not extract [package].name from Cargo.toml. Skipping."
-                );
-            }
+        let mut crate_stats_map = CrateStatsMap::new();
+        for krate in discover_crates(analysis_path_canonical)? {
+            tracing::debug!(
+                "  Found crate: '{}' at (relative) {}",
+                krate.name,
+                krate.root.display()
+            );
+            crate_stats_map.insert(
+                krate.name,
+                CrateStats {
+                    root_path: krate.root,
+                    commit_touch_count: 0,
+                    lines_added: 0,
+                    lines_deleted: 0,
+                    raw_score: 0.0,
+                    total_loc: None,
+                    normalized_score: None,
+                    birth_commit_time: None,
+                },
+            );
         }
 
         if crate_stats_map.is_empty() {
@@ -367,33 +324,36 @@ impl VolatilityRule {
     /// Finds the owning crate for a given file path.
     /// The owning crate is the one whose root_path is the longest prefix of the file_path.
     /// Paths are expected to be canonicalized or consistently relative to the repo root.
-    fn find_owning_crate(
+    pub(crate) fn find_owning_crate(
         &self,
         file_path_in_repo: &Path,
         crate_stats_map: &CrateStatsMap,
     ) -> Option<(String, PathBuf)> {
-        let mut longest_match: Option<(String, PathBuf)> = None;
-        let mut max_depth = 0;
+        let mut longest_match: Option<(usize, String, PathBuf)> = None;
 
         for (name, stats) in crate_stats_map {
             if file_path_in_repo.starts_with(&stats.root_path) {
+                // A crate at the repository root has depth 0 and must still match.
                 let depth = stats.root_path.components().count();
-                if depth > max_depth {
-                    max_depth = depth;
-                    longest_match = Some((name.clone(), stats.root_path.clone()));
+                if longest_match
+                    .as_ref()
+                    .is_none_or(|(max_depth, _, _)| depth > *max_depth)
+                {
+                    longest_match = Some((depth, name.clone(), stats.root_path.clone()));
                 }
             }
         }
-        longest_match
+        longest_match.map(|(_, name, root_path)| (name, root_path))
     }
 
     /// Calculates the lines of code (LoC) for a given crate directory.
     /// Only considers `.rs` files and counts non-blank lines.
-    #[tracing::instrument(level = "debug", skip(self), fields(crate_relative_path = %crate_relative_path.display()))]
+    #[tracing::instrument(level = "debug", skip(self, includes), fields(crate_relative_path = %crate_relative_path.display()))]
     fn calculate_loc_for_crate(
         &self,
         crate_relative_path: &Path,
         analysis_path_canonical: &Path,
+        includes: impl Fn(&Path) -> bool,
     ) -> Result<usize> {
         let crate_abs_path = analysis_path_canonical.join(crate_relative_path);
         tracing::debug!(path = %crate_abs_path.display(), "Calculating LoC for crate at absolute path");
@@ -402,6 +362,13 @@ impl VolatilityRule {
             .into_iter()
             .filter_map(|e| e.ok())
             .filter(|e| e.path().is_file() && e.path().extension().is_some_and(|ext| ext == "rs"))
+            .filter(|e| {
+                includes(
+                    e.path()
+                        .strip_prefix(analysis_path_canonical)
+                        .unwrap_or(e.path()),
+                )
+            })
         {
             let file_path = entry.path();
             tracing::trace!(file = %file_path.display(), "Counting LoC for file");
@@ -528,12 +495,15 @@ impl VolatilityRule {
     }
 
     /// Populates the `birth_commit_time` for each crate in the `crate_stats_map`.
-    /// This method iterates through commits from oldest to newest.
-    #[tracing::instrument(level = "debug", skip(self, repo, crate_stats_map), err)]
+    /// This method iterates through all ancestors of the window's tip, from oldest
+    /// to newest, regardless of the window's date bounds.
+    #[tracing::instrument(level = "debug", skip(self, repo, window, crate_stats_map), err)]
     fn populate_crate_birth_times(
         &self,
         repo: &Repository,
+        window: &HistoryWindow,
         crate_stats_map: &mut CrateStatsMap,
+        components: &ComponentResolver,
     ) -> Result<()> {
         tracing::info!(
             "Populating crate birth times by walking repository history (oldest first)..."
@@ -544,9 +514,7 @@ impl VolatilityRule {
             return Ok(());
         }
 
-        let mut revwalk = repo.revwalk()?;
-        revwalk.push_head()?;
-        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
+        let revwalk = window.full_revwalk(repo)?;
 
         let mut crates_needing_birth_time = crate_stats_map.len();
 
@@ -561,10 +529,16 @@ impl VolatilityRule {
             for (crate_name, stats) in crate_stats_map.iter_mut() {
                 if stats.birth_commit_time.is_none() {
                     let mut found_birth = false;
+                    let is_defined = components.defined_names().contains(&crate_name.as_str());
                     tree.walk(TreeWalkMode::PreOrder, |path_from_tree_root, entry| {
                         let entry_path_relative_to_repo = Path::new(path_from_tree_root).join(entry.name().unwrap_or_default());
 
-                        if entry_path_relative_to_repo.starts_with(&stats.root_path) {
+                        let belongs = if is_defined {
+                            components.defined_component(&entry_path_relative_to_repo) == Some(crate_name.as_str())
+                        } else {
+                            entry_path_relative_to_repo.starts_with(&stats.root_path)
+                        };
+                        if belongs {
                             stats.birth_commit_time = Some(commit_time);
                             tracing::debug!(%crate_name, commit_oid = %commit.id(), %commit_time, path_found = %entry_path_relative_to_repo.display(), "Set birth time for crate");
                             found_birth = true;
@@ -891,15 +865,7 @@ impl VolatilityRule {
             )
         })?;
 
-        // Get HEAD commit hash for cache key
-        let head_hash = repo
-            .head()
-            .and_then(|head| {
-                head.target()
-                    .ok_or_else(|| git2::Error::from_str("No HEAD target"))
-            })
-            .map(|oid| oid.to_string())
-            .ok();
+        let window = HistoryWindow::resolve(&repo, &args.history, args.skip_merges)?;
 
         // Build cache parameters from analysis arguments
         let mut cache_params = vec![
@@ -909,19 +875,18 @@ impl VolatilityRule {
             ),
             ("alpha".to_string(), args.alpha.to_string()),
             ("normalize".to_string(), args.normalize.to_string()),
+            (
+                "components".to_string(),
+                format!("{:?}", args.component_definitions),
+            ),
         ];
-        if let Some(ref since) = args.since {
-            cache_params.push(("since".to_string(), since.clone()));
-        }
-        if args.skip_merges {
-            cache_params.push(("skip_merges".to_string(), "true".to_string()));
-        }
+        cache_params.extend(window.cache_params());
 
         // Create cache manager and try to get cached result
         let cache_manager = CacheManager::new()?;
         let cache_key = CacheKey::new(
             format!("volatility:{}", analysis_path_canonical.display()),
-            head_hash,
+            Some(window.tip.to_string()),
             cache_params,
         );
 
@@ -939,123 +904,69 @@ impl VolatilityRule {
         tracing::info!(path = %analysis_path_canonical.display(), "Running volatility analysis on repository");
         tracing::debug!("Successfully opened Git repository.");
 
+        let components = ComponentResolver::new(
+            &analysis_path_canonical,
+            ComponentMode::Directory,
+            &args.component_definitions,
+        )?;
         let mut crate_stats_map = self.discover_crates_and_init_stats(&analysis_path_canonical)?;
-        self.populate_crate_birth_times(&repo, &mut crate_stats_map)?;
-
-        let since_timestamp = args.since.as_ref().map_or(Ok(0_i64), |date_str| {
-            NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                .map(|naive_date| {
-                    let naive_datetime = naive_date.and_hms_opt(0, 0, 0).expect(
-                        "Internal error: Failed to create NaiveDateTime from NaiveDate at midnight",
-                    );
-                    Utc.from_local_datetime(&naive_datetime)
-                        .single()
-                        .expect("Internal error: Failed to convert NaiveDateTime to DateTime<Utc>")
-                        .timestamp()
-                })
-                .map_err(|e| {
-                    RaffError::invalid_input_with_arg(
-                        format!(
-                            "Invalid --since date format '{}': {}. Please use YYYY-MM-DD.",
-                            date_str, e
-                        ),
-                        date_str.to_string(),
-                    )
-                })
-        })?;
-        tracing::debug!(
-            since_timestamp = since_timestamp,
-            "Processing commits since"
-        );
-
-        let mut revwalk = repo.revwalk()?;
-        revwalk.push_head()?;
-        revwalk.set_sorting(Sort::TIME | Sort::REVERSE)?;
-
+        // Files in a defined component are attributed to it instead of their crate
+        let crate_roots = crate_stats_map.clone();
+        for name in components.defined_names() {
+            if crate_roots.contains_key(name) {
+                return Err(RaffError::invalid_input_with_arg(
+                    format!("component '{name}' has the same name as a crate in the workspace"),
+                    "components",
+                ));
+            }
+            crate_stats_map.insert(
+                name.to_string(),
+                CrateStats {
+                    root_path: PathBuf::new(),
+                    commit_touch_count: 0,
+                    lines_added: 0,
+                    lines_deleted: 0,
+                    raw_score: 0.0,
+                    total_loc: None,
+                    normalized_score: None,
+                    birth_commit_time: None,
+                },
+            );
+        }
+        self.populate_crate_birth_times(&repo, &window, &mut crate_stats_map, &components)?;
+
+        let commits = window.commits(&repo)?;
+        let mut store = CommitStatsStore::open(&repo)?;
+        store.prefetch(
+            &repo,
+            &commits,
+            args.history.jobs.unwrap_or_else(num_cpus::get),
+        )?;
         let mut processed_commits = 0;
-        let mut pending_stat_updates: Vec<(String, char)> = Vec::new(); // For deferred updates
 
-        for oid_result in revwalk {
-            let oid = oid_result?;
+        for oid in commits {
             let commit = repo.find_commit(oid)?;
-            let commit_time = commit.time().seconds();
-
-            let parents: Vec<_> = commit.parents().collect();
-            if args.skip_merges && parents.len() > 1 {
-                tracing::trace!(commit_id = %oid, "Skipping merge commit.");
-                continue;
-            }
-
-            let tree = commit.tree()?;
-            let parent_tree_opt = if !parents.is_empty() {
-                parents[0].tree().ok()
-            } else {
-                None
-            };
-
-            let mut diff_opts = DiffOptions::new();
-            diff_opts.context_lines(0);
-            diff_opts.interhunk_lines(0);
-
-            let diff = repo.diff_tree_to_tree(
-                parent_tree_opt.as_ref(),
-                Some(&tree),
-                Some(&mut diff_opts),
-            )?;
-
-            if commit_time < since_timestamp {
-                tracing::trace!(commit_id = %oid, commit_date = %DateTime::from_timestamp(commit_time, 0).unwrap().format("%Y-%m-%d"), "Commit is older than --since date, skipping for volatility calculation (but was considered for birth date).");
-                continue;
-            }
-
+            let diff = store.get_or_compute(&repo, &commit)?;
             processed_commits += 1;
+
             let mut touched_crates_in_commit = HashSet::new();
-            pending_stat_updates.clear(); // Clear for each commit
-
-            diff.foreach(
-                &mut |delta, _progress| {
-                    if let Some(delta_path) =
-                        delta.new_file().path().or_else(|| delta.old_file().path())
-                    {
-                        // This immutable borrow of crate_stats_map is fine
-                        if let Some((crate_name, _)) =
-                            self.find_owning_crate(delta_path, &crate_stats_map)
-                        {
-                            touched_crates_in_commit.insert(crate_name.clone());
-                        }
-                    }
-                    true
-                },
-                None, // binary_callback
-                None, // hunk_callback
-                Some(&mut |delta, _hunk, line| {
-                    // line_callback
-                    if let Some(delta_path) =
-                        delta.new_file().path().or_else(|| delta.old_file().path())
-                    {
-                        // This immutable borrow of crate_stats_map is fine
-                        if let Some((crate_name, _)) =
-                            self.find_owning_crate(delta_path, &crate_stats_map)
-                        {
-                            // Defer mutation by pushing to pending_stat_updates
-                            pending_stat_updates.push((crate_name.clone(), line.origin()));
-                        }
-                    }
-                    true
-                }),
-            )
-            .map_err(|e| RaffError::git_error(format!("process diff lines: {}", e)))?;
-
-            // Apply pending updates for the current commit
-            for (crate_name, origin) in &pending_stat_updates {
-                // Iterate immutably here
-                if let Some(stats) = crate_stats_map.get_mut(crate_name) {
-                    match origin {
-                        '+' | '>' => stats.lines_added += 1,
-                        '-' | '<' => stats.lines_deleted += 1,
-                        _ => {}
-                    }
+            for change in diff
+                .files
+                .iter()
+                .filter(|change| window.filter.includes_path(&change.path))
+            {
+                let crate_name = match components.defined_component(&change.path) {
+                    Some(name) => name.to_string(),
+                    None => match self.find_owning_crate(&change.path, &crate_roots) {
+                        Some((crate_name, _)) => crate_name,
+                        None => continue,
+                    },
+                };
+                if let Some(stats) = crate_stats_map.get_mut(&crate_name) {
+                    stats.lines_added += change.lines_added as usize;
+                    stats.lines_deleted += change.lines_deleted as usize;
                 }
+                touched_crates_in_commit.insert(crate_name);
             }
 
             for crate_name in touched_crates_in_commit {
@@ -1064,6 +975,7 @@ impl VolatilityRule {
                 }
             }
         }
+        store.save()?;
         tracing::info!(
             count = processed_commits,
             "Finished processing commits for volatility stats."
@@ -1071,7 +983,16 @@ impl VolatilityRule {
 
         for (name, stats) in crate_stats_map.iter_mut() {
             if args.normalize {
-                match self.calculate_loc_for_crate(&stats.root_path, &analysis_path_canonical) {
+                let is_defined = components.defined_names().contains(&name.as_str());
+                let loc = self.calculate_loc_for_crate(
+                    &stats.root_path,
+                    &analysis_path_canonical,
+                    |path| match components.defined_component(path) {
+                        Some(component) => is_defined && component == name,
+                        None => !is_defined,
+                    },
+                );
+                match loc {
                     Ok(loc) => stats.total_loc = Some(loc),
                     Err(e) => {
                         tracing::warn!(
@@ -1117,7 +1038,8 @@ impl VolatilityRule {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{CiOutputFormat, VolatilityArgs, VolatilityOutputFormat};
+    use crate::cli::{CiOutputFormat, HistoryArgs, VolatilityArgs, VolatilityOutputFormat};
+    use std::collections::BTreeMap;
     use std::fs;
     use std::path::PathBuf;
     use std::process::Command;
@@ -1216,12 +1138,13 @@ fn main() {
         VolatilityArgs {
             path,
             alpha: 0.5,
-            since: None,
+            history: HistoryArgs::default(),
             normalize: false,
             output: VolatilityOutputFormat::Table,
             skip_merges: false,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         }
     }
 
@@ -1460,6 +1383,34 @@ fn main() {
         );
     }
 
+    #[test]
+    fn test_find_owning_crate_matches_root_crate() {
+        let rule = VolatilityRule::new();
+        let mut crate_stats_map = CrateStatsMap::new();
+
+        crate_stats_map.insert(
+            "root".to_string(),
+            CrateStats {
+                root_path: PathBuf::new(),
+                ..Default::default()
+            },
+        );
+        crate_stats_map.insert(
+            "crate-a".to_string(),
+            CrateStats {
+                root_path: PathBuf::from("crates/a"),
+                ..Default::default()
+            },
+        );
+
+        let result = rule.find_owning_crate(&PathBuf::from("src/lib.rs"), &crate_stats_map);
+        assert_eq!(result.map(|(name, _)| name), Some("root".to_string()));
+
+        let result =
+            rule.find_owning_crate(&PathBuf::from("crates/a/src/lib.rs"), &crate_stats_map);
+        assert_eq!(result.map(|(name, _)| name), Some("crate-a".to_string()));
+    }
+
     #[test]
     fn test_find_owning_crate_with_empty_map_returns_none() {
         let rule = VolatilityRule::new();
@@ -2050,6 +2001,18 @@ edition = "2021"
         );
     }
 
+    #[test]
+    fn test_analyze_rejects_component_named_like_a_crate() {
+        let temp_dir =
+            create_test_repo_with_crates().expect("Failed to create test repo with crates");
+        let mut args = create_test_args(temp_dir.path().to_path_buf());
+        args.component_definitions =
+            BTreeMap::from([("test-crate".to_string(), vec!["src/**".to_string()])]);
+
+        let error = VolatilityRule::new().analyze(&args).unwrap_err();
+        assert!(error.to_string().contains("'test-crate'"));
+    }
+
     #[test]
     fn test_rule_associated_types_match() {
         // This test verifies that the associated types are correctly set
@@ -2060,12 +2023,13 @@ edition = "2021"
         let config = VolatilityArgs {
             path: PathBuf::from("."),
             alpha: 0.5,
-            since: None,
+            history: HistoryArgs::default(),
             normalize: false,
             output: VolatilityOutputFormat::Table,
             skip_merges: false,
             ci_output: None,
             output_file: None,
+            component_definitions: Default::default(),
         };
 
         // Verify Data type is VolatilityData
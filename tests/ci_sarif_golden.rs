@@ -23,6 +23,7 @@ fn test_sarif_single_error_finding() {
         message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
             .to_string(),
         location: Some(Location::new("src/main.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
         fingerprint: Some("statement-count:src:20:5000".to_string()),
     }];
@@ -39,6 +40,7 @@ fn test_sarif_warning_finding() {
         severity: Severity::Warning,
         message: "Crate 'my-crate' has high volatility: raw_score=0.85 (alpha=0.01)".to_string(),
         location: None,
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/volatility".to_string()),
         fingerprint: Some("volatility:my-crate:0.01:0.85".to_string()),
     }];
@@ -56,6 +58,7 @@ fn test_sarif_note_finding() {
         message: "File 'src/lib.rs' metrics: SLOC=150, Cyclomatic Avg=2.5, Halstead Volume=4500"
             .to_string(),
         location: Some(Location::new("src/lib.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: None,
         fingerprint: Some("rca:src/lib.rs:150:4500".to_string()),
     }];
@@ -74,6 +77,7 @@ fn test_sarif_multiple_findings_same_rule() {
             message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
                 .to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:src:20:5000".to_string()),
         },
@@ -84,6 +88,7 @@ fn test_sarif_multiple_findings_same_rule() {
             message: "Component 'tests' has 3000 statements (15%), exceeding threshold of 10%"
                 .to_string(),
             location: Some(Location::new("tests/integration_test.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:tests:10:3000".to_string()),
         },
@@ -103,6 +108,7 @@ fn test_sarif_multiple_findings_different_rules() {
             message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
                 .to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:src:20:5000".to_string()),
         },
@@ -113,6 +119,7 @@ fn test_sarif_multiple_findings_different_rules() {
             message: "Crate 'my-crate' has high volatility: raw_score=0.85 (alpha=0.01)"
                 .to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/volatility".to_string()),
             fingerprint: Some("volatility:my-crate:0.01:0.85".to_string()),
         },
@@ -122,6 +129,7 @@ fn test_sarif_multiple_findings_different_rules() {
             severity: Severity::Warning,
             message: "Crate 'api' has high instability: Ce=15, Ca=5, I=0.75".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/coupling".to_string()),
             fingerprint: Some("coupling:api:15:5".to_string()),
         },
@@ -140,6 +148,7 @@ fn test_sarif_finding_with_line_range() {
         message: "Function 'process_data' has high cyclomatic complexity: 15 (threshold: 10)"
             .to_string(),
         location: Some(Location::with_lines("src/processor.rs".to_string(), 42, 89)),
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/rust-code-analysis".to_string()),
         fingerprint: Some("rca:process_data:15".to_string()),
     }];
@@ -158,6 +167,7 @@ fn test_sarif_finding_with_special_characters() {
             "Component 'src/utils/helpers' has 1000 statements (10%), exceeding threshold of 5%"
                 .to_string(),
         location: Some(Location::new("src/utils/helpers.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
         fingerprint: Some("statement-count:src/utils/helpers:5:1000".to_string()),
     }];
@@ -174,6 +184,7 @@ fn test_sarif_finding_without_fingerprint() {
         severity: Severity::Note,
         message: "Contributor 'alice@example.com' statistics: 50 commits, 5000 lines added, 2000 lines deleted".to_string(),
         location: None,
+        related_locations: Vec::new(),
         help_uri: None,
         fingerprint: None,
     }];
@@ -191,6 +202,7 @@ fn test_sarif_all_severity_levels() {
             severity: Severity::Error,
             message: "Component 'src' exceeds threshold".to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("error-fingerprint".to_string()),
         },
@@ -200,6 +212,7 @@ fn test_sarif_all_severity_levels() {
             severity: Severity::Warning,
             message: "Crate 'my-crate' has high volatility".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("warning-fingerprint".to_string()),
         },
@@ -209,6 +222,7 @@ fn test_sarif_all_severity_levels() {
             severity: Severity::Note,
             message: "File metrics collected".to_string(),
             location: Some(Location::new("src/lib.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("note-fingerprint".to_string()),
         },
@@ -24,6 +24,7 @@ fn test_cli_single_error_finding() {
         message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
             .to_string(),
         location: Some(Location::new("src/main.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
         fingerprint: Some("statement-count:src:20:5000".to_string()),
     }];
@@ -40,6 +41,7 @@ fn test_cli_warning_finding() {
         severity: Severity::Warning,
         message: "Crate 'my-crate' has high volatility: raw_score=0.85 (alpha=0.01)".to_string(),
         location: None,
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/volatility".to_string()),
         fingerprint: Some("volatility:my-crate:0.01:0.85".to_string()),
     }];
@@ -57,6 +59,7 @@ fn test_cli_note_finding() {
         message: "File 'src/lib.rs' metrics: SLOC=150, Cyclomatic Avg=2.5, Halstead Volume=4500"
             .to_string(),
         location: Some(Location::new("src/lib.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: None,
         fingerprint: Some("rca:src/lib.rs:150:4500".to_string()),
     }];
@@ -75,6 +78,7 @@ fn test_cli_multiple_findings_same_rule() {
             message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
                 .to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:src:20:5000".to_string()),
         },
@@ -85,6 +89,7 @@ fn test_cli_multiple_findings_same_rule() {
             message: "Component 'tests' has 3000 statements (15%), exceeding threshold of 10%"
                 .to_string(),
             location: Some(Location::new("tests/integration_test.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:tests:10:3000".to_string()),
         },
@@ -104,6 +109,7 @@ fn test_cli_multiple_findings_different_rules() {
             message: "Component 'src' has 5000 statements (25%), exceeding threshold of 20%"
                 .to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/statement-count".to_string()),
             fingerprint: Some("statement-count:src:20:5000".to_string()),
         },
@@ -114,6 +120,7 @@ fn test_cli_multiple_findings_different_rules() {
             message: "Crate 'my-crate' has high volatility: raw_score=0.85 (alpha=0.01)"
                 .to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/volatility".to_string()),
             fingerprint: Some("volatility:my-crate:0.01:0.85".to_string()),
         },
@@ -123,6 +130,7 @@ fn test_cli_multiple_findings_different_rules() {
             severity: Severity::Warning,
             message: "Crate 'api' has high instability: Ce=15, Ca=5, I=0.75".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: Some("https://github.com/liamwh/raff/docs/coupling".to_string()),
             fingerprint: Some("coupling:api:15:5".to_string()),
         },
@@ -141,6 +149,7 @@ fn test_cli_all_severity_levels() {
             severity: Severity::Error,
             message: "Component 'src' exceeds threshold".to_string(),
             location: Some(Location::new("src/main.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("error-fingerprint".to_string()),
         },
@@ -150,6 +159,7 @@ fn test_cli_all_severity_levels() {
             severity: Severity::Warning,
             message: "Crate 'my-crate' has high volatility".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("warning-fingerprint".to_string()),
         },
@@ -159,6 +169,7 @@ fn test_cli_all_severity_levels() {
             severity: Severity::Note,
             message: "File metrics collected".to_string(),
             location: Some(Location::new("src/lib.rs".to_string())),
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: Some("note-fingerprint".to_string()),
         },
@@ -177,6 +188,7 @@ fn test_cli_finding_with_line_range() {
         message: "Function 'process_data' has high cyclomatic complexity: 15 (threshold: 10)"
             .to_string(),
         location: Some(Location::with_lines("src/processor.rs".to_string(), 42, 89)),
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/rust-code-analysis".to_string()),
         fingerprint: Some("rca:process_data:15".to_string()),
     }];
@@ -194,6 +206,7 @@ fn test_cli_long_message_truncation() {
         severity: Severity::Warning,
         message: long_message.to_string(),
         location: None,
+        related_locations: Vec::new(),
         help_uri: None,
         fingerprint: None,
     }];
@@ -210,6 +223,7 @@ fn test_cli_finding_without_help_uri() {
         severity: Severity::Error,
         message: "Custom rule detected an issue".to_string(),
         location: Some(Location::new("src/custom.rs".to_string())),
+        related_locations: Vec::new(),
         help_uri: None,
         fingerprint: None,
     }];
@@ -226,6 +240,7 @@ fn test_cli_finding_without_location() {
         severity: Severity::Warning,
         message: "Crate 'utils' has high volatility".to_string(),
         location: None,
+        related_locations: Vec::new(),
         help_uri: Some("https://github.com/liamwh/raff/docs/volatility".to_string()),
         fingerprint: None,
     }];
@@ -243,6 +258,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Error,
             message: "First error".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
@@ -252,6 +268,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Error,
             message: "Second error".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
@@ -261,6 +278,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Warning,
             message: "First warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
@@ -270,6 +288,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Warning,
             message: "Second warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
@@ -279,6 +298,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Warning,
             message: "Third warning".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
@@ -288,6 +308,7 @@ fn test_cli_summary_counts() {
             severity: Severity::Note,
             message: "A note".to_string(),
             location: None,
+            related_locations: Vec::new(),
             help_uri: None,
             fingerprint: None,
         },
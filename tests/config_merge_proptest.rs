@@ -4,10 +4,14 @@
 //! satisfies important mathematical properties: idempotence, associativity,
 //! and that default acts as a neutral element (when merged on the left).
 
-use raff_core::config::{ContributorReportConfig, CouplingConfig, GeneralConfig};
+use raff_core::cli::PanicBudget;
 use raff_core::config::{
-    PreCommitProfile, ProfileConfig, RaffConfig, RustCodeAnalysisConfig, StatementCountConfig,
-    VolatilityConfig,
+    ApiSurfaceConfig, CodeAgeConfig, CodeownersConfig, ComponentsConfig, ContributorReportConfig,
+    CouplingConfig, DuplicationConfig, ErrorHandlingConfig, GeneralConfig, GenericsConfig,
+};
+use raff_core::config::{
+    OwnershipConfig, PreCommitProfile, ProfileConfig, RaffConfig, RustCodeAnalysisConfig,
+    StatementCountConfig, UnsafeCodeConfig, VolatilityConfig,
 };
 use raff_core::config_hierarchy::Mergeable;
 use raff_core::config_hierarchy::merge_configs;
@@ -27,11 +31,20 @@ proptest::proptest! {
     #[test]
     fn prop_merge_idempotence_for_raff_config(config in any_raff_config()) {
         let merged = merge_configs(&config, &config);
+        prop_assert_eq!(merged.components, config.components);
         prop_assert_eq!(merged.statement_count, config.statement_count);
         prop_assert_eq!(merged.volatility, config.volatility);
         prop_assert_eq!(merged.coupling, config.coupling);
         prop_assert_eq!(merged.rust_code_analysis, config.rust_code_analysis);
         prop_assert_eq!(merged.contributor_report, config.contributor_report);
+        prop_assert_eq!(merged.code_age, config.code_age);
+        prop_assert_eq!(merged.ownership, config.ownership);
+        prop_assert_eq!(merged.codeowners, config.codeowners);
+        prop_assert_eq!(merged.duplication, config.duplication);
+        prop_assert_eq!(merged.api_surface, config.api_surface);
+        prop_assert_eq!(merged.unsafe_code, config.unsafe_code);
+        prop_assert_eq!(merged.error_handling, config.error_handling);
+        prop_assert_eq!(merged.generics, config.generics);
         prop_assert_eq!(merged.profile, config.profile);
     }
 
@@ -59,11 +72,20 @@ proptest::proptest! {
     ) {
         let left = merge_configs(&merge_configs(&a, &b), &c);
         let right = merge_configs(&a, &merge_configs(&b, &c));
+        prop_assert_eq!(left.components, right.components);
         prop_assert_eq!(left.statement_count, right.statement_count);
         prop_assert_eq!(left.volatility, right.volatility);
         prop_assert_eq!(left.coupling, right.coupling);
         prop_assert_eq!(left.rust_code_analysis, right.rust_code_analysis);
         prop_assert_eq!(left.contributor_report, right.contributor_report);
+        prop_assert_eq!(left.code_age, right.code_age);
+        prop_assert_eq!(left.ownership, right.ownership);
+        prop_assert_eq!(left.codeowners, right.codeowners);
+        prop_assert_eq!(left.duplication, right.duplication);
+        prop_assert_eq!(left.api_surface, right.api_surface);
+        prop_assert_eq!(left.unsafe_code, right.unsafe_code);
+        prop_assert_eq!(left.error_handling, right.error_handling);
+        prop_assert_eq!(left.generics, right.generics);
         prop_assert_eq!(left.profile, right.profile);
     }
 
@@ -106,18 +128,81 @@ proptest::proptest! {
 use proptest::prelude::*;
 use std::path::PathBuf;
 
+/// Strategy for generating arbitrary component configs.
+fn any_components_config() -> BoxedStrategy<ComponentsConfig> {
+    (
+        prop::option::of(prop::sample::select(vec![
+            "directory".to_string(),
+            "crate".to_string(),
+            "module".to_string(),
+        ])),
+        prop::collection::btree_map(
+            prop::string::string_regex(r"[a-z]+").unwrap(),
+            prop::collection::vec(prop::string::string_regex(r"[a-z_/*]+").unwrap(), 0..3),
+            0..3,
+        ),
+    )
+        .prop_map(|(mode, define)| ComponentsConfig { mode, define })
+        .boxed()
+}
+
 /// Strategy for generating arbitrary statement count configs.
 fn any_statement_count_config() -> BoxedStrategy<StatementCountConfig> {
     (
         prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
         any::<usize>(),
+        prop::option::of(prop::sample::select(vec![
+            "component".to_string(),
+            "item".to_string(),
+        ])),
+        prop::option::of(any::<usize>()),
+        prop::option::of(any::<usize>()),
+        prop::option::of(any::<bool>()),
+        prop::option::of(any::<bool>()),
+        (
+            prop::option::of(any::<usize>()),
+            prop::option::of(any::<usize>()),
+            prop::option::of(any::<usize>()),
+            prop::option::of(any::<usize>()),
+            prop::option::of(0.0f64..1.0),
+        ),
         prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
     )
-        .prop_map(|(path, threshold, output)| StatementCountConfig {
-            path: path.map(PathBuf::from),
-            threshold,
-            output,
-        })
+        .prop_map(
+            |(
+                path,
+                threshold,
+                granularity,
+                top,
+                max_function_statements,
+                include_tests,
+                parse_macros,
+                (
+                    max_component_statements,
+                    min_component_statements,
+                    max_file_statements,
+                    max_module_statements,
+                    max_gini,
+                ),
+                output,
+            )| {
+                StatementCountConfig {
+                    path: path.map(PathBuf::from),
+                    threshold,
+                    granularity,
+                    top,
+                    max_function_statements,
+                    include_tests,
+                    parse_macros,
+                    max_component_statements,
+                    min_component_statements,
+                    max_file_statements,
+                    max_module_statements,
+                    max_gini,
+                    output,
+                }
+            },
+        )
         .boxed()
 }
 
@@ -130,15 +215,32 @@ fn any_volatility_config() -> BoxedStrategy<VolatilityConfig> {
         any::<bool>(),
         any::<bool>(),
         prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::collection::vec(
+            prop::string::string_regex(r"[a-zA-Z0-9_/\*\.]+").unwrap(),
+            0..3,
+        ),
     )
         .prop_map(
-            |(path, alpha, since, normalize, skip_merges, output)| VolatilityConfig {
-                path: path.map(PathBuf::from),
-                alpha,
-                since,
-                normalize,
-                skip_merges,
-                output,
+            |(path, alpha, since, normalize, skip_merges, output, exclude_paths)| {
+                VolatilityConfig {
+                    path: path.map(PathBuf::from),
+                    alpha,
+                    since,
+                    until: None,
+                    from: None,
+                    to: None,
+                    range: None,
+                    branch: None,
+                    normalize,
+                    skip_merges,
+                    include_paths: vec![],
+                    exclude_paths,
+                    exclude_authors: vec![],
+                    exclude_messages: vec![],
+                    ignore_revs_file: None,
+                    jobs: None,
+                    output,
+                }
             },
         )
         .boxed()
@@ -168,15 +270,55 @@ fn any_rca_config() -> BoxedStrategy<RustCodeAnalysisConfig> {
         prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
         any::<bool>(),
         prop::string::string_regex(r"[a-z]+").unwrap(),
+        prop::option::of(prop::sample::select(vec![
+            "builtin".to_string(),
+            "external".to_string(),
+        ])),
+        (
+            prop::option::of(0.0f64..1000.0),
+            prop::option::of(0.0f64..100.0),
+            prop::option::of(0.0f64..1000.0),
+            prop::option::of(0.0f64..100.0),
+            prop::option::of(0.0f64..100.0),
+            prop::option::of(any::<usize>()),
+            prop::option::of(any::<usize>()),
+        ),
     )
         .prop_map(
-            |(path, extra_flags, jobs, output, metrics, language)| RustCodeAnalysisConfig {
-                path: path.map(PathBuf::from),
+            |(
+                path,
                 extra_flags,
                 jobs,
                 output,
                 metrics,
                 language,
+                backend,
+                (
+                    max_file_cognitive,
+                    min_file_mi,
+                    max_file_abc,
+                    max_function_cyclomatic,
+                    max_function_cognitive,
+                    max_function_args,
+                    max_function_length,
+                ),
+            )| {
+                RustCodeAnalysisConfig {
+                    path: path.map(PathBuf::from),
+                    extra_flags,
+                    jobs,
+                    output,
+                    metrics,
+                    language,
+                    backend,
+                    max_file_cognitive,
+                    min_file_mi,
+                    max_file_abc,
+                    max_function_cyclomatic,
+                    max_function_cognitive,
+                    max_function_args,
+                    max_function_length,
+                }
             },
         )
         .boxed()
@@ -189,13 +331,272 @@ fn any_contributor_report_config() -> BoxedStrategy<ContributorReportConfig> {
         prop::option::of(prop::string::string_regex(r"\d{4}-\d{2}-\d{2}").unwrap()),
         any::<f64>(),
         prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::collection::vec(prop::string::string_regex(r"[a-z\*\[\]]+").unwrap(), 0..3),
+        prop::collection::btree_map(
+            prop::string::string_regex(r"[a-z]+@[a-z]+").unwrap(),
+            prop::collection::vec(prop::string::string_regex(r"[a-z]+").unwrap(), 0..3),
+            0..3,
+        ),
+        prop::option::of(prop::sample::select(vec![
+            "month".to_string(),
+            "quarter".to_string(),
+        ])),
     )
-        .prop_map(|(path, since, decay, output)| ContributorReportConfig {
-            path: path.map(PathBuf::from),
-            since,
-            decay,
-            output,
-        })
+        .prop_map(
+            |(path, since, decay, output, exclude_authors, aliases, period)| {
+                ContributorReportConfig {
+                    path: path.map(PathBuf::from),
+                    since,
+                    until: None,
+                    branch: None,
+                    decay,
+                    aliases,
+                    period,
+                    include_paths: vec![],
+                    exclude_paths: vec![],
+                    exclude_authors,
+                    exclude_messages: vec![],
+                    ignore_revs_file: None,
+                    jobs: None,
+                    output,
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary code age configs.
+fn any_code_age_config() -> BoxedStrategy<CodeAgeConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::option::of(any::<u32>()),
+        prop::option::of(any::<u32>()),
+        prop::option::of(any::<usize>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, granularity, stale_months, inactive_months, jobs, output)| CodeAgeConfig {
+                path: path.map(PathBuf::from),
+                granularity,
+                stale_months,
+                inactive_months,
+                jobs,
+                output,
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary ownership configs.
+fn any_ownership_config() -> BoxedStrategy<OwnershipConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"\d{4}-\d{2}-\d{2}").unwrap()),
+        prop::collection::vec(prop::string::string_regex(r"[a-z\*/]+").unwrap(), 0..3),
+        prop::option::of(any::<u32>()),
+        prop::option::of(any::<usize>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, granularity, source, since, exclude_paths, inactive_months, jobs, output)| {
+                OwnershipConfig {
+                    path: path.map(PathBuf::from),
+                    granularity,
+                    source,
+                    since,
+                    until: None,
+                    branch: None,
+                    include_paths: vec![],
+                    exclude_paths,
+                    exclude_authors: vec![],
+                    exclude_messages: vec![],
+                    ignore_revs_file: None,
+                    inactive_months,
+                    jobs,
+                    output,
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary codeowners configs.
+fn any_codeowners_config() -> BoxedStrategy<CodeownersConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::collection::vec(prop::string::string_regex(r"[a-z\*/]+").unwrap(), 0..3),
+        prop::option::of(any::<usize>()),
+        prop::option::of(0.0f64..100.0),
+        prop::collection::btree_map(
+            prop::string::string_regex(r"[a-z]+@[a-z]+").unwrap(),
+            prop::string::string_regex(r"@[a-z]+").unwrap(),
+            0..3,
+        ),
+    )
+        .prop_map(
+            |(path, granularity, exclude_paths, max_owners, min_share, handles)| CodeownersConfig {
+                path: path.map(PathBuf::from),
+                granularity,
+                codeowners_file: None,
+                since: None,
+                until: None,
+                branch: None,
+                include_paths: vec![],
+                exclude_paths,
+                exclude_authors: vec![],
+                exclude_messages: vec![],
+                ignore_revs_file: None,
+                max_owners,
+                min_share,
+                handles,
+                jobs: None,
+                output: None,
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary duplication configs.
+fn any_duplication_config() -> BoxedStrategy<DuplicationConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(any::<usize>()),
+        prop::option::of(any::<bool>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, min_tokens, include_tests, output)| DuplicationConfig {
+                path: path.map(PathBuf::from),
+                min_tokens,
+                include_tests,
+                output,
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary API surface configs.
+fn any_api_surface_config() -> BoxedStrategy<ApiSurfaceConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(any::<usize>()),
+        prop::option::of(0.0f64..100.0),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, max_public_items, max_public_statement_share, output)| ApiSurfaceConfig {
+                path: path.map(PathBuf::from),
+                max_public_items,
+                max_public_statement_share,
+                output,
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary unsafe code configs.
+fn any_unsafe_code_config() -> BoxedStrategy<UnsafeCodeConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::collection::vec(prop::string::string_regex(r"crate::[a-z]+").unwrap(), 0..3),
+        prop::option::of(any::<bool>()),
+        prop::option::of(any::<bool>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+        prop::option::of(prop::string::string_regex(r"\d{4}-\d{2}-\d{2}").unwrap()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, allowed_modules, include_tests, trend, period, since, output)| {
+                UnsafeCodeConfig {
+                    path: path.map(PathBuf::from),
+                    allowed_modules,
+                    include_tests,
+                    trend,
+                    period,
+                    since,
+                    until: None,
+                    branch: None,
+                    output,
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary error handling configs.
+fn any_error_handling_config() -> BoxedStrategy<ErrorHandlingConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::collection::vec(prop::string::string_regex(r"crate::[a-z]+").unwrap(), 0..3),
+        prop::collection::btree_map(
+            prop::string::string_regex(r"crate::[a-z]+").unwrap(),
+            (
+                prop::option::of(0usize..100),
+                prop::option::of(0usize..100),
+                prop::option::of(0usize..100),
+            )
+                .prop_map(|(total, unwrap, index)| PanicBudget {
+                    total,
+                    unwrap,
+                    index,
+                    ..PanicBudget::default()
+                }),
+            0..3,
+        ),
+        prop::option::of(any::<bool>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(path, banned_modules, budgets, include_tests, output)| ErrorHandlingConfig {
+                path: path.map(PathBuf::from),
+                banned_modules,
+                budgets,
+                include_tests,
+                output,
+            },
+        )
+        .boxed()
+}
+
+/// Strategy for generating arbitrary generics configs.
+fn any_generics_config() -> BoxedStrategy<GenericsConfig> {
+    (
+        prop::option::of(prop::string::string_regex(r"[a-zA-Z0-9_/\.]+").unwrap()),
+        prop::option::of(0usize..20),
+        prop::option::of(0usize..20),
+        prop::option::of(0usize..5),
+        prop::option::of(0usize..50),
+        prop::option::of(any::<bool>()),
+        prop::option::of(any::<bool>()),
+        prop::option::of(prop::string::string_regex(r"[a-z]+").unwrap()),
+    )
+        .prop_map(
+            |(
+                path,
+                max_generic_params,
+                max_where_predicates,
+                max_bound_depth,
+                max_trait_impls,
+                public_only,
+                include_tests,
+                output,
+            )| GenericsConfig {
+                path: path.map(PathBuf::from),
+                max_generic_params,
+                max_where_predicates,
+                max_bound_depth,
+                max_trait_impls,
+                max_blanket_impls: None,
+                public_only,
+                include_tests,
+                top: None,
+                output,
+            },
+        )
         .boxed()
 }
 
@@ -244,30 +645,55 @@ fn any_profile_config() -> BoxedStrategy<ProfileConfig> {
 fn any_raff_config() -> BoxedStrategy<RaffConfig> {
     (
         any_general_config(),
+        any_components_config(),
         any_statement_count_config(),
         any_volatility_config(),
         any_coupling_config(),
         any_rca_config(),
         any_contributor_report_config(),
+        any_code_age_config(),
+        any_ownership_config(),
+        any_codeowners_config(),
+        (
+            any_duplication_config(),
+            any_api_surface_config(),
+            any_unsafe_code_config(),
+            any_error_handling_config(),
+            any_generics_config(),
+        ),
         any_profile_config(),
     )
         .prop_map(
             |(
                 general,
+                components,
                 statement_count,
                 volatility,
                 coupling,
                 rust_code_analysis,
                 contributor_report,
+                code_age,
+                ownership,
+                codeowners,
+                (duplication, api_surface, unsafe_code, error_handling, generics),
                 profile,
             )| {
                 RaffConfig {
                     general,
+                    components,
                     statement_count,
                     volatility,
                     coupling,
                     rust_code_analysis,
                     contributor_report,
+                    code_age,
+                    ownership,
+                    codeowners,
+                    duplication,
+                    api_surface,
+                    unsafe_code,
+                    error_handling,
+                    generics,
                     profile,
                 }
             },